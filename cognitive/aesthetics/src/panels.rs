@@ -175,20 +175,28 @@ impl<C> Panel<C>
 
                     let workspace_width = calculate_text_width(&glyphs) + 10;
                     let bg_intensity = {
-                        // Paint workspace background
-                        if workspace_info.is_active {
+                        // Paint workspace background. Workspaces with surfaces demanding
+                        // attention are painted red.
+                        let color = if workspace_info.is_active {
+                            Some([255, 255, 255])
+                        } else if workspace_info.is_urgent {
+                            Some([255, 120, 100])
+                        } else {
+                            None
+                        };
+
+                        if let Some(color) = color {
                             for y in 0..height {
                                 for x in 0..workspace_width {
-                                    data[0 + 4 * (x + position + y * width)] = 255;
-                                    data[1 + 4 * (x + position + y * width)] = 255;
-                                    data[2 + 4 * (x + position + y * width)] = 255;
+                                    data[0 + 4 * (x + position + y * width)] = color[0];
+                                    data[1 + 4 * (x + position + y * width)] = color[1];
+                                    data[2 + 4 * (x + position + y * width)] = color[2];
                                     data[3 + 4 * (x + position + y * width)] = 150;
                                 }
                             }
-                            255
-                        } else {
-                            200
                         }
+
+                        if workspace_info.is_active { 255 } else { 200 }
                     };
                     position += 5;
 
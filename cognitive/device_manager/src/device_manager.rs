@@ -9,7 +9,7 @@ use std::cell::RefCell;
 use std::rc::Rc;
 
 use dharma;
-use qualia::{InputConfig, InputForwarding, InputHandling};
+use qualia::{InputConfig, InputForwarding, InputHandling, VirtualInput};
 use qualia::{EventHandling, HwGraphics, StatePublishing};
 
 use udev;
@@ -174,6 +174,11 @@ impl<C> DeviceManager<C>
     pub fn on_outputs_changed(&mut self) {
         self.scan_output_devices();
     }
+
+    /// This method is called when virtual input device generated an event.
+    pub fn on_virtual_input(&mut self, event: VirtualInput) {
+        self.input_collector.inject(event);
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -13,6 +13,7 @@ use std::sync::{Arc, Mutex};
 
 use dharma::{EventHandlerId, event_kind};
 use qualia::{Illusion, DeviceKind, EventHandling, InputConfig, InputForwarding, InputHandling};
+use qualia::VirtualInput;
 
 use evdev_driver;
 use udev::Udev;
@@ -98,6 +99,20 @@ impl<C> InputCollector<C>
 
         Ok(())
     }
+
+    /// Passes event from virtual input device to the same gateway the real devices use so it is
+    /// subject to the same built-in and user-defined bindings.
+    pub fn inject(&mut self, event: VirtualInput) {
+        let mut gateway = self.gateway.lock().unwrap();
+        match event {
+            VirtualInput::Key { code, value } => gateway.emit_key(code, value),
+            VirtualInput::Motion(vector) => gateway.emit_motion(vector.x, vector.y),
+            VirtualInput::Warp(position) => gateway.emit_warp(position.x, position.y),
+            VirtualInput::Button { code, value } => gateway.emit_button(code, value),
+            VirtualInput::Axis(vector) => gateway.emit_axis(vector.x, vector.y),
+        }
+        gateway.emit_system_activity_event();
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
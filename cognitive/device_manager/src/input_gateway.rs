@@ -104,6 +104,11 @@ impl InputForwarding for InputGateway {
         self.forwarder.emit_axis(horizontal, vertical);
     }
 
+    /// Emits pointer warp event.
+    fn emit_warp(&mut self, x: isize, y: isize) {
+        self.forwarder.emit_warp(x, y);
+    }
+
     /// Emits position reset event.
     fn emit_position_reset(&mut self) {
         self.forwarder.emit_position_reset();
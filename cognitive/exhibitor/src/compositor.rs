@@ -7,21 +7,38 @@
 // -------------------------------------------------------------------------------------------------
 
 use std;
+use std::cmp::{max, min, Ordering};
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::path::PathBuf;
 
 use timber;
 use qualia::{Action, Area, Command, Direction, Position, Size, Vector};
-use qualia::{SurfaceId, CompositorConfig, ExhibitorCoordinationTrait};
+use qualia::{SurfaceId, SurfaceInfo, CompositorConfig, ExhibitorCoordinationTrait};
+use qualia::{WorkspaceConfig, WorkspaceState};
+use qualia::surface_state;
 
 use surface_history::SurfaceHistory;
-use frames::{Frame, Geometry, Side};
-use frames::{Converting, Searching, Settling};
+use frames::{Frame, Geometry, LayoutKind, Mobility, Mode, Presentation, Side, Spacing};
+use frames::{Converting, Packing, Searching, Settling};
 
 use strategist::Strategist;
+use templates::{FrameTemplate, Placeholders, Swallow};
+use snapshots::LayoutSnapshot;
 
 // -------------------------------------------------------------------------------------------------
 
 const MAX_WORKSPACES: u32 = 1000;
 
+const MAX_UNDO_STEPS: usize = 100;
+
+/// Minimal width and height in pixels frames can be shrunk to by interactive resize.
+const MIN_RESIZED_SIZE: isize = 32;
+
+const MASTER_RATIO_STEP: f32 = 0.05;
+const MIN_MASTER_RATIO: f32 = 0.1;
+const MAX_MASTER_RATIO: f32 = 0.9;
+
 // -------------------------------------------------------------------------------------------------
 
 macro_rules! try_get_surface {
@@ -74,6 +91,20 @@ pub struct Compositor<C>
     selection: Frame,
     strategist: Strategist,
     config: CompositorConfig,
+    placeholders: Placeholders,
+    pending_presentations: HashMap<SurfaceId, Presentation>,
+    scratchpad: Frame,
+    scratchpad_sids: HashSet<SurfaceId>,
+    marks: HashMap<String, SurfaceId>,
+
+    /// Surfaces demanding attention ordered from the least recent.
+    urgent: Vec<SurfaceId>,
+
+    undo_stack: Vec<LayoutSnapshot>,
+    redo_stack: Vec<LayoutSnapshot>,
+
+    /// Frame where visual selection started. `None` if visual mode is not active.
+    visual: Option<Frame>,
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -85,23 +116,48 @@ impl<C> Compositor<C>
     /// `Compositor` constructor.
     pub fn new(coordinator: C, strategist: Strategist, config: CompositorConfig) -> Self {
         let root = Frame::new_root();
-        Compositor {
+        let compositor = Compositor {
             history: SurfaceHistory::new(),
             coordinator: coordinator,
             root: root.clone(),
             selection: root,
             strategist: strategist,
             config: config,
-        }
+            placeholders: Placeholders::new(),
+            pending_presentations: HashMap::new(),
+            scratchpad: Frame::new_root(),
+            scratchpad_sids: HashSet::new(),
+            marks: HashMap::new(),
+            urgent: Vec::new(),
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            visual: None,
+        };
+
+        // Make sure marks from previous session are not listed
+        compositor.store_marks();
+        compositor
     }
 
     /// Creates new display with default workspace.
     pub fn create_display(&mut self, id: i32, area: Area, name: String) -> Frame {
-        let mut display = Frame::new_display(id, area, name);
+        let mut display = Frame::new_display(id, area, name.clone());
         let mut workspace = self.create_next_workspace()
             .expect("Could not create workspace. This probably indicates compositor logic error");
         self.root.append(&mut display);
         workspace.settle(&mut display, None, &mut self.coordinator);
+
+        // Bring in existing workspaces pinned to this output
+        let pinned = self.config
+            .workspace_outputs
+            .iter()
+            .filter(|&(_, output)| *output == name)
+            .filter_map(|(title, _)| self.find_workspace(title))
+            .collect::<Vec<_>>();
+        for pinned_workspace in pinned {
+            self.move_workspace(pinned_workspace, display.clone(), false);
+        }
+
         self.select(workspace);
         self.update_workspace_state();
         display
@@ -109,16 +165,56 @@ impl<C> Compositor<C>
 
     /// Executes given command.
     pub fn execute_command(&mut self, command: Command) {
-        // Execute command
+        // Remember frame layout if command may modify it
+        let snapshot = if is_undoable(&command) {
+            Some(self.take_snapshot())
+        } else {
+            None
+        };
+
+        // Execute command. In visual mode some commands are applied to all selected frames.
+        let result = if let Some(result) = self.execute_visual_command(&command) {
+            result
+        } else {
+            let result = self.execute_selection_command(&command);
+            self.restart_visual();
+            result
+        };
+
+        // Check result and print appropriate log
+        match result {
+            CommandResult::Ok => {
+                if let Some(snapshot) = snapshot {
+                    self.remember(snapshot);
+                }
+
+                self.remove_empty_workspaces();
+                self.placeholders.purge(&self.root);
+                self.coordinator.notify();
+                self.log_frames();
+            }
+            _ => log_error!("Command failed: {} ({:?})", result, command),
+        }
+    }
+
+    /// Executes given command on selected frame.
+    fn execute_selection_command(&mut self, command: &Command) -> CommandResult {
         let mut frame = self.selection.clone();
-        let result = match command.action {
-            Action::Configure => self.configure(&mut frame, command.direction),
+        match command.action {
+            Action::Configure => {
+                match command.direction {
+                    Direction::None => self.equalize(&mut frame),
+                    _ => self.configure(&mut frame, command.direction),
+                }
+            }
             Action::Focus => {
                 match command.direction {
                     Direction::Workspace => {
                         self.focus_workspace(&command.string);
                         CommandResult::Ok
                     }
+                    Direction::Mark => self.focus_mark(&command.string),
+                    Direction::Urgent => self.focus_urgent(),
                     _ => self.focus(&mut frame, command.direction, command.magnitude),
                 }
             }
@@ -128,6 +224,7 @@ impl<C> Compositor<C>
                         self.jump_to_workspace(&mut frame, &command.string);
                         CommandResult::Ok
                     }
+                    Direction::Mark => self.jump_to_mark(frame, &command.string),
                     Direction::End => {
                         self.ramify(frame);
                         CommandResult::Ok
@@ -145,6 +242,7 @@ impl<C> Compositor<C>
                         self.dive_to_workspace(frame, &command.string);
                         CommandResult::Ok
                     }
+                    Direction::Mark => self.dive_to_mark(frame, &command.string),
                     Direction::Begin => {
                         self.exalt(&mut frame);
                         CommandResult::Ok
@@ -152,42 +250,101 @@ impl<C> Compositor<C>
                     _ => self.dive(&mut frame, command.direction, command.magnitude),
                 }
             }
+            Action::Swap => {
+                match command.direction {
+                    Direction::Workspace => self.swap_with_workspace(frame, &command.string),
+                    Direction::Mark => self.swap_with_mark(frame, &command.string),
+                    _ => self.swap(frame, command.direction, command.magnitude),
+                }
+            }
             Action::Resize => self.resize_frame(&mut frame, command.direction, command.magnitude),
             Action::Move => self.move_frame(&mut frame, command.direction, command.magnitude),
             Action::Anchor => self.anchorize(frame),
-            _ => CommandResult::NotHandled,
-        };
-
-        // Check result and print appropriate log
-        match result {
-            CommandResult::Ok => {
-                self.coordinator.notify();
-                self.log_frames();
+            Action::Spacing => {
+                match command.direction {
+                    Direction::None => self.toggle_spacing(),
+                    _ => self.change_spacing(command.direction, command.magnitude),
+                }
             }
-            _ => log_error!("Command failed: {} ({:?})", result, command),
+            Action::Strategy => self.change_strategy(command.direction, &command.string),
+            Action::Layout => {
+                self.change_layout(command.direction, command.magnitude, &command.string)
+            }
+            Action::Save => self.save_layout(&command.string),
+            Action::Restore => self.restore_layout(&command.string),
+            Action::Fullscreen => self.toggle_presentation(frame, Presentation::Fullscreen),
+            Action::Maximize => self.toggle_presentation(frame, Presentation::Maximized),
+            Action::Hide => self.hide(frame),
+            Action::Scratchpad => self.toggle_scratchpad(),
+            Action::Mark => self.mark(frame, &command.string),
+            Action::Undo => self.undo(),
+            Action::Redo => self.redo(),
+            Action::Close => self.close(vec![frame]),
+            Action::Rename => self.rename_workspace(&command.string),
+            Action::Relocate => self.relocate_workspace(command.direction, command.magnitude),
+            Action::Stick => self.toggle_sticky(frame),
+            _ => CommandResult::NotHandled,
         }
     }
 
     /// Handles new surface by settling it in frame tree, adding to history and notifying
     /// coordinator.
     pub fn manage_surface(&mut self, sid: SurfaceId) {
-        if self.root.find_with_sid(sid).is_none() {
+        if self.root.find_with_sid(sid).is_none() && self.scratchpad.find_with_sid(sid).is_none() {
             // Get surface
             let surface = try_get_surface!(self, sid);
 
-            // Consult about placement strategy
-            let mut decision = self.strategist.choose_target(&self.get_selection(), &surface);
-            let area = if let Some(floating) = decision.floating {
-                Some(floating.area)
+            if let Some(placeholder) = self.placeholders.take(&self.root, &surface) {
+                // Let the placeholder from restored layout swallow the surface
+                self.swallow(placeholder, sid);
             } else {
-                None
-            };
+                // Consult about placement strategy
+                let reference = self.find_reference(&surface);
+                let mut decision = self.strategist.choose_target(&reference, &surface);
+                if !is_in_active_workspace(&reference) {
+                    decision.selection = false;
+                }
 
-            // Settle and optionally select new frame
-            let mut frame = Frame::new_leaf(sid, decision.geometry);
-            frame.settle(&mut decision.target, area, &mut self.coordinator);
-            if decision.selection {
-                self.select(frame);
+                let area = if let Some(floating) = decision.floating {
+                    Some(floating.area)
+                } else {
+                    None
+                };
+
+                // Frames in workspaces with automatic layout are placed directly in the workspace
+                if area.is_none() {
+                    if let Some(workspace) = decision.target.find_top() {
+                        if workspace.get_layout().is_automatic() {
+                            decision.target = workspace;
+                        }
+                    }
+                }
+
+                // Enclose new frame in container if requested geometry differs from target's
+                if let (Some(geometry), None) = (decision.container, area) {
+                    if decision.target.get_geometry() != geometry {
+                        let mut container = Frame::new_container(geometry);
+                        container.settle(&mut decision.target, None, &mut self.coordinator);
+                        decision.target = container;
+                    }
+                }
+
+                // Settle and optionally select new frame
+                let mut frame = Frame::new_leaf(sid, decision.geometry);
+                frame.settle(&mut decision.target, area, &mut self.coordinator);
+                if let Some(area) = area {
+                    self.strategist.remember_position(&surface.app_id, area.pos);
+                }
+                if decision.selection {
+                    self.select(frame);
+                }
+            }
+
+            // Apply state requested by client before the surface was shown
+            if let Some(presentation) = self.pending_presentations.remove(&sid) {
+                if let Some(frame) = self.root.find_with_sid(sid) {
+                    self.present(frame, presentation);
+                }
             }
 
             // Finalize
@@ -218,11 +375,23 @@ impl<C> Compositor<C>
 
     /// Handles destruction of surface. Removes it from history and frame free.
     pub fn unmanage_surface(&mut self, sid: SurfaceId) {
+        self.pending_presentations.remove(&sid);
+        self.scratchpad_sids.remove(&sid);
+        self.unmark(sid);
+        let was_urgent = self.clear_urgency(sid);
+        if let Some(ref mut frame) = self.scratchpad.find_with_sid(sid) {
+            frame.destroy_self(&mut self.coordinator);
+            self.log_frames();
+            return;
+        }
+
         if let Some(ref mut frame) = self.root.find_with_sid(sid) {
             self.history.remove(sid);
             if self.selection.get_sid() == sid {
                 let new_selection = {
-                    if let Some(previous_sid) = self.history.get_nth(0) {
+                    let previous_sid =
+                        self.history.get_nth_matching(0, |other| self.is_cyclable(other));
+                    if let Some(previous_sid) = previous_sid {
                         self.root.find_with_sid(previous_sid).expect("Find previous frame")
                     } else {
                         self.selection.find_buildable().expect("Find buildable")
@@ -232,6 +401,12 @@ impl<C> Compositor<C>
             }
 
             frame.destroy_self(&mut self.coordinator);
+            self.restart_visual();
+            self.remove_empty_workspaces();
+            self.placeholders.purge(&self.root);
+            if was_urgent {
+                self.update_workspace_state();
+            }
             self.coordinator.notify();
             self.log_frames();
         }
@@ -253,15 +428,82 @@ impl<C> Compositor<C>
         }
     }
 
-    /// Moves given surface by given vector. Only floating surfaces are moved. This movement is
-    /// associated with interaction with user and `pin_point` describes point on surface by which
-    /// the surface is dragged. If `pin_point` changes display the surface frame is resettled to
-    /// corresponding workspace.
+    /// Handles client request to set or unset maximized or fullscreen state of given surface.
+    ///
+    /// Request to maximize fullscreen surface is ignored. Requests for surfaces not yet managed
+    /// are remembered and applied when the surface gets managed.
+    pub fn request_surface_state(&mut self,
+                                 sid: SurfaceId,
+                                 state: surface_state::SurfaceState,
+                                 enable: bool) {
+        let requested = if state.intersects(surface_state::FULLSCREEN) {
+            Presentation::Fullscreen
+        } else if state.intersects(surface_state::MAXIMIZED) {
+            Presentation::Maximized
+        } else {
+            return;
+        };
+
+        let frame = self.root.find_with_sid(sid);
+        let current = if let Some(ref frame) = frame {
+            frame.get_presentation()
+        } else {
+            *self.pending_presentations.get(&sid).unwrap_or(&Presentation::Regular)
+        };
+
+        let presentation = if enable {
+            if current.is_fullscreen() {
+                Presentation::Fullscreen
+            } else {
+                requested
+            }
+        } else if current == requested {
+            Presentation::Regular
+        } else {
+            current
+        };
+
+        if let Some(frame) = frame {
+            if presentation != current {
+                self.present(frame, presentation);
+                self.coordinator.notify();
+                self.log_frames();
+            }
+        } else if presentation.is_regular() {
+            self.pending_presentations.remove(&sid);
+        } else {
+            self.pending_presentations.insert(sid, presentation);
+        }
+    }
+
+    /// Handles client request to activate given surface. If the request was granted the surface
+    /// is focused, otherwise it is marked as demanding attention.
+    pub fn activate_surface(&mut self, sid: SurfaceId, granted: bool) {
+        if self.selection.get_sid() == sid {
+            return;
+        }
+
+        if granted {
+            log_info2!("Compositor: Activate surface {}", sid);
+            self.focus_surface(sid);
+        } else if self.root.find_with_sid(sid).is_some() ||
+                  self.scratchpad.find_with_sid(sid).is_some() {
+            log_info2!("Compositor: Surface {} demands attention", sid);
+            self.clear_urgency(sid);
+            self.urgent.push(sid);
+            self.update_workspace_state();
+        }
+    }
+
+    /// Moves given surface by given vector. Only floating surfaces which are not fullscreen or
+    /// maximized are moved. This movement is associated with interaction with user and `pin_point`
+    /// describes point on surface by which the surface is dragged. If `pin_point` changes display
+    /// the surface frame is resettled to corresponding workspace.
     pub fn move_globally(&mut self, sid: SurfaceId, vector: Vector, pin_point: Position) {
         if let Some(mut frame) = self.root.find_with_sid(sid) {
-            if frame.get_mobility().is_floating() {
+            if frame.get_mobility().is_floating() && frame.get_presentation().is_regular() {
                 // Find workspaces
-                let pointed = self.root.find_pointed(pin_point);
+                let pointed = find_visible_pointed(&self.root, pin_point);
                 let mut pointed_workspace = pointed.find_top().expect("frame must have parent");
                 let frame_workspace = frame.find_top().expect("workspace must have parent");
 
@@ -278,11 +520,74 @@ impl<C> Compositor<C>
                                    &mut self.coordinator);
                     self.root.pop_recursively(&mut frame);
                 }
+                self.remember_position(&frame);
                 self.log_frames();
             }
         }
     }
 
+    /// Finds surface which should be resized by dragging pointer from given global position and
+    /// edges of its frame which should follow the pointer. If the pointer is in gap between
+    /// anchored frames the closest surface is chosen. Fullscreen and maximized frames are not
+    /// resized.
+    pub fn find_resize_target(&self, position: Position) -> Option<(SurfaceId, Vec<Direction>)> {
+        let pointed = find_visible_pointed(&self.root, position);
+        if !pointed.get_presentation().is_regular() {
+            log_info2!("Compositor: {:?} is presented and can not be resized", pointed);
+            return None;
+        }
+
+        let frame = if pointed.get_sid().is_valid() {
+            pointed
+        } else {
+            match find_leaf_across_gap(&pointed, position) {
+                Some(frame) => frame,
+                None => return None,
+            }
+        };
+
+        if frame.is_top() || frame.find_top().is_none() {
+            return None;
+        }
+
+        if is_placed_automatically(&frame) {
+            log_info2!("Compositor: {:?} is placed by automatic layout and can not be resized",
+                       frame);
+            return None;
+        }
+
+        let edges = choose_edges(&frame, position);
+        Some((frame.get_sid(), edges))
+    }
+
+    /// Resizes frame of given surface by moving given edges by given vector. This resize is
+    /// associated with interaction with user. In case of anchored frames the split ratio between
+    /// the frame and its neighbours is adjusted.
+    pub fn resize_globally(&mut self, sid: SurfaceId, edges: &[Direction], vector: Vector) {
+        if let Some(mut frame) = self.root.find_with_sid(sid) {
+            for edge in edges.iter() {
+                let magnitude = match *edge {
+                    Direction::East => vector.x,
+                    Direction::West => -vector.x,
+                    Direction::South => vector.y,
+                    Direction::North => -vector.y,
+                    _ => 0,
+                };
+
+                let magnitude = limit_resize(&frame, *edge, magnitude);
+                if magnitude != 0 {
+                    frame.resize(*edge, magnitude, &mut self.coordinator);
+                }
+            }
+            self.log_frames();
+        }
+    }
+
+    /// Informs strategist about global position of the pointer.
+    pub fn set_pointer_position(&mut self, position: Position) {
+        self.strategist.set_pointer_position(position);
+    }
+
     /// Returns root frame.
     pub fn get_root(&self) -> Frame {
         self.root.clone()
@@ -292,6 +597,88 @@ impl<C> Compositor<C>
     pub fn get_selection(&self) -> Frame {
         self.selection.clone()
     }
+
+    /// Returns root of frames hidden in scratchpad.
+    pub fn get_scratchpad(&self) -> Frame {
+        self.scratchpad.clone()
+    }
+
+    /// Returns map from names of marks to IDs of marked surfaces.
+    pub fn get_marks(&self) -> HashMap<String, SurfaceId> {
+        self.marks.clone()
+    }
+
+    /// Returns IDs of surfaces demanding attention ordered from the least recent.
+    pub fn get_urgent(&self) -> Vec<SurfaceId> {
+        self.urgent.clone()
+    }
+
+    /// Returns state of workspaces. Workspaces containing surfaces demanding attention are marked
+    /// as urgent.
+    pub fn get_workspace_state(&self) -> WorkspaceState {
+        let mut state = self.root.to_workspace_state();
+        let titles = self.urgent
+            .iter()
+            .filter_map(|sid| self.root.find_with_sid(*sid))
+            .filter_map(|frame| frame.find_top())
+            .map(|workspace| workspace.get_title())
+            .collect::<Vec<_>>();
+        for infos in state.workspaces.values_mut() {
+            for info in infos.iter_mut() {
+                info.is_urgent = titles.contains(&info.name);
+            }
+        }
+        state
+    }
+
+    /// Starts visual mode. Selection becomes the anchor of visual selection.
+    pub fn start_visual(&mut self) {
+        log_info2!("Compositor: Start visual selection");
+        self.visual = Some(self.selection.clone());
+    }
+
+    /// Ends visual mode.
+    pub fn end_visual(&mut self) {
+        log_info2!("Compositor: End visual selection");
+        self.visual = None;
+    }
+
+    /// Returns frames selected in visual mode in spatial order. These are siblings placed between
+    /// the anchor and current selection (inclusive). If the anchor is not a sibling of selection
+    /// only selection is returned. Empty vector is returned if visual mode is not active.
+    pub fn get_visual_selection(&self) -> Vec<Frame> {
+        let anchor = match self.visual {
+            Some(ref anchor) => anchor.clone(),
+            None => return Vec::new(),
+        };
+
+        if !self.selection.is_reanchorizable() {
+            return Vec::new();
+        }
+
+        let parent = match (anchor.get_parent(), self.selection.get_parent()) {
+            (Some(ref anchor_parent), Some(ref parent)) if anchor_parent.equals_exact(parent) => {
+                parent.clone()
+            }
+            _ => return vec![self.selection.clone()],
+        };
+
+        let mut result = Vec::new();
+        let mut is_inside = false;
+        for frame in parent.space_iter() {
+            let is_end = frame.equals_exact(&anchor) || frame.equals_exact(&self.selection);
+            if (is_inside || is_end) && frame.is_reanchorizable() {
+                result.push(frame.clone());
+            }
+            if is_end {
+                if is_inside || anchor.equals_exact(&self.selection) {
+                    break;
+                }
+                is_inside = true;
+            }
+        }
+        result
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -321,7 +708,8 @@ impl<C> Compositor<C>
             Direction::East | Direction::West => Geometry::Horizontal,
             Direction::Begin | Direction::End => Geometry::Stacked,
             Direction::Up => parent.get_geometry(),
-            Direction::None | Direction::Backward | Direction::Forward | Direction::Workspace => {
+            Direction::None | Direction::Backward | Direction::Forward | Direction::Workspace |
+            Direction::Mark | Direction::Urgent => {
                 return CommandResult::NotHandled;
             }
         };
@@ -337,6 +725,236 @@ impl<C> Compositor<C>
         CommandResult::Ok
     }
 
+    /// Resets sizes of frames inside container so they all have the same size.
+    ///
+    /// For convenience if target is `Leaf` its parent is equalized.
+    fn equalize(&mut self, frame: &mut Frame) -> CommandResult {
+        if !frame.is_reorientable() {
+            log_warn1!("Can not equalize sizes in this frame: {:?}", frame);
+            return CommandResult::WrongFrame;
+        }
+
+        log_info2!("Compositor: Equalize frame sizes");
+
+        if frame.has_children() {
+            frame.homogenize(&mut self.coordinator);
+        } else {
+            let mut parent = frame.get_parent().expect("equalized frame should have parent");
+            parent.homogenize(&mut self.coordinator);
+        }
+        CommandResult::Ok
+    }
+
+    /// Enables or disables gaps and borders in current workspace.
+    fn toggle_spacing(&mut self) -> CommandResult {
+        let mut workspace = self.find_current_workspace();
+        let mut spacing = workspace.get_spacing();
+        spacing.enabled = !spacing.enabled;
+
+        log_info2!("Compositor: Toggle spacing (enabled: {})", spacing.enabled);
+
+        workspace.set_plumbing_spacing(spacing);
+        workspace.repack(&mut self.coordinator);
+        CommandResult::Ok
+    }
+
+    /// Changes gaps of current workspace.
+    ///
+    /// - `North` and `South` increase and decrease inner gap,
+    /// - `East` and `West` increase and decrease outer gap.
+    fn change_spacing(&mut self, direction: Direction, magnitude: i32) -> CommandResult {
+        let mut workspace = self.find_current_workspace();
+        let mut spacing = workspace.get_spacing();
+        {
+            let (gap, magnitude) = match direction {
+                Direction::North => (&mut spacing.inner_gap, magnitude),
+                Direction::South => (&mut spacing.inner_gap, -magnitude),
+                Direction::East => (&mut spacing.outer_gap, magnitude),
+                Direction::West => (&mut spacing.outer_gap, -magnitude),
+                _ => return CommandResult::NotHandled,
+            };
+            let value = *gap as i32 + magnitude;
+            *gap = if value > 0 { value as usize } else { 0 };
+        }
+
+        log_info2!("Compositor: Change spacing to {:?}", spacing);
+
+        workspace.set_plumbing_spacing(spacing);
+        workspace.repack(&mut self.coordinator);
+        CommandResult::Ok
+    }
+
+    /// Changes strategies of current workspace.
+    ///
+    /// - `None` direction sets strategy with name given in `string`,
+    /// - `Forward` and `Backward` cycle through strategies deciding how to handle new surfaces.
+    fn change_strategy(&mut self, direction: Direction, string: &str) -> CommandResult {
+        let title = self.find_current_workspace().get_title();
+        match direction {
+            Direction::None => {
+                if !self.strategist.set_workspace_strategy(&title, string) {
+                    log_warn1!("Unknown strategy: '{}'", string);
+                    return CommandResult::NotHandled;
+                }
+            }
+            Direction::Forward | Direction::Backward => {
+                let forward = direction == Direction::Forward;
+                self.strategist.cycle_workspace_target(&title, forward);
+            }
+            _ => return CommandResult::NotHandled,
+        }
+
+        log_info2!("Compositor: Change strategies of workspace '{}' to {:?}",
+                   title,
+                   self.strategist.get_workspace_strategy_names(&title));
+        CommandResult::Ok
+    }
+
+    /// Changes automatic layout of current workspace.
+    ///
+    /// - `None` direction sets layout with name given in `string`,
+    /// - `Forward` and `Backward` cycle through layouts,
+    /// - `North` and `South` increase and decrease number of master frames,
+    /// - `East` and `West` increase and decrease part of workspace taken by master frames.
+    fn change_layout(&mut self,
+                     direction: Direction,
+                     magnitude: i32,
+                     string: &str)
+                     -> CommandResult {
+        let mut workspace = self.find_current_workspace();
+        let mut layout = workspace.get_layout();
+        match direction {
+            Direction::None => {
+                if let Some(kind) = LayoutKind::from_name(string) {
+                    layout.kind = kind;
+                } else {
+                    log_warn1!("Unknown layout: '{}'", string);
+                    return CommandResult::NotHandled;
+                }
+            }
+            Direction::Forward => layout.kind = layout.kind.next(),
+            Direction::Backward => layout.kind = layout.kind.previous(),
+            Direction::North | Direction::South => {
+                let magnitude = if direction == Direction::North { magnitude } else { -magnitude };
+                let count = layout.master_count as i32 + magnitude;
+                layout.master_count = if count > 0 { count as usize } else { 0 };
+            }
+            Direction::East | Direction::West => {
+                let magnitude = if direction == Direction::East { magnitude } else { -magnitude };
+                let ratio = layout.master_ratio + MASTER_RATIO_STEP * magnitude as f32;
+                layout.master_ratio = ratio.max(MIN_MASTER_RATIO).min(MAX_MASTER_RATIO);
+            }
+            _ => return CommandResult::NotHandled,
+        }
+
+        log_info2!("Compositor: Change layout to {:?}", layout);
+
+        workspace.set_plumbing_layout(layout);
+        workspace.repack(&mut self.coordinator);
+        CommandResult::Ok
+    }
+
+    /// Saves frame layout of current workspace to file with given name. Surfaces are described
+    /// by their application IDs or titles. Not yet filled placeholders keep their criteria.
+    fn save_layout(&mut self, name: &str) -> CommandResult {
+        let workspace = self.find_current_workspace();
+        let path = if let Some(path) = self.get_layout_path(name, &workspace) {
+            path
+        } else {
+            return CommandResult::NotHandled;
+        };
+
+        log_info1!("Compositor: Save layout of workspace '{}' to {:?}",
+                   workspace.get_title(),
+                   path);
+
+        let template = {
+            let coordinator = &self.coordinator;
+            let placeholders = &self.placeholders;
+            let describe = |frame: &Frame| if frame.get_sid().is_valid() {
+                coordinator.get_surface(frame.get_sid()).and_then(|info| Swallow::describe(&info))
+            } else {
+                placeholders.get(frame).cloned()
+            };
+            FrameTemplate::from_frame(&workspace, &describe)
+        };
+
+        if let Some(dir) = path.parent() {
+            if let Err(err) = std::fs::create_dir_all(dir) {
+                log_error!("Failed to create directory {:?}: {}", dir, err);
+                return CommandResult::NotHandled;
+            }
+        }
+
+        match template.save(&path) {
+            Ok(()) => CommandResult::Ok,
+            Err(err) => {
+                log_error!("Failed to save layout: {}", err);
+                CommandResult::NotHandled
+            }
+        }
+    }
+
+    /// Restores frame layout of current workspace from file with given name. Anchored frames of
+    /// the workspace are replaced by the restored ones. Surfaces already present in the workspace
+    /// are swallowed by matching placeholders, other placeholders wait for matching surfaces.
+    fn restore_layout(&mut self, name: &str) -> CommandResult {
+        let mut workspace = self.find_current_workspace();
+        let path = if let Some(path) = self.get_layout_path(name, &workspace) {
+            path
+        } else {
+            return CommandResult::NotHandled;
+        };
+
+        log_info1!("Compositor: Restore layout of workspace '{}' from {:?}",
+                   workspace.get_title(),
+                   path);
+
+        let template = match FrameTemplate::load(&path) {
+            Ok(template) => template,
+            Err(err) => {
+                log_error!("Failed to restore layout: {}", err);
+                return CommandResult::NotHandled;
+            }
+        };
+
+        // Take surfaces out of the workspace and instantiate the template in their place
+        let selected_sid = self.selection.get_sid();
+        let mut frames = Vec::new();
+        dismantle_anchored(&workspace, &mut frames);
+        workspace.set_plumbing_geometry(template.geometry);
+        template.instantiate(&mut workspace, &mut self.placeholders, &mut self.coordinator);
+
+        // Let placeholders swallow surfaces already present in the workspace
+        for mut frame in frames {
+            let placeholder = self.coordinator
+                .get_surface(frame.get_sid())
+                .and_then(|info| self.placeholders.take(&workspace, &info));
+
+            if let Some(mut placeholder) = placeholder {
+                log_info2!("Compositor: Swallowing surface {}", frame.get_sid());
+                frame.set_plumbing_ratio(placeholder.get_ratio());
+                placeholder.replace(&mut frame);
+                placeholder.destroy();
+            } else {
+                frame.settle(&mut workspace, None, &mut self.coordinator);
+            }
+        }
+        workspace.repack(&mut self.coordinator);
+
+        // Containers were recreated so current selection may not exist anymore
+        self.selection = workspace.clone();
+        let frame = if selected_sid.is_valid() {
+            self.root.find_with_sid(selected_sid)
+        } else {
+            None
+        };
+        let frame = frame.unwrap_or_else(|| self.find_most_recent(workspace));
+        self.select(frame);
+        self.restart_visual();
+        CommandResult::Ok
+    }
+
     /// Focus frame found in given direction relatively to given `frame`.
     fn focus(&mut self,
              frame: &mut Frame,
@@ -344,13 +962,15 @@ impl<C> Compositor<C>
              mut position: i32)
              -> CommandResult {
         match direction {
-            Direction::Workspace => CommandResult::NotHandled,
+            Direction::Workspace | Direction::Mark | Direction::Urgent => CommandResult::NotHandled,
             Direction::Backward | Direction::Forward => {
                 if direction == Direction::Forward {
                     position = -1 * position;
                 }
 
-                if let Some(sid) = self.history.get_nth(position as isize) {
+                let sid = self.history
+                    .get_nth_matching(position as isize, |sid| self.is_cyclable(sid));
+                if let Some(sid) = sid {
                     self.pop_surface(sid);
                 }
                 CommandResult::Ok
@@ -456,34 +1076,125 @@ impl<C> Compositor<C>
         }
     }
 
-    /// Resizes the frame in given direction.
-    fn resize_frame(&mut self,
-                    frame: &mut Frame,
-                    direction: Direction,
-                    magnitude: i32)
-                    -> CommandResult {
-        let magnitude = self.config.resize_step as isize * magnitude as isize;
-        frame.resize(direction, magnitude, &mut self.coordinator);
-        CommandResult::Ok
-    }
+    /// Swaps given frame with frame found in given direction by given distance. In case of
+    /// `Forward` and `Backward` directions the second frame is chosen from surface history.
+    /// Selection stays on the swapped frame.
+    fn swap(&mut self, mut frame: Frame, mut direction: Direction, distance: i32) -> CommandResult {
+        log_info2!("Compositor: swap");
 
-    /// Moves the frame in given direction.
-    ///
-    /// TODO: If selected frame is anchored but inside floating frame move whole floating frame.
-    fn move_frame(&mut self,
-                  frame: &mut Frame,
-                  direction: Direction,
-                  distance: i32)
-                  -> CommandResult {
-        if frame.get_mobility().is_floating() {
-            let distance = distance as isize;
-            let vector = {
-                    if direction == Direction::North {
-                        Vector::new(0, -distance)
-                    } else if direction == Direction::East {
-                        Vector::new(distance, 0)
-                    } else if direction == Direction::South {
-                        Vector::new(0, distance)
+        // Find the second frame
+        let target = match direction {
+            Direction::Backward | Direction::Forward => {
+                let position = if direction == Direction::Forward {
+                    -1 * distance
+                } else {
+                    distance
+                };
+
+                if let Some(sid) = self.history.get_nth(position as isize) {
+                    self.root.find_with_sid(sid)
+                } else {
+                    None
+                }
+            }
+            Direction::North | Direction::East | Direction::South | Direction::West |
+            Direction::Begin | Direction::End => {
+                let distance = if distance < 0 {
+                    direction = direction.reversed();
+                    -distance
+                } else {
+                    distance
+                } as u32;
+
+                frame.find_adjacent(direction, distance)
+            }
+            Direction::None | Direction::Up | Direction::Workspace | Direction::Mark |
+            Direction::Urgent => {
+                return CommandResult::NotHandled;
+            }
+        };
+
+        // Perform swap
+        if let Some(mut target) = target {
+            self.swap_frames(&mut frame, &mut target)
+        } else {
+            CommandResult::Ok
+        }
+    }
+
+    /// Swaps given frame with most recently used frame from workspace with given title. If the
+    /// workspace is empty or does not exist the frame is dived into it. Chosen workspace becomes
+    /// focused.
+    fn swap_with_workspace(&mut self, mut frame: Frame, title: &String) -> CommandResult {
+        log_info2!("Compositor: swap with workspace '{}'", title);
+        let old_workspace = self.find_current_workspace();
+        if let Some(new_workspace) = self.find_workspace(title) {
+            if !old_workspace.equals_exact(&new_workspace) {
+                let mut target = self.find_most_recent(new_workspace.clone());
+                if target.equals_exact(&new_workspace) {
+                    self.dive_to_workspace(frame, title);
+                    CommandResult::Ok
+                } else {
+                    let result = self.swap_frames(&mut frame, &mut target);
+                    self.focus_workspace(title);
+                    self.select(frame);
+                    result
+                }
+            } else {
+                CommandResult::Ok
+            }
+        } else {
+            self.dive_to_workspace(frame, title);
+            CommandResult::Ok
+        }
+    }
+
+    /// Exchanges places and sizes of two given frames and keeps selection on the first one.
+    fn swap_frames(&mut self, frame: &mut Frame, target: &mut Frame) -> CommandResult {
+        if !frame.is_reanchorizable() || !target.is_reanchorizable() {
+            log_warn1!("Can not swap frames: {:?} and {:?}", frame, target);
+            return CommandResult::WrongFrame;
+        }
+
+        frame.swap(target, &mut self.coordinator);
+        self.select(frame.clone());
+        CommandResult::Ok
+    }
+
+    /// Resizes the frame in given direction.
+    fn resize_frame(&mut self,
+                    frame: &mut Frame,
+                    direction: Direction,
+                    magnitude: i32)
+                    -> CommandResult {
+        if is_placed_automatically(frame) {
+            log_warn2!("Compositor: {:?} is placed by automatic layout and can not be resized",
+                       frame);
+            return CommandResult::NotHandled;
+        }
+
+        let magnitude = self.config.resize_step as isize * magnitude as isize;
+        frame.resize(direction, magnitude, &mut self.coordinator);
+        CommandResult::Ok
+    }
+
+    /// Moves the frame in given direction.
+    ///
+    /// TODO: If selected frame is anchored but inside floating frame move whole floating frame.
+    fn move_frame(&mut self,
+                  frame: &mut Frame,
+                  direction: Direction,
+                  distance: i32)
+                  -> CommandResult {
+        if frame.get_mobility().is_floating() {
+            let distance = distance as isize;
+            let vector = {
+                    if direction == Direction::North {
+                        Vector::new(0, -distance)
+                    } else if direction == Direction::East {
+                        Vector::new(distance, 0)
+                    } else if direction == Direction::South {
+                        Vector::new(0, distance)
                     } else if direction == Direction::West {
                         Vector::new(-distance, 0)
                     } else {
@@ -494,32 +1205,94 @@ impl<C> Compositor<C>
 
             if !vector.is_zero() {
                 frame.move_with_contents(vector);
+                self.remember_position(frame);
             }
         }
         CommandResult::Ok
     }
 
-    /// Handles anchorization command.
+    /// Makes given frame fullscreen or maximized or brings it back to its place in layout if it
+    /// already has requested presentation.
+    fn toggle_presentation(&mut self, frame: Frame, presentation: Presentation) -> CommandResult {
+        if !frame.get_mode().is_leaf() || !frame.get_sid().is_valid() {
+            log_warn1!("Only surfaces can be maximized or made fullscreen: {:?}", frame);
+            return CommandResult::WrongFrame;
+        }
+
+        let presentation = if frame.get_presentation() == presentation {
+            Presentation::Regular
+        } else {
+            presentation
+        };
+
+        log_info2!("Compositor: Change presentation to {:?}", presentation);
+
+        self.present(frame, presentation);
+        CommandResult::Ok
+    }
+
+    /// Handles anchorization command. Anchored frames stop belonging to scratchpad.
     ///
     /// TODO: Extract preferred size from frame.
     fn anchorize(&mut self, mut frame: Frame) -> CommandResult {
         if frame.get_mobility().is_anchored() {
             let workspace = self.find_current_workspace();
-            let decision = self.strategist.choose_floating(workspace.get_size(), None);
+            let decision = self.strategist.choose_floating(&workspace, None);
             frame.deanchorize(decision.area, &mut self.coordinator);
         } else {
+            frame.set_plumbing_sticky(false);
             frame.anchorize(&mut self.coordinator);
+            self.scratchpad_sids.remove(&frame.get_sid());
+        }
+        CommandResult::Ok
+    }
+
+    /// Makes given floating frame visible on every workspace of its display or back only on its
+    /// own workspace if it already is sticky.
+    fn toggle_sticky(&mut self, mut frame: Frame) -> CommandResult {
+        let in_workspace = match frame.get_parent() {
+            Some(parent) => parent.get_mode().is_workspace(),
+            None => false,
+        };
+        if !frame.get_mobility().is_floating() || !in_workspace {
+            log_warn1!("Only floating frames can be made sticky: {:?}", frame);
+            return CommandResult::WrongFrame;
         }
+
+        let sticky = !frame.is_sticky();
+        log_info2!("Compositor: Make {:?} {}", frame, if sticky { "sticky" } else { "not sticky" });
+        frame.set_plumbing_sticky(sticky);
         CommandResult::Ok
     }
 
-    /// Adds new container just above selection.
+    /// Adds new container just above selection. Geometry of the container is taken from
+    /// configuration of current workspace.
     fn ramify(&mut self, mut frame: Frame) {
-        // TODO: Geometry should be configurable.
-        frame.ramify(Geometry::Stacked);
+        let geometry = self.geometry_for(&self.find_current_workspace().get_title());
+        frame.ramify(geometry);
         self.select(frame);
     }
 
+    /// Asks clients to close all surfaces placed in given frames.
+    fn close(&mut self, frames: Vec<Frame>) -> CommandResult {
+        for frame in frames {
+            self.close_recursively(&frame);
+        }
+        CommandResult::Ok
+    }
+
+    /// Helper for `close`. Asks to close surface of given frame and all its subframes.
+    fn close_recursively(&mut self, frame: &Frame) {
+        let sid = frame.get_sid();
+        if sid.is_valid() {
+            log_info2!("Compositor: Close {}", sid);
+            self.coordinator.close(sid);
+        }
+        for ref subframe in frame.space_iter() {
+            self.close_recursively(subframe);
+        }
+    }
+
     /// Jumps frame one level higher.
     fn exalt(&mut self, frame: &mut Frame) {
         // Choose target
@@ -536,8 +1309,523 @@ impl<C> Compositor<C>
                 above.ramify(Geometry::Stacked)
             };
 
-            // Resettle to target
-            frame.resettle(&mut target, None, &mut self.coordinator);
+            // Resettle to target
+            frame.resettle(&mut target, None, &mut self.coordinator);
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Private methods related to scratchpad.
+impl<C> Compositor<C>
+    where C: ExhibitorCoordinationTrait
+{
+    /// Hides given surface frame in scratchpad. Scratchpad is not attached to any display so its
+    /// frames are never drawn. Anchored frames are made floating with half of size of workspace.
+    fn hide(&mut self, frame: Frame) -> CommandResult {
+        if !frame.get_mode().is_leaf() || !frame.get_sid().is_valid() {
+            log_warn1!("Only surfaces can be hidden in scratchpad: {:?}", frame);
+            return CommandResult::WrongFrame;
+        }
+
+        log_info2!("Compositor: Hide {} in scratchpad", frame.get_sid());
+
+        self.scratchpad_sids.insert(frame.get_sid());
+        self.stash(frame, true);
+        CommandResult::Ok
+    }
+
+    /// Shows most recently hidden scratchpad frame floating in the center of current workspace.
+    /// If selected frame comes from scratchpad it is hidden back instead and becomes the least
+    /// recent one, so that repeated toggling cycles through all scratchpad frames.
+    fn toggle_scratchpad(&mut self) -> CommandResult {
+        let selection = self.selection.clone();
+        if self.scratchpad_sids.contains(&selection.get_sid()) {
+            log_info2!("Compositor: Hide {} back in scratchpad", selection.get_sid());
+            self.stash(selection, false);
+        } else if let Some(frame) = self.scratchpad.get_first_time() {
+            log_info2!("Compositor: Show {} from scratchpad", frame.get_sid());
+            self.unstash(frame);
+        } else {
+            log_info2!("Compositor: Scratchpad is empty");
+        }
+        CommandResult::Ok
+    }
+
+    /// Moves given frame from its workspace to scratchpad and selects most recently used frame
+    /// from the workspace. If `recent` is `true` the frame will be the first to be shown.
+    fn stash(&mut self, mut frame: Frame, recent: bool) {
+        let workspace = frame.find_top().expect("hidden frame should have `top`");
+        if !frame.get_presentation().is_regular() {
+            frame.present(Presentation::Regular, &mut self.coordinator);
+        }
+
+        if frame.get_mobility().is_anchored() {
+            frame.set_size(workspace.get_size().scaled(0.5), &mut self.coordinator);
+        }
+
+        frame.remove_self(&mut self.coordinator);
+        frame.set_plumbing_mobility(Mobility::Floating);
+        self.scratchpad.prepend(&mut frame);
+        if recent {
+            frame.pop();
+        }
+        self.history.remove(frame.get_sid());
+
+        let most_recent = self.find_most_recent(workspace);
+        if !most_recent.get_sid().is_valid() {
+            self.coordinator.set_keyboard_focus(SurfaceId::invalid());
+        }
+        self.select(most_recent);
+    }
+
+    /// Moves given frame from scratchpad to the center of current workspace and selects it.
+    fn unstash(&mut self, mut frame: Frame) {
+        let mut workspace = self.find_current_workspace();
+        let workspace_size = workspace.get_size();
+        let frame_size = frame.get_size();
+        let size = Size::new(std::cmp::min(frame_size.width, workspace_size.width),
+                             std::cmp::min(frame_size.height, workspace_size.height));
+        let pos = Position::new(((workspace_size.width - size.width) / 2) as isize,
+                                ((workspace_size.height - size.height) / 2) as isize);
+
+        frame.remove();
+        workspace.prepend(&mut frame);
+        frame.set_size(size, &mut self.coordinator);
+        frame.set_plumbing_position(pos);
+        self.history.add(frame.get_sid());
+        self.select(frame);
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Private methods related to marks.
+impl<C> Compositor<C>
+    where C: ExhibitorCoordinationTrait
+{
+    /// Attaches mark with given name to given surface frame. If the mark was attached to other
+    /// frame it is moved. One frame may have many marks.
+    fn mark(&mut self, frame: Frame, name: &str) -> CommandResult {
+        if name.is_empty() || !frame.get_sid().is_valid() {
+            log_warn1!("Only surfaces can be marked: {:?} ('{}')", frame, name);
+            return CommandResult::WrongFrame;
+        }
+
+        log_info2!("Compositor: Mark {} as '{}'", frame.get_sid(), name);
+        self.marks.insert(name.to_owned(), frame.get_sid());
+        self.store_marks();
+        CommandResult::Ok
+    }
+
+    /// Removes all marks attached to given surface.
+    fn unmark(&mut self, sid: SurfaceId) {
+        let count = self.marks.len();
+        self.marks.retain(|_, marked| *marked != sid);
+        if self.marks.len() != count {
+            self.store_marks();
+        }
+    }
+
+    /// Searches frame tree for frame with given mark.
+    fn find_marked(&self, name: &str) -> Option<Frame> {
+        if let Some(sid) = self.marks.get(name) {
+            self.root.find_with_sid(*sid)
+        } else {
+            log_warn2!("Compositor: Mark '{}' not set", name);
+            None
+        }
+    }
+
+    /// Focuses frame with given mark. If the frame is placed in inactive workspace the workspace
+    /// gets focused. Frame hidden in scratchpad is shown in current workspace.
+    fn focus_mark(&mut self, name: &str) -> CommandResult {
+        log_info2!("Compositor: Focus mark '{}'", name);
+        if let Some(sid) = self.marks.get(name).cloned() {
+            self.focus_surface(sid);
+        } else {
+            log_warn2!("Compositor: Mark '{}' not set", name);
+        }
+        CommandResult::Ok
+    }
+
+    /// Jumps given frame next to frame with given mark. Like when jumping to workspace, current
+    /// workspace stays focused.
+    fn jump_to_mark(&mut self, mut frame: Frame, name: &str) -> CommandResult {
+        log_info2!("Compositor: Jump to mark '{}'", name);
+        if let Some(mut target) = self.find_marked(name) {
+            if !frame.is_reanchorizable() || frame.equals_exact(&target) ||
+               frame.contains(&target) {
+                log_warn1!("Can not jump frame {:?} to {:?}", frame, target);
+                return CommandResult::WrongFrame;
+            }
+
+            let old_workspace = self.find_current_workspace();
+            let mut source = frame.get_parent().expect("jump reference must have parent");
+            frame.jump(Side::After, &mut target, &mut self.coordinator);
+            source.deramify();
+
+            let new_workspace = frame.find_top().expect("jumped frame should have `top`");
+            if new_workspace.equals_exact(&old_workspace) {
+                self.select(frame);
+            } else {
+                let most_recent = self.find_most_recent(old_workspace);
+                self.select(most_recent);
+            }
+        }
+        CommandResult::Ok
+    }
+
+    /// Dives given frame into frame with given mark. Like when diving to workspace, the frame
+    /// stays focused.
+    fn dive_to_mark(&mut self, mut frame: Frame, name: &str) -> CommandResult {
+        log_info2!("Compositor: Dive to mark '{}'", name);
+        if let Some(mut target) = self.find_marked(name) {
+            if !frame.is_reanchorizable() || frame.equals_exact(&target) ||
+               frame.contains(&target) {
+                log_warn1!("Can not dive frame {:?} to {:?}", frame, target);
+                return CommandResult::WrongFrame;
+            }
+
+            let mut source = frame.get_parent().expect("dive reference must have parent");
+            frame.jump(Side::On, &mut target, &mut self.coordinator);
+            source.deramify();
+            self.reveal(frame);
+        }
+        CommandResult::Ok
+    }
+
+    /// Swaps given frame with frame with given mark. Selection follows the swapped frame.
+    fn swap_with_mark(&mut self, mut frame: Frame, name: &str) -> CommandResult {
+        log_info2!("Compositor: Swap with mark '{}'", name);
+        if let Some(mut target) = self.find_marked(name) {
+            let result = self.swap_frames(&mut frame, &mut target);
+            self.reveal(frame);
+            result
+        } else {
+            CommandResult::Ok
+        }
+    }
+
+    /// Selects given frame. If it is placed in inactive workspace the workspace gets focused.
+    fn reveal(&mut self, frame: Frame) {
+        if !is_in_active_workspace(&frame) {
+            if let Some(workspace) = frame.find_top() {
+                self.focus_workspace(&workspace.get_title());
+            }
+        }
+        self.select(frame);
+    }
+
+    /// Writes list of marks to file configured as `marks_path` so they can be presented by other
+    /// applications. Each line contains name of the mark, application ID and title of marked
+    /// surface separated by tabulators. The file is written only when marks change.
+    fn store_marks(&self) {
+        let path = if let Some(ref path) = self.config.marks_path {
+            path
+        } else {
+            return;
+        };
+
+        let mut names = self.marks.keys().collect::<Vec<_>>();
+        names.sort();
+
+        let mut contents = String::new();
+        for name in names {
+            let sid = self.marks[name];
+            let (app_id, title) = match self.coordinator.get_surface(sid) {
+                Some(info) => (info.app_id, info.title),
+                None => (String::default(), String::default()),
+            };
+            let line = format!("{}\t{}\t{}", name, app_id, title);
+            contents.push_str(&line.replace('\n', " "));
+            contents.push('\n');
+        }
+
+        let result = std::fs::File::create(path)
+            .and_then(|mut file| file.write_all(contents.as_bytes()));
+        if let Err(err) = result {
+            log_warn1!("Failed to store marks in {:?}: {}", path, err);
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Private methods related to surfaces demanding attention.
+impl<C> Compositor<C>
+    where C: ExhibitorCoordinationTrait
+{
+    /// Focuses the most recent surface demanding attention.
+    fn focus_urgent(&mut self) -> CommandResult {
+        log_info2!("Compositor: Focus urgent");
+        if let Some(sid) = self.urgent.last().cloned() {
+            self.focus_surface(sid);
+        } else {
+            log_info2!("Compositor: No surface demands attention");
+        }
+        CommandResult::Ok
+    }
+
+    /// Forgets given surface demands attention. Returns `true` if it did.
+    fn clear_urgency(&mut self, sid: SurfaceId) -> bool {
+        let len = self.urgent.len();
+        self.urgent.retain(|urgent_sid| *urgent_sid != sid);
+        self.urgent.len() != len
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Private methods related to undoing and redoing commands.
+impl<C> Compositor<C>
+    where C: ExhibitorCoordinationTrait
+{
+    /// Takes snapshot of current frame layout.
+    fn take_snapshot(&self) -> LayoutSnapshot {
+        LayoutSnapshot::new(&self.root, self.selection.get_sid(), &self.placeholders)
+    }
+
+    /// Puts snapshot taken before executing command on undo stack. Redo stack is cleared as the
+    /// states it describes are not reachable anymore.
+    fn remember(&mut self, snapshot: LayoutSnapshot) {
+        if self.undo_stack.len() >= MAX_UNDO_STEPS {
+            self.undo_stack.remove(0);
+        }
+        self.undo_stack.push(snapshot);
+        self.redo_stack.clear();
+    }
+
+    /// Brings frame layout back to the state from before last undoable command.
+    fn undo(&mut self) -> CommandResult {
+        if let Some(snapshot) = self.undo_stack.pop() {
+            log_info2!("Compositor: Undo");
+            let current = self.take_snapshot();
+            self.restore_snapshot(snapshot);
+            self.redo_stack.push(current);
+        } else {
+            log_info2!("Compositor: Nothing to undo");
+        }
+        CommandResult::Ok
+    }
+
+    /// Brings frame layout back to the state from before last undo.
+    fn redo(&mut self) -> CommandResult {
+        if let Some(snapshot) = self.redo_stack.pop() {
+            log_info2!("Compositor: Redo");
+            let current = self.take_snapshot();
+            self.restore_snapshot(snapshot);
+            self.undo_stack.push(current);
+        } else {
+            log_info2!("Compositor: Nothing to redo");
+        }
+        CommandResult::Ok
+    }
+
+    /// Restores frame layout from given snapshot and selects surface which was selected when the
+    /// snapshot was taken. If it does not exist anymore current selection is kept if possible.
+    fn restore_snapshot(&mut self, snapshot: LayoutSnapshot) {
+        let workspace = self.find_current_workspace();
+        let selected_sid = self.selection.get_sid();
+
+        // Containers are recreated so current selection may not exist after restoring
+        snapshot.restore(&self.root, &mut self.placeholders, &mut self.coordinator);
+        self.selection = workspace.clone();
+
+        let frame = vec![snapshot.selection, selected_sid]
+            .into_iter()
+            .filter(|sid| sid.is_valid())
+            .filter_map(|sid| self.root.find_with_sid(sid))
+            .next();
+
+        if let Some(frame) = frame {
+            self.reveal(frame);
+        } else {
+            let most_recent = self.find_most_recent(workspace);
+            self.select(most_recent);
+        }
+        self.restart_visual();
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Private methods related to visual mode.
+impl<C> Compositor<C>
+    where C: ExhibitorCoordinationTrait
+{
+    /// Executes given command on all frames selected in visual mode. Returns `None` if visual mode
+    /// is not active or the command does not apply to group of frames. Such commands are executed
+    /// on selection as in normal mode.
+    fn execute_visual_command(&mut self, command: &Command) -> Option<CommandResult> {
+        let frames = self.get_visual_selection();
+        if frames.is_empty() {
+            return None;
+        }
+
+        match command.action {
+            Action::Focus if command.direction.is_planar() => {
+                Some(self.extend_visual(command.direction, command.magnitude))
+            }
+            Action::Jump if command.direction.is_planar() => {
+                Some(self.jump_visual(frames, command.direction, command.magnitude, false))
+            }
+            Action::Dive if command.direction.is_planar() => {
+                Some(self.jump_visual(frames, command.direction, command.magnitude, true))
+            }
+            Action::Jump if command.direction == Direction::Workspace => {
+                for mut frame in frames {
+                    self.jump_to_workspace(&mut frame, &command.string);
+                }
+                self.restart_visual();
+                Some(CommandResult::Ok)
+            }
+            Action::Dive if command.direction == Direction::Workspace => {
+                Some(self.dive_visual_to_workspace(frames, &command.string))
+            }
+            Action::Configure if frames.len() > 1 => {
+                let geometry = match command.direction {
+                    Direction::North | Direction::South => Geometry::Vertical,
+                    Direction::East | Direction::West => Geometry::Horizontal,
+                    Direction::Begin | Direction::End => Geometry::Stacked,
+                    _ => return None,
+                };
+                Some(self.wrap_visual(frames, geometry))
+            }
+            Action::Close => Some(self.close(frames)),
+            _ => None,
+        }
+    }
+
+    /// Extends visual selection by moving selection to sibling frame in given direction.
+    fn extend_visual(&mut self, mut direction: Direction, distance: i32) -> CommandResult {
+        let distance = if distance < 0 {
+            direction = direction.reversed();
+            -distance
+        } else {
+            distance
+        };
+
+        for _ in 0..distance {
+            match self.selection.find_neighbouring(direction) {
+                Some(ref frame) if frame.is_reanchorizable() => self.select(frame.clone()),
+                _ => break,
+            }
+        }
+        CommandResult::Ok
+    }
+
+    /// Moves all selected frames in given direction. The frame lying the furthest in given
+    /// direction jumps (or dives) as in normal mode and the other frames jump next to it, so the
+    /// group stays contiguous and keeps its order.
+    fn jump_visual(&mut self,
+                   mut frames: Vec<Frame>,
+                   mut direction: Direction,
+                   distance: i32,
+                   dive: bool)
+                   -> CommandResult {
+        log_info2!("Compositor: jump {} frames", frames.len());
+
+        // Modify direction if needed
+        let distance = if distance < 0 {
+            direction = direction.reversed();
+            -distance
+        } else {
+            distance
+        } as u32;
+
+        // Choose side and leading frame
+        let side = match direction {
+            Direction::North | Direction::West => Side::Before,
+            Direction::South | Direction::East => Side::After,
+            _ => {
+                return CommandResult::NotHandled;
+            }
+        };
+
+        let mut lead = match side {
+            Side::Before => frames.remove(0),
+            _ => frames.pop().expect("visual selection should not be empty"),
+        };
+
+        // Perform jump. Source is deramified only after all frames left it because deramifying
+        // could destroy frames which are still going to be moved.
+        if let Some(mut target) = lead.find_adjacent(direction, distance) {
+            let mut source = lead.get_parent().expect("jump reference must have parent");
+            let lead_side = if dive { Side::On } else { side };
+            lead.jump(lead_side, &mut target, &mut self.coordinator);
+            match side {
+                Side::Before => {
+                    let mut previous = lead;
+                    for mut frame in frames {
+                        frame.jump(Side::After, &mut previous, &mut self.coordinator);
+                        previous = frame;
+                    }
+                }
+                _ => {
+                    for mut frame in frames {
+                        frame.jump(Side::Before, &mut lead, &mut self.coordinator);
+                    }
+                }
+            }
+            source.deramify();
+        }
+        CommandResult::Ok
+    }
+
+    /// Dives all selected frames to workspace with given title. Chosen workspace becomes focused
+    /// and visual selection is kept on the moved frames.
+    fn dive_visual_to_workspace(&mut self,
+                                mut frames: Vec<Frame>,
+                                title: &String)
+                                -> CommandResult {
+        let last = frames.pop().expect("visual selection should not be empty");
+        let first = frames.first().cloned().unwrap_or(last.clone());
+        for mut frame in frames {
+            self.jump_to_workspace(&mut frame, title);
+        }
+        self.dive_to_workspace(last, title);
+        self.visual = Some(first);
+        CommandResult::Ok
+    }
+
+    /// Wraps all selected frames into new container with given geometry. If selected frames are
+    /// all frames of their parent, the parent is reconfigured instead. The new container becomes
+    /// selected.
+    fn wrap_visual(&mut self, frames: Vec<Frame>, geometry: Geometry) -> CommandResult {
+        let mut parent = frames[0].get_parent().expect("selected frame must have parent");
+        if parent.count_children() == frames.len() {
+            log_info2!("Compositor: Change frame geometry to '{:?}'", geometry);
+            parent.change_geometry(geometry, &mut self.coordinator);
+            return CommandResult::Ok;
+        }
+
+        log_info2!("Compositor: Wrap {} frames into '{:?}' container", frames.len(), geometry);
+        let ratio = frames.iter().fold(0.0, |sum, frame| sum + frame.get_ratio());
+        let mut container = Frame::new_container(geometry);
+        container.set_plumbing_ratio(ratio);
+        frames[0].clone().prejoin(&mut container);
+        for mut frame in frames {
+            let frame_ratio = if ratio > 0.0 { frame.get_ratio() / ratio } else { 1.0 };
+            frame.remove();
+            frame.set_plumbing_mobility(Mobility::Anchored);
+            frame.set_plumbing_position(Position::default());
+            frame.set_plumbing_ratio(frame_ratio);
+            container.append(&mut frame);
+        }
+        parent.relax(&mut self.coordinator);
+        container.relax(&mut self.coordinator);
+
+        self.visual = Some(container.clone());
+        self.select(container);
+        CommandResult::Ok
+    }
+
+    /// Makes current selection the anchor of visual selection if visual mode is active.
+    fn restart_visual(&mut self) {
+        if self.visual.is_some() {
+            self.visual = Some(self.selection.clone());
         }
     }
 }
@@ -558,7 +1846,7 @@ impl<C> Compositor<C>
     /// surface with given ID is somewhere in workspace three. Not the most efficient... Any ideas
     /// for improvement?
     fn find_most_recent(&self, reference: Frame) -> Frame {
-        for sid in self.history.iter() {
+        for sid in self.history.iter().filter(|sid| self.is_cyclable(*sid)) {
             if let Some(frame) = reference.find_with_sid(sid) {
                 return frame.clone();
             }
@@ -569,6 +1857,80 @@ impl<C> Compositor<C>
 
 // -------------------------------------------------------------------------------------------------
 
+/// Private methods related to window rules.
+impl<C> Compositor<C>
+    where C: ExhibitorCoordinationTrait
+{
+    /// Returns frame relatively to which new surface should be placed. By default it is the
+    /// selection, but rule matching the surface may point to other workspace or output.
+    fn find_reference(&mut self, surface: &SurfaceInfo) -> Frame {
+        let (workspace, output) = match self.strategist.find_rule(surface) {
+            Some(rule) => (rule.workspace.clone(), rule.output.clone()),
+            None => return self.get_selection(),
+        };
+
+        if let Some(title) = workspace {
+            let workspace = self.bring_workspace(&title, false);
+            return self.find_most_recent(workspace);
+        }
+
+        if let Some(name) = output {
+            if let Some(display) = self.find_display_with_name(&name) {
+                let active = display.space_iter().find(|frame| {
+                    frame.get_mode() == Mode::Workspace { is_active: true }
+                });
+                if let Some(workspace) = active {
+                    return self.find_most_recent(workspace);
+                }
+            } else {
+                log_warn2!("Output '{}' requested by rule not found", name);
+            }
+        }
+
+        self.get_selection()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Private methods related to saved layouts.
+impl<C> Compositor<C>
+    where C: ExhibitorCoordinationTrait
+{
+    /// Returns path to file with layout of given name. Empty name stands for title of given
+    /// workspace. Absolute paths are used as they are.
+    fn get_layout_path(&self, name: &str, workspace: &Frame) -> Option<PathBuf> {
+        let name = if name.is_empty() {
+            workspace.get_title()
+        } else {
+            name.to_owned()
+        };
+
+        let path = PathBuf::from(&name);
+        if path.is_absolute() {
+            Some(path)
+        } else if let Some(ref dir) = self.config.layouts_path {
+            Some(dir.join(format!("{}.yaml", name)))
+        } else {
+            log_warn1!("Directory for layouts is not configured");
+            None
+        }
+    }
+
+    /// Turns given placeholder into leaf frame of given surface and selects it.
+    fn swallow(&mut self, mut placeholder: Frame, sid: SurfaceId) {
+        log_info2!("Compositor: Swallowing surface {}", sid);
+        placeholder.set_plumbing_mode(Mode::Leaf);
+        placeholder.set_plumbing_sid(sid);
+        let size = placeholder.get_size();
+        placeholder.set_size(size, &mut self.coordinator);
+        placeholder.refresh_spacing(&mut self.coordinator);
+        self.select(placeholder);
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
 /// Private methods related to workspaces.
 impl<C> Compositor<C>
     where C: ExhibitorCoordinationTrait
@@ -594,15 +1956,22 @@ impl<C> Compositor<C>
                             -> Frame {
         log_info2!("Compositor: create new workspace (title: {}, focus: {})", title, focus);
         // Create and configure workspace
-        // TODO: Make default workspace geometry configurable.
-        let mut workspace = Frame::new_workspace(title.clone(), Geometry::Stacked, true);
-        workspace.settle(&mut container, None, &mut self.coordinator);
+        let mut workspace = self.new_workspace(title.clone(), true);
+        self.place_workspace(&mut workspace, &mut container);
 
         // Focus if requested or make sure current selection stays focused
         if focus {
             self.select(workspace.clone());
             self.root.pop_recursively(&mut workspace);
         } else {
+            // Workspace created in background must not be shown over already active one
+            let has_active = container.space_iter().any(|frame| {
+                !frame.equals_exact(&workspace) &&
+                frame.get_mode() == Mode::Workspace { is_active: true }
+            });
+            if has_active {
+                workspace.make_active(false);
+            }
             self.root.pop_recursively(&mut self.selection);
         }
 
@@ -621,22 +1990,84 @@ impl<C> Compositor<C>
         for i in 1..MAX_WORKSPACES {
             let title = i.to_string();
             if self.find_workspace(&title).is_none() {
-                return Some(Frame::new_workspace(title, Geometry::Stacked, true));
+                return Some(self.new_workspace(title, true));
             }
         }
         log_error!("Don't you think {} workspaces isn't enough?", MAX_WORKSPACES);
         None
     }
 
+    /// Creates new workspace frame with given title configured according to configuration of
+    /// that title. The frame is not placed in frame tree.
+    fn new_workspace(&mut self, title: String, active: bool) -> Frame {
+        let geometry = self.geometry_for(&title);
+        let mut workspace = Frame::new_workspace(title, geometry, active);
+        self.configure_workspace(&mut workspace);
+        workspace
+    }
+
+    /// Applies gaps, automatic layout and strategies configured for title of given workspace.
+    /// Whether gaps are enabled and numbers of master frames are kept.
+    fn configure_workspace(&mut self, workspace: &mut Frame) {
+        let title = workspace.get_title();
+        let (choose_target, choose_floating, layout_name) = {
+            let config = self.workspace_config(&title);
+            (config.choose_target.clone(), config.choose_floating.clone(), config.layout.clone())
+        };
+
+        let mut spacing = self.spacing_for(&title);
+        spacing.enabled = workspace.get_spacing().enabled;
+        workspace.set_plumbing_spacing(spacing);
+
+        let mut layout = workspace.get_layout();
+        if let Some(kind) = LayoutKind::from_name(&layout_name) {
+            layout.kind = kind;
+        } else {
+            log_warn1!("Unknown layout '{}' configured for workspace '{}'", layout_name, title);
+        }
+        workspace.set_plumbing_layout(layout);
+
+        self.strategist.configure_workspace(&title, &choose_target, &choose_floating);
+    }
+
+    /// Returns configuration of workspace with given title.
+    fn workspace_config(&self, title: &str) -> &WorkspaceConfig {
+        self.config.workspaces.get(title).unwrap_or(&self.config.workspace)
+    }
+
+    /// Returns gaps and borders configured for workspace with given title.
+    fn spacing_for(&self, title: &str) -> Spacing {
+        let config = &self.workspace_config(title).spacing;
+        Spacing {
+            inner_gap: config.inner_gap as usize,
+            outer_gap: config.outer_gap as usize,
+            border: config.border as usize,
+            smart_gaps: config.smart_gaps,
+            enabled: true,
+        }
+    }
+
+    /// Returns geometry configured for workspace with given title.
+    fn geometry_for(&self, title: &str) -> Geometry {
+        let name = &self.workspace_config(title).geometry;
+        Geometry::from_name(name).unwrap_or_else(|| {
+            log_warn1!("Unknown geometry '{}' configured for workspace '{}'", name, title);
+            Geometry::Stacked
+        })
+    }
+
     /// Search for existing workspace or create new with given title.
     fn bring_workspace(&mut self, title: &String, focus: bool) -> Frame {
         if let Some(workspace) = self.find_workspace(&title) {
             workspace.clone()
         } else {
-            // TODO: For many output setup this should be configurable on which output the
-            // workspace will be created.
-            let mut workspace_container =
-                self.find_current_workspace().get_parent().expect("workspace must have parent");
+            // Workspace may be pinned to other output. If not, it will be placed on current one.
+            let mut workspace_container = match self.find_pinned_container(title) {
+                Some(container) => container,
+                None => {
+                    self.find_current_workspace().get_parent().expect("workspace must have parent")
+                }
+            };
 
             self.create_new_workspace(&mut workspace_container, title, focus)
         }
@@ -654,6 +2085,7 @@ impl<C> Compositor<C>
             neighbour.make_active(false);
         }
         workspace.make_active(true);
+        self.gather_sticky(&workspace);
 
         // Select most recently used surface.
         let mut most_recent = self.find_most_recent(workspace);
@@ -664,9 +2096,313 @@ impl<C> Compositor<C>
         self.update_workspace_state();
     }
 
+    /// Moves sticky frames from other workspaces of the same display to given workspace, so they
+    /// stay visible and reachable after switching workspaces.
+    fn gather_sticky(&mut self, workspace: &Frame) {
+        let container = match workspace.get_parent() {
+            Some(container) => container,
+            None => return,
+        };
+
+        let mut sticky = Vec::new();
+        for other in container.space_iter() {
+            if other.get_mode().is_workspace() && !other.equals_exact(workspace) {
+                sticky.extend(other.space_iter().filter(|frame| frame.is_sticky()));
+            }
+        }
+
+        let mut target = workspace.clone();
+        for mut frame in sticky {
+            let position = frame.get_position();
+            frame.resettle(&mut target, Some(position), &mut self.coordinator);
+        }
+    }
+
     /// Updates global workspace state.
     fn update_workspace_state(&mut self) {
-        self.coordinator.set_workspace_state(self.root.to_workspace_state());
+        let state = self.get_workspace_state();
+        self.coordinator.set_workspace_state(state);
+    }
+
+    /// Inserts given workspace into given container keeping workspaces ordered by their titles.
+    fn place_workspace(&mut self, workspace: &mut Frame, container: &mut Frame) {
+        let title = workspace.get_title();
+        let next = container.space_iter().find(|frame| {
+            frame.get_mode().is_workspace() &&
+            compare_titles(&frame.get_title(), &title) == Ordering::Greater
+        });
+
+        if let Some(mut next) = next {
+            next.prejoin(workspace);
+        } else {
+            container.append(workspace);
+        }
+        workspace.set_plumbing_mobility(Mobility::Anchored);
+        container.relax(&mut self.coordinator);
+    }
+
+    /// Searches for display frame of output with given name.
+    fn find_display_with_name(&self, name: &str) -> Option<Frame> {
+        let matcher = |frame: &Frame| frame.get_mode().is_display() && (frame.get_title() == name);
+        self.root.find(&matcher)
+    }
+
+    /// Searches for frame containing workspaces of given display.
+    fn find_workspace_container(&self, display: &Frame) -> Option<Frame> {
+        let matcher = |frame: &Frame| frame.get_mode().is_workspace();
+        display.find(&matcher).and_then(|workspace| workspace.get_parent())
+    }
+
+    /// Searches for frame containing workspaces of display to which workspace with given title is
+    /// pinned in configuration. Returns `None` if workspace is not pinned or the display does not
+    /// exist.
+    fn find_pinned_container(&self, title: &str) -> Option<Frame> {
+        let display = match self.config.workspace_outputs.get(title) {
+            Some(name) => self.find_display_with_name(name),
+            None => None,
+        };
+        display.and_then(|display| self.find_workspace_container(&display))
+    }
+
+    /// Searches for display closest to the display of current workspace in given direction.
+    fn find_display_in_direction(&self, direction: Direction) -> Option<Frame> {
+        let display = match self.find_current_workspace().find_display() {
+            Some(display) => display,
+            None => return None,
+        };
+        let center = display.get_area().calculate_center();
+
+        let mut closest: Option<(isize, Frame)> = None;
+        for candidate in self.root.space_iter() {
+            if !candidate.get_mode().is_display() || candidate.equals_exact(&display) {
+                continue;
+            }
+
+            let other = candidate.get_area().calculate_center();
+            let (dx, dy) = (other.x - center.x, other.y - center.y);
+            let is_ahead = match direction {
+                Direction::North => dy < 0,
+                Direction::East => dx > 0,
+                Direction::South => dy > 0,
+                Direction::West => dx < 0,
+                _ => false,
+            };
+
+            let distance = dx.abs() + dy.abs();
+            if is_ahead && closest.as_ref().map_or(true, |&(d, _)| distance < d) {
+                closest = Some((distance, candidate));
+            }
+        }
+        closest.map(|(_, frame)| frame)
+    }
+
+    /// Moves given workspace to given workspace container (of other display).
+    ///
+    /// If the workspace was active on its display other workspace is activated there. If there is
+    /// no other workspace new one is created. If `focus` is `true` the workspace becomes the only
+    /// active workspace in new container, otherwise it becomes inactive.
+    fn move_workspace(&mut self, mut workspace: Frame, mut container: Frame, focus: bool) {
+        let mut old_container = workspace.get_parent().expect("workspace must have parent");
+        if old_container.equals_exact(&container) {
+            return;
+        }
+
+        log_info1!("Compositor: Move workspace '{}'", workspace.get_title());
+        let was_active = workspace.get_mode() == Mode::Workspace { is_active: true };
+
+        // Workspace being moved must still be in the tree while creating the next one to avoid
+        // title clash
+        let replacement = if was_active {
+            let other = old_container.space_iter().find(|frame| {
+                frame.get_mode().is_workspace() && !frame.equals_exact(&workspace)
+            });
+            if other.is_some() {
+                other
+            } else {
+                self.create_next_workspace()
+            }
+        } else {
+            None
+        };
+
+        // Sticky frames stay on their display
+        if let Some(mut replacement) = replacement {
+            if !replacement.has_parent() {
+                self.place_workspace(&mut replacement, &mut old_container);
+            }
+            replacement.make_active(true);
+            self.gather_sticky(&replacement);
+        }
+        workspace.remove();
+
+        if focus {
+            for neighbour in container.space_iter() {
+                neighbour.make_active(false);
+            }
+        }
+        workspace.make_active(focus);
+        self.place_workspace(&mut workspace, &mut container);
+        if focus {
+            self.gather_sticky(&workspace);
+        }
+        workspace.repack(&mut self.coordinator);
+        self.update_workspace_state();
+    }
+
+    /// Renames current workspace. Configuration of the new title is applied to the workspace. If
+    /// the new title is pinned to other output the workspace is moved there.
+    fn rename_workspace(&mut self, title: &str) -> CommandResult {
+        if title.is_empty() {
+            log_warn2!("Compositor: Workspace title can not be empty");
+            return CommandResult::Ok;
+        }
+        if self.find_workspace(title).is_some() {
+            log_warn2!("Compositor: Workspace '{}' already exists", title);
+            return CommandResult::Ok;
+        }
+
+        let mut workspace = self.find_current_workspace();
+        let old_title = workspace.get_title();
+        log_info1!("Compositor: Rename workspace '{}' to '{}'", old_title, title);
+        workspace.set_plumbing_title(title.to_owned());
+        self.strategist.forget_workspace(&old_title);
+        self.configure_workspace(&mut workspace);
+        workspace.repack(&mut self.coordinator);
+
+        if let Some(container) = self.find_pinned_container(title) {
+            self.move_workspace(workspace, container, true);
+        }
+        self.update_workspace_state();
+        CommandResult::Ok
+    }
+
+    /// Moves current workspace to display in given planar direction or changes its place among
+    /// workspaces of its display in given temporal direction.
+    fn relocate_workspace(&mut self, direction: Direction, magnitude: i32) -> CommandResult {
+        match direction {
+            Direction::North | Direction::East | Direction::South | Direction::West => {
+                if let Some(display) = self.find_display_in_direction(direction) {
+                    if let Some(container) = self.find_workspace_container(&display) {
+                        let workspace = self.find_current_workspace();
+                        self.move_workspace(workspace, container, true);
+                    }
+                }
+                CommandResult::Ok
+            }
+            Direction::Backward | Direction::Forward | Direction::Begin | Direction::End => {
+                self.reorder_workspace(direction, magnitude)
+            }
+            _ => CommandResult::NotHandled,
+        }
+    }
+
+    /// Moves current workspace backward or forward among workspaces of its display by given
+    /// number of places or to the beginning or end of the list.
+    fn reorder_workspace(&mut self, mut direction: Direction, magnitude: i32) -> CommandResult {
+        let mut workspace = self.find_current_workspace();
+        let container = workspace.get_parent().expect("workspace must have parent");
+        let workspaces = container.space_iter()
+            .filter(|frame| frame.get_mode().is_workspace())
+            .collect::<Vec<_>>();
+        let index = match workspaces.iter().position(|frame| frame.equals_exact(&workspace)) {
+            Some(index) => index,
+            None => return CommandResult::WrongFrame,
+        };
+
+        let distance = if magnitude < 0 {
+            direction = direction.reversed();
+            -magnitude
+        } else {
+            magnitude
+        } as usize;
+
+        let last = workspaces.len() - 1;
+        let target = match direction {
+            Direction::Backward => index.saturating_sub(distance),
+            Direction::Forward => std::cmp::min(index + distance, last),
+            Direction::Begin => 0,
+            _ => last,
+        };
+        if target == index {
+            return CommandResult::Ok;
+        }
+
+        log_info1!("Compositor: Reorder workspace '{}' ({:?})", workspace.get_title(), direction);
+        workspace.remove();
+        let mut neighbour = workspaces[target].clone();
+        if target < index {
+            neighbour.prejoin(&mut workspace);
+        } else {
+            neighbour.adjoin(&mut workspace);
+        }
+        self.root.pop_recursively(&mut self.selection);
+        self.update_workspace_state();
+        CommandResult::Ok
+    }
+
+    /// Destroys all inactive workspaces without any frames.
+    fn remove_empty_workspaces(&mut self) {
+        let selection = self.selection.clone();
+        let matcher = |frame: &Frame| {
+            frame.get_mode() == Mode::Workspace { is_active: false } && !frame.has_children() &&
+            !frame.equals_exact(&selection)
+        };
+
+        let mut removed = false;
+        while let Some(mut workspace) = self.root.find(&matcher) {
+            log_info2!("Compositor: Remove empty workspace '{}'", workspace.get_title());
+            self.strategist.forget_workspace(&workspace.get_title());
+            workspace.remove();
+            workspace.destroy();
+            removed = true;
+        }
+
+        if removed {
+            self.update_workspace_state();
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Private methods related to fullscreen and maximized frames.
+impl<C> Compositor<C>
+    where C: ExhibitorCoordinationTrait
+{
+    /// Changes presentation of given frame. There can be only one fullscreen or maximized frame in
+    /// workspace, so presentation of other frames is reset. Fullscreen and maximized frames get
+    /// selected if they are in active workspace.
+    fn present(&mut self, mut frame: Frame, presentation: Presentation) {
+        if !presentation.is_regular() {
+            if let Some(workspace) = frame.find_top() {
+                while let Some(mut presented) = workspace.find_presented() {
+                    presented.present(Presentation::Regular, &mut self.coordinator);
+                }
+            }
+        }
+
+        frame.present(presentation, &mut self.coordinator);
+
+        if !presentation.is_regular() && is_in_active_workspace(&frame) {
+            self.select(frame);
+        }
+    }
+
+    /// Fullscreen or maximized frame covers other frames in its workspace except floating ones.
+    /// If one of covered frames is going to be selected, the covering frame is brought back to its
+    /// place in layout.
+    fn uncover(&mut self, frame: &Frame) {
+        if frame.get_mobility().is_floating() {
+            return;
+        }
+
+        if let Some(workspace) = frame.find_top() {
+            if let Some(mut presented) = workspace.find_presented() {
+                if !presented.equals_exact(frame) {
+                    presented.present(Presentation::Regular, &mut self.coordinator);
+                }
+            }
+        }
     }
 }
 
@@ -678,10 +2414,45 @@ impl<C> Compositor<C>
 {
     /// Set given frame as selected.
     fn select(&mut self, mut frame: Frame) {
+        self.uncover(&frame);
         self.root.pop_recursively(&mut frame);
         self.selection = frame;
-        if self.selection.get_sid().is_valid() {
-            self.coordinator.set_keyboard_focus(self.selection.get_sid());
+        let sid = self.selection.get_sid();
+        if sid.is_valid() {
+            self.coordinator.set_keyboard_focus(sid);
+            if self.clear_urgency(sid) {
+                self.update_workspace_state();
+            }
+        }
+    }
+
+    /// Focuses frame of given surface bringing it from scratchpad or switching to its workspace
+    /// if needed.
+    fn focus_surface(&mut self, sid: SurfaceId) {
+        if let Some(frame) = self.scratchpad.find_with_sid(sid) {
+            self.unstash(frame);
+        } else if let Some(frame) = self.root.find_with_sid(sid) {
+            self.reveal(frame);
+            self.history.pop(sid);
+        }
+    }
+
+    /// Checks if surface with given ID may be focused by cycling through surface history. Sticky
+    /// frames are skipped unless they are selected.
+    fn is_cyclable(&self, sid: SurfaceId) -> bool {
+        if sid == self.selection.get_sid() {
+            return true;
+        }
+        match self.root.find_with_sid(sid) {
+            Some(frame) => !frame.is_sticky(),
+            None => true,
+        }
+    }
+
+    /// Lets strategist remember position of given floating frame for application of its surface.
+    fn remember_position(&mut self, frame: &Frame) {
+        if let Some(info) = self.coordinator.get_surface(frame.get_sid()) {
+            self.strategist.remember_position(&info.app_id, frame.get_position());
         }
     }
 
@@ -713,3 +2484,188 @@ impl<C> Compositor<C>
 }
 
 // -------------------------------------------------------------------------------------------------
+
+/// Checks if given command modifies frame tree and should be possible to undo.
+fn is_undoable(command: &Command) -> bool {
+    match command.action {
+        Action::Configure | Action::Jump | Action::Dive | Action::Resize | Action::Anchor => true,
+        _ => false,
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Compares titles of workspaces. Numeric titles go first in numeric order, then other titles in
+/// alphabetic order.
+fn compare_titles(a: &str, b: &str) -> Ordering {
+    match (a.parse::<u32>(), b.parse::<u32>()) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => a.cmp(b),
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Checks if given frame is placed in active workspace.
+fn is_in_active_workspace(frame: &Frame) -> bool {
+    match frame.find_top() {
+        Some(workspace) => workspace.get_mode() != Mode::Workspace { is_active: false },
+        None => false,
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Returns width or height of given frame depending on which dimension moving given edge changes.
+fn dimension_along(frame: &Frame, edge: Direction) -> isize {
+    let size = frame.get_size();
+    if edge == Direction::North || edge == Direction::South {
+        size.height as isize
+    } else {
+        size.width as isize
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Finds frame pointed by given global position. Anchored frames covered by presented (fullscreen
+/// or maximized) frame are not visible, so in such case the presented frame is returned instead.
+fn find_visible_pointed(root: &Frame, position: Position) -> Frame {
+    let pointed = root.find_pointed(position);
+    if !pointed.get_mobility().is_floating() {
+        if let Some(presented) = pointed.find_top().and_then(|top| top.find_presented()) {
+            return presented;
+        }
+    }
+    pointed
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Removes anchored subframes of given frame recursively. Surface frames are collected, other
+/// frames are destroyed.
+fn dismantle_anchored(frame: &Frame, surfaces: &mut Vec<Frame>) {
+    let subframes = frame.space_iter()
+        .filter(|subframe| subframe.get_mobility().is_anchored())
+        .collect::<Vec<_>>();
+
+    for mut subframe in subframes {
+        dismantle_anchored(&subframe, surfaces);
+        subframe.remove();
+        if subframe.get_sid().is_valid() {
+            surfaces.push(subframe);
+        } else {
+            subframe.destroy();
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Checks if given frame is anchored in frame with automatic layout. Such frames are placed only by
+/// the layout and can not be resized.
+fn is_placed_automatically(frame: &Frame) -> bool {
+    frame.get_mobility().is_anchored() &&
+    frame.get_parent().map_or(false, |parent| parent.get_layout().is_automatic())
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Limits magnitude of moving given edge of given frame so that neither the frame nor its direct
+/// neighbour gets smaller than `MIN_RESIZED_SIZE`. Frames already smaller are not forced to grow.
+fn limit_resize(frame: &Frame, edge: Direction, magnitude: isize) -> isize {
+    let shrink_limit = min(0, MIN_RESIZED_SIZE - dimension_along(frame, edge));
+    let mut magnitude = max(magnitude, shrink_limit);
+    if frame.get_mobility().is_anchored() {
+        if let Some(neighbour) = frame.find_neighbouring(edge) {
+            let grow_limit = max(0, dimension_along(&neighbour, edge) - MIN_RESIZED_SIZE);
+            magnitude = min(magnitude, grow_limit);
+        }
+    }
+    magnitude
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Returns distance (in taxicab metric) of given point from given area.
+fn distance_from_area(area: &Area, point: Position) -> isize {
+    let right = area.pos.x + area.size.width as isize;
+    let bottom = area.pos.y + area.size.height as isize;
+    let dx = max(0, max(area.pos.x - point.x, point.x - right));
+    let dy = max(0, max(area.pos.y - point.y, point.y - bottom));
+    dx + dy
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Finds leaf closest to given global position among anchored descendants of given container or
+/// workspace. Leaves further from the position than gaps of the workspace are not considered.
+fn find_leaf_across_gap(frame: &Frame, position: Position) -> Option<Frame> {
+    let mode = frame.get_mode();
+    if mode != Mode::Container && !mode.is_workspace() {
+        return None;
+    }
+
+    let spacing = frame.get_effective_spacing();
+    let margin = max(spacing.inner_gap, spacing.outer_gap) as isize;
+
+    let mut current = frame.clone();
+    while !current.get_sid().is_valid() {
+        let closest = current.space_iter()
+            .filter(|subframe| subframe.get_mobility().is_anchored())
+            .min_by_key(|subframe| {
+                let area = Area::new(subframe.calculate_global_position(), subframe.get_size());
+                distance_from_area(&area, position)
+            });
+        current = match closest {
+            Some(closest) => closest,
+            None => return None,
+        };
+    }
+
+    let area = Area::new(current.calculate_global_position(), current.get_size());
+    if distance_from_area(&area, position) <= margin {
+        Some(current)
+    } else {
+        None
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Chooses edges of given frame closest to given global position. Position in corner third of the
+/// frame chooses both edges of the corner, in middle third of a side - only that side. For
+/// position in the middle of the frame the closest edge is chosen.
+fn choose_edges(frame: &Frame, position: Position) -> Vec<Direction> {
+    let frame_position = frame.calculate_global_position();
+    let size = frame.get_size();
+    let (width, height) = (size.width as isize, size.height as isize);
+    let (x, y) = (position.x - frame_position.x, position.y - frame_position.y);
+
+    let mut edges = Vec::new();
+    if x < width / 3 {
+        edges.push(Direction::West);
+    } else if x >= width - width / 3 {
+        edges.push(Direction::East);
+    }
+    if y < height / 3 {
+        edges.push(Direction::North);
+    } else if y >= height - height / 3 {
+        edges.push(Direction::South);
+    }
+
+    if edges.is_empty() {
+        let distances = [(x, Direction::West),
+                         (width - x, Direction::East),
+                         (y, Direction::North),
+                         (height - y, Direction::South)];
+        if let Some(&(_, edge)) = distances.iter().min_by_key(|&&(distance, _)| distance) {
+            edges.push(edge);
+        }
+    }
+    edges
+}
+
+// -------------------------------------------------------------------------------------------------
@@ -7,14 +7,16 @@
 
 use std::rc::Rc;
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::time::Instant;
 
-use qualia::{Buffer, Illusion, Milliseconds, OutputInfo, perceptron, Perceptron, Position};
+use qualia::{Area, Buffer, Illusion, Milliseconds, OutputInfo, perceptron, Perceptron, Position};
 use qualia::{ExhibitorCoordinationTrait, SurfaceContext, SurfaceId};
 
 use frames::{Frame, Converting};
 use outputs::Output;
 
+use overview::Overview;
 use pointer::Pointer;
 
 // -------------------------------------------------------------------------------------------------
@@ -31,6 +33,19 @@ pub struct Display<C>
     redraw_needed: bool,
     page_flip_scheduled: bool,
     background_sid: SurfaceId,
+
+    /// Areas (relative to display) to be outlined above surfaces.
+    highlights: Vec<Area>,
+
+    /// Lock surface ID. `None` if session is not locked. Invalid ID if session is locked but there
+    /// is no lock surface for this display.
+    lock_sid: Option<SurfaceId>,
+
+    /// If `true` thumbnails of all surfaces are drawn instead of active workspaces.
+    is_overview_active: bool,
+
+    /// Global positions of drawn surfaces as last announced to other parts of application.
+    positions: HashMap<SurfaceId, Position>,
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -54,6 +69,10 @@ impl<C> Display<C>
             redraw_needed: true,
             page_flip_scheduled: false,
             background_sid: SurfaceId::invalid(),
+            highlights: Vec::new(),
+            lock_sid: None,
+            is_overview_active: false,
+            positions: HashMap::new(),
         };
         d.redraw_all(); // TODO: Remove when notifications are supported in Wayland module.
         d
@@ -74,6 +93,26 @@ impl<C> Display<C>
         self.frame = frame
     }
 
+    /// Sets areas to be outlined during next redraw.
+    pub fn set_highlights(&mut self, highlights: Vec<Area>) {
+        self.highlights = highlights;
+    }
+
+    /// Starts or stops showing thumbnails of all surfaces instead of active workspaces.
+    pub fn set_overview_active(&mut self, active: bool) {
+        self.is_overview_active = active;
+        self.on_notify();
+    }
+
+    /// Returns layout of thumbnails or `None` if display is not in overview mode.
+    pub fn get_overview(&self) -> Option<Overview> {
+        if self.is_overview_active {
+            Some(Overview::new(&self.frame, self.output.get_info().area.size))
+        } else {
+            None
+        }
+    }
+
     /// Schedule page flip on assigned output.
     pub fn schedule_pageflip(&mut self) -> Result<(), Illusion> {
         if !self.page_flip_scheduled {
@@ -125,6 +164,23 @@ impl<C> Display<C>
                                  self.pointer.borrow().get_global_position() - display_position)]
     }
 
+    /// Prepare rendering context for lock surface.
+    ///
+    /// Lock surface is shown only after it was attached a buffer. Until then nothing is drawn.
+    pub fn prepare_lock_context(&self, lock_sid: SurfaceId) -> Vec<SurfaceContext> {
+        let mut result = Vec::new();
+        if let Some(info) = self.coordinator.get_surface(lock_sid) {
+            if !info.data_source.is_none() {
+                if let Some(array) = self.coordinator.get_renderer_context(lock_sid) {
+                    for context in array.iter().rev() {
+                        result.push(context.clone());
+                    }
+                }
+            }
+        }
+        result
+    }
+
     /// Prepare rendering context for layunder.
     pub fn prepare_layunder_context(&self) -> Vec<SurfaceContext> {
         if self.background_sid.is_valid() {
@@ -140,13 +196,39 @@ impl<C> Display<C>
     fn redraw_all(&mut self) {
         let info = self.output.get_info();
 
-        let mut surfaces = self.frame.to_array(Position::default(), &self.coordinator);
+        // While session is locked draw only lock surface, without background and highlights. In
+        // overview mode draw thumbnails instead of workspaces.
+        let no_highlights = Vec::new();
+        let (mut surfaces, layunder, highlights) = if let Some(lock_sid) = self.lock_sid {
+            (self.prepare_lock_context(lock_sid), Vec::new(), &no_highlights)
+        } else if let Some(overview) = self.get_overview() {
+            (overview.to_array(&self.coordinator),
+             self.prepare_layunder_context(),
+             &self.highlights)
+        } else {
+            (self.frame.to_array(Position::default(), &self.coordinator),
+             self.prepare_layunder_context(),
+             &self.highlights)
+        };
         surfaces.reverse();
         let layover = self.prepare_layover_context(info.area.pos);
-        let layunder = self.prepare_layunder_context();
-        self.pointer.borrow_mut().update_hover_state(info.area, &surfaces);
 
-        if let Err(err) = self.output.draw(&layunder, &surfaces, &layover, &self.coordinator) {
+        // Thumbnails do not receive pointer events
+        {
+            let no_surfaces = Vec::new();
+            let hoverable = if self.is_overview_active && self.lock_sid.is_none() {
+                &no_surfaces
+            } else {
+                &surfaces
+            };
+            self.pointer.borrow_mut().update_hover_state(info.area, hoverable);
+        }
+
+        if let Err(err) = self.output.draw(&layunder,
+                                         &surfaces,
+                                         highlights,
+                                         &layover,
+                                         &self.coordinator) {
             log_error!("Display: {}", err);
         }
 
@@ -154,6 +236,11 @@ impl<C> Display<C>
             log_error!("Display: {}", err);
         }
 
+        // Thumbnails are not placed where their surfaces are
+        if !self.is_overview_active {
+            self.announce_positions(&surfaces, info.area.pos);
+        }
+
         // Send frame notifications
         for context in surfaces {
             let ms = Milliseconds::elapsed_from(&self.reference_time);
@@ -167,6 +254,19 @@ impl<C> Display<C>
         }
     }
 
+    /// Informs other parts of application about surfaces which were drawn in different place than
+    /// before.
+    fn announce_positions(&mut self, surfaces: &[SurfaceContext], display_position: Position) {
+        for context in surfaces {
+            let position = display_position + context.pos;
+            if self.positions.get(&context.id) != Some(&position) {
+                self.positions.insert(context.id, position);
+                let moved = Perceptron::SurfaceMoved(context.id, position);
+                self.coordinator.emit(perceptron::SURFACE_MOVED, moved);
+            }
+        }
+    }
+
     /// Requests output to take screenshot. Return `Buffer` containing image data.
     pub fn take_screenshot(&self) -> Option<Buffer> {
         match self.output.take_screenshot() {
@@ -182,6 +282,43 @@ impl<C> Display<C>
     pub fn on_background_change(&mut self, sid: SurfaceId) {
         self.background_sid = sid;
     }
+
+    /// Handles locking the session. Until lock surface is provided display shows solid colour.
+    pub fn lock(&mut self) {
+        if self.lock_sid.is_none() {
+            self.lock_sid = Some(SurfaceId::invalid());
+        }
+        self.on_notify();
+    }
+
+    /// Handles unlocking the session.
+    pub fn unlock(&mut self) {
+        self.lock_sid = None;
+        self.on_notify();
+    }
+
+    /// Returns lock surface ID or `None` if session is not locked.
+    pub fn get_lock_sid(&self) -> Option<SurfaceId> {
+        self.lock_sid
+    }
+
+    /// Sets lock surface. Ignored if session is not locked.
+    pub fn set_lock_surface(&mut self, sid: SurfaceId) {
+        if self.lock_sid.is_some() {
+            self.lock_sid = Some(sid);
+            self.on_notify();
+        }
+    }
+
+    /// Handles destruction of surface. If lock surface was destroyed (e.g. because locking client
+    /// crashed) session stays locked and display shows solid colour.
+    pub fn on_surface_destroyed(&mut self, sid: SurfaceId) {
+        self.positions.remove(&sid);
+        if self.lock_sid == Some(sid) {
+            self.lock_sid = Some(SurfaceId::invalid());
+            self.on_notify();
+        }
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -8,21 +8,29 @@
 use std::rc::Rc;
 use std::cell::RefCell;
 use std::collections::HashMap;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use qualia::{SurfaceId, Button, InteractionMode, Command, OptionalPosition, Position, Size, Vector};
-use qualia::{perceptron, Perceptron};
-use qualia::{CompositorConfig, ExhibitorCoordinationTrait};
+use qualia::{Action, Direction};
+use qualia::{perceptron, Perceptron, Area, WorkspaceState};
+use qualia::{CompositorConfig, ExhibitorCoordinationTrait, surface_state};
 use outputs::Output;
-use frames::Frame;
+use frames::{Frame, Searching};
 
 use compositor::Compositor;
-use pointer::Pointer;
+use pointer::{FocusPolicy, Pointer};
 use display::Display;
+use overview::Overview;
 use strategist::Strategist;
 
 // -------------------------------------------------------------------------------------------------
 
+/// Minimal interval in milliseconds between consecutive resizes of surface resized with pointer.
+/// Limits rate of reconfigure events sent to the client.
+const RESIZE_INTERVAL_MS: u64 = 30;
+
+// -------------------------------------------------------------------------------------------------
+
 /// Helper structure for dragging surfaces.
 struct SurfaceDragger {
     sid: SurfaceId,
@@ -31,6 +39,28 @@ struct SurfaceDragger {
 
 // -------------------------------------------------------------------------------------------------
 
+/// Helper structure for resizing surfaces.
+struct SurfaceResizer {
+    sid: SurfaceId,
+    edges: Vec<Direction>,
+    position: Position,
+    last_resize: Option<Instant>,
+    by_border: bool,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Helper structure for choosing surfaces in overview mode.
+struct ThumbnailChooser {
+    /// Surface of currently chosen thumbnail.
+    sid: SurfaceId,
+
+    /// Surface focused before overview mode was started.
+    previous_sid: SurfaceId,
+}
+
+// -------------------------------------------------------------------------------------------------
+
 /// `Exhibitor` manages tasks related to drawing and compositing surfaces.
 pub struct Exhibitor<C>
     where C: ExhibitorCoordinationTrait
@@ -39,8 +69,12 @@ pub struct Exhibitor<C>
     pointer: Rc<RefCell<Pointer<C>>>,
     displays: HashMap<i32, Display<C>>,
     dragger: Option<SurfaceDragger>,
+    resizer: Option<SurfaceResizer>,
+    chooser: Option<ThumbnailChooser>,
     reference_time: Instant,
     coordinator: C,
+    is_session_locked: bool,
+    warp_pointer: bool,
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -55,13 +89,26 @@ impl<C> Exhibitor<C>
                strategist: Strategist,
                compositor_config: CompositorConfig)
                -> Self {
+        let focus = compositor_config.focus.clone();
+        let policy = FocusPolicy::from_name(&focus.policy).unwrap_or_else(|| {
+            log_warn1!("Unknown focus policy '{}'", focus.policy);
+            FocusPolicy::Click
+        });
+
+        let mut pointer = Pointer::new(reference_time, coordinator.clone());
+        pointer.set_focus_policy(policy, focus.delay);
+
         Exhibitor {
             compositor: Compositor::new(coordinator.clone(), strategist, compositor_config),
-            pointer: Rc::new(RefCell::new(Pointer::new(reference_time, coordinator.clone()))),
+            pointer: Rc::new(RefCell::new(pointer)),
             displays: HashMap::new(),
             dragger: None,
+            resizer: None,
+            chooser: None,
             reference_time: reference_time,
             coordinator: coordinator,
+            is_session_locked: false,
+            warp_pointer: focus.warp_pointer,
         }
     }
 }
@@ -73,10 +120,45 @@ impl<C> Exhibitor<C>
     where C: ExhibitorCoordinationTrait + Clone
 {
     /// Handles notification about needed redraw.
+    ///
+    /// Frames selected in visual mode or chosen thumbnail in overview mode are passed to displays
+    /// to be highlighted.
     pub fn on_notify(&mut self) {
+        let visual = self.compositor.get_visual_selection();
+        let chosen = self.chooser.as_ref().map(|chooser| chooser.sid);
         for ref mut display in self.displays.values_mut() {
+            let display_frame = display.get_frame();
+            let display_position = display_frame.calculate_global_position();
+            let highlights = if let Some(sid) = chosen {
+                let mut highlights = Vec::new();
+                if let Some(overview) = display.get_overview() {
+                    if let Some(thumbnail) = overview.find(sid) {
+                        highlights.push(thumbnail.area);
+                    }
+                }
+                highlights
+            } else {
+                visual.iter()
+                    .filter(|frame| {
+                        frame.find_display().map_or(false, |d| d.equals_exact(&display_frame))
+                    })
+                    .map(|frame| {
+                        Area::new(frame.calculate_global_position() - display_position,
+                                  frame.get_size())
+                    })
+                    .collect()
+            };
+            display.set_highlights(highlights);
             display.on_notify();
         }
+        self.focus_hovered_surface_if_needed();
+    }
+
+    /// Handles timer notification.
+    ///
+    /// Focuses surface hovered by the pointer if focus policy delay elapsed in the meantime.
+    pub fn on_timer(&mut self) {
+        self.focus_hovered_surface_if_needed();
     }
 
     /// Handles notification about deactivation of seat.
@@ -104,11 +186,17 @@ impl<C> Exhibitor<C>
 
         log_info1!("Exhibitor: creating display");
         let display_frame = self.compositor.create_display(info.id, info.area, info.make.clone());
-        let display = Display::new(self.coordinator.clone(),
+        let mut display = Display::new(self.coordinator.clone(),
                                    self.reference_time,
                                    self.pointer.clone(),
                                    output,
                                    display_frame);
+        if self.is_session_locked {
+            display.lock();
+        }
+        if self.chooser.is_some() {
+            display.set_overview_active(true);
+        }
         self.displays.insert(info.id, display);
 
         self.coordinator.emit(perceptron::DISPLAY_CREATED, Perceptron::DisplayCreated(info));
@@ -121,12 +209,34 @@ impl<C> Exhibitor<C>
         if let Some(ref mut display) = self.displays.get_mut(&id) {
             display.on_pageflip();
         }
+        self.focus_hovered_surface_if_needed();
     }
 
     /// This method is called when a command was requested to be executed by compositor.
+    ///
+    /// Commands are ignored while session is locked. In overview mode commands choose thumbnails.
+    /// If configured, the pointer is placed at the center of frame selected by the command.
     pub fn on_command(&mut self, command: Command) {
+        if self.is_session_locked {
+            log_warn2!("Session is locked, ignoring command: {:?}", command);
+            return;
+        }
+
+        if self.chooser.is_some() {
+            self.execute_overview_command(command);
+            return;
+        }
+
         log_info2!("Received command: {:?}", command);
+        let previous = self.compositor.get_selection();
         self.compositor.execute_command(command);
+
+        let selection = self.compositor.get_selection();
+        if self.warp_pointer && !selection.equals_exact(&previous) {
+            let size = selection.get_size();
+            let center = Vector::new(size.width as isize / 2, size.height as isize / 2);
+            self.on_warp(selection.calculate_global_position() + center);
+        }
     }
 
     /// This method is called when changing cursor surface was requested.
@@ -145,7 +255,12 @@ impl<C> Exhibitor<C>
 
     /// This method is called when new surface is ready to be managed.
     pub fn on_surface_ready(&mut self, sid: SurfaceId) {
+        let position = self.pointer.borrow().get_global_position();
+        self.compositor.set_pointer_position(position);
         self.compositor.manage_surface(sid);
+        if self.is_session_locked {
+            self.focus_lock_surface();
+        }
     }
 
     /// Dock given surface with given size on given display.
@@ -162,6 +277,30 @@ impl<C> Exhibitor<C>
     pub fn on_surface_destroyed(&mut self, sid: SurfaceId) {
         self.compositor.unmanage_surface(sid);
         self.pointer.borrow_mut().on_surface_destroyed(sid);
+        for display in self.displays.values_mut() {
+            display.on_surface_destroyed(sid);
+        }
+        if self.is_session_locked {
+            self.focus_lock_surface();
+        }
+    }
+
+    /// This method is called when client requested to set or unset maximized or fullscreen state
+    /// of surface.
+    pub fn on_surface_state_requested(&mut self,
+                                      sid: SurfaceId,
+                                      state: surface_state::SurfaceState,
+                                      enable: bool) {
+        self.compositor.request_surface_state(sid, state, enable);
+    }
+
+    /// This method is called when client requested activation of surface.
+    ///
+    /// While session is locked surfaces are only marked as demanding attention.
+    pub fn on_surface_activation_requested(&mut self, sid: SurfaceId, granted: bool) {
+        let granted = granted && !self.is_session_locked;
+        self.compositor.activate_surface(sid, granted);
+        self.coordinator.notify();
     }
 
     /// This method is called when keyboard focus changed.
@@ -169,6 +308,46 @@ impl<C> Exhibitor<C>
         self.pointer.borrow_mut().on_keyboard_focus_changed(sid);
     }
 
+    /// This method is called when session lock was requested.
+    ///
+    /// Displays stop showing workspaces and keyboard focus is taken away from regular surfaces.
+    /// After that the rest of application is informed the session is locked.
+    pub fn on_lock_session(&mut self) {
+        log_info1!("Exhibitor: locking session");
+        self.is_session_locked = true;
+        self.deactivate_surface_drag();
+        self.compositor.end_visual();
+        self.stop_overview(false);
+        for display in self.displays.values_mut() {
+            display.lock();
+        }
+        self.focus_lock_surface();
+        self.coordinator.emit(perceptron::SESSION_LOCKED, Perceptron::SessionLocked);
+    }
+
+    /// This method is called when session unlock was requested.
+    pub fn on_unlock_session(&mut self) {
+        if self.is_session_locked {
+            log_info1!("Exhibitor: unlocking session");
+            self.is_session_locked = false;
+            for display in self.displays.values_mut() {
+                display.unlock();
+            }
+            let sid = self.compositor.get_selection().get_sid();
+            self.coordinator.set_keyboard_focus(sid);
+        }
+    }
+
+    /// This method is called when lock surface for given display was provided.
+    pub fn on_lock_surface(&mut self, sid: SurfaceId, display_id: i32) {
+        if let Some(display) = self.displays.get_mut(&display_id) {
+            display.set_lock_surface(sid);
+        }
+        if self.is_session_locked {
+            self.focus_lock_surface();
+        }
+    }
+
     /// This method is called when screenshot was requested.
     pub fn take_screenshot(&mut self, id: i32) {
         if let Some(ref mut display) = self.displays.get_mut(&id) {
@@ -189,6 +368,8 @@ impl<C> Exhibitor<C>
     pub fn on_motion(&mut self, vector: Vector) {
         self.pointer.borrow_mut().move_and_cast(vector, &self.displays);
         self.drag_surface_if_needed();
+        self.resize_surface_if_needed(false);
+        self.choose_pointed_thumbnail();
         self.coordinator.notify();
     }
 
@@ -196,17 +377,36 @@ impl<C> Exhibitor<C>
     pub fn on_position(&mut self, position: OptionalPosition) {
         self.pointer.borrow_mut().update_position(position, &self.displays);
         self.drag_surface_if_needed();
+        self.resize_surface_if_needed(false);
+        self.choose_pointed_thumbnail();
+        self.coordinator.notify();
+    }
+
+    /// Handle pointer warp event.
+    pub fn on_warp(&mut self, position: Position) {
+        self.pointer.borrow_mut().warp_and_cast(position, &self.displays);
+        self.drag_surface_if_needed();
+        self.resize_surface_if_needed(false);
+        self.choose_pointed_thumbnail();
         self.coordinator.notify();
     }
 
     /// Handle pointer button event.
+    ///
+    /// Pressing button over frame border or gap between frames starts resizing the frame until
+    /// the button is released.
     pub fn on_button(&mut self, button: Button) {
         // TODO: Be more specific about button codes and values.
-        if button.value != 0 {
+        if (button.value != 0) && !self.is_session_locked {
             let pfsid = self.pointer.borrow_mut().get_pointer_focussed_sid();
             if self.pointer.borrow_mut().get_keyboard_focussed_sid() != pfsid {
                 self.compositor.pop_surface(pfsid);
             }
+            if !pfsid.is_valid() && self.resizer.is_none() {
+                self.activate_surface_resize(true);
+            }
+        } else if (button.value == 0) && self.resizer.as_ref().map_or(false, |r| r.by_border) {
+            self.deactivate_surface_resize();
         }
     }
 
@@ -217,15 +417,44 @@ impl<C> Exhibitor<C>
 
     /// Handles interaction mode switch.
     ///
-    /// Exhibitor is concerned only about visual mode. When switched on it starts dragging surface
-    /// under cursor along the cursor.
+    /// When drag mode is switched on exhibitor starts dragging surface under cursor along the
+    /// cursor. When resize mode is switched on edges of frame under cursor closest to the cursor
+    /// follow it. When visual mode is switched on following commands are applied to group of
+    /// frames. When overview mode is switched on displays show thumbnails of all surfaces and
+    /// surface of chosen thumbnail is focused when overview mode is switched off.
     pub fn on_mode_switched(&mut self, active: bool, mode: InteractionMode) {
-        if mode == InteractionMode::Visual {
-            if active {
-                self.activate_surface_drag();
-            } else {
-                self.deactivate_surface_drag();
+        match mode {
+            InteractionMode::Drag => {
+                if active && !self.is_session_locked {
+                    self.activate_surface_drag();
+                } else {
+                    self.deactivate_surface_drag();
+                }
+            }
+            InteractionMode::Resize => {
+                if active && !self.is_session_locked {
+                    self.activate_surface_resize(false);
+                } else {
+                    self.deactivate_surface_resize();
+                }
             }
+            InteractionMode::Visual => {
+                if active && !self.is_session_locked {
+                    self.compositor.start_visual();
+                } else {
+                    self.compositor.end_visual();
+                }
+                self.coordinator.notify();
+            }
+            InteractionMode::Overview => {
+                if active && !self.is_session_locked {
+                    self.start_overview();
+                } else {
+                    self.stop_overview(true);
+                }
+                self.coordinator.notify();
+            }
+            _ => {}
         }
     }
 }
@@ -245,6 +474,46 @@ impl<C> Exhibitor<C>
     pub fn get_selection(&self) -> Frame {
         self.compositor.get_selection()
     }
+
+    /// Returns frames selected in visual mode. Empty if visual mode is not active.
+    pub fn get_visual_selection(&self) -> Vec<Frame> {
+        self.compositor.get_visual_selection()
+    }
+
+    /// Returns root of frames hidden in scratchpad.
+    pub fn get_scratchpad(&self) -> Frame {
+        self.compositor.get_scratchpad()
+    }
+
+    /// Returns map from names of marks to IDs of marked surfaces.
+    pub fn get_marks(&self) -> HashMap<String, SurfaceId> {
+        self.compositor.get_marks()
+    }
+
+    /// Returns IDs of surfaces demanding attention ordered from the least recent.
+    pub fn get_urgent(&self) -> Vec<SurfaceId> {
+        self.compositor.get_urgent()
+    }
+
+    /// Returns state of workspaces.
+    pub fn get_workspace_state(&self) -> WorkspaceState {
+        self.compositor.get_workspace_state()
+    }
+
+    /// Returns ID of surface of chosen thumbnail or `None` if overview mode is not active.
+    pub fn get_chosen_thumbnail(&self) -> Option<SurfaceId> {
+        self.chooser.as_ref().map(|chooser| chooser.sid)
+    }
+
+    /// Returns position of the pointer in global coordinates.
+    pub fn get_pointer_position(&self) -> Position {
+        self.pointer.borrow().get_global_position()
+    }
+
+    /// Checks if session is locked.
+    pub fn is_session_locked(&self) -> bool {
+        self.is_session_locked
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -281,10 +550,196 @@ impl<C> Exhibitor<C>
 
 // -------------------------------------------------------------------------------------------------
 
+/// Resizing helper methods
+impl<C> Exhibitor<C>
+    where C: ExhibitorCoordinationTrait
+{
+    fn activate_surface_resize(&mut self, by_border: bool) {
+        let position = self.pointer.borrow().get_global_position();
+        if let Some((sid, edges)) = self.compositor.find_resize_target(position) {
+            self.resizer = Some(SurfaceResizer {
+                                    sid: sid,
+                                    edges: edges,
+                                    position: position,
+                                    last_resize: None,
+                                    by_border: by_border,
+                                })
+        }
+    }
+
+    fn deactivate_surface_resize(&mut self) {
+        self.resize_surface_if_needed(true);
+        self.resizer = None;
+    }
+
+    /// Resizes surface along the pointer. To limit number of reconfigurations the surface is
+    /// resized only if `RESIZE_INTERVAL_MS` elapsed from previous resize or if `force` is `true`.
+    fn resize_surface_if_needed(&mut self, force: bool) {
+        if let Some(ref mut resizer) = self.resizer {
+            let interval = Duration::from_millis(RESIZE_INTERVAL_MS);
+            let due = force || resizer.last_resize.map_or(true, |time| time.elapsed() >= interval);
+            let new_position = self.pointer.borrow().get_global_position();
+            let vector = new_position - resizer.position;
+            if due && !vector.is_zero() {
+                self.compositor.resize_globally(resizer.sid, &resizer.edges, vector);
+                resizer.position = new_position;
+                resizer.last_resize = Some(Instant::now());
+            }
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Overview helper methods
+impl<C> Exhibitor<C>
+    where C: ExhibitorCoordinationTrait
+{
+    fn start_overview(&mut self) {
+        if self.chooser.is_none() {
+            let sid = self.compositor.get_selection().get_sid();
+            self.chooser = Some(ThumbnailChooser {
+                                    sid: sid,
+                                    previous_sid: sid,
+                                });
+            for display in self.displays.values_mut() {
+                display.set_overview_active(true);
+            }
+        }
+    }
+
+    /// Stops overview mode. If `focus` is `true` surface of chosen thumbnail gets focused.
+    fn stop_overview(&mut self, focus: bool) {
+        if let Some(chooser) = self.chooser.take() {
+            for display in self.displays.values_mut() {
+                display.set_overview_active(false);
+            }
+            if focus && chooser.sid.is_valid() {
+                self.compositor.activate_surface(chooser.sid, true);
+            }
+        }
+    }
+
+    /// Executes command in overview mode. Focusing in planar direction chooses neighbouring
+    /// thumbnail, focusing `Begin` chooses thumbnail with number equal to magnitude and focusing
+    /// `Backward` chooses back surface focused before overview mode was started. Other commands
+    /// are ignored.
+    fn execute_overview_command(&mut self, command: Command) {
+        let (sid, previous_sid) = if let Some(ref chooser) = self.chooser {
+            (chooser.sid, chooser.previous_sid)
+        } else {
+            return;
+        };
+
+        let overview = self.find_overview(sid);
+        let chosen = match (command.action, command.direction) {
+            (Action::Focus, Direction::Backward) => Some(previous_sid),
+            (Action::Focus, Direction::Begin) if command.magnitude > 0 => {
+                overview.and_then(|o| o.find_nth(command.magnitude as usize))
+            }
+            (Action::Focus, direction) if direction.is_planar() => {
+                overview.and_then(|o| o.find_neighbouring(sid, direction))
+            }
+            _ => {
+                log_warn2!("Command not supported in overview mode: {:?}", command);
+                None
+            }
+        };
+
+        if let Some(chosen) = chosen {
+            if let Some(ref mut chooser) = self.chooser {
+                chooser.sid = chosen;
+            }
+            self.coordinator.notify();
+        }
+    }
+
+    /// Chooses thumbnail hovered by the pointer.
+    fn choose_pointed_thumbnail(&mut self) {
+        if self.chooser.is_none() {
+            return;
+        }
+
+        let position = self.pointer.borrow().get_global_position();
+        let mut pointed = None;
+        for display in self.displays.values() {
+            let area = display.get_info().area;
+            if area.contains(&position) {
+                if let Some(overview) = display.get_overview() {
+                    pointed = overview.find_pointed(position - area.pos);
+                }
+            }
+        }
+
+        if let Some(sid) = pointed {
+            if let Some(ref mut chooser) = self.chooser {
+                chooser.sid = sid;
+            }
+        }
+    }
+
+    /// Returns overview of display showing thumbnail of given surface. If there is no such display
+    /// overview of display the pointer is on is returned.
+    fn find_overview(&self, sid: SurfaceId) -> Option<Overview> {
+        let position = self.pointer.borrow().get_global_position();
+        let mut result = None;
+        for display in self.displays.values() {
+            if let Some(overview) = display.get_overview() {
+                if overview.find(sid).is_some() {
+                    return Some(overview);
+                }
+                if display.get_info().area.contains(&position) {
+                    result = Some(overview);
+                }
+            }
+        }
+        result
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
 /// Other helper methods
 impl<C> Exhibitor<C>
     where C: ExhibitorCoordinationTrait
 {
+    /// Focuses surface hovered by the pointer if focus policy requested it. Nothing is focused
+    /// while session is locked, when surface is dragged or resized or in overview mode.
+    fn focus_hovered_surface_if_needed(&mut self) {
+        let request = self.pointer.borrow_mut().take_focus_request();
+        if let Some(sid) = request {
+            if self.is_session_locked || self.dragger.is_some() || self.resizer.is_some() ||
+               self.chooser.is_some() {
+                return;
+            }
+
+            if sid.is_valid() {
+                if self.pointer.borrow().get_keyboard_focussed_sid() != sid {
+                    self.compositor.pop_surface(sid);
+                }
+            } else {
+                self.coordinator.set_keyboard_focus(sid);
+            }
+        }
+    }
+
+    /// Sets keyboard focus on lock surface of display the pointer is on. If that display has no
+    /// lock surface other lock surface is chosen. If there are no lock surfaces at all no surface
+    /// will be focused.
+    fn focus_lock_surface(&mut self) {
+        let position = self.pointer.borrow().get_global_position();
+        let mut sid = SurfaceId::invalid();
+        for display in self.displays.values() {
+            if let Some(lock_sid) = display.get_lock_sid() {
+                if lock_sid.is_valid() &&
+                   (!sid.is_valid() || display.get_info().area.contains(&position)) {
+                    sid = lock_sid;
+                }
+            }
+        }
+        self.coordinator.set_keyboard_focus(sid);
+    }
+
     /// Chooses new display position.
     ///
     /// New position is always chosen to be right to most right display.
@@ -5,6 +5,8 @@
 //! etc. - high level logic for drawing surfaces.
 
 extern crate rand;
+extern crate regex;
+extern crate yaml_rust;
 
 #[macro_use]
 extern crate timber;
@@ -20,7 +22,10 @@ mod compositor;
 pub use compositor::Compositor;
 
 mod pointer;
-pub use pointer::Pointer;
+pub use pointer::{FocusPolicy, Pointer};
+
+mod overview;
+pub use overview::{Overview, Thumbnail};
 
 mod display;
 pub use display::Display;
@@ -28,6 +33,12 @@ pub use display::Display;
 mod exhibitor;
 pub use exhibitor::Exhibitor;
 
-mod strategies;
+mod templates;
+pub use templates::{FrameTemplate, Swallow};
+
+mod snapshots;
+
+mod rules;
+pub mod strategies;
 mod strategist;
-pub use strategist::Strategist;
+pub use strategist::{FloatingContext, FloatingDecision, Strategist, TargetDecision};
@@ -0,0 +1,190 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! This module contains functionality for laying out thumbnails of surfaces from all workspaces of
+//! a display in a grid when the display is in overview mode.
+
+// -------------------------------------------------------------------------------------------------
+
+use qualia::{Area, Direction, Position, Size, SurfaceContext, SurfaceId, SurfaceListing};
+use frames::Frame;
+
+// -------------------------------------------------------------------------------------------------
+
+/// Distance in pixels between edges of a grid cell and the thumbnail placed in it.
+const THUMBNAIL_MARGIN: usize = 10;
+
+// -------------------------------------------------------------------------------------------------
+
+/// Scaled down view of a surface frame.
+#[derive(Clone, Debug)]
+pub struct Thumbnail {
+    /// ID of the surface.
+    pub sid: SurfaceId,
+
+    /// Area of the thumbnail relative to the display.
+    pub area: Area,
+
+    /// Ratio of size of the thumbnail to size of the frame.
+    pub scale: f32,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Grid of thumbnails of surfaces of one display.
+///
+/// Thumbnails are ordered workspace by workspace and are numbered starting from one in reading
+/// order.
+#[derive(Clone, Debug)]
+pub struct Overview {
+    thumbnails: Vec<Thumbnail>,
+    columns: usize,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Overview {
+    /// Lays out thumbnails of all surfaces from workspaces of given display frame on display of
+    /// given size. Docked surfaces are not shown.
+    pub fn new(display_frame: &Frame, display_size: Size) -> Self {
+        let mut frames = Vec::new();
+        collect_workspace_frames(display_frame, &mut frames);
+
+        let number = frames.len();
+        let mut columns = 1;
+        while columns * columns < number {
+            columns += 1;
+        }
+        let rows = if number > 0 { (number + columns - 1) / columns } else { 1 };
+
+        let cell = Size::new(display_size.width / columns, display_size.height / rows);
+        let thumbnails = frames.iter()
+            .enumerate()
+            .map(|(i, frame)| {
+                let cell_position = Position::new((cell.width * (i % columns)) as isize,
+                                                  (cell.height * (i / columns)) as isize);
+                Self::fit(frame, cell_position, cell)
+            })
+            .collect();
+
+        Overview {
+            thumbnails: thumbnails,
+            columns: columns,
+        }
+    }
+
+    /// Returns thumbnails in reading order.
+    pub fn get_thumbnails(&self) -> &Vec<Thumbnail> {
+        &self.thumbnails
+    }
+
+    /// Searches for thumbnail of surface with given ID.
+    pub fn find(&self, sid: SurfaceId) -> Option<&Thumbnail> {
+        self.thumbnails.iter().find(|thumbnail| thumbnail.sid == sid)
+    }
+
+    /// Returns ID of surface with thumbnail of given number. Numbering starts from one.
+    pub fn find_nth(&self, number: usize) -> Option<SurfaceId> {
+        if number > 0 {
+            self.thumbnails.get(number - 1).map(|thumbnail| thumbnail.sid)
+        } else {
+            None
+        }
+    }
+
+    /// Returns ID of surface with thumbnail containing given point relative to display.
+    pub fn find_pointed(&self, point: Position) -> Option<SurfaceId> {
+        self.thumbnails
+            .iter()
+            .find(|thumbnail| thumbnail.area.contains(&point))
+            .map(|thumbnail| thumbnail.sid)
+    }
+
+    /// Returns ID of surface with thumbnail neighbouring in grid with thumbnail of given surface in
+    /// given direction. If given surface has no thumbnail the first one is returned.
+    pub fn find_neighbouring(&self, sid: SurfaceId, direction: Direction) -> Option<SurfaceId> {
+        let index = if let Some(index) = self.thumbnails.iter().position(|t| t.sid == sid) {
+            index
+        } else {
+            return self.find_nth(1);
+        };
+
+        let neighbour = match direction {
+            Direction::West if index % self.columns > 0 => Some(index - 1),
+            Direction::East if (index + 1) % self.columns > 0 => Some(index + 1),
+            Direction::North if index >= self.columns => Some(index - self.columns),
+            Direction::South => Some(index + self.columns),
+            _ => None,
+        };
+        neighbour.and_then(|index| self.thumbnails.get(index)).map(|thumbnail| thumbnail.sid)
+    }
+
+    /// Converts thumbnails to list of `SurfaceContext` suitable for drawing by renderer.
+    pub fn to_array(&self, listing: &SurfaceListing) -> Vec<SurfaceContext> {
+        let mut result = Vec::new();
+        for thumbnail in self.thumbnails.iter() {
+            if let Some(array) = listing.get_renderer_context(thumbnail.sid) {
+                for context in array.iter().rev() {
+                    result.push(context.moved(thumbnail.area.pos)
+                        .scaled(thumbnail.area.pos, thumbnail.scale));
+                }
+            }
+        }
+        result
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Helper methods.
+impl Overview {
+    /// Creates thumbnail of given frame scaled to fit in cell of given size keeping its aspect
+    /// ratio. Thumbnails are centered in cells and never scaled up.
+    fn fit(frame: &Frame, cell_position: Position, cell_size: Size) -> Thumbnail {
+        let size = frame.get_size();
+        let max_width = cell_size.width.saturating_sub(2 * THUMBNAIL_MARGIN).max(1);
+        let max_height = cell_size.height.saturating_sub(2 * THUMBNAIL_MARGIN).max(1);
+        let scale = (max_width as f32 / size.width.max(1) as f32)
+            .min(max_height as f32 / size.height.max(1) as f32)
+            .min(1.0);
+
+        let thumbnail_size = Size::new((size.width as f32 * scale) as usize,
+                                       (size.height as f32 * scale) as usize);
+        let x = cell_position.x + cell_size.width.saturating_sub(thumbnail_size.width) as isize / 2;
+        let y = cell_position.y +
+                cell_size.height.saturating_sub(thumbnail_size.height) as isize / 2;
+        Thumbnail {
+            sid: frame.get_sid(),
+            area: Area::new(Position::new(x, y), thumbnail_size),
+            scale: scale,
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Appends surface frames from workspaces found in given frame to `result`.
+fn collect_workspace_frames(frame: &Frame, result: &mut Vec<Frame>) {
+    for subframe in frame.space_iter() {
+        if subframe.get_mode().is_workspace() {
+            collect_surface_frames(&subframe, result);
+        } else if !subframe.get_sid().is_valid() {
+            collect_workspace_frames(&subframe, result);
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Appends frames with surfaces found in given frame to `result`.
+fn collect_surface_frames(frame: &Frame, result: &mut Vec<Frame>) {
+    for subframe in frame.space_iter() {
+        if subframe.get_sid().is_valid() {
+            result.push(subframe);
+        } else {
+            collect_surface_frames(&subframe, result);
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
@@ -6,7 +6,7 @@
 // -------------------------------------------------------------------------------------------------
 
 use std::collections::HashMap;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use qualia::{Area, Milliseconds, OptionalPosition, Position, Vector};
 use qualia::{perceptron, Perceptron, SurfaceContext, SurfaceId, ExhibitorCoordinationTrait};
@@ -15,6 +15,35 @@ use display::Display;
 
 // -------------------------------------------------------------------------------------------------
 
+/// Decides how keyboard focus follows the pointer.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FocusPolicy {
+    /// Surface gets focused when clicked.
+    Click,
+
+    /// Surface gets focused when hovered. Focus is taken away when no surface is hovered.
+    Follow,
+
+    /// Surface gets focused when hovered. Focus stays on last surface when no surface is hovered.
+    Sloppy,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl FocusPolicy {
+    /// Returns focus policy with given name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "click" => Some(FocusPolicy::Click),
+            "follow" => Some(FocusPolicy::Follow),
+            "sloppy" => Some(FocusPolicy::Sloppy),
+            _ => None,
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
 /// State of the pointer.
 pub struct Pointer<C>
     where C: ExhibitorCoordinationTrait
@@ -40,6 +69,15 @@ pub struct Pointer<C>
     /// Surface ID of keyboard-focused surface.
     kfsid: SurfaceId,
 
+    /// Focus policy.
+    focus_policy: FocusPolicy,
+
+    /// Time the pointer has to stay over surface before it gets focused.
+    focus_delay: Duration,
+
+    /// Surface hovered by the pointer waiting to be focused and time since it is hovered.
+    focus_request: Option<(SurfaceId, Instant)>,
+
     /// Reference time for obtaining event timestamps.
     reference_time: Instant,
 
@@ -62,6 +100,9 @@ impl<C> Pointer<C>
             csid: SurfaceId::invalid(),
             pfsid: SurfaceId::invalid(),
             kfsid: SurfaceId::invalid(),
+            focus_policy: FocusPolicy::Click,
+            focus_delay: Duration::from_millis(0),
+            focus_request: None,
             reference_time: reference_time,
             coordinator: coordinator,
         }
@@ -71,6 +112,14 @@ impl<C> Pointer<C>
         self.position = area.calculate_center();
         self.display_area = area;
     }
+
+    /// Sets focus policy and time in milliseconds the pointer has to stay over surface before it
+    /// gets focused.
+    pub fn set_focus_policy(&mut self, policy: FocusPolicy, delay: u32) {
+        self.focus_policy = policy;
+        self.focus_delay = Duration::from_millis(delay as u64);
+        self.focus_request = None;
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -138,6 +187,11 @@ impl<C> Pointer<C>
         self.move_and_cast(vector.clone(), displays);
     }
 
+    /// Place pointer at given global position and cast to correct output.
+    pub fn warp_and_cast(&mut self, position: Position, displays: &HashMap<i32, Display<C>>) {
+        self.position = self.cast(position, displays);
+    }
+
     /// Reset position of the pointer.
     pub fn reset_position(&mut self) {
         self.last_position = OptionalPosition::default()
@@ -172,7 +226,8 @@ impl<C> Pointer<C>
         if sid != self.pfsid {
             self.pfsid = sid;
             self.csid = SurfaceId::invalid();
-            self.coordinator.set_pointer_focus(sid, surface_relative)
+            self.coordinator.set_pointer_focus(sid, surface_relative);
+            self.request_focus(sid);
         } else if self.pfsid.is_valid() && (surface_relative != self.last_surface_relative) {
             let ms = Milliseconds::elapsed_from(&self.reference_time);
             self.last_surface_relative = surface_relative;
@@ -200,6 +255,17 @@ impl<C> Pointer<C>
         self.kfsid = sid;
     }
 
+    /// Returns ID of surface which should be focused because of the focus policy or `None` if
+    /// focus should not change. Invalid ID means focus should be taken away from all surfaces.
+    pub fn take_focus_request(&mut self) -> Option<SurfaceId> {
+        let due = self.focus_request.map_or(false, |(_, time)| time.elapsed() >= self.focus_delay);
+        if due {
+            self.focus_request.take().map(|(sid, _)| sid)
+        } else {
+            None
+        }
+    }
+
     /// Handles cursor surface change request.
     pub fn on_surface_change(&mut self, sid: SurfaceId) {
         self.csid = sid;
@@ -237,6 +303,22 @@ impl<C> Pointer<C>
         }
         position
     }
+
+    /// Remembers newly hovered surface to be focused later if focus policy requires it. When no
+    /// surface is hovered, with sloppy focus pending request is cancelled.
+    fn request_focus(&mut self, sid: SurfaceId) {
+        self.focus_request = match self.focus_policy {
+            FocusPolicy::Click => None,
+            FocusPolicy::Follow => Some((sid, Instant::now())),
+            FocusPolicy::Sloppy => {
+                if sid.is_valid() {
+                    Some((sid, Instant::now()))
+                } else {
+                    None
+                }
+            }
+        };
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
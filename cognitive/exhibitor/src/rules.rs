@@ -0,0 +1,119 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! This module contains window rules used by `Strategist` to override strategies for surfaces
+//! matching given criteria.
+
+// -------------------------------------------------------------------------------------------------
+
+use regex::Regex;
+
+use qualia::{RuleConfig, SurfaceInfo};
+use frames::Geometry;
+
+// -------------------------------------------------------------------------------------------------
+
+/// Window rule with criteria ready to be matched against surfaces.
+#[derive(Clone, Debug)]
+pub struct Rule {
+    /// Application ID (or class) the surface must have.
+    pub app_id: Option<String>,
+
+    /// Regular expression the title of the surface must match.
+    pub title: Option<Regex>,
+
+    /// Required popup status of the surface.
+    pub popup: Option<bool>,
+
+    /// Title of workspace the surface should be placed in.
+    pub workspace: Option<String>,
+
+    /// Name of output on which active workspace the surface should be placed.
+    pub output: Option<String>,
+
+    /// Forced floating (`true`) or anchored (`false`) mode.
+    pub floating: Option<bool>,
+
+    /// Initial width of floating surface.
+    pub width: Option<usize>,
+
+    /// Initial height of floating surface.
+    pub height: Option<usize>,
+
+    /// Initial horizontal position of floating surface.
+    pub x: Option<isize>,
+
+    /// Initial vertical position of floating surface.
+    pub y: Option<isize>,
+
+    /// Geometry of container enclosing anchored surface.
+    pub geometry: Option<Geometry>,
+
+    /// If `true` the surface should not be focused when mapped.
+    pub no_focus: bool,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Rule {
+    /// Creates new `Rule` from configuration. Returns description of error if configuration is
+    /// invalid.
+    pub fn new(config: &RuleConfig) -> Result<Self, String> {
+        let title = match config.title {
+            Some(ref pattern) => {
+                match Regex::new(pattern) {
+                    Ok(regex) => Some(regex),
+                    Err(err) => return Err(format!("invalid title '{}': {}", pattern, err)),
+                }
+            }
+            None => None,
+        };
+
+        let geometry = match config.geometry {
+            Some(ref name) => {
+                match Geometry::from_name(name) {
+                    Some(geometry) => Some(geometry),
+                    None => return Err(format!("unknown geometry '{}'", name)),
+                }
+            }
+            None => None,
+        };
+
+        Ok(Rule {
+            app_id: config.app_id.clone(),
+            title: title,
+            popup: config.popup,
+            workspace: config.workspace.clone(),
+            output: config.output.clone(),
+            floating: config.floating,
+            width: config.width.map(|v| v as usize),
+            height: config.height.map(|v| v as usize),
+            x: config.x.map(|v| v as isize),
+            y: config.y.map(|v| v as isize),
+            geometry: geometry,
+            no_focus: config.no_focus,
+        })
+    }
+
+    /// Checks if given surface meets all criteria of the rule.
+    pub fn matches(&self, surface: &SurfaceInfo) -> bool {
+        if let Some(ref app_id) = self.app_id {
+            if *app_id != surface.app_id {
+                return false;
+            }
+        }
+        if let Some(ref title) = self.title {
+            if !title.is_match(&surface.title) {
+                return false;
+            }
+        }
+        if let Some(popup) = self.popup {
+            if popup != surface.parent_sid.is_valid() {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
@@ -0,0 +1,236 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! This module contains functionality for taking snapshots of frame layout and bringing the layout
+//! back to the state described by a snapshot. It is used for undoing and redoing commands.
+
+// -------------------------------------------------------------------------------------------------
+
+use std::collections::HashMap;
+
+use qualia::{Area, SurfaceAccess, SurfaceId};
+use frames::{Frame, Geometry, Mobility};
+use frames::{Packing, Settling};
+
+use templates::{Placeholders, Swallow};
+
+// -------------------------------------------------------------------------------------------------
+
+/// Map from surface ID to surface frame and workspace it was taken from.
+type SurfaceFrames = HashMap<SurfaceId, (Frame, Frame)>;
+
+// -------------------------------------------------------------------------------------------------
+
+/// Description of frame and its not docked subframes.
+#[derive(Clone, Debug)]
+pub struct FrameSnapshot {
+    pub sid: SurfaceId,
+    pub geometry: Geometry,
+    pub mobility: Mobility,
+    pub ratio: f32,
+    pub area: Area,
+    pub swallow: Option<Swallow>,
+    pub branches: Vec<FrameSnapshot>,
+
+    /// Indices of `branches` in time order.
+    pub time_order: Vec<usize>,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl FrameSnapshot {
+    /// Creates snapshot of given frame and its subframes. Criteria of placeholders are taken from
+    /// `placeholders`.
+    pub fn from_frame(frame: &Frame, placeholders: &Placeholders) -> Self {
+        let mut branches = Vec::new();
+        let mut subframes = Vec::new();
+        for subframe in frame.space_iter() {
+            if !subframe.get_mobility().is_docked() {
+                branches.push(Self::from_frame(&subframe, placeholders));
+                subframes.push(subframe);
+            }
+        }
+
+        let time_order = frame.time_iter()
+            .filter_map(|child| subframes.iter().position(|subframe| subframe.equals_exact(&child)))
+            .collect();
+
+        let swallow = placeholders.get(frame).cloned();
+
+        FrameSnapshot {
+            sid: frame.get_sid(),
+            geometry: frame.get_geometry(),
+            mobility: frame.get_mobility(),
+            ratio: frame.get_ratio(),
+            area: frame.get_area(),
+            swallow: swallow,
+            branches: branches,
+            time_order: time_order,
+        }
+    }
+
+    /// Recreates subframes described by branches of the snapshot inside `target`.
+    fn rebuild_branches(&self,
+                        target: &mut Frame,
+                        surfaces: &mut SurfaceFrames,
+                        placeholders: &mut Placeholders,
+                        sa: &mut SurfaceAccess) {
+        let mut rebuilt = Vec::new();
+        for (index, branch) in self.branches.iter().enumerate() {
+            if let Some(mut frame) = branch.rebuild(surfaces, placeholders, sa) {
+                target.append(&mut frame);
+                if branch.mobility.is_floating() {
+                    frame.set_size(branch.area.size, sa);
+                    frame.set_plumbing_position(branch.area.pos);
+                }
+                rebuilt.push((index, frame));
+            }
+        }
+
+        for index in self.time_order.iter().rev() {
+            if let Some(&mut (_, ref mut frame)) = rebuilt.iter_mut().find(|e| e.0 == *index) {
+                frame.pop();
+            }
+        }
+    }
+
+    /// Recreates frame described by the snapshot. Surface frames are taken from `surfaces`. If
+    /// the surface does not exist anymore or container has no subframes left, `None` is returned.
+    fn rebuild(&self,
+               surfaces: &mut SurfaceFrames,
+               placeholders: &mut Placeholders,
+               sa: &mut SurfaceAccess)
+               -> Option<Frame> {
+        let mut frame = if self.sid.is_valid() {
+            if let Some((frame, _)) = surfaces.remove(&self.sid) {
+                frame
+            } else {
+                return None;
+            }
+        } else {
+            let mut frame = Frame::new_container(self.geometry);
+            self.rebuild_branches(&mut frame, surfaces, placeholders, sa);
+            if let Some(ref swallow) = self.swallow {
+                placeholders.add(&mut frame, swallow.clone());
+            } else if !frame.has_children() {
+                frame.destroy();
+                return None;
+            }
+            frame
+        };
+
+        frame.set_plumbing_geometry(self.geometry);
+        frame.set_plumbing_mobility(self.mobility);
+        frame.set_plumbing_ratio(self.ratio);
+        Some(frame)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Description of workspace and its frames.
+#[derive(Clone, Debug)]
+pub struct WorkspaceSnapshot {
+    pub title: String,
+    pub frame: FrameSnapshot,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Description of layouts of all workspaces together with selected surface.
+#[derive(Clone, Debug)]
+pub struct LayoutSnapshot {
+    pub workspaces: Vec<WorkspaceSnapshot>,
+    pub selection: SurfaceId,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl LayoutSnapshot {
+    /// Creates snapshot of all workspaces found in given root frame.
+    pub fn new(root: &Frame, selection: SurfaceId, placeholders: &Placeholders) -> Self {
+        let mut workspaces = Vec::new();
+        for display in root.space_iter() {
+            for workspace in display.space_iter() {
+                if workspace.get_mode().is_workspace() {
+                    workspaces.push(WorkspaceSnapshot {
+                        title: workspace.get_title(),
+                        frame: FrameSnapshot::from_frame(&workspace, placeholders),
+                    });
+                }
+            }
+        }
+
+        LayoutSnapshot {
+            workspaces: workspaces,
+            selection: selection,
+        }
+    }
+
+    /// Brings layouts of workspaces found in given root frame back to the state described by the
+    /// snapshot.
+    ///
+    /// Surfaces destroyed in the meantime are skipped together with containers left empty.
+    /// Surfaces which appeared after the snapshot was taken are settled back in workspaces they
+    /// were in. `placeholders` are replaced with placeholders recreated from the snapshot.
+    pub fn restore(&self, root: &Frame, placeholders: &mut Placeholders, sa: &mut SurfaceAccess) {
+        // Take all frames out of workspaces
+        let mut surfaces = SurfaceFrames::new();
+        let mut workspaces = Vec::new();
+        for display in root.space_iter() {
+            for workspace in display.space_iter() {
+                if workspace.get_mode().is_workspace() {
+                    dismantle(&workspace, &workspace, &mut surfaces);
+                    workspaces.push(workspace);
+                }
+            }
+        }
+
+        // Rebuild workspaces from snapshot
+        placeholders.clear();
+        for snapshot in self.workspaces.iter() {
+            let workspace = workspaces.iter().find(|w| w.get_title() == snapshot.title).cloned();
+            if let Some(mut workspace) = workspace {
+                workspace.set_plumbing_geometry(snapshot.frame.geometry);
+                snapshot.frame
+                    .rebuild_branches(&mut workspace, &mut surfaces, placeholders, sa);
+            }
+        }
+
+        // Bring back surfaces not present in snapshot
+        for (_, (mut frame, mut workspace)) in surfaces.drain() {
+            let area = if frame.get_mobility().is_floating() {
+                Some(frame.get_area())
+            } else {
+                None
+            };
+            frame.settle(&mut workspace, area, sa);
+        }
+
+        for mut workspace in workspaces {
+            workspace.repack(sa);
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Removes all not docked subframes of given frame recursively. Surface frames are collected
+/// together with given workspace. Other frames are destroyed.
+fn dismantle(frame: &Frame, workspace: &Frame, surfaces: &mut SurfaceFrames) {
+    let subframes = frame.space_iter()
+        .filter(|subframe| !subframe.get_mobility().is_docked())
+        .collect::<Vec<_>>();
+
+    for mut subframe in subframes {
+        dismantle(&subframe, workspace, surfaces);
+        subframe.remove();
+        if subframe.get_sid().is_valid() {
+            surfaces.insert(subframe.get_sid(), (subframe, workspace.clone()));
+        } else {
+            subframe.destroy();
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
@@ -3,13 +3,11 @@
 
 //! This module provides strategies to be used by `Strategist`.
 
-// TODO: Add strategy to place floating frame in random corner.
-
 #![allow(dead_code)]
 
 // -------------------------------------------------------------------------------------------------
 
-use std::cmp::max;
+use std::cmp::{max, min};
 
 use rand;
 use rand::distributions::{IndependentSample, Range};
@@ -18,7 +16,12 @@ use qualia::{Area, Position, Size, SurfaceInfo};
 
 use frames::{self, Frame, Searching};
 
-use strategist::{TargetDecision, FloatingDecision, Strategist};
+use strategist::{TargetDecision, FloatingContext, FloatingDecision, Strategist};
+
+// -------------------------------------------------------------------------------------------------
+
+/// Offset between consecutive frames placed by `cascade` strategy.
+const CASCADE_STEP: isize = 32;
 
 // -------------------------------------------------------------------------------------------------
 
@@ -29,19 +32,14 @@ pub fn choose_target_always_floating(strategist: &Strategist,
                                      selection: &Frame,
                                      surface: &SurfaceInfo)
                                      -> TargetDecision {
-    let preferred_size = if !surface.requested_size.is_zero() {
-        Some(surface.requested_size)
-    } else {
-        None
-    };
-
     let workspace = selection.find_top().expect("searching workspace for floating");
-    let floating = strategist.choose_floating(workspace.get_size(), preferred_size);
+    let floating = strategist.choose_floating(&workspace, Some(surface));
     TargetDecision {
         target: workspace,
         geometry: frames::Geometry::Vertical,
         selection: true,
         floating: Some(floating),
+        container: None,
     }
 }
 
@@ -62,6 +60,7 @@ pub fn choose_target_anchored_but_popups(strategist: &Strategist,
             geometry: frames::Geometry::Stacked,
             selection: true,
             floating: None,
+            container: None,
         }
     } else {
         choose_target_always_floating(strategist, selection, surface)
@@ -75,14 +74,10 @@ pub fn choose_target_anchored_but_popups(strategist: &Strategist,
 /// The frame will always be centered. If preferred size was not provided the frame will have 1/2
 /// of width and height of workspace.
 pub fn choose_floating_always_centered(_strategist: &Strategist,
-                                       workspace_size: Size,
-                                       preferred_size: Option<Size>)
+                                       context: &FloatingContext)
                                        -> FloatingDecision {
-    let size = if let Some(preferred_size) = preferred_size {
-        preferred_size
-    } else {
-        workspace_size.scaled(0.5)
-    };
+    let size = choose_size(context);
+    let workspace_size = context.workspace_size;
     let pos = Position::new((workspace_size.width / 4) as isize,
                             (workspace_size.height / 4) as isize);
     FloatingDecision { area: Area::new(pos, size) }
@@ -95,17 +90,12 @@ pub fn choose_floating_always_centered(_strategist: &Strategist,
 /// The frame will be placed in random position in workspace. If preferred size was not provided
 /// the frame will have 1/2 of width and height of workspace.
 pub fn choose_floating_random(_strategist: &Strategist,
-                              workspace_size: Size,
-                              preferred_size: Option<Size>)
+                              context: &FloatingContext)
                               -> FloatingDecision {
     let mut rng = rand::thread_rng();
 
-    let size = if let Some(preferred_size) = preferred_size {
-        preferred_size
-    } else {
-        workspace_size.scaled(0.5)
-    };
-
+    let size = choose_size(context);
+    let workspace_size = context.workspace_size;
     let x_range = Range::new(0, max(1, workspace_size.width - size.width));
     let y_range = Range::new(0, max(1, workspace_size.height - size.height));
     let pos = Position::new(x_range.ind_sample(&mut rng) as isize,
@@ -114,3 +104,174 @@ pub fn choose_floating_random(_strategist: &Strategist,
 }
 
 // -------------------------------------------------------------------------------------------------
+
+/// Decides where to place floating surface.
+///
+/// The frame will be placed in randomly chosen corner of workspace.
+pub fn choose_floating_random_corner(_strategist: &Strategist,
+                                     context: &FloatingContext)
+                                     -> FloatingDecision {
+    let mut rng = rand::thread_rng();
+
+    let size = choose_size(context);
+    let (x, y) = max_position(size, context.workspace_size);
+    let corners = [Position::new(0, 0),
+                   Position::new(x, 0),
+                   Position::new(0, y),
+                   Position::new(x, y)];
+    let pos = corners[Range::new(0, corners.len()).ind_sample(&mut rng)];
+    FloatingDecision { area: Area::new(pos, size) }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Decides where to place floating surface.
+///
+/// Every next frame will be shifted diagonally by constant offset relative to the previous one.
+/// When frame does not fit in workspace the cascade wraps.
+pub fn choose_floating_cascade(_strategist: &Strategist,
+                               context: &FloatingContext)
+                               -> FloatingDecision {
+    let size = choose_size(context);
+    let (x, y) = max_position(size, context.workspace_size);
+    let offset = CASCADE_STEP * (context.floating_areas.len() as isize + 1);
+    let pos = Position::new(offset % (x + 1), offset % (y + 1));
+    FloatingDecision { area: Area::new(pos, size) }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Decides where to place floating surface.
+///
+/// The frame will be centered under the pointer but kept inside the workspace. If pointer is not
+/// in the workspace `always_centered` strategy will be used.
+pub fn choose_floating_under_pointer(strategist: &Strategist,
+                                     context: &FloatingContext)
+                                     -> FloatingDecision {
+    if let Some(pointer) = context.pointer {
+        let size = choose_size(context);
+        let pos = center_at(pointer, size);
+        FloatingDecision { area: Area::new(fit(pos, size, context.workspace_size), size) }
+    } else {
+        choose_floating_always_centered(strategist, context)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Decides where to place floating surface.
+///
+/// The frame will be placed where it overlaps the least with other floating frames. Considered
+/// positions are top-left corner of workspace and positions adjacent to other floating frames.
+/// From equally good positions the first one is chosen.
+pub fn choose_floating_smart(_strategist: &Strategist,
+                             context: &FloatingContext)
+                             -> FloatingDecision {
+    let size = choose_size(context);
+    let mut candidates = vec![Position::new(0, 0)];
+    for area in context.floating_areas.iter() {
+        let right = area.pos.x + area.size.width as isize;
+        let bottom = area.pos.y + area.size.height as isize;
+        candidates.push(Position::new(right, area.pos.y));
+        candidates.push(Position::new(area.pos.x, bottom));
+        candidates.push(Position::new(area.pos.x - size.width as isize, area.pos.y));
+        candidates.push(Position::new(area.pos.x, area.pos.y - size.height as isize));
+        candidates.push(Position::new(right, bottom));
+    }
+
+    let pos = candidates.iter()
+        .map(|pos| fit(*pos, size, context.workspace_size))
+        .min_by_key(|pos| {
+            let candidate = Area::new(*pos, size);
+            context.floating_areas
+                .iter()
+                .map(|area| calculate_overlap(&candidate, area))
+                .sum::<usize>()
+        })
+        .unwrap_or_default();
+    FloatingDecision { area: Area::new(pos, size) }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Decides where to place floating surface.
+///
+/// Dialogs will be centered over their parent surface. Other surfaces (or dialogs which parent is
+/// not in the workspace) will be placed using `always_centered` strategy.
+pub fn choose_floating_relative_to_parent(strategist: &Strategist,
+                                          context: &FloatingContext)
+                                          -> FloatingDecision {
+    if let Some(parent_area) = context.parent_area {
+        let size = choose_size(context);
+        let center = Position::new(parent_area.pos.x + (parent_area.size.width / 2) as isize,
+                                   parent_area.pos.y + (parent_area.size.height / 2) as isize);
+        let pos = center_at(center, size);
+        FloatingDecision { area: Area::new(fit(pos, size, context.workspace_size), size) }
+    } else {
+        choose_floating_always_centered(strategist, context)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Decides where to place floating surface.
+///
+/// The frame will be placed where floating frame of the same application was last placed or
+/// moved to. If there is no such position `always_centered` strategy will be used.
+pub fn choose_floating_remembered(strategist: &Strategist,
+                                  context: &FloatingContext)
+                                  -> FloatingDecision {
+    if let Some(pos) = strategist.get_remembered_position(&context.app_id) {
+        let size = choose_size(context);
+        FloatingDecision { area: Area::new(fit(pos, size, context.workspace_size), size) }
+    } else {
+        choose_floating_always_centered(strategist, context)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Returns preferred size or 1/2 of width and height of workspace if preferred size was not
+/// provided.
+fn choose_size(context: &FloatingContext) -> Size {
+    if let Some(preferred_size) = context.preferred_size {
+        preferred_size
+    } else {
+        context.workspace_size.scaled(0.5)
+    }
+}
+
+/// Returns maximal coordinates of frame with given size still fitting in workspace.
+fn max_position(size: Size, workspace_size: Size) -> (isize, isize) {
+    (max(0, workspace_size.width as isize - size.width as isize),
+     max(0, workspace_size.height as isize - size.height as isize))
+}
+
+/// Returns position of frame with given size centered at given point.
+fn center_at(point: Position, size: Size) -> Position {
+    Position::new(point.x - (size.width / 2) as isize, point.y - (size.height / 2) as isize)
+}
+
+/// Moves given position so that frame with given size fits in workspace. If the frame is bigger
+/// than workspace it is placed in the top-left corner.
+fn fit(pos: Position, size: Size, workspace_size: Size) -> Position {
+    let (x, y) = max_position(size, workspace_size);
+    Position::new(max(0, min(pos.x, x)), max(0, min(pos.y, y)))
+}
+
+/// Calculates area of common part of given areas.
+fn calculate_overlap(first: &Area, second: &Area) -> usize {
+    let left = max(first.pos.x, second.pos.x);
+    let top = max(first.pos.y, second.pos.y);
+    let right = min(first.pos.x + first.size.width as isize,
+                    second.pos.x + second.size.width as isize);
+    let bottom = min(first.pos.y + first.size.height as isize,
+                     second.pos.y + second.size.height as isize);
+    if (left < right) && (top < bottom) {
+        ((right - left) * (bottom - top)) as usize
+    } else {
+        0
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
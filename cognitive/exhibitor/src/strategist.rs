@@ -5,9 +5,12 @@
 
 // -------------------------------------------------------------------------------------------------
 
-use qualia::{Area, Size, StrategistConfig, SurfaceInfo};
-use frames::{self, Frame};
+use std::collections::HashMap;
 
+use qualia::{Area, Position, Size, StrategistConfig, SurfaceInfo};
+use frames::{self, Frame, Searching};
+
+use rules::Rule;
 use strategies;
 
 // -------------------------------------------------------------------------------------------------
@@ -26,6 +29,10 @@ pub struct TargetDecision {
 
     /// `Some` if frame should be floating. `None` otherwise.
     pub floating: Option<FloatingDecision>,
+
+    /// `Some` if new frame should be enclosed in new container with given geometry. `None`
+    /// otherwise.
+    pub container: Option<frames::Geometry>,
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -39,8 +46,74 @@ pub struct FloatingDecision {
 
 // -------------------------------------------------------------------------------------------------
 
+/// Structure describing circumstances in which floating frame is being placed. All positions are
+/// relative to the workspace.
+#[derive(Clone, Debug)]
+pub struct FloatingContext {
+    /// Size of the workspace.
+    pub workspace_size: Size,
+
+    /// Size requested by the client if any.
+    pub preferred_size: Option<Size>,
+
+    /// Areas of floating frames already placed in the workspace.
+    pub floating_areas: Vec<Area>,
+
+    /// Position of the pointer if it is inside the workspace.
+    pub pointer: Option<Position>,
+
+    /// Area of parent surface if it is placed in the workspace.
+    pub parent_area: Option<Area>,
+
+    /// Application ID of the surface.
+    pub app_id: String,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl FloatingContext {
+    /// Constructs new `FloatingContext` with empty workspace.
+    pub fn new(workspace_size: Size, preferred_size: Option<Size>) -> Self {
+        FloatingContext {
+            workspace_size: workspace_size,
+            preferred_size: preferred_size,
+            floating_areas: Vec::new(),
+            pointer: None,
+            parent_area: None,
+            app_id: String::new(),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
 type TargetDecider = fn(&Strategist, &Frame, &SurfaceInfo) -> TargetDecision;
-type FloatingDecider = fn(&Strategist, Size, Option<Size>) -> FloatingDecision;
+type FloatingDecider = fn(&Strategist, &FloatingContext) -> FloatingDecision;
+
+/// Names of available strategies deciding how to handle new surface.
+const TARGET_DECIDERS: &'static [(&'static str, TargetDecider)] =
+    &[("always_floating", strategies::choose_target_always_floating),
+      ("anchored_but_popups", strategies::choose_target_anchored_but_popups)];
+
+/// Names of available strategies deciding where to place floating surface.
+const FLOATING_DECIDERS: &'static [(&'static str, FloatingDecider)] =
+    &[("always_centered", strategies::choose_floating_always_centered),
+      ("random", strategies::choose_floating_random),
+      ("random_corner", strategies::choose_floating_random_corner),
+      ("cascade", strategies::choose_floating_cascade),
+      ("under_pointer", strategies::choose_floating_under_pointer),
+      ("smart", strategies::choose_floating_smart),
+      ("relative_to_parent", strategies::choose_floating_relative_to_parent),
+      ("remembered", strategies::choose_floating_remembered)];
+
+// -------------------------------------------------------------------------------------------------
+
+/// Strategies used in one workspace.
+#[derive(Clone, Copy)]
+struct Strategies {
+    choose_target: TargetDecider,
+    choose_floating: FloatingDecider,
+}
 
 // -------------------------------------------------------------------------------------------------
 
@@ -48,9 +121,18 @@ type FloatingDecider = fn(&Strategist, Size, Option<Size>) -> FloatingDecision;
 ///
 /// Moving strategies outside `Compositor` simplifies its implementations and allows greater
 /// customization or injecting functionalities.
+///
+/// Strategies can be overridden for workspaces with given titles.
+///
+/// `Strategist` also keeps track of global position of the pointer and last positions of floating
+/// frames of each application to let strategies place new frames relative to them.
 pub struct Strategist {
     choose_target: TargetDecider,
     choose_floating: FloatingDecider,
+    workspaces: HashMap<String, Strategies>,
+    rules: Vec<Rule>,
+    pointer: Position,
+    positions: HashMap<String, Position>,
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -61,6 +143,10 @@ impl Strategist {
         Strategist {
             choose_target: choose_target,
             choose_floating: choose_floating,
+            workspaces: HashMap::new(),
+            rules: Vec::new(),
+            pointer: Position::default(),
+            positions: HashMap::new(),
         }
     }
 
@@ -70,28 +156,18 @@ impl Strategist {
 
         // NOTE: Using literals here is mediocre but otherwise `qualia` would have to define
         // strategies. Maybe that would be better?
-        //
-        // TODO: Provide macro for reading strategies from configuration.
-        match config.choose_target.as_ref() {
-            "always_floating" => {
-                strategist.choose_target = strategies::choose_target_always_floating;
-            }
-            "anchored_but_popups" => {
-                strategist.choose_target = strategies::choose_target_anchored_but_popups;
-            }
-            "" => {}
-            _ => log_warn1!("Unknown 'choose_target' strategy: {}", config.choose_target),
+        if let Some(decider) = find_decider(TARGET_DECIDERS, &config.choose_target) {
+            strategist.choose_target = decider;
+        }
+        if let Some(decider) = find_decider(FLOATING_DECIDERS, &config.choose_floating) {
+            strategist.choose_floating = decider;
         }
 
-        match config.choose_floating.as_ref() {
-            "always_centered" => {
-                strategist.choose_floating = strategies::choose_floating_always_centered;
+        for rule_config in config.rules.iter() {
+            match Rule::new(rule_config) {
+                Ok(rule) => strategist.rules.push(rule),
+                Err(err) => log_warn1!("Skipping rule {:?}: {}", rule_config, err),
             }
-            "random" => {
-                strategist.choose_floating = strategies::choose_floating_random;
-            }
-            "" => {}
-            _ => log_warn1!("Unknown 'choose_floating' strategy: {}", config.choose_floating),
         }
 
         strategist
@@ -111,18 +187,233 @@ impl Default for Strategist {
 
 // Strategy callers
 impl Strategist {
-    /// Decides how to handle new surface.
+    /// Decides how to handle new surface. Decision made by strategy of workspace containing given
+    /// frame is modified by first rule matching the surface.
     pub fn choose_target(&self, frame: &Frame, surface: &SurfaceInfo) -> TargetDecision {
-        (self.choose_target)(self, frame, surface)
+        let choose_target = self.strategies_for(frame).choose_target;
+        let mut decision = choose_target(self, frame, surface);
+        if let Some(rule) = self.find_rule(surface) {
+            self.apply_rule(rule, frame, surface, &mut decision);
+        }
+        decision
     }
 
-    /// Decides where to place floating surface.
+    /// Decides where to place floating surface in given workspace. If `surface` is `None` the
+    /// frame being placed is not a new surface (e.g. anchored frame is made floating).
     pub fn choose_floating(&self,
-                           workspace_size: Size,
-                           preferred_size: Option<Size>)
+                           workspace: &Frame,
+                           surface: Option<&SurfaceInfo>)
                            -> FloatingDecision {
-        (self.choose_floating)(self, workspace_size, preferred_size)
+        let choose_floating = self.strategies_for(workspace).choose_floating;
+        let context = self.build_floating_context(workspace, surface);
+        choose_floating(self, &context)
+    }
+
+    /// Gathers information about given workspace needed to place floating surface in it.
+    fn build_floating_context(&self,
+                              workspace: &Frame,
+                              surface: Option<&SurfaceInfo>)
+                              -> FloatingContext {
+        let workspace_position = workspace.calculate_global_position();
+        let mut context = FloatingContext::new(workspace.get_size(), None);
+
+        context.floating_areas = workspace.space_iter()
+            .filter(|frame| frame.get_mobility().is_floating())
+            .map(|frame| frame.get_area())
+            .collect();
+
+        let pointer = self.pointer - workspace_position;
+        if pointer.is_inside(&Area::new(Position::default(), context.workspace_size)) {
+            context.pointer = Some(pointer);
+        }
+
+        if let Some(surface) = surface {
+            if !surface.requested_size.is_zero() {
+                context.preferred_size = Some(surface.requested_size);
+            }
+            if surface.parent_sid.is_valid() {
+                if let Some(parent) = workspace.find_with_sid(surface.parent_sid) {
+                    let position = parent.calculate_global_position() - workspace_position;
+                    context.parent_area = Some(Area::new(position, parent.get_size()));
+                }
+            }
+            context.app_id = surface.app_id.clone();
+        }
+
+        context
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+// Tracking positions
+impl Strategist {
+    /// Updates global position of the pointer.
+    pub fn set_pointer_position(&mut self, position: Position) {
+        self.pointer = position;
+    }
+
+    /// Remembers position (relative to workspace) of floating frame of application with given ID.
+    pub fn remember_position(&mut self, app_id: &str, position: Position) {
+        if !app_id.is_empty() {
+            self.positions.insert(app_id.to_owned(), position);
+        }
+    }
+
+    /// Returns last remembered position of floating frame of application with given ID.
+    pub fn get_remembered_position(&self, app_id: &str) -> Option<Position> {
+        self.positions.get(app_id).cloned()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+// Workspace strategies
+impl Strategist {
+    /// Sets strategies used in workspace with given title. Empty names stand for strategies
+    /// configured globally.
+    pub fn configure_workspace(&mut self, title: &str, choose_target: &str, choose_floating: &str) {
+        let mut strategies = self.get_global_strategies();
+        if let Some(decider) = find_decider(TARGET_DECIDERS, choose_target) {
+            strategies.choose_target = decider;
+        }
+        if let Some(decider) = find_decider(FLOATING_DECIDERS, choose_floating) {
+            strategies.choose_floating = decider;
+        }
+        self.workspaces.insert(title.to_owned(), strategies);
+    }
+
+    /// Forgets strategies of workspace with given title.
+    pub fn forget_workspace(&mut self, title: &str) {
+        self.workspaces.remove(title);
+    }
+
+    /// Sets strategy with given name in workspace with given title. Name may refer to strategy of
+    /// any kind. Returns `false` if there is no such strategy.
+    pub fn set_workspace_strategy(&mut self, title: &str, name: &str) -> bool {
+        let mut strategies = self.get_workspace_strategies(title);
+        if let Some(&(_, decider)) = TARGET_DECIDERS.iter().find(|&&(n, _)| n == name) {
+            strategies.choose_target = decider;
+        } else if let Some(&(_, decider)) = FLOATING_DECIDERS.iter().find(|&&(n, _)| n == name) {
+            strategies.choose_floating = decider;
+        } else {
+            return false;
+        }
+        self.workspaces.insert(title.to_owned(), strategies);
+        true
+    }
+
+    /// Switches workspace with given title to next (or previous if `forward` is `false`) strategy
+    /// deciding how to handle new surfaces. Returns name of chosen strategy.
+    pub fn cycle_workspace_target(&mut self, title: &str, forward: bool) -> &'static str {
+        let mut strategies = self.get_workspace_strategies(title);
+        let len = TARGET_DECIDERS.len();
+        let current = TARGET_DECIDERS.iter()
+            .position(|&(_, decider)| decider as usize == strategies.choose_target as usize)
+            .unwrap_or(0);
+        let next = if forward { (current + 1) % len } else { (current + len - 1) % len };
+
+        let (name, decider) = TARGET_DECIDERS[next];
+        strategies.choose_target = decider;
+        self.workspaces.insert(title.to_owned(), strategies);
+        name
+    }
+
+    /// Returns names of strategies used in workspace with given title: deciding how to handle
+    /// new surfaces and where to place floating surfaces.
+    pub fn get_workspace_strategy_names(&self, title: &str) -> (&'static str, &'static str) {
+        let strategies = self.get_workspace_strategies(title);
+        let target = TARGET_DECIDERS.iter()
+            .find(|&&(_, decider)| decider as usize == strategies.choose_target as usize)
+            .map_or("", |&(name, _)| name);
+        let floating = FLOATING_DECIDERS.iter()
+            .find(|&&(_, decider)| decider as usize == strategies.choose_floating as usize)
+            .map_or("", |&(name, _)| name);
+        (target, floating)
+    }
+
+    /// Returns strategies configured globally.
+    fn get_global_strategies(&self) -> Strategies {
+        Strategies {
+            choose_target: self.choose_target,
+            choose_floating: self.choose_floating,
+        }
+    }
+
+    /// Returns strategies used in workspace with given title.
+    fn get_workspace_strategies(&self, title: &str) -> Strategies {
+        match self.workspaces.get(title) {
+            Some(strategies) => *strategies,
+            None => self.get_global_strategies(),
+        }
+    }
+
+    /// Returns strategies used in workspace containing given frame.
+    fn strategies_for(&self, frame: &Frame) -> Strategies {
+        match frame.find_top() {
+            Some(workspace) => self.get_workspace_strategies(&workspace.get_title()),
+            None => self.get_global_strategies(),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+// Rules
+impl Strategist {
+    /// Returns first rule matching given surface.
+    pub fn find_rule(&self, surface: &SurfaceInfo) -> Option<&Rule> {
+        self.rules.iter().find(|rule| rule.matches(surface))
+    }
+
+    /// Modifies decision according to given rule.
+    fn apply_rule(&self,
+                  rule: &Rule,
+                  frame: &Frame,
+                  surface: &SurfaceInfo,
+                  decision: &mut TargetDecision) {
+        match rule.floating {
+            Some(true) if decision.floating.is_none() => {
+                let floating = strategies::choose_target_always_floating(self, frame, surface);
+                decision.target = floating.target;
+                decision.floating = floating.floating;
+            }
+            Some(false) if decision.floating.is_some() => {
+                decision.target = frame.find_buildable().expect("searching buildable for rule");
+                decision.floating = None;
+            }
+            _ => {}
+        }
+
+        if let Some(ref mut floating) = decision.floating {
+            let area = &mut floating.area;
+            area.size.width = rule.width.unwrap_or(area.size.width);
+            area.size.height = rule.height.unwrap_or(area.size.height);
+            area.pos.x = rule.x.unwrap_or(area.pos.x);
+            area.pos.y = rule.y.unwrap_or(area.pos.y);
+        } else {
+            decision.container = rule.geometry;
+        }
+
+        if rule.no_focus {
+            decision.selection = false;
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Searches for strategy with given name. Empty name is silently ignored, unknown one is reported.
+fn find_decider<D: Copy>(deciders: &[(&'static str, D)], name: &str) -> Option<D> {
+    if name.is_empty() {
+        return None;
+    }
+
+    let decider = deciders.iter().find(|&&(n, _)| n == name).map(|&(_, decider)| decider);
+    if decider.is_none() {
+        log_warn1!("Unknown strategy: {}", name);
     }
+    decider
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -44,12 +44,28 @@ impl SurfaceHistory {
     pub fn get_nth(&self, n: isize) -> Option<SurfaceId> {
         if n < 0 {
             let m = -n as usize;
-            self.history.get(self.history.len() - m).cloned()
+            if m <= self.history.len() {
+                self.history.get(self.history.len() - m).cloned()
+            } else {
+                None
+            }
         } else {
             self.history.get(n as usize).cloned()
         }
     }
 
+    /// Return `n`th element from begin counting only surfaces matching given predicate. If `n` is
+    /// negative, return `n`th matching element from end.
+    pub fn get_nth_matching<F>(&self, n: isize, predicate: F) -> Option<SurfaceId>
+        where F: Fn(SurfaceId) -> bool
+    {
+        if n < 0 {
+            self.history.iter().rev().cloned().filter(|sid| predicate(*sid)).nth((-n - 1) as usize)
+        } else {
+            self.history.iter().cloned().filter(|sid| predicate(*sid)).nth(n as usize)
+        }
+    }
+
     /// Make given surface the latest in history.
     pub fn pop(&mut self, sid: SurfaceId) {
         self.simple_remove(sid);
@@ -0,0 +1,372 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! This module contains functionality for saving frame layout of workspace to file and restoring
+//! it later. Restored layout consists of containers and empty placeholder frames waiting for
+//! surfaces matching their criteria to be "swallowed".
+
+// -------------------------------------------------------------------------------------------------
+
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+
+use regex::{self, Regex};
+use yaml_rust::{Yaml, YamlEmitter, YamlLoader};
+use yaml_rust::yaml::Hash;
+
+use qualia::{Illusion, SurfaceAccess, SurfaceInfo};
+use frames::{Frame, Geometry};
+use frames::{Packing, Searching};
+
+// -------------------------------------------------------------------------------------------------
+
+/// Criteria a surface must meet to be swallowed by placeholder.
+#[derive(Clone, Debug)]
+pub struct Swallow {
+    /// Application ID (or class) the surface must have.
+    pub app_id: Option<String>,
+
+    /// Regular expression the title of the surface must match.
+    pub title: Option<Regex>,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Swallow {
+    /// Creates criteria describing given surface. Application ID is preferred as titles tend to
+    /// change. If surface does not provide application ID its exact title is used. Returns `None`
+    /// if surface provides neither.
+    pub fn describe(info: &SurfaceInfo) -> Option<Self> {
+        if !info.app_id.is_empty() {
+            Some(Swallow {
+                app_id: Some(info.app_id.clone()),
+                title: None,
+            })
+        } else if !info.title.is_empty() {
+            let pattern = format!("^{}$", regex::escape(&info.title));
+            Some(Swallow {
+                app_id: None,
+                title: Regex::new(&pattern).ok(),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Checks if given surface meets the criteria. Criteria without any conditions match nothing.
+    pub fn matches(&self, info: &SurfaceInfo) -> bool {
+        if self.app_id.is_none() && self.title.is_none() {
+            return false;
+        }
+        if let Some(ref app_id) = self.app_id {
+            if *app_id != info.app_id {
+                return false;
+            }
+        }
+        if let Some(ref title) = self.title {
+            if !title.is_match(&info.title) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Registry of placeholders - empty frames created when restoring layout, waiting for matching
+/// surfaces. Placeholder frames are recognized by ID stored in the frame, so placeholders whose
+/// frames were destroyed are never confused with other frames.
+pub struct Placeholders {
+    last_id: u64,
+    swallows: HashMap<u64, Swallow>,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Placeholders {
+    /// Constructs new empty `Placeholders`.
+    pub fn new() -> Self {
+        Placeholders {
+            last_id: 0,
+            swallows: HashMap::new(),
+        }
+    }
+
+    /// Makes given frame a placeholder waiting for surface meeting given criteria.
+    pub fn add(&mut self, frame: &mut Frame, swallow: Swallow) {
+        self.last_id += 1;
+        frame.set_plumbing_placeholder(Some(self.last_id));
+        self.swallows.insert(self.last_id, swallow);
+    }
+
+    /// Returns criteria of given frame if it is a placeholder.
+    pub fn get(&self, frame: &Frame) -> Option<&Swallow> {
+        frame.get_placeholder().and_then(|id| self.swallows.get(&id))
+    }
+
+    /// Removes all placeholders.
+    pub fn clear(&mut self) {
+        self.swallows.clear();
+    }
+
+    /// Drops placeholders whose frames are not present in given frame tree anymore or were
+    /// already filled.
+    pub fn purge(&mut self, root: &Frame) {
+        if self.swallows.is_empty() {
+            return;
+        }
+
+        let mut present = HashSet::new();
+        collect_placeholders(root, &mut present);
+        self.swallows.retain(|id, _| present.contains(id));
+    }
+
+    /// Finds placeholder in given frame tree whose criteria are met by given surface and removes
+    /// it from the registry. The oldest placeholders are preferred.
+    pub fn take(&mut self, root: &Frame, info: &SurfaceInfo) -> Option<Frame> {
+        self.purge(root);
+        let id = self.swallows
+            .iter()
+            .filter(|&(_, swallow)| swallow.matches(info))
+            .map(|(id, _)| *id)
+            .min();
+
+        if let Some(id) = id {
+            self.swallows.remove(&id);
+            root.find(&|frame| frame.get_placeholder() == Some(id)).map(|mut frame| {
+                frame.set_plumbing_placeholder(None);
+                frame
+            })
+        } else {
+            None
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Serializable description of frame and its anchored subframes.
+#[derive(Clone, Debug)]
+pub struct FrameTemplate {
+    pub geometry: Geometry,
+    pub ratio: f32,
+    pub swallow: Option<Swallow>,
+    pub branches: Vec<FrameTemplate>,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+// Constructing
+impl FrameTemplate {
+    /// Creates template describing given top frame (e.g. workspace) and its anchored subframes.
+    ///
+    /// Frames without children are described by `describe`. If it returns `None` the frame is
+    /// skipped. Containers left without any described frames are skipped too.
+    pub fn from_frame(frame: &Frame, describe: &Fn(&Frame) -> Option<Swallow>) -> Self {
+        FrameTemplate {
+            geometry: frame.get_geometry(),
+            ratio: frame.get_ratio(),
+            swallow: None,
+            branches: Self::describe_branches(frame, describe),
+        }
+    }
+
+    /// Helper method for `from_frame` describing subframes recursively.
+    fn describe_branches(frame: &Frame, describe: &Fn(&Frame) -> Option<Swallow>) -> Vec<Self> {
+        let mut branches = Vec::new();
+        for subframe in frame.space_iter() {
+            if !subframe.get_mobility().is_anchored() {
+                continue;
+            }
+
+            if subframe.has_children() {
+                let subbranches = Self::describe_branches(&subframe, describe);
+                if !subbranches.is_empty() {
+                    branches.push(FrameTemplate {
+                        geometry: subframe.get_geometry(),
+                        ratio: subframe.get_ratio(),
+                        swallow: None,
+                        branches: subbranches,
+                    });
+                }
+            } else if let Some(swallow) = describe(&subframe) {
+                branches.push(FrameTemplate {
+                    geometry: subframe.get_geometry(),
+                    ratio: subframe.get_ratio(),
+                    swallow: Some(swallow),
+                    branches: Vec::new(),
+                });
+            }
+        }
+        branches
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+// Serializing
+impl FrameTemplate {
+    /// Reads template from YAML file.
+    pub fn load(path: &Path) -> Result<Self, Illusion> {
+        let mut contents = String::new();
+        let mut file = File::open(path)?;
+        file.read_to_string(&mut contents)?;
+        let yamls = match YamlLoader::load_from_str(&contents) {
+            Ok(yamls) => yamls,
+            Err(err) => return Err(Illusion::Config(path.to_owned(), err.description().to_owned())),
+        };
+        if let Some(yaml) = yamls.first() {
+            Self::from_yaml(yaml).map_err(|err| Illusion::Config(path.to_owned(), err))
+        } else {
+            Err(Illusion::Config(path.to_owned(), "Empty layout".to_owned()))
+        }
+    }
+
+    /// Writes template to YAML file.
+    pub fn save(&self, path: &Path) -> Result<(), Illusion> {
+        let mut contents = String::new();
+        if let Err(err) = YamlEmitter::new(&mut contents).dump(&self.to_yaml()) {
+            return Err(Illusion::General(format!("Failed to serialize layout: {:?}", err)));
+        }
+        contents.push('\n');
+
+        let mut file = File::create(path)?;
+        file.write_all(contents.as_bytes())?;
+        Ok(())
+    }
+
+    /// Converts template to YAML document.
+    pub fn to_yaml(&self) -> Yaml {
+        let mut hash = Hash::new();
+        hash.insert(Yaml::String("geometry".to_owned()),
+                    Yaml::String(self.geometry.name().to_owned()));
+        hash.insert(Yaml::String("ratio".to_owned()), Yaml::Real(format!("{:?}", self.ratio)));
+
+        if let Some(ref swallow) = self.swallow {
+            let mut swallow_hash = Hash::new();
+            if let Some(ref app_id) = swallow.app_id {
+                swallow_hash.insert(Yaml::String("app_id".to_owned()),
+                                    Yaml::String(app_id.clone()));
+            }
+            if let Some(ref title) = swallow.title {
+                swallow_hash.insert(Yaml::String("title".to_owned()),
+                                    Yaml::String(title.as_str().to_owned()));
+            }
+            hash.insert(Yaml::String("swallow".to_owned()), Yaml::Hash(swallow_hash));
+        }
+
+        if !self.branches.is_empty() {
+            let branches = self.branches.iter().map(|branch| branch.to_yaml()).collect();
+            hash.insert(Yaml::String("frames".to_owned()), Yaml::Array(branches));
+        }
+
+        Yaml::Hash(hash)
+    }
+
+    /// Creates template from YAML document. Returns description of error if document is invalid.
+    pub fn from_yaml(yaml: &Yaml) -> Result<Self, String> {
+        if yaml.as_hash().is_none() {
+            return Err("Frame description should be a map".to_owned());
+        }
+
+        let geometry = match yaml["geometry"].as_str() {
+            Some(name) => {
+                match Geometry::from_name(name) {
+                    Some(geometry) => geometry,
+                    None => return Err(format!("Unknown geometry: '{}'", name)),
+                }
+            }
+            None => Geometry::Stacked,
+        };
+
+        let ratio = match yaml["ratio"] {
+            Yaml::Real(_) => yaml["ratio"].as_f64().unwrap_or(1.0) as f32,
+            Yaml::Integer(value) => value as f32,
+            _ => 1.0,
+        };
+
+        let swallow = if yaml["swallow"].as_hash().is_some() {
+            let app_id = yaml["swallow"]["app_id"].as_str().map(|s| s.to_owned());
+            let title = match yaml["swallow"]["title"].as_str() {
+                Some(pattern) => {
+                    match Regex::new(pattern) {
+                        Ok(regex) => Some(regex),
+                        Err(err) => return Err(format!("Invalid title '{}': {}", pattern, err)),
+                    }
+                }
+                None => None,
+            };
+            Some(Swallow {
+                app_id: app_id,
+                title: title,
+            })
+        } else {
+            None
+        };
+
+        let mut branches = Vec::new();
+        if let Some(array) = yaml["frames"].as_vec() {
+            for entry in array.iter() {
+                branches.push(Self::from_yaml(entry)?);
+            }
+        }
+
+        Ok(FrameTemplate {
+            geometry: geometry,
+            ratio: ratio,
+            swallow: swallow,
+            branches: branches,
+        })
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+// Instantiating
+impl FrameTemplate {
+    /// Creates frames described by branches of the template inside `target`. Branches with
+    /// swallowing criteria become empty placeholder frames which are added to `placeholders`.
+    pub fn instantiate(&self,
+                       target: &mut Frame,
+                       placeholders: &mut Placeholders,
+                       sa: &mut SurfaceAccess) {
+        self.instantiate_branches(target, placeholders);
+        target.repack(sa);
+    }
+
+    /// Helper method for `instantiate` creating subframes recursively.
+    fn instantiate_branches(&self, target: &mut Frame, placeholders: &mut Placeholders) {
+        for branch in self.branches.iter() {
+            let mut frame = Frame::new_container(branch.geometry);
+            frame.set_plumbing_ratio(branch.ratio);
+            target.append(&mut frame);
+
+            if let Some(ref swallow) = branch.swallow {
+                placeholders.add(&mut frame, swallow.clone());
+            } else {
+                branch.instantiate_branches(&mut frame, placeholders);
+            }
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Collects IDs of empty placeholder frames found in given frame tree.
+fn collect_placeholders(frame: &Frame, ids: &mut HashSet<u64>) {
+    for subframe in frame.space_iter() {
+        collect_placeholders(&subframe, ids);
+    }
+
+    if let Some(id) = frame.get_placeholder() {
+        if !frame.has_children() && !frame.get_sid().is_valid() {
+            ids.insert(id);
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
@@ -3,7 +3,9 @@
 
 //! Default configurations for tests.
 
-use qualia::{CompositorConfig, StrategistConfig};
+use std::collections::HashMap;
+
+use qualia::{CompositorConfig, FocusConfig, SpacingConfig, StrategistConfig, WorkspaceConfig};
 
 // -------------------------------------------------------------------------------------------------
 
@@ -11,6 +13,45 @@ pub fn compositor() -> CompositorConfig {
     CompositorConfig {
         move_step: 10,
         resize_step: 10,
+        focus: focus(),
+        workspace: workspace(),
+        workspaces: HashMap::new(),
+        workspace_outputs: HashMap::new(),
+        layouts_path: None,
+        marks_path: None,
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+pub fn focus() -> FocusConfig {
+    FocusConfig {
+        policy: "click".to_owned(),
+        delay: 0,
+        warp_pointer: false,
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+pub fn workspace() -> WorkspaceConfig {
+    WorkspaceConfig {
+        geometry: "stacked".to_owned(),
+        choose_target: String::new(),
+        choose_floating: String::new(),
+        spacing: spacing(),
+        layout: "manual".to_owned(),
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+pub fn spacing() -> SpacingConfig {
+    SpacingConfig {
+        inner_gap: 0,
+        outer_gap: 0,
+        border: 0,
+        smart_gaps: false,
     }
 }
 
@@ -20,6 +61,7 @@ pub fn strategist() -> StrategistConfig {
     StrategistConfig {
         choose_target: "always_floating".to_owned(),
         choose_floating: "random".to_owned(),
+        rules: Vec::new(),
     }
 }
 
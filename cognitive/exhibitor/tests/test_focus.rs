@@ -0,0 +1,158 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! Unit tests of focus policies and warping the pointer.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+
+extern crate cognitive_qualia as qualia;
+extern crate cognitive_outputs as outputs;
+extern crate cognitive_exhibitor as exhibitor;
+
+mod common;
+
+use qualia::{OutputInfo, SurfaceControl, SurfaceId};
+use qualia::{Area, Position, Size};
+use qualia::{Action, Command, Direction, FocusConfig};
+use qualia::coordinator_mock::CoordinatorMock;
+use outputs::output_mock::OutputMock;
+use exhibitor::{Exhibitor, Strategist};
+
+// -------------------------------------------------------------------------------------------------
+
+struct Environment {
+    exhibitor: Exhibitor<CoordinatorMock>,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Environment {
+    /// Creates display with two anchored surfaces placed side by side. Surface 2 is focused.
+    pub fn create(focus: FocusConfig) -> Self {
+        let output_info = OutputInfo::new(1,
+                                          Area::new(Position::new(0, 0), Size::new(100, 100)),
+                                          Size::new(100, 100),
+                                          60,
+                                          "test_make".to_owned(),
+                                          "test_model".to_owned());
+
+        let mut config = common::configurations::strategist();
+        config.choose_target = "anchored_but_popups".to_owned();
+        let strategist = Strategist::new_from_config(config);
+
+        let mut compositor_config = common::configurations::compositor();
+        compositor_config.workspace.geometry = "horizontal".to_owned();
+        compositor_config.focus = focus;
+
+        let output = Box::new(OutputMock::new(output_info.clone()));
+        let coordinator = CoordinatorMock::new();
+        let mut exhibitor = Exhibitor::new(coordinator.clone(),
+                                           std::time::Instant::now(),
+                                           strategist,
+                                           compositor_config);
+
+        exhibitor.on_output_found(output);
+        for sid in 1..3 {
+            coordinator.set_surface_app_id(SurfaceId::new(sid), format!("app{}", sid));
+            exhibitor.on_surface_ready(SurfaceId::new(sid));
+        }
+
+        Environment { exhibitor: exhibitor }
+    }
+
+    /// Creates focus configuration with given policy.
+    pub fn focus(policy: &str, delay: u32, warp_pointer: bool) -> FocusConfig {
+        FocusConfig {
+            policy: policy.to_owned(),
+            delay: delay,
+            warp_pointer: warp_pointer,
+        }
+    }
+
+    /// Places the pointer in given position and redraws display to update hover state.
+    pub fn hover(&mut self, x: isize, y: isize) {
+        self.exhibitor.on_warp(Position::new(x, y));
+        self.exhibitor.on_notify();
+        self.exhibitor.on_pageflip(1);
+    }
+
+    /// Executes given command on focused frame.
+    pub fn execute(&mut self, action: Action, direction: Direction) {
+        self.exhibitor.on_command(Command {
+            action: action,
+            direction: direction,
+            magnitude: 1,
+            string: String::new(),
+        });
+    }
+
+    /// Returns ID of focused surface.
+    pub fn selected(&self) -> SurfaceId {
+        self.exhibitor.get_selection().get_sid()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if hovering surface does not change focus with click-to-focus policy.
+#[test]
+fn test_click_to_focus() {
+    let mut e = Environment::create(Environment::focus("click", 0, false));
+    assert_eq!(e.selected(), SurfaceId::new(2));
+
+    e.hover(20, 50);
+    assert_eq!(e.selected(), SurfaceId::new(2));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if hovered surface gets focused with sloppy focus policy.
+#[test]
+fn test_sloppy_focus() {
+    let mut e = Environment::create(Environment::focus("sloppy", 0, false));
+    assert_eq!(e.selected(), SurfaceId::new(2));
+
+    e.hover(20, 50);
+    assert_eq!(e.selected(), SurfaceId::new(1));
+
+    e.hover(70, 50);
+    assert_eq!(e.selected(), SurfaceId::new(2));
+
+    // Focus changed by command stays until the pointer hovers other surface
+    e.execute(Action::Focus, Direction::West);
+    e.hover(80, 50);
+    assert_eq!(e.selected(), SurfaceId::new(1));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if hovered surface is not focused before delay elapses.
+#[test]
+fn test_focus_follows_mouse_with_delay() {
+    let mut e = Environment::create(Environment::focus("follow", 60000, false));
+
+    e.hover(20, 50);
+    e.exhibitor.on_timer();
+    assert_eq!(e.selected(), SurfaceId::new(2));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if the pointer is placed at the center of frame selected by command.
+#[test]
+fn test_warping_pointer_on_focus_change() {
+    let mut e = Environment::create(Environment::focus("click", 0, true));
+    e.hover(90, 90);
+
+    e.execute(Action::Focus, Direction::West);
+    assert_eq!(e.selected(), SurfaceId::new(1));
+    assert_eq!(e.exhibitor.get_pointer_position(), Position::new(25, 50));
+
+    // Command not changing selection does not move the pointer
+    e.hover(10, 10);
+    e.execute(Action::Focus, Direction::West);
+    assert_eq!(e.selected(), SurfaceId::new(1));
+    assert_eq!(e.exhibitor.get_pointer_position(), Position::new(10, 10));
+}
+
+// -------------------------------------------------------------------------------------------------
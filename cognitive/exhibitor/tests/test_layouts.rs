@@ -0,0 +1,131 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! Unit tests of automatic layouts.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+
+extern crate cognitive_qualia as qualia;
+extern crate cognitive_outputs as outputs;
+extern crate cognitive_frames as frames;
+extern crate cognitive_exhibitor as exhibitor;
+
+mod common;
+
+use qualia::{OutputInfo, SurfaceId};
+use qualia::{Area, Position, Size};
+use qualia::{Action, Command, Direction};
+use qualia::coordinator_mock::CoordinatorMock;
+use outputs::output_mock::OutputMock;
+use frames::Searching;
+use exhibitor::{Exhibitor, Strategist};
+
+// -------------------------------------------------------------------------------------------------
+
+struct Environment {
+    exhibitor: Exhibitor<CoordinatorMock>,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Environment {
+    pub fn create() -> Self {
+        let output_info = OutputInfo::new(1,
+                                          Area::new(Position::new(0, 0), Size::new(100, 100)),
+                                          Size::new(100, 100),
+                                          60,
+                                          "test_make".to_owned(),
+                                          "test_model".to_owned());
+
+        let mut config = common::configurations::strategist();
+        config.choose_target = "anchored_but_popups".to_owned();
+        let strategist = Strategist::new_from_config(config);
+
+        let output = Box::new(OutputMock::new(output_info.clone()));
+        let coordinator = CoordinatorMock::new();
+        let mut exhibitor = Exhibitor::new(coordinator.clone(),
+                                           std::time::Instant::now(),
+                                           strategist,
+                                           common::configurations::compositor());
+
+        exhibitor.on_output_found(output);
+
+        Environment { exhibitor: exhibitor }
+    }
+
+    /// Executes layout command.
+    pub fn layout(&mut self, direction: Direction, magnitude: i32, name: &str) {
+        self.exhibitor.on_command(Command {
+            action: Action::Layout,
+            direction: direction,
+            magnitude: magnitude,
+            string: name.to_owned(),
+        });
+    }
+
+    /// Checks area of frame with given surface ID.
+    pub fn assert_area(&self, sid: u64, x: isize, y: isize, width: usize, height: usize) {
+        let frame = self.exhibitor.get_root().find_with_sid(SurfaceId::new(sid)).unwrap();
+        assert_eq!(frame.get_area(), Area::create(x, y, width, height), "sid: {}", sid);
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if new surfaces are placed according to automatic layout.
+#[test]
+fn test_placing_surfaces_in_master_stack_layout() {
+    let mut e = Environment::create();
+    e.layout(Direction::None, 0, "master_stack");
+
+    e.exhibitor.on_surface_ready(SurfaceId::new(1));
+    e.assert_area(1,  0,  0, 100, 100);
+
+    e.exhibitor.on_surface_ready(SurfaceId::new(2));
+    e.assert_area(2,  0,  0,  50, 100);
+    e.assert_area(1, 50,  0,  50, 100);
+
+    e.exhibitor.on_surface_ready(SurfaceId::new(3));
+    e.assert_area(3,  0,  0,  50, 100);
+    e.assert_area(2, 50,  0,  50,  50);
+    e.assert_area(1, 50, 50,  50,  50);
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if layout can be switched and its parameters changed.
+#[test]
+fn test_changing_layout() {
+    let mut e = Environment::create();
+    e.layout(Direction::None, 0, "master_stack");
+    e.exhibitor.on_surface_ready(SurfaceId::new(1));
+    e.exhibitor.on_surface_ready(SurfaceId::new(2));
+    e.exhibitor.on_surface_ready(SurfaceId::new(3));
+
+    // Cycle to grid
+    e.layout(Direction::Forward, 1, "");
+    e.assert_area(3,  0,  0,  50,  50);
+    e.assert_area(2, 50,  0,  50,  50);
+    e.assert_area(1,  0, 50, 100,  50);
+
+    // Cycle back and increase number of master frames
+    e.layout(Direction::Backward, 1, "");
+    e.layout(Direction::North, 1, "");
+    e.assert_area(3,  0,  0,  50,  50);
+    e.assert_area(2,  0, 50,  50,  50);
+    e.assert_area(1, 50,  0,  50, 100);
+
+    // Enlarge master frames
+    e.layout(Direction::East, 2, "");
+    e.assert_area(3,  0,  0,  60,  50);
+    e.assert_area(2,  0, 50,  60,  50);
+    e.assert_area(1, 60,  0,  40, 100);
+
+    // Switch to manual layout
+    e.layout(Direction::None, 0, "manual");
+    e.assert_area(3,  0,  0, 100, 100);
+    e.assert_area(2,  0,  0, 100, 100);
+    e.assert_area(1,  0,  0, 100, 100);
+}
+
+// -------------------------------------------------------------------------------------------------
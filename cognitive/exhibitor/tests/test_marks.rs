@@ -0,0 +1,182 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! Unit tests of marking frames.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+
+extern crate cognitive_qualia as qualia;
+extern crate cognitive_outputs as outputs;
+extern crate cognitive_frames as frames;
+extern crate cognitive_exhibitor as exhibitor;
+
+mod common;
+
+use qualia::{OutputInfo, SurfaceId};
+use qualia::{Area, Position, Size};
+use qualia::{Action, Command, Direction};
+use qualia::coordinator_mock::CoordinatorMock;
+use outputs::output_mock::OutputMock;
+use frames::{Geometry, Searching};
+use exhibitor::{Exhibitor, Strategist};
+use common::exhibitor_mixins::ExhibitorCommandShorthands;
+
+// -------------------------------------------------------------------------------------------------
+
+struct Environment {
+    exhibitor: Exhibitor<CoordinatorMock>,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Environment {
+    /// Creates display with surface 1 in workspace "1" and surface 2 in workspace "2". Surface 1
+    /// is focused.
+    pub fn create() -> Self {
+        let output_info = OutputInfo::new(1,
+                                          Area::new(Position::new(0, 0), Size::new(100, 100)),
+                                          Size::new(100, 100),
+                                          60,
+                                          "test_make".to_owned(),
+                                          "test_model".to_owned());
+
+        let mut config = common::configurations::strategist();
+        config.choose_target = "anchored_but_popups".to_owned();
+        let strategist = Strategist::new_from_config(config);
+
+        let output = Box::new(OutputMock::new(output_info.clone()));
+        let coordinator = CoordinatorMock::new();
+        let mut exhibitor = Exhibitor::new(coordinator.clone(),
+                                           std::time::Instant::now(),
+                                           strategist,
+                                           common::configurations::compositor());
+
+        exhibitor.on_output_found(output);
+        exhibitor.on_surface_ready(SurfaceId::new(1));
+        exhibitor.on_surface_ready(SurfaceId::new(2));
+        exhibitor.jump_to_workspace("2");
+
+        Environment { exhibitor: exhibitor }
+    }
+
+    /// Executes command with given action and mark direction.
+    pub fn execute(&mut self, action: Action, direction: Direction, name: &str) {
+        self.exhibitor.on_command(Command {
+            action: action,
+            direction: direction,
+            magnitude: 1,
+            string: name.to_owned(),
+        });
+    }
+
+    /// Marks focused frame.
+    pub fn mark(&mut self, name: &str) {
+        self.execute(Action::Mark, Direction::None, name);
+    }
+
+    /// Returns ID of focused surface.
+    pub fn selected(&self) -> SurfaceId {
+        self.exhibitor.get_selection().get_sid()
+    }
+
+    /// Returns title of workspace surface with given ID is placed in.
+    pub fn workspace_of(&self, sid: u64) -> String {
+        let frame = self.exhibitor.get_root().find_with_sid(SurfaceId::new(sid)).unwrap();
+        frame.find_top().unwrap().get_title()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if marked surface can be focused from other workspace.
+#[test]
+fn test_focusing_mark() {
+    let mut e = Environment::create();
+
+    e.mark("a");
+    assert_eq!(e.exhibitor.get_marks().get("a"), Some(&SurfaceId::new(1)));
+
+    e.exhibitor.focus_workspace("2");
+    assert_eq!(e.selected(), SurfaceId::new(2));
+
+    e.execute(Action::Focus, Direction::Mark, "a");
+    assert_eq!(e.selected(), SurfaceId::new(1));
+
+    e.execute(Action::Focus, Direction::Mark, "b");
+    assert_eq!(e.selected(), SurfaceId::new(1));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if marking other surface with the same name moves the mark and if one surface can have
+/// many marks.
+#[test]
+fn test_moving_mark() {
+    let mut e = Environment::create();
+
+    e.mark("a");
+    e.exhibitor.focus_workspace("2");
+    e.mark("a");
+    e.mark("b");
+
+    let marks = e.exhibitor.get_marks();
+    assert_eq!(marks.len(), 2);
+    assert_eq!(marks.get("a"), Some(&SurfaceId::new(2)));
+    assert_eq!(marks.get("b"), Some(&SurfaceId::new(2)));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if marks are removed when marked surface is destroyed.
+#[test]
+fn test_destroying_marked_surface() {
+    let mut e = Environment::create();
+
+    e.exhibitor.focus_workspace("2");
+    e.mark("a");
+    e.exhibitor.focus_workspace("1");
+    e.exhibitor.on_surface_destroyed(SurfaceId::new(2));
+    assert!(e.exhibitor.get_marks().is_empty());
+
+    e.execute(Action::Focus, Direction::Mark, "a");
+    assert_eq!(e.selected(), SurfaceId::new(1));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if jumping to mark moves focused frame next to marked one and keeps current workspace
+/// focused.
+#[test]
+fn test_jumping_to_mark() {
+    let mut e = Environment::create();
+
+    e.mark("a");
+    e.exhibitor.focus_workspace("2");
+    e.execute(Action::Jump, Direction::Mark, "a");
+
+    assert_eq!(e.workspace_of(2), "1");
+    assert!(!e.selected().is_valid());
+    assert_eq!(e.exhibitor.get_selection().get_title(), "2");
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if diving to mark moves focused frame into stack with marked one and focuses its
+/// workspace.
+#[test]
+fn test_diving_to_mark() {
+    let mut e = Environment::create();
+
+    e.mark("a");
+    e.exhibitor.focus_workspace("2");
+    e.execute(Action::Dive, Direction::Mark, "a");
+
+    assert_eq!(e.workspace_of(2), "1");
+    assert_eq!(e.selected(), SurfaceId::new(2));
+    let frame = e.exhibitor.get_root().find_with_sid(SurfaceId::new(1)).unwrap();
+    let parent = frame.get_parent().unwrap();
+    assert_eq!(parent.get_geometry(), Geometry::Stacked);
+    assert!(parent.contains(&e.exhibitor.get_selection()));
+}
+
+// -------------------------------------------------------------------------------------------------
@@ -0,0 +1,212 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! Unit tests of overview mode.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+
+extern crate cognitive_qualia as qualia;
+extern crate cognitive_outputs as outputs;
+extern crate cognitive_frames as frames;
+extern crate cognitive_exhibitor as exhibitor;
+
+mod common;
+
+use qualia::{OutputInfo, SurfaceControl, SurfaceId};
+use qualia::{Area, Position, Size};
+use qualia::{Action, Command, Direction, InteractionMode};
+use qualia::coordinator_mock::CoordinatorMock;
+use outputs::output_mock::OutputMock;
+use frames::Searching;
+use exhibitor::{Exhibitor, Strategist};
+
+use common::exhibitor_mixins::ExhibitorCommandShorthands;
+
+// -------------------------------------------------------------------------------------------------
+
+struct Environment {
+    exhibitor: Exhibitor<CoordinatorMock>,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Environment {
+    /// Creates display with anchored surfaces 1 and 2 in workspace "1" and 3 and 4 in workspace
+    /// "2". Surface 4 is focused. In overview thumbnails are placed in 2x2 grid.
+    pub fn create() -> Self {
+        let output_info = OutputInfo::new(1,
+                                          Area::new(Position::new(0, 0), Size::new(200, 200)),
+                                          Size::new(200, 200),
+                                          60,
+                                          "test_make".to_owned(),
+                                          "test_model".to_owned());
+
+        let mut config = common::configurations::strategist();
+        config.choose_target = "anchored_but_popups".to_owned();
+        let strategist = Strategist::new_from_config(config);
+
+        let mut compositor_config = common::configurations::compositor();
+        compositor_config.workspace.geometry = "horizontal".to_owned();
+
+        let output = Box::new(OutputMock::new(output_info.clone()));
+        let coordinator = CoordinatorMock::new();
+        let mut exhibitor = Exhibitor::new(coordinator.clone(),
+                                           std::time::Instant::now(),
+                                           strategist,
+                                           compositor_config);
+
+        exhibitor.on_output_found(output);
+        for sid in 1..5 {
+            if sid == 3 {
+                exhibitor.focus_workspace("2");
+            }
+            coordinator.set_surface_app_id(SurfaceId::new(sid), format!("app{}", sid));
+            exhibitor.on_surface_ready(SurfaceId::new(sid));
+        }
+
+        Environment { exhibitor: exhibitor }
+    }
+
+    /// Executes focus command with given direction and magnitude.
+    pub fn focus(&mut self, direction: Direction, magnitude: i32) {
+        self.exhibitor.on_command(Command {
+            action: Action::Focus,
+            direction: direction,
+            magnitude: magnitude,
+            string: String::new(),
+        });
+    }
+
+    /// Switches overview mode on or off.
+    pub fn overview(&mut self, active: bool) {
+        self.exhibitor.on_mode_switched(active, InteractionMode::Overview);
+    }
+
+    /// Returns ID of surface of chosen thumbnail.
+    pub fn chosen(&self) -> Option<SurfaceId> {
+        self.exhibitor.get_chosen_thumbnail()
+    }
+
+    /// Returns ID of focused surface.
+    pub fn selected(&self) -> SurfaceId {
+        self.exhibitor.get_selection().get_sid()
+    }
+
+    /// Returns title of active workspace.
+    pub fn active_workspace(&self) -> String {
+        self.exhibitor.get_selection().find_top().unwrap().get_title()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if thumbnails can be chosen with arrows and if surface of chosen thumbnail gets focused
+/// after leaving overview mode.
+#[test]
+fn test_choosing_thumbnail_with_arrows() {
+    let mut e = Environment::create();
+    assert_eq!(e.chosen(), None);
+
+    e.overview(true);
+    assert_eq!(e.chosen(), Some(SurfaceId::new(4)));
+
+    e.focus(Direction::East, 1);
+    assert_eq!(e.chosen(), Some(SurfaceId::new(4)));
+
+    e.focus(Direction::West, 1);
+    assert_eq!(e.chosen(), Some(SurfaceId::new(3)));
+
+    e.focus(Direction::North, 1);
+    assert_eq!(e.chosen(), Some(SurfaceId::new(1)));
+
+    e.focus(Direction::East, 1);
+    assert_eq!(e.chosen(), Some(SurfaceId::new(2)));
+
+    // Focus does not change until overview mode is switched off
+    assert_eq!(e.selected(), SurfaceId::new(4));
+
+    e.overview(false);
+    assert_eq!(e.chosen(), None);
+    assert_eq!(e.selected(), SurfaceId::new(2));
+    assert_eq!(e.active_workspace(), "1");
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if thumbnail can be chosen by its number.
+#[test]
+fn test_choosing_thumbnail_by_number() {
+    let mut e = Environment::create();
+
+    e.overview(true);
+    e.focus(Direction::Begin, 1);
+    assert_eq!(e.chosen(), Some(SurfaceId::new(1)));
+
+    // Number without thumbnail does not change choice
+    e.focus(Direction::Begin, 5);
+    assert_eq!(e.chosen(), Some(SurfaceId::new(1)));
+
+    e.overview(false);
+    assert_eq!(e.selected(), SurfaceId::new(1));
+    assert_eq!(e.active_workspace(), "1");
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if focusing backward chooses back surface focused before overview mode was started.
+#[test]
+fn test_cancelling_overview() {
+    let mut e = Environment::create();
+
+    e.overview(true);
+    e.focus(Direction::West, 1);
+    assert_eq!(e.chosen(), Some(SurfaceId::new(3)));
+
+    e.focus(Direction::Backward, 1);
+    assert_eq!(e.chosen(), Some(SurfaceId::new(4)));
+
+    e.overview(false);
+    assert_eq!(e.selected(), SurfaceId::new(4));
+    assert_eq!(e.active_workspace(), "2");
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if thumbnail hovered by the pointer gets chosen. Thumbnails of surfaces of size 100x200
+/// are scaled down to 40x80 and placed in the centers of cells of size 100x100.
+#[test]
+fn test_choosing_thumbnail_with_pointer() {
+    let mut e = Environment::create();
+    e.overview(true);
+
+    e.exhibitor.on_warp(Position::new(50, 150));
+    assert_eq!(e.chosen(), Some(SurfaceId::new(3)));
+
+    // Pointer outside of thumbnails does not change choice
+    e.exhibitor.on_warp(Position::new(5, 5));
+    assert_eq!(e.chosen(), Some(SurfaceId::new(3)));
+
+    e.exhibitor.on_warp(Position::new(160, 20));
+    assert_eq!(e.chosen(), Some(SurfaceId::new(2)));
+
+    e.overview(false);
+    assert_eq!(e.selected(), SurfaceId::new(2));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if overview mode is stopped without changing focus when session gets locked.
+#[test]
+fn test_locking_session_in_overview() {
+    let mut e = Environment::create();
+
+    e.overview(true);
+    e.focus(Direction::Begin, 1);
+    e.exhibitor.on_lock_session();
+    assert_eq!(e.chosen(), None);
+
+    e.exhibitor.on_unlock_session();
+    assert_eq!(e.selected(), SurfaceId::new(4));
+}
+
+// -------------------------------------------------------------------------------------------------
@@ -0,0 +1,210 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! Unit tests of fullscreen and maximized surfaces.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+
+extern crate cognitive_qualia as qualia;
+extern crate cognitive_outputs as outputs;
+extern crate cognitive_frames as frames;
+extern crate cognitive_exhibitor as exhibitor;
+
+mod common;
+
+use qualia::{OutputInfo, SurfaceContext, SurfaceId, SurfaceViewer, surface_state};
+use qualia::{Area, Position, Size};
+use qualia::{Action, Command, Direction};
+use qualia::coordinator_mock::CoordinatorMock;
+use outputs::output_mock::OutputMock;
+use frames::{Converting, Presentation, Searching};
+use exhibitor::{Exhibitor, Strategist};
+use common::exhibitor_mixins::ExhibitorCommandShorthands;
+
+// -------------------------------------------------------------------------------------------------
+
+struct Environment {
+    exhibitor: Exhibitor<CoordinatorMock>,
+    coordinator: CoordinatorMock,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Environment {
+    /// Creates display with dock and two anchored surfaces placed side by side. Surface 2 is
+    /// placed on the left and focused.
+    pub fn create() -> Self {
+        let output_info = OutputInfo::new(1,
+                                          Area::new(Position::new(0, 0), Size::new(100, 100)),
+                                          Size::new(100, 100),
+                                          60,
+                                          "test_make".to_owned(),
+                                          "test_model".to_owned());
+
+        let mut config = common::configurations::strategist();
+        config.choose_target = "anchored_but_popups".to_owned();
+        let strategist = Strategist::new_from_config(config);
+
+        let output = Box::new(OutputMock::new(output_info.clone()));
+        let mut coordinator = CoordinatorMock::new();
+        let mut exhibitor = Exhibitor::new(coordinator.clone(),
+                                           std::time::Instant::now(),
+                                           strategist,
+                                           common::configurations::compositor());
+
+        exhibitor.on_output_found(output);
+        for sid in 1..4 {
+            coordinator.add_surface(SurfaceId::new(sid));
+        }
+        exhibitor.on_surface_ready(SurfaceId::new(1));
+        exhibitor.on_surface_ready(SurfaceId::new(2));
+        exhibitor.horizontalize();
+        exhibitor.on_dock_surface(SurfaceId::new(3), Size::new(100, 10), output_info.id);
+
+        Environment { exhibitor: exhibitor, coordinator: coordinator }
+    }
+
+    /// Executes given command on focused frame.
+    pub fn execute(&mut self, action: Action) {
+        self.exhibitor.on_command(Command {
+            action: action,
+            direction: Direction::None,
+            magnitude: 0,
+            string: String::new(),
+        });
+    }
+
+    /// Returns presentation of frame with given surface ID.
+    pub fn presentation(&self, sid: u64) -> Presentation {
+        let frame = self.exhibitor.get_root().find_with_sid(SurfaceId::new(sid)).unwrap();
+        frame.get_presentation()
+    }
+
+    /// Checks size and state the surface was last configured with.
+    pub fn assert_configured(&self,
+                             sid: u64,
+                             width: usize,
+                             height: usize,
+                             state: surface_state::SurfaceState) {
+        let info = self.coordinator.get_surface(SurfaceId::new(sid)).unwrap();
+        assert_eq!(info.requested_size, Size::new(width, height), "sid: {}", sid);
+        assert_eq!(info.state_flags, state, "sid: {}", sid);
+    }
+
+    /// Returns contexts of surfaces to be drawn on the display.
+    pub fn draw(&self) -> Vec<SurfaceContext> {
+        let display = self.exhibitor.get_root().get_first_space().unwrap();
+        display.to_array(Position::default(), &self.coordinator)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if fullscreen surface covers whole display including docks and if it gets back to its
+/// place in layout when leaving fullscreen.
+#[test]
+fn test_toggling_fullscreen() {
+    let mut e = Environment::create();
+
+    e.execute(Action::Fullscreen);
+    assert_eq!(e.presentation(2), Presentation::Fullscreen);
+    e.assert_configured(2, 100, 100, surface_state::FULLSCREEN);
+    assert_eq!(e.draw(), vec![SurfaceContext::new(SurfaceId::new(2), Position::new(0, 0))]);
+
+    e.execute(Action::Fullscreen);
+    assert_eq!(e.presentation(2), Presentation::Regular);
+    e.assert_configured(2, 50, 90, surface_state::MAXIMIZED);
+    assert_eq!(e.draw().len(), 3);
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if maximized surface covers whole workspace but not docks.
+#[test]
+fn test_toggling_maximization() {
+    let mut e = Environment::create();
+
+    e.execute(Action::Maximize);
+    assert_eq!(e.presentation(2), Presentation::Maximized);
+    e.assert_configured(2, 100, 90, surface_state::MAXIMIZED);
+
+    let contexts = e.draw();
+    assert_eq!(contexts.len(), 2);
+    assert!(contexts.contains(&SurfaceContext::new(SurfaceId::new(2), Position::new(0, 10))));
+    assert!(contexts.contains(&SurfaceContext::new(SurfaceId::new(3), Position::new(0, 0))));
+
+    e.execute(Action::Maximize);
+    assert_eq!(e.presentation(2), Presentation::Regular);
+    e.assert_configured(2, 50, 90, surface_state::MAXIMIZED);
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if focusing covered surface brings fullscreen surface back to its place in layout.
+#[test]
+fn test_leaving_fullscreen_on_focus_change() {
+    let mut e = Environment::create();
+
+    e.execute(Action::Fullscreen);
+    e.exhibitor.focus_right();
+
+    assert_eq!(e.exhibitor.get_selection().get_sid(), SurfaceId::new(1));
+    assert_eq!(e.presentation(2), Presentation::Regular);
+    e.assert_configured(2, 50, 90, surface_state::MAXIMIZED);
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if surface stays fullscreen after switching workspace back and forth.
+#[test]
+fn test_keeping_fullscreen_on_workspace_switch() {
+    let mut e = Environment::create();
+
+    e.execute(Action::Fullscreen);
+    e.exhibitor.focus_workspace("2");
+    assert_eq!(e.presentation(2), Presentation::Fullscreen);
+    assert_eq!(e.draw(), vec![SurfaceContext::new(SurfaceId::new(3), Position::new(0, 0))]);
+
+    e.exhibitor.focus_workspace("1");
+    assert_eq!(e.exhibitor.get_selection().get_sid(), SurfaceId::new(2));
+    assert_eq!(e.presentation(2), Presentation::Fullscreen);
+    assert_eq!(e.draw(), vec![SurfaceContext::new(SurfaceId::new(2), Position::new(0, 0))]);
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if client requests are honoured and if request to maximize does not make fullscreen
+/// surface leave fullscreen.
+#[test]
+fn test_client_requests() {
+    let mut e = Environment::create();
+
+    e.exhibitor.on_surface_state_requested(SurfaceId::new(1), surface_state::FULLSCREEN, true);
+    assert_eq!(e.exhibitor.get_selection().get_sid(), SurfaceId::new(1));
+    assert_eq!(e.presentation(1), Presentation::Fullscreen);
+
+    e.exhibitor.on_surface_state_requested(SurfaceId::new(1), surface_state::MAXIMIZED, true);
+    assert_eq!(e.presentation(1), Presentation::Fullscreen);
+
+    e.exhibitor.on_surface_state_requested(SurfaceId::new(1), surface_state::FULLSCREEN, false);
+    assert_eq!(e.presentation(1), Presentation::Regular);
+    e.assert_configured(1, 50, 90, surface_state::MAXIMIZED);
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if request sent before surface was managed is applied when the surface gets managed.
+#[test]
+fn test_client_request_before_managing() {
+    let mut e = Environment::create();
+
+    e.coordinator.add_surface(SurfaceId::new(4));
+    e.exhibitor.on_surface_state_requested(SurfaceId::new(4), surface_state::FULLSCREEN, true);
+    e.exhibitor.on_surface_ready(SurfaceId::new(4));
+
+    assert_eq!(e.exhibitor.get_selection().get_sid(), SurfaceId::new(4));
+    assert_eq!(e.presentation(4), Presentation::Fullscreen);
+    e.assert_configured(4, 100, 100, surface_state::FULLSCREEN);
+}
+
+// -------------------------------------------------------------------------------------------------
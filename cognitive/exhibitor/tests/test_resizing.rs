@@ -0,0 +1,158 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! Unit tests of interactive resizing of frames with the pointer.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+
+extern crate cognitive_qualia as qualia;
+extern crate cognitive_outputs as outputs;
+extern crate cognitive_frames as frames;
+extern crate cognitive_exhibitor as exhibitor;
+
+mod common;
+
+use qualia::{OutputInfo, SurfaceId};
+use qualia::{Area, Position, Size, Vector};
+use qualia::{Action, Command, Direction, InteractionMode};
+use qualia::coordinator_mock::CoordinatorMock;
+use outputs::output_mock::OutputMock;
+use frames::{Frame, Searching};
+use exhibitor::{Exhibitor, Strategist};
+
+// -------------------------------------------------------------------------------------------------
+
+struct Environment {
+    exhibitor: Exhibitor<CoordinatorMock>,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Environment {
+    /// Creates display where every surface is floating and centered.
+    pub fn create_floating() -> Self {
+        let mut config = common::configurations::strategist();
+        config.choose_target = "always_floating".to_owned();
+        config.choose_floating = "always_centered".to_owned();
+        Self::create(config, common::configurations::compositor())
+    }
+
+    /// Creates display where surfaces are anchored side by side.
+    pub fn create_anchored() -> Self {
+        let mut config = common::configurations::strategist();
+        config.choose_target = "anchored_but_popups".to_owned();
+        let mut compositor_config = common::configurations::compositor();
+        compositor_config.workspace.geometry = "horizontal".to_owned();
+        Self::create(config, compositor_config)
+    }
+
+    fn create(strategist_config: qualia::StrategistConfig,
+              compositor_config: qualia::CompositorConfig)
+              -> Self {
+        let output_info = OutputInfo::new(1,
+                                          Area::new(Position::new(0, 0), Size::new(100, 100)),
+                                          Size::new(100, 100),
+                                          60,
+                                          "test_make".to_owned(),
+                                          "test_model".to_owned());
+
+        let strategist = Strategist::new_from_config(strategist_config);
+        let output = Box::new(OutputMock::new(output_info.clone()));
+        let coordinator = CoordinatorMock::new();
+        let mut exhibitor = Exhibitor::new(coordinator.clone(),
+                                           std::time::Instant::now(),
+                                           strategist,
+                                           compositor_config);
+
+        exhibitor.on_output_found(output);
+
+        Environment { exhibitor: exhibitor }
+    }
+
+    /// Places the pointer in given position and drags it by given vector in resize mode.
+    pub fn resize(&mut self, position: Position, vector: Vector) {
+        self.exhibitor.on_warp(position);
+        self.exhibitor.on_mode_switched(true, InteractionMode::Resize);
+        self.exhibitor.on_motion(vector);
+        self.exhibitor.on_mode_switched(false, InteractionMode::Resize);
+    }
+
+    /// Returns frame of surface with given ID.
+    pub fn frame(&self, sid: u64) -> Frame {
+        self.exhibitor.get_root().find_with_sid(SurfaceId::new(sid)).unwrap()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if floating frame is resized by the corner nearest to the pointer.
+#[test]
+fn test_resizing_floating_frame_by_corner() {
+    let mut e = Environment::create_floating();
+    e.exhibitor.on_surface_ready(SurfaceId::new(1));
+    assert_eq!(e.frame(1).get_area(), Area::create(25, 25, 50, 50));
+
+    // Bottom-right corner changes only size
+    e.resize(Position::new(70, 70), Vector::new(10, 5));
+    assert_eq!(e.frame(1).get_area(), Area::create(25, 25, 60, 55));
+
+    // Top-left corner changes also position
+    e.resize(Position::new(30, 30), Vector::new(-5, -10));
+    assert_eq!(e.frame(1).get_area(), Area::create(20, 15, 65, 65));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if floating frame can not be shrunk below minimal size.
+#[test]
+fn test_resizing_floating_frame_below_minimum() {
+    let mut e = Environment::create_floating();
+    e.exhibitor.on_surface_ready(SurfaceId::new(1));
+
+    e.resize(Position::new(70, 70), Vector::new(-60, 0));
+    assert_eq!(e.frame(1).get_area(), Area::create(25, 25, 32, 50));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if resizing anchored frame moves the split between it and its neighbour and that the
+/// neighbour is not shrunk below minimal size.
+#[test]
+fn test_resizing_anchored_frame() {
+    let mut e = Environment::create_anchored();
+    e.exhibitor.on_surface_ready(SurfaceId::new(1));
+    e.exhibitor.on_surface_ready(SurfaceId::new(2));
+    assert_eq!(e.frame(1).get_area(), Area::create(0, 0, 50, 100));
+    assert_eq!(e.frame(2).get_area(), Area::create(50, 0, 50, 100));
+
+    e.resize(Position::new(45, 50), Vector::new(10, 0));
+    assert_eq!(e.frame(1).get_area(), Area::create(0, 0, 60, 100));
+    assert_eq!(e.frame(2).get_area(), Area::create(60, 0, 40, 100));
+
+    e.resize(Position::new(55, 50), Vector::new(30, 0));
+    assert_eq!(e.frame(1).get_area(), Area::create(0, 0, 68, 100));
+    assert_eq!(e.frame(2).get_area(), Area::create(68, 0, 32, 100));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if frames hidden under maximized frame are not resized and maximized frame itself is
+/// not resized either.
+#[test]
+fn test_resizing_under_maximized_frame() {
+    let mut e = Environment::create_anchored();
+    e.exhibitor.on_surface_ready(SurfaceId::new(1));
+    e.exhibitor.on_surface_ready(SurfaceId::new(2));
+    e.exhibitor.on_command(Command {
+        action: Action::Maximize,
+        direction: Direction::None,
+        magnitude: 0,
+        string: String::new(),
+    });
+
+    e.resize(Position::new(45, 50), Vector::new(10, 0));
+    assert_eq!(e.frame(1).get_area(), Area::create(0, 0, 50, 100));
+    assert_eq!(e.frame(2).get_area(), Area::create(50, 0, 50, 100));
+}
+
+// -------------------------------------------------------------------------------------------------
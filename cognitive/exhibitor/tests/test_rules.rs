@@ -0,0 +1,177 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! Unit tests of window rules.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+
+extern crate cognitive_qualia as qualia;
+extern crate cognitive_outputs as outputs;
+extern crate cognitive_frames as frames;
+extern crate cognitive_exhibitor as exhibitor;
+
+mod common;
+
+use qualia::{OutputInfo, RuleConfig, SurfaceControl, SurfaceId};
+use qualia::{Area, Position, Size};
+use qualia::coordinator_mock::CoordinatorMock;
+use outputs::output_mock::OutputMock;
+use frames::{Geometry, Mobility, Searching};
+use exhibitor::{Exhibitor, Strategist};
+
+// -------------------------------------------------------------------------------------------------
+
+struct Environment {
+    exhibitor: Exhibitor<CoordinatorMock>,
+    coordinator: CoordinatorMock,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Environment {
+    pub fn create(rules: Vec<RuleConfig>) -> Self {
+        let output_info = OutputInfo::new(1,
+                                          Area::new(Position::new(0, 0), Size::new(100, 100)),
+                                          Size::new(100, 100),
+                                          60,
+                                          "test_make".to_owned(),
+                                          "test_model".to_owned());
+
+        let mut config = common::configurations::strategist();
+        config.choose_target = "anchored_but_popups".to_owned();
+        config.rules = rules;
+        let strategist = Strategist::new_from_config(config);
+
+        let output = Box::new(OutputMock::new(output_info.clone()));
+        let coordinator = CoordinatorMock::new();
+        let mut exhibitor = Exhibitor::new(coordinator.clone(),
+                                           std::time::Instant::now(),
+                                           strategist,
+                                           common::configurations::compositor());
+
+        exhibitor.on_output_found(output);
+
+        Environment { exhibitor: exhibitor, coordinator: coordinator }
+    }
+
+    /// Creates new surface with given application ID.
+    pub fn create_surface(&mut self, sid: u64, app_id: &str) {
+        self.coordinator.set_surface_app_id(SurfaceId::new(sid), app_id.to_owned());
+        self.exhibitor.on_surface_ready(SurfaceId::new(sid));
+    }
+
+    /// Returns frame of surface with given ID.
+    pub fn find(&self, sid: u64) -> frames::Frame {
+        self.exhibitor.get_root().find_with_sid(SurfaceId::new(sid)).unwrap()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if rule can make surface floating with given area.
+#[test]
+fn test_rule_making_surface_floating() {
+    let mut e = Environment::create(vec![
+        RuleConfig {
+            app_id: Some("calculator".to_owned()),
+            floating: Some(true),
+            width: Some(30),
+            height: Some(20),
+            x: Some(10),
+            y: Some(5),
+            ..RuleConfig::default()
+        }
+    ]);
+
+    e.create_surface(1, "terminal");
+    e.create_surface(2, "calculator");
+
+    assert_eq!(e.find(1).get_mobility(), Mobility::Anchored);
+    assert_eq!(e.find(2).get_mobility(), Mobility::Floating);
+    assert_eq!(e.find(2).get_area(), Area::create(10, 5, 30, 20));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if rule can place surface in other workspace without focusing it.
+#[test]
+fn test_rule_placing_surface_in_workspace() {
+    let mut e = Environment::create(vec![
+        RuleConfig {
+            app_id: Some("mail".to_owned()),
+            workspace: Some("2".to_owned()),
+            ..RuleConfig::default()
+        }
+    ]);
+
+    e.create_surface(1, "terminal");
+    e.create_surface(2, "mail");
+
+    assert_eq!(e.find(1).find_top().unwrap().get_title(), "1");
+    assert_eq!(e.find(2).find_top().unwrap().get_title(), "2");
+    assert_eq!(e.exhibitor.get_selection().get_sid(), SurfaceId::new(1));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if rule can prevent focusing new surface.
+#[test]
+fn test_rule_preventing_focus() {
+    let mut e = Environment::create(vec![
+        RuleConfig {
+            app_id: Some("notifier".to_owned()),
+            no_focus: true,
+            ..RuleConfig::default()
+        }
+    ]);
+
+    e.create_surface(1, "terminal");
+    e.create_surface(2, "notifier");
+
+    assert_eq!(e.exhibitor.get_selection().get_sid(), SurfaceId::new(1));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if rule can enclose surfaces in container with given geometry and if following matching
+/// surfaces are placed in the same container.
+#[test]
+fn test_rule_enclosing_surface_in_container() {
+    let mut e = Environment::create(vec![
+        RuleConfig {
+            app_id: Some("terminal".to_owned()),
+            geometry: Some("vertical".to_owned()),
+            ..RuleConfig::default()
+        }
+    ]);
+
+    e.create_surface(1, "terminal");
+    e.create_surface(2, "terminal");
+
+    let container = e.find(1).get_parent().unwrap();
+    assert_eq!(container.get_geometry(), Geometry::Vertical);
+    assert!(!container.get_mode().is_workspace());
+    assert!(e.find(2).get_parent().unwrap().equals_exact(&container));
+    assert_eq!(e.find(1).get_area(), Area::create(0,  0, 100, 50));
+    assert_eq!(e.find(2).get_area(), Area::create(0, 50, 100, 50));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if rules matching popups do not apply to toplevel surfaces and the other way around.
+#[test]
+fn test_rule_matching_popups() {
+    let mut e = Environment::create(vec![
+        RuleConfig {
+            popup: Some(false),
+            floating: Some(true),
+            ..RuleConfig::default()
+        }
+    ]);
+
+    e.create_surface(1, "terminal");
+
+    assert_eq!(e.find(1).get_mobility(), Mobility::Floating);
+}
+
+// -------------------------------------------------------------------------------------------------
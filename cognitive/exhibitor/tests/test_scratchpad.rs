@@ -0,0 +1,150 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! Unit tests of hiding surfaces in scratchpad.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+
+extern crate cognitive_qualia as qualia;
+extern crate cognitive_outputs as outputs;
+extern crate cognitive_frames as frames;
+extern crate cognitive_exhibitor as exhibitor;
+
+mod common;
+
+use qualia::{OutputInfo, SurfaceId};
+use qualia::{Area, Position, Size};
+use qualia::{Action, Command, Direction};
+use qualia::coordinator_mock::CoordinatorMock;
+use outputs::output_mock::OutputMock;
+use frames::{Mobility, Searching};
+use exhibitor::{Exhibitor, Strategist};
+
+// -------------------------------------------------------------------------------------------------
+
+struct Environment {
+    exhibitor: Exhibitor<CoordinatorMock>,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Environment {
+    /// Creates display with two anchored surfaces. Surface 2 is focused.
+    pub fn create() -> Self {
+        let output_info = OutputInfo::new(1,
+                                          Area::new(Position::new(0, 0), Size::new(100, 100)),
+                                          Size::new(100, 100),
+                                          60,
+                                          "test_make".to_owned(),
+                                          "test_model".to_owned());
+
+        let mut config = common::configurations::strategist();
+        config.choose_target = "anchored_but_popups".to_owned();
+        let strategist = Strategist::new_from_config(config);
+
+        let output = Box::new(OutputMock::new(output_info.clone()));
+        let coordinator = CoordinatorMock::new();
+        let mut exhibitor = Exhibitor::new(coordinator.clone(),
+                                           std::time::Instant::now(),
+                                           strategist,
+                                           common::configurations::compositor());
+
+        exhibitor.on_output_found(output);
+        exhibitor.on_surface_ready(SurfaceId::new(1));
+        exhibitor.on_surface_ready(SurfaceId::new(2));
+
+        Environment { exhibitor: exhibitor }
+    }
+
+    /// Executes given command on focused frame.
+    pub fn execute(&mut self, action: Action) {
+        self.exhibitor.on_command(Command {
+            action: action,
+            direction: Direction::None,
+            magnitude: 0,
+            string: String::new(),
+        });
+    }
+
+    /// Checks if surface with given ID is hidden in scratchpad.
+    pub fn is_hidden(&self, sid: u64) -> bool {
+        let sid = SurfaceId::new(sid);
+        assert!(self.exhibitor.get_root().find_with_sid(sid).is_some() !=
+                self.exhibitor.get_scratchpad().find_with_sid(sid).is_some());
+        self.exhibitor.get_scratchpad().find_with_sid(sid).is_some()
+    }
+
+    /// Returns ID of focused surface.
+    pub fn selected(&self) -> SurfaceId {
+        self.exhibitor.get_selection().get_sid()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if hidden surface is taken out of workspace and shown floating and centered.
+#[test]
+fn test_hiding_and_showing() {
+    let mut e = Environment::create();
+
+    e.execute(Action::Hide);
+    assert!(e.is_hidden(2));
+    assert_eq!(e.selected(), SurfaceId::new(1));
+    let frame = e.exhibitor.get_root().find_with_sid(SurfaceId::new(1)).unwrap();
+    assert_eq!(frame.get_area(), Area::create(0, 0, 100, 100));
+
+    e.execute(Action::Scratchpad);
+    assert!(!e.is_hidden(2));
+    assert_eq!(e.selected(), SurfaceId::new(2));
+    let frame = e.exhibitor.get_root().find_with_sid(SurfaceId::new(2)).unwrap();
+    assert_eq!(frame.get_mobility(), Mobility::Floating);
+    assert_eq!(frame.get_area(), Area::create(25, 25, 50, 50));
+
+    e.execute(Action::Scratchpad);
+    assert!(e.is_hidden(2));
+    assert_eq!(e.selected(), SurfaceId::new(1));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if toggling scratchpad cycles through all hidden surfaces starting from the most recent.
+#[test]
+fn test_cycling_through_scratchpad() {
+    let mut e = Environment::create();
+
+    e.execute(Action::Hide);
+    e.execute(Action::Hide);
+    assert!(e.is_hidden(1));
+    assert!(e.is_hidden(2));
+
+    e.execute(Action::Scratchpad);
+    assert_eq!(e.selected(), SurfaceId::new(1));
+    e.execute(Action::Scratchpad);
+    assert!(e.is_hidden(1));
+
+    e.execute(Action::Scratchpad);
+    assert_eq!(e.selected(), SurfaceId::new(2));
+    e.execute(Action::Scratchpad);
+    assert!(e.is_hidden(2));
+
+    e.execute(Action::Scratchpad);
+    assert_eq!(e.selected(), SurfaceId::new(1));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if destroying hidden surface removes it from scratchpad.
+#[test]
+fn test_destroying_hidden_surface() {
+    let mut e = Environment::create();
+
+    e.execute(Action::Hide);
+    e.exhibitor.on_surface_destroyed(SurfaceId::new(2));
+    assert!(e.exhibitor.get_scratchpad().find_with_sid(SurfaceId::new(2)).is_none());
+
+    e.execute(Action::Scratchpad);
+    assert_eq!(e.selected(), SurfaceId::new(1));
+    assert!(e.exhibitor.get_root().find_with_sid(SurfaceId::new(2)).is_none());
+}
+
+// -------------------------------------------------------------------------------------------------
@@ -0,0 +1,126 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! Unit test of Exhibitor behavior while session is locked.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+
+extern crate cognitive_qualia as qualia;
+extern crate cognitive_outputs as outputs;
+extern crate cognitive_frames as frames;
+extern crate cognitive_exhibitor as exhibitor;
+
+mod common;
+
+use qualia::{OutputInfo, SurfaceId};
+use qualia::{Area, Position, Size};
+use qualia::{Action, Command, Direction};
+use qualia::coordinator_mock::CoordinatorMock;
+use outputs::output_mock::OutputMock;
+use frames::Geometry::{Stacked, Vertical};
+use frames::Mobility::Floating;
+use frames::representation::FrameRepresentation;
+use exhibitor::{Exhibitor, Strategist};
+
+// -------------------------------------------------------------------------------------------------
+
+struct Environment {
+    exhibitor: Exhibitor<CoordinatorMock>,
+    output_info: OutputInfo,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Environment {
+    pub fn create() -> Self {
+        let output_info = OutputInfo::new(1,
+                                          Area::new(Position::new(0, 0), Size::new(100, 100)),
+                                          Size::new(100, 100),
+                                          60,
+                                          "test_make".to_owned(),
+                                          "test_model".to_owned());
+
+        let output = Box::new(OutputMock::new(output_info.clone()));
+        let coordinator = CoordinatorMock::new();
+        let mut exhibitor = Exhibitor::new(coordinator.clone(),
+                                           std::time::Instant::now(),
+                                           Strategist::default(),
+                                           common::configurations::compositor());
+
+        exhibitor.on_output_found(output);
+
+        Environment {
+            exhibitor: exhibitor,
+            output_info: output_info,
+        }
+    }
+
+    pub fn configure_north(&mut self) {
+        self.exhibitor.on_command(Command {
+            action: Action::Configure,
+            direction: Direction::North,
+            magnitude: 0,
+            string: String::default(),
+        });
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if commands are ignored while session is locked and executed again after unlocking.
+#[test]
+fn test_commands_while_locked() {
+    let mut e = Environment::create();
+    e.exhibitor.on_surface_ready(SurfaceId::new(1));
+    e.exhibitor.on_surface_ready(SurfaceId::new(2));
+
+    e.exhibitor.on_lock_session();
+    assert!(e.exhibitor.is_session_locked());
+    e.configure_north();
+
+    let repr = FrameRepresentation::single_workspace(e.output_info.area, Stacked,
+        vec![
+            FrameRepresentation::new_leaf(2, Vertical).with_mobility(Floating),
+            FrameRepresentation::new_leaf(1, Vertical).with_mobility(Floating),
+        ]);
+
+    repr.assert_frames_spaced(&e.exhibitor.get_root());
+    assert!(e.exhibitor.get_selection().get_sid() == SurfaceId::new(2));
+
+    e.exhibitor.on_unlock_session();
+    assert!(!e.exhibitor.is_session_locked());
+    e.configure_north();
+
+    let repr = FrameRepresentation::single_workspace(e.output_info.area, Vertical,
+        vec![
+            FrameRepresentation::new_leaf(2, Vertical).with_mobility(Floating),
+            FrameRepresentation::new_leaf(1, Vertical).with_mobility(Floating),
+        ]);
+
+    repr.assert_frames_spaced(&e.exhibitor.get_root());
+    assert!(e.exhibitor.get_selection().get_sid() == SurfaceId::new(2));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if destroying the lock surface does not unlock the session.
+#[test]
+fn test_session_stays_locked_after_lock_surface_destroyed() {
+    let mut e = Environment::create();
+    e.exhibitor.on_surface_ready(SurfaceId::new(1));
+
+    e.exhibitor.on_lock_session();
+    e.exhibitor.on_lock_surface(SurfaceId::new(2), e.output_info.id);
+    e.exhibitor.on_surface_destroyed(SurfaceId::new(2));
+    assert!(e.exhibitor.is_session_locked());
+
+    e.configure_north();
+    let repr = FrameRepresentation::single_workspace(e.output_info.area, Stacked,
+        vec![
+            FrameRepresentation::new_leaf(1, Vertical).with_mobility(Floating),
+        ]);
+
+    repr.assert_frames_spaced(&e.exhibitor.get_root());
+}
+
+// -------------------------------------------------------------------------------------------------
@@ -124,13 +124,13 @@ fn test_adding_dock_and_workspace() {
                         Parameters::new_container(Stacked),
                         vec![
                             FrameRepresentation::new(
-                                Parameters::new_workspace("2".to_owned(), Stacked, true),
-                                vec![FrameRepresentation::new_leaf(3, Vertical)
+                                Parameters::new_workspace("1".to_owned(), Stacked, false),
+                                vec![FrameRepresentation::new_leaf(1, Vertical)
                                                          .with_mobility(Floating)]
                             ).with_area(0, 0, 100, 90),
                             FrameRepresentation::new(
-                                Parameters::new_workspace("1".to_owned(), Stacked, false),
-                                vec![FrameRepresentation::new_leaf(1, Vertical)
+                                Parameters::new_workspace("2".to_owned(), Stacked, true),
+                                vec![FrameRepresentation::new_leaf(3, Vertical)
                                                          .with_mobility(Floating)]
                             ).with_area(0, 0, 100, 90),
                         ]
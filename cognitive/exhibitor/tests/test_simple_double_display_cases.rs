@@ -174,13 +174,13 @@ fn test_dragging_surface_in_visual_mode() {
     let mut area = selection.get_area();
 
     // Switch visual mode on and move cursor
-    e.exhibitor.on_mode_switched(true, InteractionMode::Visual);
+    e.exhibitor.on_mode_switched(true, InteractionMode::Drag);
     e.exhibitor.on_motion(vector);
     area.pos = area.pos + vector;
     assert_eq!(e.exhibitor.get_selection().get_area().pos, area.pos);
 
     // After switching visual mode off nothing should be moved
-    e.exhibitor.on_mode_switched(false, InteractionMode::Visual);
+    e.exhibitor.on_mode_switched(false, InteractionMode::Drag);
     e.exhibitor.on_motion(vector);
     assert_eq!(e.exhibitor.get_selection().get_area().pos, area.pos);
 }
@@ -207,7 +207,7 @@ fn test_dragging_surface_in_visual_mode_to_different_display() {
 
     // Switch visual mode on and move cursor to different display
     let vector = Vector::new(110, 20);
-    e.exhibitor.on_mode_switched(true, InteractionMode::Visual);
+    e.exhibitor.on_mode_switched(true, InteractionMode::Drag);
     e.exhibitor.on_motion(vector);
 
     let new_pos = Position::new(35, 45);
@@ -244,7 +244,7 @@ fn test_dragging_surface_in_visual_mode_to_different_display() {
 
     // After switching visual mode off nothing should be moved
     let vector = Vector::new(10, 20);
-    e.exhibitor.on_mode_switched(false, InteractionMode::Visual);
+    e.exhibitor.on_mode_switched(false, InteractionMode::Drag);
     e.exhibitor.on_motion(vector);
     repr.assert_frames_spaced(&e.exhibitor.get_root());
 }
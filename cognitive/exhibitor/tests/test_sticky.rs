@@ -0,0 +1,161 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! Unit tests of sticky frames.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+
+extern crate cognitive_qualia as qualia;
+extern crate cognitive_outputs as outputs;
+extern crate cognitive_frames as frames;
+extern crate cognitive_exhibitor as exhibitor;
+
+mod common;
+
+use qualia::{OutputInfo, SurfaceId};
+use qualia::{Area, Position, Size};
+use qualia::{Action, Command, Direction};
+use qualia::coordinator_mock::CoordinatorMock;
+use outputs::output_mock::OutputMock;
+use frames::{Frame, Searching};
+use exhibitor::{Exhibitor, Strategist};
+
+use common::exhibitor_mixins::ExhibitorCommandShorthands;
+
+// -------------------------------------------------------------------------------------------------
+
+struct Environment {
+    exhibitor: Exhibitor<CoordinatorMock>,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Environment {
+    /// Creates display where every surface is floating and centered.
+    pub fn create() -> Self {
+        let output_info = OutputInfo::new(1,
+                                          Area::new(Position::new(0, 0), Size::new(100, 100)),
+                                          Size::new(100, 100),
+                                          60,
+                                          "test_make".to_owned(),
+                                          "test_model".to_owned());
+
+        let mut config = common::configurations::strategist();
+        config.choose_target = "always_floating".to_owned();
+        config.choose_floating = "always_centered".to_owned();
+        let strategist = Strategist::new_from_config(config);
+
+        let output = Box::new(OutputMock::new(output_info.clone()));
+        let coordinator = CoordinatorMock::new();
+        let mut exhibitor = Exhibitor::new(coordinator.clone(),
+                                           std::time::Instant::now(),
+                                           strategist,
+                                           common::configurations::compositor());
+
+        exhibitor.on_output_found(output);
+
+        Environment { exhibitor: exhibitor }
+    }
+
+    /// Executes given command on focused frame.
+    pub fn execute(&mut self, action: Action, direction: Direction, magnitude: i32) {
+        self.exhibitor.on_command(Command {
+            action: action,
+            direction: direction,
+            magnitude: magnitude,
+            string: String::new(),
+        });
+    }
+
+    /// Returns frame of surface with given ID.
+    pub fn frame(&self, sid: u64) -> Frame {
+        self.exhibitor.get_root().find_with_sid(SurfaceId::new(sid)).unwrap()
+    }
+
+    /// Returns title of workspace containing surface with given ID.
+    pub fn workspace_of(&self, sid: u64) -> String {
+        self.frame(sid).find_top().unwrap().get_title()
+    }
+
+    /// Returns ID of focused surface.
+    pub fn selected(&self) -> SurfaceId {
+        self.exhibitor.get_selection().get_sid()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if sticky frame is moved to activated workspace keeping its position while other frames
+/// stay in their workspace.
+#[test]
+fn test_sticky_frame_follows_active_workspace() {
+    let mut e = Environment::create();
+    e.exhibitor.on_surface_ready(SurfaceId::new(1));
+    e.exhibitor.on_surface_ready(SurfaceId::new(2));
+
+    e.execute(Action::Stick, Direction::None, 0);
+    assert!(e.frame(2).is_sticky());
+    assert!(!e.frame(1).is_sticky());
+
+    e.exhibitor.focus_workspace("2");
+    assert_eq!(e.workspace_of(1), "1");
+    assert_eq!(e.workspace_of(2), "2");
+    assert_eq!(e.frame(2).get_area(), Area::create(25, 25, 50, 50));
+
+    e.exhibitor.focus_workspace("1");
+    assert_eq!(e.workspace_of(1), "1");
+    assert_eq!(e.workspace_of(2), "1");
+
+    // Sticky frames are skipped in history so surface 1 gets focus after switching workspace
+    assert_eq!(e.selected(), SurfaceId::new(1));
+    e.exhibitor.on_surface_activation_requested(SurfaceId::new(2), true);
+
+    // Frame which is not sticky any more stays in its workspace
+    e.execute(Action::Stick, Direction::None, 0);
+    assert!(!e.frame(2).is_sticky());
+    e.exhibitor.focus_workspace("2");
+    assert_eq!(e.workspace_of(2), "1");
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if sticky frames are skipped while cycling through surface history.
+#[test]
+fn test_cycling_history_skips_sticky_frames() {
+    let mut e = Environment::create();
+    e.exhibitor.on_surface_ready(SurfaceId::new(1));
+    e.execute(Action::Stick, Direction::None, 0);
+    e.exhibitor.on_surface_ready(SurfaceId::new(2));
+    e.exhibitor.on_surface_ready(SurfaceId::new(3));
+    assert_eq!(e.selected(), SurfaceId::new(3));
+
+    e.execute(Action::Focus, Direction::Forward, 1);
+    assert_eq!(e.selected(), SurfaceId::new(2));
+
+    e.execute(Action::Focus, Direction::Backward, 1);
+    assert_eq!(e.selected(), SurfaceId::new(3));
+
+    e.execute(Action::Focus, Direction::Backward, 2);
+    assert_eq!(e.selected(), SurfaceId::new(3));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if anchoring sticky frame makes it regular and that anchored frames can not be made
+/// sticky.
+#[test]
+fn test_anchoring_sticky_frame() {
+    let mut e = Environment::create();
+    e.exhibitor.on_surface_ready(SurfaceId::new(1));
+
+    e.execute(Action::Stick, Direction::None, 0);
+    assert!(e.frame(1).is_sticky());
+
+    e.execute(Action::Anchor, Direction::None, 0);
+    assert!(!e.frame(1).is_sticky());
+
+    e.execute(Action::Stick, Direction::None, 0);
+    assert!(!e.frame(1).is_sticky());
+}
+
+// -------------------------------------------------------------------------------------------------
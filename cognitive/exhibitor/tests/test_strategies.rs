@@ -0,0 +1,158 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! Unit tests of strategies placing floating surfaces.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+
+extern crate cognitive_qualia as qualia;
+extern crate cognitive_exhibitor as exhibitor;
+
+use qualia::{Area, Position, Size};
+use exhibitor::{FloatingContext, Strategist};
+use exhibitor::strategies;
+
+// -------------------------------------------------------------------------------------------------
+
+/// Constructs context of workspace 200x100 with new surface of size 40x20.
+fn context() -> FloatingContext {
+    FloatingContext::new(Size::new(200, 100), Some(Size::new(40, 20)))
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if frame is placed in one of corners of workspace.
+#[test]
+fn test_random_corner() {
+    let strategist = Strategist::default();
+    let corners = vec![Position::new(0, 0),
+                       Position::new(160, 0),
+                       Position::new(0, 80),
+                       Position::new(160, 80)];
+
+    for _ in 0..10 {
+        let decision = strategies::choose_floating_random_corner(&strategist, &context());
+        assert!(corners.contains(&decision.area.pos));
+        assert_eq!(decision.area.size, Size::new(40, 20));
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if consecutive frames are shifted diagonally and cascade wraps when frame does not fit in
+/// workspace.
+#[test]
+fn test_cascade() {
+    let strategist = Strategist::default();
+    let mut context = context();
+
+    let decision = strategies::choose_floating_cascade(&strategist, &context);
+    assert_eq!(decision.area, Area::create(32, 32, 40, 20));
+
+    context.floating_areas.push(decision.area);
+    let decision = strategies::choose_floating_cascade(&strategist, &context);
+    assert_eq!(decision.area, Area::create(64, 64, 40, 20));
+
+    context.floating_areas.push(decision.area);
+    let decision = strategies::choose_floating_cascade(&strategist, &context);
+    assert_eq!(decision.area, Area::create(96, 15, 40, 20));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if frame is centered under the pointer and kept inside workspace. Without pointer frame
+/// should be centered.
+#[test]
+fn test_under_pointer() {
+    let strategist = Strategist::default();
+    let mut context = context();
+
+    context.pointer = Some(Position::new(100, 50));
+    let decision = strategies::choose_floating_under_pointer(&strategist, &context);
+    assert_eq!(decision.area, Area::create(80, 40, 40, 20));
+
+    context.pointer = Some(Position::new(195, 5));
+    let decision = strategies::choose_floating_under_pointer(&strategist, &context);
+    assert_eq!(decision.area, Area::create(160, 0, 40, 20));
+
+    context.pointer = None;
+    let decision = strategies::choose_floating_under_pointer(&strategist, &context);
+    assert_eq!(decision.area, Area::create(50, 25, 40, 20));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if frame is placed where it overlaps the least with other floating frames.
+#[test]
+fn test_smart() {
+    let strategist = Strategist::default();
+    let mut context = context();
+
+    let decision = strategies::choose_floating_smart(&strategist, &context);
+    assert_eq!(decision.area, Area::create(0, 0, 40, 20));
+
+    context.floating_areas.push(Area::create(0, 0, 40, 20));
+    let decision = strategies::choose_floating_smart(&strategist, &context);
+    assert_eq!(decision.area, Area::create(40, 0, 40, 20));
+
+    context.floating_areas.push(Area::create(40, 0, 40, 20));
+    let decision = strategies::choose_floating_smart(&strategist, &context);
+    assert_eq!(decision.area, Area::create(0, 20, 40, 20));
+
+    // When there is no free space the position with the smallest overlap is chosen
+    context.floating_areas.clear();
+    context.floating_areas.push(Area::create(0, 0, 200, 60));
+    context.floating_areas.push(Area::create(0, 60, 150, 40));
+    let decision = strategies::choose_floating_smart(&strategist, &context);
+    assert_eq!(decision.area, Area::create(160, 60, 40, 20));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if dialogs are centered over their parents and kept inside workspace. Surfaces without
+/// parent should be centered.
+#[test]
+fn test_relative_to_parent() {
+    let strategist = Strategist::default();
+    let mut context = context();
+
+    context.parent_area = Some(Area::create(100, 0, 100, 100));
+    let decision = strategies::choose_floating_relative_to_parent(&strategist, &context);
+    assert_eq!(decision.area, Area::create(130, 40, 40, 20));
+
+    context.parent_area = Some(Area::create(-50, 90, 60, 60));
+    let decision = strategies::choose_floating_relative_to_parent(&strategist, &context);
+    assert_eq!(decision.area, Area::create(0, 80, 40, 20));
+
+    context.parent_area = None;
+    let decision = strategies::choose_floating_relative_to_parent(&strategist, &context);
+    assert_eq!(decision.area, Area::create(50, 25, 40, 20));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if frame is placed in last remembered position of its application. Applications without
+/// remembered position should be centered.
+#[test]
+fn test_remembered() {
+    let mut strategist = Strategist::default();
+    strategist.remember_position("app1", Position::new(10, 20));
+    strategist.remember_position("app2", Position::new(190, 90));
+    strategist.remember_position("app1", Position::new(30, 40));
+
+    let mut context = context();
+
+    context.app_id = "app1".to_owned();
+    let decision = strategies::choose_floating_remembered(&strategist, &context);
+    assert_eq!(decision.area, Area::create(30, 40, 40, 20));
+
+    context.app_id = "app2".to_owned();
+    let decision = strategies::choose_floating_remembered(&strategist, &context);
+    assert_eq!(decision.area, Area::create(160, 80, 40, 20));
+
+    context.app_id = "app3".to_owned();
+    let decision = strategies::choose_floating_remembered(&strategist, &context);
+    assert_eq!(decision.area, Area::create(50, 25, 40, 20));
+}
+
+// -------------------------------------------------------------------------------------------------
@@ -0,0 +1,272 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! Unit tests of swapping frames.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+
+extern crate cognitive_qualia as qualia;
+extern crate cognitive_outputs as outputs;
+extern crate cognitive_frames as frames;
+extern crate cognitive_exhibitor as exhibitor;
+
+mod common;
+
+use qualia::{OutputInfo, SurfaceId};
+use qualia::{Area, Position, Size};
+use qualia::{Action, Command, Direction};
+use qualia::coordinator_mock::CoordinatorMock;
+use outputs::output_mock::OutputMock;
+use frames::Searching;
+use frames::Geometry::{Horizontal, Stacked, Vertical};
+use frames::Parameters;
+use frames::representation::FrameRepresentation;
+use exhibitor::{Exhibitor, Strategist};
+use common::exhibitor_mixins::ExhibitorCommandShorthands;
+
+// -------------------------------------------------------------------------------------------------
+
+struct Environment {
+    exhibitor: Exhibitor<CoordinatorMock>,
+    output_info: OutputInfo,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Environment {
+    pub fn create() -> Self {
+        let output_info = OutputInfo::new(1,
+                                          Area::new(Position::new(0, 0), Size::new(100, 100)),
+                                          Size::new(100, 100),
+                                          60,
+                                          "test_make".to_owned(),
+                                          "test_model".to_owned());
+
+        let mut config = common::configurations::strategist();
+        config.choose_target = "anchored_but_popups".to_owned();
+        let strategist = Strategist::new_from_config(config);
+
+        let output = Box::new(OutputMock::new(output_info.clone()));
+        let coordinator = CoordinatorMock::new();
+        let mut exhibitor = Exhibitor::new(coordinator.clone(),
+                                           std::time::Instant::now(),
+                                           strategist,
+                                           common::configurations::compositor());
+
+        exhibitor.on_output_found(output);
+
+        Environment {
+            exhibitor: exhibitor,
+            output_info: output_info,
+        }
+    }
+
+    /// Creates three anchored surfaces placed in vertical workspace.
+    pub fn create_with_three_vertical() -> Self {
+        let mut e = Self::create();
+        e.exhibitor.on_surface_ready(SurfaceId::new(1));
+        e.exhibitor.on_surface_ready(SurfaceId::new(2));
+        e.exhibitor.on_surface_ready(SurfaceId::new(3));
+        e.exhibitor.verticalize();
+        assert!(e.exhibitor.get_selection().get_sid() == SurfaceId::new(3));
+        e
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if swapping with planar neighbour works.
+#[test]
+fn test_swapping_with_neighbour() {
+    let mut e = Environment::create_with_three_vertical();
+
+    e.exhibitor.on_command(Command {
+        action: Action::Swap,
+        direction: Direction::South,
+        magnitude: 1,
+        string: String::default(),
+    });
+
+    let repr = FrameRepresentation::single_workspace(e.output_info.area, Vertical,
+        vec![
+            FrameRepresentation::new_leaf(2, Stacked).with_area(0,  0, 100, 33),
+            FrameRepresentation::new_leaf(3, Stacked).with_area(0, 33, 100, 33),
+            FrameRepresentation::new_leaf(1, Stacked).with_area(0, 66, 100, 33),
+        ]);
+
+    repr.assert_frames_spaced(&e.exhibitor.get_root());
+    assert!(e.exhibitor.get_selection().get_sid() == SurfaceId::new(3))
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if swapping with farther frames works for positive and negative magnitude.
+#[test]
+fn test_swapping_with_magnitude() {
+    let mut e = Environment::create_with_three_vertical();
+
+    e.exhibitor.on_command(Command {
+        action: Action::Swap,
+        direction: Direction::South,
+        magnitude: 2,
+        string: String::default(),
+    });
+
+    let repr = FrameRepresentation::single_workspace(e.output_info.area, Vertical,
+        vec![
+            FrameRepresentation::new_leaf(1, Stacked).with_area(0,  0, 100, 33),
+            FrameRepresentation::new_leaf(2, Stacked).with_area(0, 33, 100, 33),
+            FrameRepresentation::new_leaf(3, Stacked).with_area(0, 66, 100, 33),
+        ]);
+
+    repr.assert_frames_spaced(&e.exhibitor.get_root());
+    assert!(e.exhibitor.get_selection().get_sid() == SurfaceId::new(3));
+
+    e.exhibitor.on_command(Command {
+        action: Action::Swap,
+        direction: Direction::South,
+        magnitude: -1,
+        string: String::default(),
+    });
+
+    let repr = FrameRepresentation::single_workspace(e.output_info.area, Vertical,
+        vec![
+            FrameRepresentation::new_leaf(1, Stacked).with_area(0,  0, 100, 33),
+            FrameRepresentation::new_leaf(3, Stacked).with_area(0, 33, 100, 33),
+            FrameRepresentation::new_leaf(2, Stacked).with_area(0, 66, 100, 33),
+        ]);
+
+    repr.assert_frames_spaced(&e.exhibitor.get_root());
+    assert!(e.exhibitor.get_selection().get_sid() == SurfaceId::new(3))
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if swapping with frames chosen from history works.
+#[test]
+fn test_swapping_in_history_order() {
+    let mut e = Environment::create_with_three_vertical();
+
+    e.exhibitor.on_command(Command {
+        action: Action::Swap,
+        direction: Direction::Backward,
+        magnitude: 1,
+        string: String::default(),
+    });
+
+    let repr = FrameRepresentation::single_workspace(e.output_info.area, Vertical,
+        vec![
+            FrameRepresentation::new_leaf(2, Stacked),
+            FrameRepresentation::new_leaf(3, Stacked),
+            FrameRepresentation::new_leaf(1, Stacked),
+        ]);
+
+    repr.assert_frames_spaced(&e.exhibitor.get_root());
+    assert!(e.exhibitor.get_selection().get_sid() == SurfaceId::new(3));
+
+    e.exhibitor.on_command(Command {
+        action: Action::Swap,
+        direction: Direction::Forward,
+        magnitude: 1,
+        string: String::default(),
+    });
+
+    let repr = FrameRepresentation::single_workspace(e.output_info.area, Vertical,
+        vec![
+            FrameRepresentation::new_leaf(2, Stacked),
+            FrameRepresentation::new_leaf(1, Stacked),
+            FrameRepresentation::new_leaf(3, Stacked),
+        ]);
+
+    repr.assert_frames_spaced(&e.exhibitor.get_root());
+    assert!(e.exhibitor.get_selection().get_sid() == SurfaceId::new(3))
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if frames placed in different containers exchange places and sizes.
+#[test]
+fn test_swapping_across_containers() {
+    let mut e = Environment::create();
+
+    // Create layout
+    e.exhibitor.on_surface_ready(SurfaceId::new(1));
+    e.exhibitor.on_surface_ready(SurfaceId::new(2));
+    e.exhibitor.on_surface_ready(SurfaceId::new(3));
+    e.exhibitor.on_surface_ready(SurfaceId::new(4));
+    e.exhibitor.verticalize();
+    e.exhibitor.ramify();
+    e.exhibitor.focus_down();
+    e.exhibitor.dive_up();
+    e.exhibitor.horizontalize();
+    e.exhibitor.focus_down();
+    e.exhibitor.focus_down();
+    e.exhibitor.dive_up();
+    e.exhibitor.horizontalize();
+    assert!(e.exhibitor.get_selection().get_sid() == SurfaceId::new(1));
+
+    // Swap
+    e.exhibitor.on_command(Command {
+        action: Action::Swap,
+        direction: Direction::North,
+        magnitude: 1,
+        string: String::default(),
+    });
+
+    // Check structure
+    let repr = FrameRepresentation::single_workspace(e.output_info.area, Vertical,
+        vec![
+            FrameRepresentation::new(
+                Parameters::new_container(Horizontal),
+                vec![
+                    FrameRepresentation::new_leaf(1, Stacked).with_area( 0, 0, 50, 50),
+                    FrameRepresentation::new_leaf(4, Stacked).with_area(50, 0, 50, 50),
+                ],
+            ).with_area(0, 0, 100, 50),
+            FrameRepresentation::new(
+                Parameters::new_container(Horizontal),
+                vec![
+                    FrameRepresentation::new_leaf(3, Stacked).with_area( 0, 0, 50, 50),
+                    FrameRepresentation::new_leaf(2, Stacked).with_area(50, 0, 50, 50),
+                ],
+            ).with_area(0, 50, 100, 50),
+        ]);
+
+    repr.assert_frames_spaced(&e.exhibitor.get_root());
+    assert!(e.exhibitor.get_selection().get_sid() == SurfaceId::new(1))
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if swapping with most recently used frame on another workspace works.
+#[test]
+fn test_swapping_with_workspace() {
+    let mut e = Environment::create();
+
+    // Create one surface on each workspace
+    e.exhibitor.on_surface_ready(SurfaceId::new(1));
+    e.exhibitor.focus_workspace("2");
+    e.exhibitor.on_surface_ready(SurfaceId::new(2));
+    e.exhibitor.focus_workspace("1");
+    assert!(e.exhibitor.get_selection().get_sid() == SurfaceId::new(1));
+
+    // Swap
+    e.exhibitor.on_command(Command {
+        action: Action::Swap,
+        direction: Direction::Workspace,
+        magnitude: 0,
+        string: "2".to_owned(),
+    });
+
+    // Check placement
+    let root = e.exhibitor.get_root();
+    let first = root.find_with_sid(SurfaceId::new(1)).unwrap();
+    let second = root.find_with_sid(SurfaceId::new(2)).unwrap();
+    assert_eq!(first.find_top().unwrap().get_title(), "2");
+    assert_eq!(second.find_top().unwrap().get_title(), "1");
+    assert_eq!(first.get_area(), Area::new(Position::new(0, 0), Size::new(100, 100)));
+    assert_eq!(second.get_area(), Area::new(Position::new(0, 0), Size::new(100, 100)));
+    assert!(e.exhibitor.get_selection().get_sid() == SurfaceId::new(1))
+}
+
+// -------------------------------------------------------------------------------------------------
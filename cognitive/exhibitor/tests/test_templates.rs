@@ -0,0 +1,188 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! Unit tests of saving and restoring layouts of workspaces.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+
+extern crate cognitive_qualia as qualia;
+extern crate cognitive_outputs as outputs;
+extern crate cognitive_frames as frames;
+extern crate cognitive_exhibitor as exhibitor;
+
+mod common;
+
+use std::io::Write;
+use std::path::PathBuf;
+
+use qualia::{OutputInfo, SurfaceControl, SurfaceId};
+use qualia::{Area, Position, Size};
+use qualia::{Action, Command, Direction};
+use qualia::coordinator_mock::CoordinatorMock;
+use outputs::output_mock::OutputMock;
+use frames::{Geometry, Searching};
+use exhibitor::{Exhibitor, FrameTemplate, Strategist};
+
+// -------------------------------------------------------------------------------------------------
+
+const LAYOUT: &'static str = r#"
+geometry: horizontal
+frames:
+  - ratio: 3.0
+    swallow:
+      app_id: editor
+  - geometry: vertical
+    frames:
+      - swallow:
+          title: "^Terminal"
+      - swallow:
+          app_id: browser
+"#;
+
+// -------------------------------------------------------------------------------------------------
+
+struct Environment {
+    exhibitor: Exhibitor<CoordinatorMock>,
+    coordinator: CoordinatorMock,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Environment {
+    pub fn create() -> Self {
+        let output_info = OutputInfo::new(1,
+                                          Area::new(Position::new(0, 0), Size::new(100, 100)),
+                                          Size::new(100, 100),
+                                          60,
+                                          "test_make".to_owned(),
+                                          "test_model".to_owned());
+
+        let mut config = common::configurations::strategist();
+        config.choose_target = "anchored_but_popups".to_owned();
+        let strategist = Strategist::new_from_config(config);
+
+        let output = Box::new(OutputMock::new(output_info.clone()));
+        let coordinator = CoordinatorMock::new();
+        let mut exhibitor = Exhibitor::new(coordinator.clone(),
+                                           std::time::Instant::now(),
+                                           strategist,
+                                           common::configurations::compositor());
+
+        exhibitor.on_output_found(output);
+
+        Environment { exhibitor: exhibitor, coordinator: coordinator }
+    }
+
+    /// Writes given layout to temporary file and returns its path.
+    pub fn write_layout(name: &str, contents: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(name);
+        let mut file = std::fs::File::create(&path).unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+        path
+    }
+
+    /// Executes save or restore command.
+    pub fn execute(&mut self, action: Action, path: &PathBuf) {
+        self.exhibitor.on_command(Command {
+            action: action,
+            direction: Direction::None,
+            magnitude: 0,
+            string: path.to_str().unwrap().to_owned(),
+        });
+    }
+
+    /// Creates new surface with given title and application ID.
+    pub fn create_surface(&mut self, sid: u64, title: &str, app_id: &str) {
+        self.coordinator.set_surface_title(SurfaceId::new(sid), title.to_owned());
+        self.coordinator.set_surface_app_id(SurfaceId::new(sid), app_id.to_owned());
+        self.exhibitor.on_surface_ready(SurfaceId::new(sid));
+    }
+
+    /// Checks area of frame with given surface ID.
+    pub fn assert_area(&self, sid: u64, x: isize, y: isize, width: usize, height: usize) {
+        let frame = self.exhibitor.get_root().find_with_sid(SurfaceId::new(sid)).unwrap();
+        assert_eq!(frame.get_area(), Area::create(x, y, width, height), "sid: {}", sid);
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if surfaces matching placeholders of restored layout are placed in them.
+#[test]
+fn test_swallowing_surfaces_by_restored_layout() {
+    let mut e = Environment::create();
+    let path = Environment::write_layout("perceptia-test-swallowing.yaml", LAYOUT);
+    e.execute(Action::Restore, &path);
+
+    e.create_surface(1, "Some page", "browser");
+    e.assert_area(1,  0, 50, 25,  50);
+
+    e.create_surface(2, "Terminal 1", "");
+    e.assert_area(2,  0,  0, 25,  50);
+
+    e.create_surface(3, "main.rs", "editor");
+    e.assert_area(3,  0,  0, 75, 100);
+
+    assert_eq!(e.exhibitor.get_selection().get_sid(), SurfaceId::new(3));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if saved layout describes surfaces by application ID or by title and keeps criteria of
+/// not yet filled placeholders.
+#[test]
+fn test_saving_layout() {
+    let mut e = Environment::create();
+    let path = Environment::write_layout("perceptia-test-saving.yaml", LAYOUT);
+    e.execute(Action::Restore, &path);
+    e.create_surface(1, "Some page", "browser");
+    e.create_surface(2, "Terminal 1", "");
+
+    let path = std::env::temp_dir().join("perceptia-test-saved.yaml");
+    e.execute(Action::Save, &path);
+    let template = FrameTemplate::load(&path).unwrap();
+
+    assert_eq!(template.geometry, Geometry::Horizontal);
+    assert_eq!(template.branches.len(), 2);
+
+    let editor = template.branches[0].swallow.as_ref().unwrap();
+    assert_eq!(editor.app_id, Some("editor".to_owned()));
+    assert_eq!(template.branches[0].ratio, 3.0);
+
+    let column = &template.branches[1];
+    assert_eq!(column.geometry, Geometry::Vertical);
+    assert_eq!(column.branches.len(), 2);
+
+    let terminal = column.branches[0].swallow.as_ref().unwrap();
+    assert_eq!(terminal.app_id, None);
+    assert_eq!(terminal.title.as_ref().unwrap().as_str(), "^Terminal 1$");
+
+    let browser = column.branches[1].swallow.as_ref().unwrap();
+    assert_eq!(browser.app_id, Some("browser".to_owned()));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if restoring layout replaces frames of the workspace and lets placeholders swallow
+/// surfaces already present in it. Surfaces not matching any placeholder are settled back.
+#[test]
+fn test_swallowing_existing_surfaces_by_restored_layout() {
+    let mut e = Environment::create();
+    e.create_surface(1, "Some page", "browser");
+    e.create_surface(2, "main.rs", "editor");
+    e.create_surface(3, "Calculator", "calculator");
+
+    let path = Environment::write_layout("perceptia-test-swallowing-existing.yaml", LAYOUT);
+    e.execute(Action::Restore, &path);
+    e.assert_area(1,  0, 50, 20,  50);
+    e.assert_area(2,  0,  0, 60, 100);
+    e.assert_area(3, 80,  0, 20, 100);
+
+    e.create_surface(4, "Terminal 1", "");
+    e.assert_area(4,  0,  0, 20,  50);
+
+    let workspace = e.exhibitor.get_root().find_with_sid(SurfaceId::new(2)).unwrap().find_top();
+    assert_eq!(workspace.unwrap().count_children(), 3);
+}
+
+// -------------------------------------------------------------------------------------------------
@@ -0,0 +1,182 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! Unit tests of undoing and redoing commands changing frame layout.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+
+extern crate cognitive_qualia as qualia;
+extern crate cognitive_outputs as outputs;
+extern crate cognitive_frames as frames;
+extern crate cognitive_exhibitor as exhibitor;
+
+mod common;
+
+use qualia::{OutputInfo, SurfaceId};
+use qualia::{Area, Position, Size};
+use qualia::{Action, Command, Direction};
+use qualia::coordinator_mock::CoordinatorMock;
+use outputs::output_mock::OutputMock;
+use frames::{Geometry, Searching};
+use exhibitor::{Exhibitor, Strategist};
+use common::exhibitor_mixins::ExhibitorCommandShorthands;
+
+// -------------------------------------------------------------------------------------------------
+
+struct Environment {
+    exhibitor: Exhibitor<CoordinatorMock>,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Environment {
+    /// Creates display with three anchored surfaces placed side by side in order 3, 2, 1. Surface 3
+    /// is focused.
+    pub fn create() -> Self {
+        let output_info = OutputInfo::new(1,
+                                          Area::new(Position::new(0, 0), Size::new(90, 90)),
+                                          Size::new(90, 90),
+                                          60,
+                                          "test_make".to_owned(),
+                                          "test_model".to_owned());
+
+        let mut config = common::configurations::strategist();
+        config.choose_target = "anchored_but_popups".to_owned();
+        let strategist = Strategist::new_from_config(config);
+
+        let output = Box::new(OutputMock::new(output_info.clone()));
+        let coordinator = CoordinatorMock::new();
+        let mut exhibitor = Exhibitor::new(coordinator.clone(),
+                                           std::time::Instant::now(),
+                                           strategist,
+                                           common::configurations::compositor());
+
+        exhibitor.on_output_found(output);
+        exhibitor.on_surface_ready(SurfaceId::new(1));
+        exhibitor.on_surface_ready(SurfaceId::new(2));
+        exhibitor.on_surface_ready(SurfaceId::new(3));
+        exhibitor.horizontalize();
+
+        Environment { exhibitor: exhibitor }
+    }
+
+    /// Executes given command on focused frame.
+    pub fn execute(&mut self, action: Action) {
+        self.exhibitor.on_command(Command {
+            action: action,
+            direction: Direction::None,
+            magnitude: 0,
+            string: String::new(),
+        });
+    }
+
+    /// Returns ID of focused surface.
+    pub fn selected(&self) -> SurfaceId {
+        self.exhibitor.get_selection().get_sid()
+    }
+
+    /// Checks if surface with given ID is placed directly in workspace with given area.
+    pub fn assert_in_workspace(&self, sid: u64, area: Area) {
+        let frame = self.exhibitor.get_root().find_with_sid(SurfaceId::new(sid)).unwrap();
+        assert!(frame.get_parent().unwrap().get_mode().is_workspace(), "sid: {}", sid);
+        assert_eq!(frame.get_area(), area, "sid: {}", sid);
+    }
+
+    /// Checks if surfaces 3, 2 and 1 are placed side by side as after creation.
+    pub fn assert_initial_layout(&self) {
+        self.assert_in_workspace(3, Area::create(0, 0, 30, 90));
+        self.assert_in_workspace(2, Area::create(30, 0, 30, 90));
+        self.assert_in_workspace(1, Area::create(60, 0, 30, 90));
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if dive can be undone and redone.
+#[test]
+fn test_undoing_and_redoing_dive() {
+    let mut e = Environment::create();
+
+    e.exhibitor.dive_right();
+    let frame = e.exhibitor.get_root().find_with_sid(SurfaceId::new(3)).unwrap();
+    assert!(!frame.get_parent().unwrap().get_mode().is_workspace());
+
+    e.execute(Action::Undo);
+    e.assert_initial_layout();
+    assert_eq!(e.selected(), SurfaceId::new(3));
+
+    e.execute(Action::Redo);
+    let frame = e.exhibitor.get_root().find_with_sid(SurfaceId::new(3)).unwrap();
+    assert!(!frame.get_parent().unwrap().get_mode().is_workspace());
+    assert_eq!(e.selected(), SurfaceId::new(3));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if many commands can be undone in reverse order.
+#[test]
+fn test_undoing_many_commands() {
+    let mut e = Environment::create();
+
+    e.exhibitor.verticalize();
+    e.exhibitor.exalt();
+    e.exhibitor.ramify();
+
+    e.execute(Action::Undo);
+    e.execute(Action::Undo);
+    let workspace = e.exhibitor.get_selection().get_parent().unwrap();
+    assert_eq!(workspace.get_geometry(), Geometry::Vertical);
+
+    e.execute(Action::Undo);
+    e.assert_initial_layout();
+
+    // Configuration done while creating the environment is the oldest command
+    e.execute(Action::Undo);
+    let workspace = e.exhibitor.get_selection().get_parent().unwrap();
+    assert_eq!(workspace.get_geometry(), Geometry::Stacked);
+
+    e.execute(Action::Undo);
+    let workspace = e.exhibitor.get_selection().get_parent().unwrap();
+    assert_eq!(workspace.get_geometry(), Geometry::Stacked);
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if executing new command discards commands which could be redone.
+#[test]
+fn test_new_command_discards_redo() {
+    let mut e = Environment::create();
+
+    e.exhibitor.dive_right();
+    e.execute(Action::Undo);
+    e.exhibitor.focus_right();
+    e.exhibitor.verticalize();
+    e.execute(Action::Redo);
+
+    let workspace = e.exhibitor.get_selection().get_parent().unwrap();
+    assert!(workspace.get_mode().is_workspace());
+    assert_eq!(workspace.get_geometry(), Geometry::Vertical);
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if surfaces destroyed after command are skipped and surfaces created after command are
+/// kept while undoing.
+#[test]
+fn test_undoing_with_changed_surfaces() {
+    let mut e = Environment::create();
+
+    e.exhibitor.dive_right();
+    e.exhibitor.on_surface_destroyed(SurfaceId::new(2));
+    e.exhibitor.on_surface_ready(SurfaceId::new(4));
+    e.execute(Action::Undo);
+
+    assert!(e.exhibitor.get_root().find_with_sid(SurfaceId::new(2)).is_none());
+    assert!(e.exhibitor.get_root().find_with_sid(SurfaceId::new(4)).is_some());
+    let frame = e.exhibitor.get_root().find_with_sid(SurfaceId::new(1)).unwrap();
+    assert!(frame.get_parent().unwrap().get_mode().is_workspace());
+    let frame = e.exhibitor.get_root().find_with_sid(SurfaceId::new(3)).unwrap();
+    assert!(frame.get_parent().unwrap().get_mode().is_workspace());
+}
+
+// -------------------------------------------------------------------------------------------------
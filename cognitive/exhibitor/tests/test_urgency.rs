@@ -0,0 +1,161 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! Unit tests of activating surfaces and surfaces demanding attention.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+
+extern crate cognitive_qualia as qualia;
+extern crate cognitive_outputs as outputs;
+extern crate cognitive_frames as frames;
+extern crate cognitive_exhibitor as exhibitor;
+
+mod common;
+
+use qualia::{OutputInfo, SurfaceId};
+use qualia::{Area, Position, Size};
+use qualia::{Action, Command, Direction};
+use qualia::coordinator_mock::CoordinatorMock;
+use outputs::output_mock::OutputMock;
+use frames::Searching;
+use exhibitor::{Exhibitor, Strategist};
+
+use common::exhibitor_mixins::ExhibitorCommandShorthands;
+
+// -------------------------------------------------------------------------------------------------
+
+struct Environment {
+    exhibitor: Exhibitor<CoordinatorMock>,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Environment {
+    /// Creates display with surface 1 in workspace "1" and surfaces 2 and 3 in workspace "2".
+    /// Surface 3 is focused.
+    pub fn create() -> Self {
+        let output_info = OutputInfo::new(1,
+                                          Area::new(Position::new(0, 0), Size::new(100, 100)),
+                                          Size::new(100, 100),
+                                          60,
+                                          "test_make".to_owned(),
+                                          "test_model".to_owned());
+
+        let mut config = common::configurations::strategist();
+        config.choose_floating = "always_centered".to_owned();
+        let strategist = Strategist::new_from_config(config);
+
+        let output = Box::new(OutputMock::new(output_info.clone()));
+        let coordinator = CoordinatorMock::new();
+        let mut exhibitor = Exhibitor::new(coordinator.clone(),
+                                           std::time::Instant::now(),
+                                           strategist,
+                                           common::configurations::compositor());
+
+        exhibitor.on_output_found(output);
+        exhibitor.on_surface_ready(SurfaceId::new(1));
+        exhibitor.focus_workspace("2");
+        exhibitor.on_surface_ready(SurfaceId::new(2));
+        exhibitor.on_surface_ready(SurfaceId::new(3));
+
+        Environment { exhibitor: exhibitor }
+    }
+
+    /// Executes command focusing the most recent surface demanding attention.
+    pub fn focus_urgent(&mut self) {
+        self.exhibitor.on_command(Command {
+            action: Action::Focus,
+            direction: Direction::Urgent,
+            magnitude: 1,
+            string: String::new(),
+        });
+    }
+
+    /// Returns sorted titles of workspaces marked as urgent.
+    pub fn urgent_workspaces(&self) -> Vec<String> {
+        let state = self.exhibitor.get_workspace_state();
+        let mut names: Vec<String> = state.workspaces
+            .values()
+            .flat_map(|infos| infos.iter())
+            .filter(|info| info.is_urgent)
+            .map(|info| info.name.clone())
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Returns title of active workspace.
+    pub fn active_workspace(&self) -> String {
+        self.exhibitor.get_selection().find_top().unwrap().get_title()
+    }
+
+    /// Returns ID of focused surface.
+    pub fn selected(&self) -> SurfaceId {
+        self.exhibitor.get_selection().get_sid()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if granted activation focuses the surface switching to its workspace.
+#[test]
+fn test_granted_activation() {
+    let mut e = Environment::create();
+
+    e.exhibitor.on_surface_activation_requested(SurfaceId::new(1), true);
+    assert_eq!(e.selected(), SurfaceId::new(1));
+    assert_eq!(e.active_workspace(), "1");
+    assert!(e.exhibitor.get_urgent().is_empty());
+    assert!(e.urgent_workspaces().is_empty());
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if not granted activation marks the surface and its workspace as urgent without changing
+/// focus and that focusing urgent surfaces starts from the most recent one.
+#[test]
+fn test_focusing_urgent_surfaces() {
+    let mut e = Environment::create();
+
+    e.exhibitor.on_surface_activation_requested(SurfaceId::new(1), false);
+    e.exhibitor.on_surface_activation_requested(SurfaceId::new(2), false);
+    assert_eq!(e.selected(), SurfaceId::new(3));
+    assert_eq!(e.exhibitor.get_urgent(), vec![SurfaceId::new(1), SurfaceId::new(2)]);
+    assert_eq!(e.urgent_workspaces(), vec!["1".to_owned(), "2".to_owned()]);
+
+    e.focus_urgent();
+    assert_eq!(e.selected(), SurfaceId::new(2));
+    assert_eq!(e.active_workspace(), "2");
+    assert_eq!(e.urgent_workspaces(), vec!["1".to_owned()]);
+
+    e.focus_urgent();
+    assert_eq!(e.selected(), SurfaceId::new(1));
+    assert_eq!(e.active_workspace(), "1");
+    assert!(e.urgent_workspaces().is_empty());
+
+    // Nothing happens when no surface demands attention
+    e.focus_urgent();
+    assert_eq!(e.selected(), SurfaceId::new(1));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if surface stops demanding attention when it is focused or destroyed.
+#[test]
+fn test_clearing_urgency() {
+    let mut e = Environment::create();
+
+    e.exhibitor.on_surface_activation_requested(SurfaceId::new(2), false);
+    e.exhibitor.on_surface_activation_requested(SurfaceId::new(1), false);
+    e.exhibitor.on_surface_activation_requested(SurfaceId::new(2), false);
+    assert_eq!(e.exhibitor.get_urgent(), vec![SurfaceId::new(1), SurfaceId::new(2)]);
+
+    e.exhibitor.on_surface_destroyed(SurfaceId::new(1));
+    assert_eq!(e.exhibitor.get_urgent(), vec![SurfaceId::new(2)]);
+
+    e.exhibitor.on_surface_activation_requested(SurfaceId::new(2), true);
+    assert_eq!(e.selected(), SurfaceId::new(2));
+    assert!(e.exhibitor.get_urgent().is_empty());
+}
+
+// -------------------------------------------------------------------------------------------------
@@ -0,0 +1,219 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! Unit tests of selecting and manipulating many frames at once in visual mode.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+
+extern crate cognitive_qualia as qualia;
+extern crate cognitive_outputs as outputs;
+extern crate cognitive_frames as frames;
+extern crate cognitive_exhibitor as exhibitor;
+
+mod common;
+
+use qualia::{OutputInfo, SurfaceId};
+use qualia::{Area, Position, Size};
+use qualia::{Action, Command, Direction, InteractionMode};
+use qualia::coordinator_mock::CoordinatorMock;
+use outputs::output_mock::OutputMock;
+use frames::{Geometry, Mode, Searching};
+use exhibitor::{Exhibitor, Strategist};
+use common::exhibitor_mixins::ExhibitorCommandShorthands;
+
+// -------------------------------------------------------------------------------------------------
+
+struct Environment {
+    output: OutputMock,
+    coordinator: CoordinatorMock,
+    exhibitor: Exhibitor<CoordinatorMock>,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Environment {
+    /// Creates display with three anchored surfaces placed side by side. New frames are placed
+    /// before older ones so surfaces are ordered 3, 2, 1 from the left. Surface 3 is focused.
+    pub fn create() -> Self {
+        let output_info = OutputInfo::new(1,
+                                          Area::new(Position::new(0, 0), Size::new(90, 90)),
+                                          Size::new(90, 90),
+                                          60,
+                                          "test_make".to_owned(),
+                                          "test_model".to_owned());
+
+        let mut config = common::configurations::strategist();
+        config.choose_target = "anchored_but_popups".to_owned();
+        let strategist = Strategist::new_from_config(config);
+
+        let output = OutputMock::new(output_info.clone());
+        let mut coordinator = CoordinatorMock::new();
+        coordinator.add_surface(SurfaceId::new(1));
+        coordinator.add_surface(SurfaceId::new(2));
+        coordinator.add_surface(SurfaceId::new(3));
+        let mut exhibitor = Exhibitor::new(coordinator.clone(),
+                                           std::time::Instant::now(),
+                                           strategist,
+                                           common::configurations::compositor());
+
+        exhibitor.on_output_found(Box::new(output.clone()));
+        exhibitor.on_surface_ready(SurfaceId::new(1));
+        exhibitor.on_surface_ready(SurfaceId::new(2));
+        exhibitor.on_surface_ready(SurfaceId::new(3));
+        exhibitor.horizontalize();
+
+        Environment {
+            output: output,
+            coordinator: coordinator,
+            exhibitor: exhibitor,
+        }
+    }
+
+    /// Executes given command on selected frames.
+    pub fn execute(&mut self, action: Action, direction: Direction, string: &str) {
+        self.exhibitor.on_command(Command {
+            action: action,
+            direction: direction,
+            magnitude: 1,
+            string: string.to_owned(),
+        });
+    }
+
+    /// Switches to visual mode and extends selection from surface 3 to surface 2.
+    pub fn select_two(&mut self) {
+        self.exhibitor.on_mode_switched(true, InteractionMode::Visual);
+        self.execute(Action::Focus, Direction::East, "");
+    }
+
+    /// Returns IDs of surfaces selected in visual mode.
+    pub fn visual_sids(&self) -> Vec<SurfaceId> {
+        self.exhibitor.get_visual_selection().iter().map(|frame| frame.get_sid()).collect()
+    }
+
+    /// Returns area of surface with given ID.
+    pub fn area_of(&self, sid: u64) -> Area {
+        self.exhibitor.get_root().find_with_sid(SurfaceId::new(sid)).unwrap().get_area()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if visual selection contains only selection after entering visual mode and grows when
+/// focus is moved.
+#[test]
+fn test_extending_visual_selection() {
+    let mut e = Environment::create();
+
+    assert!(e.visual_sids().is_empty());
+
+    e.exhibitor.on_mode_switched(true, InteractionMode::Visual);
+    assert_eq!(e.visual_sids(), vec![SurfaceId::new(3)]);
+
+    e.execute(Action::Focus, Direction::East, "");
+    assert_eq!(e.visual_sids(), vec![SurfaceId::new(3), SurfaceId::new(2)]);
+
+    e.execute(Action::Focus, Direction::East, "");
+    assert_eq!(e.visual_sids(), vec![SurfaceId::new(3), SurfaceId::new(2), SurfaceId::new(1)]);
+
+    e.execute(Action::Focus, Direction::West, "");
+    assert_eq!(e.visual_sids(), vec![SurfaceId::new(3), SurfaceId::new(2)]);
+    assert_eq!(e.exhibitor.get_selection().get_sid(), SurfaceId::new(2));
+
+    e.exhibitor.on_mode_switched(false, InteractionMode::Visual);
+    assert!(e.visual_sids().is_empty());
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if selected frames are outlined on display and outlines disappear after leaving visual
+/// mode.
+#[test]
+fn test_highlighting_visual_selection() {
+    let mut e = Environment::create();
+
+    e.select_two();
+    e.exhibitor.on_notify();
+    e.exhibitor.on_pageflip(1);
+    assert_eq!(e.output.get_highlights(),
+               vec![Area::create(0, 0, 30, 90), Area::create(30, 0, 30, 90)]);
+
+    e.exhibitor.on_mode_switched(false, InteractionMode::Visual);
+    e.exhibitor.on_notify();
+    e.exhibitor.on_pageflip(1);
+    assert!(e.output.get_highlights().is_empty());
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if selected frames can be wrapped into new container.
+#[test]
+fn test_wrapping_visual_selection() {
+    let mut e = Environment::create();
+
+    e.select_two();
+    e.execute(Action::Configure, Direction::North, "");
+
+    let container = e.exhibitor.get_selection();
+    assert_eq!(container.get_mode(), Mode::Container);
+    assert_eq!(container.get_geometry(), Geometry::Vertical);
+    assert_eq!(container.get_area(), Area::create(0, 0, 60, 90));
+    assert!(container.get_parent().unwrap().get_mode().is_workspace());
+
+    assert_eq!(e.area_of(3), Area::create(0, 0, 60, 45));
+    assert_eq!(e.area_of(2), Area::create(0, 45, 60, 45));
+    assert_eq!(e.area_of(1), Area::create(60, 0, 30, 90));
+    assert_eq!(e.exhibitor.get_visual_selection().len(), 1);
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if selected frames jump together keeping their order.
+#[test]
+fn test_jumping_visual_selection() {
+    let mut e = Environment::create();
+
+    e.select_two();
+    e.execute(Action::Jump, Direction::East, "");
+
+    assert_eq!(e.area_of(1), Area::create(0, 0, 30, 90));
+    assert_eq!(e.area_of(3), Area::create(30, 0, 30, 90));
+    assert_eq!(e.area_of(2), Area::create(60, 0, 30, 90));
+    assert_eq!(e.visual_sids(), vec![SurfaceId::new(3), SurfaceId::new(2)]);
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if selected frames can be moved to other workspace.
+#[test]
+fn test_diving_visual_selection_to_workspace() {
+    let mut e = Environment::create();
+
+    e.select_two();
+    e.execute(Action::Dive, Direction::Workspace, "2");
+
+    for sid in vec![2, 3] {
+        let frame = e.exhibitor.get_root().find_with_sid(SurfaceId::new(sid)).unwrap();
+        assert_eq!(frame.find_top().unwrap().get_title(), "2", "sid: {}", sid);
+    }
+    let frame = e.exhibitor.get_root().find_with_sid(SurfaceId::new(1)).unwrap();
+    assert_eq!(frame.find_top().unwrap().get_title(), "1");
+    assert_eq!(e.visual_sids().len(), 2);
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if clients are asked to close selected surfaces in visual and normal mode.
+#[test]
+fn test_closing() {
+    let mut e = Environment::create();
+
+    e.execute(Action::Close, Direction::None, "");
+    assert_eq!(e.coordinator.get_closed_sids(), vec![SurfaceId::new(3)]);
+
+    e.select_two();
+    e.execute(Action::Close, Direction::None, "");
+    assert_eq!(e.coordinator.get_closed_sids(),
+               vec![SurfaceId::new(3), SurfaceId::new(3), SurfaceId::new(2)]);
+}
+
+// -------------------------------------------------------------------------------------------------
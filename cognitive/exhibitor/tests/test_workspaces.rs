@@ -0,0 +1,379 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! Unit tests of workspace management: ordering, renaming, relocating and removing workspaces and
+//! their configuration.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+
+extern crate cognitive_qualia as qualia;
+extern crate cognitive_outputs as outputs;
+extern crate cognitive_frames as frames;
+extern crate cognitive_exhibitor as exhibitor;
+
+mod common;
+
+use qualia::{CompositorConfig, OutputInfo, SurfaceId, WorkspaceInfo};
+use qualia::{Area, Position, Size};
+use qualia::{Action, Command, Direction};
+use qualia::coordinator_mock::CoordinatorMock;
+use outputs::output_mock::OutputMock;
+use frames::{Converting, Geometry, LayoutKind, Mobility, Searching};
+use exhibitor::{Exhibitor, Strategist};
+use common::exhibitor_mixins::ExhibitorCommandShorthands;
+
+// -------------------------------------------------------------------------------------------------
+
+struct Environment {
+    coordinator: CoordinatorMock,
+    exhibitor: Exhibitor<CoordinatorMock>,
+    output2_info: OutputInfo,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Environment {
+    /// Creates environment with first display. Second display is placed on the right of the first
+    /// one but has to be added explicitly.
+    pub fn create(config: CompositorConfig) -> Self {
+        let output1_info = OutputInfo::new(1,
+                                           Area::new(Position::new(0, 0), Size::new(100, 100)),
+                                           Size::new(100, 100),
+                                           60,
+                                           "test_make_1".to_owned(),
+                                           "test_model_1".to_owned());
+
+        let output2_info = OutputInfo::new(2,
+                                           Area::new(Position::new(100, 0), Size::new(200, 200)),
+                                           Size::new(200, 200),
+                                           60,
+                                           "test_make_2".to_owned(),
+                                           "test_model_2".to_owned());
+
+        let mut strategist_config = common::configurations::strategist();
+        strategist_config.choose_target = "anchored_but_popups".to_owned();
+        let strategist = Strategist::new_from_config(strategist_config);
+
+        let coordinator = CoordinatorMock::new();
+        let mut exhibitor =
+            Exhibitor::new(coordinator.clone(), std::time::Instant::now(), strategist, config);
+
+        exhibitor.on_output_found(Box::new(OutputMock::new(output1_info)));
+
+        Environment {
+            coordinator: coordinator,
+            exhibitor: exhibitor,
+            output2_info: output2_info,
+        }
+    }
+
+    /// Adds second display.
+    pub fn add_second_output(&mut self) {
+        self.exhibitor.on_output_found(Box::new(OutputMock::new(self.output2_info.clone())));
+    }
+
+    /// Creates new surface. It will be placed in current workspace.
+    pub fn create_surface(&mut self, id: u64) {
+        let sid = SurfaceId::new(id);
+        self.coordinator.add_surface(sid);
+        self.exhibitor.on_surface_ready(sid);
+    }
+
+    /// Executes given command.
+    pub fn execute(&mut self, action: Action, direction: Direction, magnitude: i32, string: &str) {
+        self.exhibitor.on_command(Command {
+            action: action,
+            direction: direction,
+            magnitude: magnitude,
+            string: string.to_owned(),
+        });
+    }
+
+    /// Returns titles and states of workspaces of display with given ID.
+    pub fn workspaces(&self, id: i32) -> Vec<WorkspaceInfo> {
+        let state = self.exhibitor.get_root().to_workspace_state();
+        state.workspaces.get(&id).cloned().unwrap_or(Vec::new())
+    }
+
+    /// Returns current workspace.
+    pub fn workspace(&self) -> frames::Frame {
+        self.exhibitor.get_selection().find_top().unwrap()
+    }
+
+    /// Returns mobility of surface with given ID.
+    pub fn mobility_of(&self, sid: u64) -> Mobility {
+        self.exhibitor.get_root().find_with_sid(SurfaceId::new(sid)).unwrap().get_mobility()
+    }
+
+    /// Returns title of display of the workspace containing surface with given ID.
+    pub fn display_of(&self, sid: u64) -> String {
+        let frame = self.exhibitor.get_root().find_with_sid(SurfaceId::new(sid)).unwrap();
+        frame.find_display().unwrap().get_title()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+fn info(name: &str, is_active: bool) -> WorkspaceInfo {
+    WorkspaceInfo::new(name.to_owned(), is_active)
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if new workspaces are inserted keeping numeric order of titles regardless of the order
+/// they were created in.
+#[test]
+fn test_ordering_new_workspaces() {
+    let mut e = Environment::create(common::configurations::compositor());
+
+    e.create_surface(1);
+    e.exhibitor.focus_workspace("3");
+    e.create_surface(2);
+    e.exhibitor.focus_workspace("2");
+    e.create_surface(3);
+    e.exhibitor.focus_workspace("a");
+    e.create_surface(4);
+    e.exhibitor.focus_workspace("10");
+    e.create_surface(5);
+
+    assert_eq!(e.workspaces(1),
+               vec![info("1", false), info("2", false), info("3", false), info("10", true),
+                    info("a", false)]);
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if empty workspaces are destroyed when they are not shown anymore.
+#[test]
+fn test_removing_empty_workspaces() {
+    let mut e = Environment::create(common::configurations::compositor());
+
+    e.create_surface(1);
+    e.exhibitor.focus_workspace("2");
+    assert_eq!(e.workspaces(1), vec![info("1", false), info("2", true)]);
+
+    e.exhibitor.focus_workspace("1");
+    assert_eq!(e.workspaces(1), vec![info("1", true)]);
+
+    // Workspace left empty after destroying its last surface
+    e.exhibitor.focus_workspace("2");
+    e.create_surface(2);
+    e.exhibitor.focus_workspace("1");
+    e.exhibitor.on_surface_destroyed(SurfaceId::new(2));
+    assert_eq!(e.workspaces(1), vec![info("1", true)]);
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if current workspace can be renamed and if empty or already used titles are rejected.
+#[test]
+fn test_renaming_workspace() {
+    let mut e = Environment::create(common::configurations::compositor());
+
+    e.create_surface(1);
+    e.exhibitor.focus_workspace("2");
+    e.create_surface(2);
+
+    e.execute(Action::Rename, Direction::None, 1, "web");
+    assert_eq!(e.workspaces(1), vec![info("1", false), info("web", true)]);
+
+    e.execute(Action::Rename, Direction::None, 1, "");
+    e.execute(Action::Rename, Direction::None, 1, "1");
+    assert_eq!(e.workspaces(1), vec![info("1", false), info("web", true)]);
+
+    e.exhibitor.focus_workspace("web");
+    assert_eq!(e.exhibitor.get_selection().get_sid(), SurfaceId::new(2));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if current workspace can be moved among workspaces of its display.
+#[test]
+fn test_reordering_workspaces() {
+    let mut e = Environment::create(common::configurations::compositor());
+
+    e.create_surface(1);
+    e.exhibitor.focus_workspace("2");
+    e.create_surface(2);
+    e.exhibitor.focus_workspace("3");
+    e.create_surface(3);
+    e.exhibitor.focus_workspace("1");
+
+    e.execute(Action::Relocate, Direction::Forward, 1, "");
+    assert_eq!(e.workspaces(1), vec![info("2", false), info("1", true), info("3", false)]);
+
+    e.execute(Action::Relocate, Direction::End, 1, "");
+    assert_eq!(e.workspaces(1), vec![info("2", false), info("3", false), info("1", true)]);
+
+    e.execute(Action::Relocate, Direction::Backward, 5, "");
+    assert_eq!(e.workspaces(1), vec![info("1", true), info("2", false), info("3", false)]);
+
+    e.execute(Action::Relocate, Direction::Forward, -1, "");
+    assert_eq!(e.workspaces(1), vec![info("1", true), info("2", false), info("3", false)]);
+    assert_eq!(e.exhibitor.get_selection().get_sid(), SurfaceId::new(1));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if whole workspace can be moved to other display. New workspace should be created on
+/// display left without workspaces.
+#[test]
+fn test_moving_workspace_to_other_display() {
+    let mut e = Environment::create(common::configurations::compositor());
+    e.add_second_output();
+
+    e.exhibitor.focus_workspace("1");
+    e.create_surface(1);
+    e.execute(Action::Relocate, Direction::East, 1, "");
+
+    assert_eq!(e.workspaces(1), vec![info("3", true)]);
+    assert_eq!(e.workspaces(2), vec![info("1", true)]);
+    assert_eq!(e.display_of(1), "test_make_2");
+    assert_eq!(e.exhibitor.get_selection().get_sid(), SurfaceId::new(1));
+    assert_eq!(e.exhibitor.get_selection().get_size(), Size::new(200, 200));
+
+    // There is no display further on the right
+    e.execute(Action::Relocate, Direction::East, 1, "");
+    assert_eq!(e.display_of(1), "test_make_2");
+
+    e.execute(Action::Relocate, Direction::West, 1, "");
+    assert_eq!(e.workspaces(1), vec![info("1", true)]);
+    assert_eq!(e.display_of(1), "test_make_1");
+    assert_eq!(e.exhibitor.get_selection().get_size(), Size::new(100, 100));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if workspaces pinned to output are created on that output, moved there when renamed and
+/// when the output appears.
+#[test]
+fn test_pinning_workspaces_to_outputs() {
+    let mut config = common::configurations::compositor();
+    config.workspace_outputs.insert("web".to_owned(), "test_make_2".to_owned());
+    config.workspace_outputs.insert("mail".to_owned(), "test_make_2".to_owned());
+    config.workspace_outputs.insert("music".to_owned(), "test_make_2".to_owned());
+    let mut e = Environment::create(config);
+
+    // Output not present yet, workspace is created on current display
+    e.create_surface(1);
+    e.exhibitor.focus_workspace("web");
+    e.create_surface(2);
+    assert_eq!(e.display_of(2), "test_make_1");
+
+    // Workspace is brought to the output when it appears
+    e.add_second_output();
+    assert_eq!(e.workspaces(1), vec![info("1", true)]);
+    assert_eq!(e.workspaces(2), vec![info("2", true), info("web", false)]);
+    assert_eq!(e.display_of(2), "test_make_2");
+
+    // Workspace is created on pinned output
+    e.exhibitor.focus_workspace("1");
+    e.exhibitor.focus_workspace("mail");
+    e.create_surface(3);
+    assert_eq!(e.display_of(3), "test_make_2");
+    assert_eq!(e.workspaces(2), vec![info("mail", true), info("web", false)]);
+
+    // Workspace is moved to pinned output when renamed
+    e.exhibitor.focus_workspace("1");
+    e.execute(Action::Rename, Direction::None, 1, "music");
+    assert_eq!(e.display_of(1), "test_make_2");
+    assert_eq!(e.workspaces(1), vec![info("1", true)]);
+    assert_eq!(e.workspaces(2),
+               vec![info("mail", false), info("music", true), info("web", false)]);
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if workspaces are created with geometry, layout, gaps and strategies configured for their
+/// titles and if renamed workspace takes configuration of new title.
+#[test]
+fn test_configuring_workspaces() {
+    let mut web = common::configurations::workspace();
+    web.geometry = "stacked".to_owned();
+    web.layout = "grid".to_owned();
+    web.choose_target = "always_floating".to_owned();
+    web.spacing.inner_gap = 5;
+
+    let mut mail = common::configurations::workspace();
+    mail.geometry = "horizontal".to_owned();
+
+    let mut config = common::configurations::compositor();
+    config.workspace.geometry = "vertical".to_owned();
+    config.workspaces.insert("web".to_owned(), web);
+    config.workspaces.insert("mail".to_owned(), mail);
+    let mut e = Environment::create(config);
+
+    e.create_surface(1);
+    assert_eq!(e.workspace().get_geometry(), Geometry::Vertical);
+    assert_eq!(e.workspace().get_layout().kind, LayoutKind::Manual);
+    assert_eq!(e.mobility_of(1), Mobility::Anchored);
+
+    e.exhibitor.focus_workspace("web");
+    e.create_surface(2);
+    assert_eq!(e.workspace().get_geometry(), Geometry::Stacked);
+    assert_eq!(e.workspace().get_layout().kind, LayoutKind::Grid);
+    assert_eq!(e.workspace().get_spacing().inner_gap, 5);
+    assert_eq!(e.mobility_of(2), Mobility::Floating);
+
+    // Renamed workspace takes configuration of the new title except for geometry
+    e.execute(Action::Rename, Direction::None, 1, "mail");
+    e.create_surface(3);
+    assert_eq!(e.workspace().get_geometry(), Geometry::Stacked);
+    assert_eq!(e.workspace().get_layout().kind, LayoutKind::Manual);
+    assert_eq!(e.workspace().get_spacing().inner_gap, 0);
+    assert_eq!(e.mobility_of(3), Mobility::Anchored);
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if ramifying creates container with geometry configured for current workspace.
+#[test]
+fn test_ramifying_with_configured_geometry() {
+    let mut config = common::configurations::compositor();
+    config.workspace.geometry = "vertical".to_owned();
+    let mut e = Environment::create(config);
+
+    e.create_surface(1);
+    e.create_surface(2);
+    assert_eq!(e.workspace().get_geometry(), Geometry::Vertical);
+
+    e.exhibitor.ramify();
+    let container = e.exhibitor.get_selection().get_parent().unwrap();
+    assert!(!container.get_mode().is_workspace());
+    assert_eq!(container.get_geometry(), Geometry::Vertical);
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if gaps and strategies of current workspace can be changed with commands.
+#[test]
+fn test_changing_workspace_configuration() {
+    let mut e = Environment::create(common::configurations::compositor());
+    e.create_surface(1);
+
+    e.execute(Action::Spacing, Direction::North, 3, "");
+    e.execute(Action::Spacing, Direction::East, 2, "");
+    e.execute(Action::Spacing, Direction::West, 5, "");
+    e.execute(Action::Spacing, Direction::East, 1, "");
+    assert_eq!(e.workspace().get_spacing().inner_gap, 3);
+    assert_eq!(e.workspace().get_spacing().outer_gap, 1);
+    assert_eq!(e.exhibitor.get_selection().get_area(), Area::create(1, 1, 98, 98));
+
+    e.execute(Action::Strategy, Direction::Forward, 1, "");
+    e.create_surface(2);
+    assert_eq!(e.mobility_of(2), Mobility::Floating);
+
+    e.execute(Action::Strategy, Direction::None, 1, "unknown");
+    e.execute(Action::Strategy, Direction::None, 1, "anchored_but_popups");
+    e.create_surface(3);
+    assert_eq!(e.mobility_of(3), Mobility::Anchored);
+
+    // Other workspaces are not affected
+    e.exhibitor.focus_workspace("2");
+    e.execute(Action::Strategy, Direction::Forward, 1, "");
+    e.exhibitor.focus_workspace("1");
+    e.create_surface(4);
+    assert_eq!(e.mobility_of(4), Mobility::Anchored);
+}
+
+// -------------------------------------------------------------------------------------------------
@@ -0,0 +1,203 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! This module contains algorithms of automatic layouts used by `Packing` to place anchored
+//! subframes.
+
+// -------------------------------------------------------------------------------------------------
+
+use std::cmp::min;
+
+use qualia::{Area, Position, Size};
+
+use frame::{Geometry, Layout, LayoutKind};
+
+// -------------------------------------------------------------------------------------------------
+
+/// Calculates areas of `count` frames placed inside `area` according to given `layout`. Returned
+/// areas are ordered the same way as frames in space order.
+pub fn arrange(layout: Layout, area: Area, count: usize, gap: usize) -> Vec<Area> {
+    match layout.kind {
+        LayoutKind::Manual => vec![area; count],
+        LayoutKind::MasterStack => arrange_master_stack(layout, area, count, gap),
+        LayoutKind::Grid => arrange_grid(area, count, gap),
+        LayoutKind::Spiral => arrange_spiral(layout, area, count, gap),
+        LayoutKind::CenteredMaster => arrange_centered_master(layout, area, count, gap),
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Places master frames in column on the left and the rest in column on the right. If there are
+/// no master or no stacked frames all frames are placed in one column.
+fn arrange_master_stack(layout: Layout, area: Area, count: usize, gap: usize) -> Vec<Area> {
+    let masters = min(layout.master_count, count);
+    let stacked = count - masters;
+    if masters == 0 || stacked == 0 {
+        return split(area, count, gap, Geometry::Vertical);
+    }
+
+    let (master_area, stack_area) = divide(area, layout.master_ratio, gap, Geometry::Horizontal);
+    let mut result = split(master_area, masters, gap, Geometry::Vertical);
+    result.append(&mut split(stack_area, stacked, gap, Geometry::Vertical));
+    result
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Places frames in rows. Number of columns is the smallest number whose square is not smaller
+/// than number of frames. Frames in last row take all the width if the row is not full.
+fn arrange_grid(area: Area, count: usize, gap: usize) -> Vec<Area> {
+    let mut result = Vec::with_capacity(count);
+    if count == 0 {
+        return result;
+    }
+
+    let columns = (count as f32).sqrt().ceil() as usize;
+    let rows = (count + columns - 1) / columns;
+    let mut left = count;
+    for row in split(area, rows, gap, Geometry::Vertical) {
+        let len = min(columns, left);
+        result.append(&mut split(row, len, gap, Geometry::Horizontal));
+        left -= len;
+    }
+    result
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Every frame takes half of space left by previous frames. Frames are placed clockwise starting
+/// from the left. First frame takes part of space defined by master ratio.
+fn arrange_spiral(layout: Layout, area: Area, count: usize, gap: usize) -> Vec<Area> {
+    let mut result = Vec::with_capacity(count);
+    let mut rest = area;
+    for i in 0..count {
+        if i == count - 1 {
+            result.push(rest);
+            break;
+        }
+
+        let ratio = if i == 0 { layout.master_ratio } else { 0.5 };
+        let (frame_area, rest_area) = match i % 4 {
+            0 => divide(rest, ratio, gap, Geometry::Horizontal),
+            1 => divide(rest, ratio, gap, Geometry::Vertical),
+            2 => {
+                let (first, second) = divide(rest, 1.0 - ratio, gap, Geometry::Horizontal);
+                (second, first)
+            }
+            _ => {
+                let (first, second) = divide(rest, 1.0 - ratio, gap, Geometry::Vertical);
+                (second, first)
+            }
+        };
+        result.push(frame_area);
+        rest = rest_area;
+    }
+    result
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Places master frames in column in the center and the rest alternately in columns on the right
+/// and on the left. With only one stacked frame falls back to master-stack layout.
+fn arrange_centered_master(layout: Layout, area: Area, count: usize, gap: usize) -> Vec<Area> {
+    let masters = min(layout.master_count, count);
+    let stacked = count - masters;
+    if masters == 0 || stacked < 2 {
+        return arrange_master_stack(layout, area, count, gap);
+    }
+
+    // Calculate columns
+    let available = area.size.width.saturating_sub(2 * gap);
+    let master_width = min((available as f32 * layout.master_ratio) as usize, available);
+    let left_width = (available - master_width) / 2;
+    let right_width = available - master_width - left_width;
+    let height = area.size.height;
+    let left_area = Area::new(area.pos, Size::new(left_width, height));
+    let master_pos = Position::new(area.pos.x + (left_width + gap) as isize, area.pos.y);
+    let master_area = Area::new(master_pos, Size::new(master_width, height));
+    let right_pos = Position::new(master_pos.x + (master_width + gap) as isize, area.pos.y);
+    let right_area = Area::new(right_pos, Size::new(right_width, height));
+
+    // Place frames
+    let right = split(right_area, (stacked + 1) / 2, gap, Geometry::Vertical);
+    let left = split(left_area, stacked / 2, gap, Geometry::Vertical);
+    let mut result = split(master_area, masters, gap, Geometry::Vertical);
+    for i in 0..stacked {
+        if i % 2 == 0 {
+            result.push(right[i / 2]);
+        } else {
+            result.push(left[i / 2]);
+        }
+    }
+    result
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Splits given area in `count` areas of the same size separated by `gap`. `Vertical` geometry
+/// places areas one below another, `Horizontal` one beside another. Last area takes the space
+/// left after integer division.
+fn split(area: Area, count: usize, gap: usize, geometry: Geometry) -> Vec<Area> {
+    let mut result = Vec::with_capacity(count);
+    if count == 0 {
+        return result;
+    }
+
+    let total = match geometry {
+        Geometry::Vertical => area.size.height,
+        Geometry::Horizontal | Geometry::Stacked => area.size.width,
+    };
+    let available = total.saturating_sub(gap * (count - 1));
+    let length = available / count;
+
+    let mut pos = area.pos;
+    for i in 0..count {
+        let len = if i == count - 1 {
+            available - length * (count - 1)
+        } else {
+            length
+        };
+
+        match geometry {
+            Geometry::Vertical => {
+                result.push(Area::new(pos, Size::new(area.size.width, len)));
+                pos.y += (len + gap) as isize;
+            }
+            Geometry::Horizontal | Geometry::Stacked => {
+                result.push(Area::new(pos, Size::new(len, area.size.height)));
+                pos.x += (len + gap) as isize;
+            }
+        }
+    }
+    result
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Divides given area in two areas separated by `gap`. First area takes `ratio` of available
+/// space.
+fn divide(area: Area, ratio: f32, gap: usize, geometry: Geometry) -> (Area, Area) {
+    let total = match geometry {
+        Geometry::Vertical => area.size.height,
+        Geometry::Horizontal | Geometry::Stacked => area.size.width,
+    };
+    let available = total.saturating_sub(gap);
+    let first = min((available as f32 * ratio) as usize, available);
+    let second = available - first;
+
+    match geometry {
+        Geometry::Vertical => {
+            let pos = Position::new(area.pos.x, area.pos.y + (first + gap) as isize);
+            (Area::new(area.pos, Size::new(area.size.width, first)),
+             Area::new(pos, Size::new(area.size.width, second)))
+        }
+        Geometry::Horizontal | Geometry::Stacked => {
+            let pos = Position::new(area.pos.x + (first + gap) as isize, area.pos.y);
+            (Area::new(area.pos, Size::new(first, area.size.height)),
+             Area::new(pos, Size::new(second, area.size.height)))
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
@@ -9,8 +9,10 @@
 use std::collections::HashMap;
 
 use frame::{Frame, Mode};
+use packing::Packing;
+use searching::Searching;
 
-use qualia::{Position, SurfaceListing, SurfaceContext, WorkspaceInfo, WorkspaceState};
+use qualia::{Position, SurfaceListing, SurfaceContext, Vector, WorkspaceInfo, WorkspaceState};
 
 // -------------------------------------------------------------------------------------------------
 
@@ -35,6 +37,32 @@ impl Converting for Frame {
                 relative_position: Position,
                 listing: &SurfaceListing)
                 -> Vec<SurfaceContext> {
+        // Fullscreen frame covers whole display and maximized frame whole workspace. Other frames
+        // from the same workspace are not drawn except for floating ones.
+        match self.get_mode() {
+            Mode::Display { id: _ } => {
+                if let Some(presented) = self.find_presented() {
+                    if presented.get_presentation().is_fullscreen() {
+                        let workspace_position = presented.find_top()
+                            .map_or(relative_position, |w| relative_position + w.get_position());
+                        return presented_to_array(&presented,
+                                                  relative_position,
+                                                  workspace_position,
+                                                  listing);
+                    }
+                }
+            }
+            Mode::Workspace { is_active: _ } => {
+                if let Some(presented) = self.find_presented() {
+                    return presented_to_array(&presented,
+                                              relative_position,
+                                              relative_position,
+                                              listing);
+                }
+            }
+            _ => {}
+        }
+
         let mut result = Vec::new();
         for frame in self.time_iter() {
             if let Mode::Workspace { is_active } = frame.get_mode() {
@@ -43,16 +71,7 @@ impl Converting for Frame {
                 }
             }
 
-            let pos = relative_position + frame.get_position();
-            if frame.get_sid().is_valid() {
-                if let Some(ref mut array) = listing.get_renderer_context(frame.get_sid()) {
-                    for ref mut c in array.iter().rev() {
-                        result.push(c.moved(pos));
-                    }
-                }
-            } else {
-                result.append(&mut frame.to_array(pos, listing));
-            }
+            frame_to_array(&frame, relative_position, listing, &mut result);
         }
         result
     }
@@ -66,11 +85,63 @@ impl Converting for Frame {
 
 // -------------------------------------------------------------------------------------------------
 
+/// Appends contexts of given frame placed relatively to `relative_position` to `result`.
+fn frame_to_array(frame: &Frame,
+                  relative_position: Position,
+                  listing: &SurfaceListing,
+                  result: &mut Vec<SurfaceContext>) {
+    let pos = relative_position + frame.get_position();
+    if frame.get_sid().is_valid() {
+        // Leave place for border around anchored leaves
+        let pos = if frame.get_mobility().is_anchored() {
+            let border = frame.get_effective_spacing().border as isize;
+            pos + Vector::new(border, border)
+        } else {
+            pos
+        };
+
+        if let Some(ref mut array) = listing.get_renderer_context(frame.get_sid()) {
+            for ref mut c in array.iter().rev() {
+                result.push(c.moved(pos));
+            }
+        }
+    } else {
+        result.append(&mut frame.to_array(pos, listing));
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Returns contexts of maximized or fullscreen frame placed in `presented_position` with floating
+/// frames of its workspace above it.
+fn presented_to_array(presented: &Frame,
+                      presented_position: Position,
+                      workspace_position: Position,
+                      listing: &SurfaceListing)
+                      -> Vec<SurfaceContext> {
+    let mut result = Vec::new();
+    if let Some(workspace) = presented.find_top() {
+        for frame in workspace.time_iter() {
+            if frame.get_mobility().is_floating() && !frame.equals_exact(presented) {
+                frame_to_array(&frame, workspace_position, listing, &mut result);
+            }
+        }
+    }
+
+    if let Some(ref mut array) = listing.get_renderer_context(presented.get_sid()) {
+        for ref mut c in array.iter().rev() {
+            result.push(c.moved(presented_position));
+        }
+    }
+    result
+}
+
+// -------------------------------------------------------------------------------------------------
+
 fn root_to_displays(frame: &Frame, displays: &mut HashMap<i32, Vec<WorkspaceInfo>>) {
     if let Mode::Display { id } = frame.get_mode() {
         let mut workspaces = Vec::new();
         display_to_workspaces(frame, &mut workspaces);
-        workspaces.sort();
         displays.insert(id, workspaces);
     } else {
         for subframe in frame.space_iter() {
@@ -88,6 +88,29 @@ pub enum Geometry {
 
 // -------------------------------------------------------------------------------------------------
 
+impl Geometry {
+    /// Returns geometry with given name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "vertical" => Some(Geometry::Vertical),
+            "horizontal" => Some(Geometry::Horizontal),
+            "stacked" => Some(Geometry::Stacked),
+            _ => None,
+        }
+    }
+
+    /// Returns name of the geometry.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Geometry::Vertical => "vertical",
+            Geometry::Horizontal => "horizontal",
+            Geometry::Stacked => "stacked",
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
 /// Defines what spatial operations are allowed in the frame.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Mobility {
@@ -122,6 +145,35 @@ impl Mobility {
 
 // -------------------------------------------------------------------------------------------------
 
+/// Defines how surface of the frame is presented on screen regardless of its place in layout.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Presentation {
+    /// Frame is drawn in its place in layout.
+    Regular,
+
+    /// Frame covers whole area of its workspace.
+    Maximized,
+
+    /// Frame covers whole area of its display including docks.
+    Fullscreen,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Presentation {
+    /// Returns `true` if frame is drawn in its place in layout.
+    pub fn is_regular(&self) -> bool {
+        *self == Presentation::Regular
+    }
+
+    /// Returns `true` if frame covers whole area of its display.
+    pub fn is_fullscreen(&self) -> bool {
+        *self == Presentation::Fullscreen
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
 /// Defines mode of the frame.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Mode {
@@ -176,6 +228,150 @@ pub enum Side {
 
 // -------------------------------------------------------------------------------------------------
 
+/// Defines gaps and borders used while packing frames inside workspace.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Spacing {
+    /// Gap in pixels between sibling frames.
+    pub inner_gap: usize,
+
+    /// Gap in pixels between frames and edges of workspace.
+    pub outer_gap: usize,
+
+    /// Width in pixels of border reserved around anchored leaf frames.
+    pub border: usize,
+
+    /// If `true` gaps are not used when workspace contains only one anchored leaf.
+    pub smart_gaps: bool,
+
+    /// If `false` neither gaps nor borders are used.
+    pub enabled: bool,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Spacing {
+    /// Constructs `Spacing` with no gaps and borders.
+    pub fn none() -> Self {
+        Spacing {
+            inner_gap: 0,
+            outer_gap: 0,
+            border: 0,
+            smart_gaps: false,
+            enabled: true,
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Default for Spacing {
+    fn default() -> Self {
+        Spacing::none()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Defines algorithm used to automatically place anchored subframes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LayoutKind {
+    /// Subframes are placed according to frames geometry.
+    Manual,
+
+    /// Master frames are placed in one column on the left and the rest in one column on the right.
+    MasterStack,
+
+    /// Subframes are placed in grid with (almost) the same number of rows and columns.
+    Grid,
+
+    /// Every next subframe takes half of space left by previous ones going clockwise inside.
+    Spiral,
+
+    /// Master frames are placed in the center and the rest alternately on the right and left.
+    CenteredMaster,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl LayoutKind {
+    /// Returns layout kind with given name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "manual" => Some(LayoutKind::Manual),
+            "master_stack" => Some(LayoutKind::MasterStack),
+            "grid" => Some(LayoutKind::Grid),
+            "spiral" => Some(LayoutKind::Spiral),
+            "centered_master" => Some(LayoutKind::CenteredMaster),
+            _ => None,
+        }
+    }
+
+    /// Returns next layout kind. Used for cycling layouts.
+    pub fn next(self) -> Self {
+        match self {
+            LayoutKind::Manual => LayoutKind::MasterStack,
+            LayoutKind::MasterStack => LayoutKind::Grid,
+            LayoutKind::Grid => LayoutKind::Spiral,
+            LayoutKind::Spiral => LayoutKind::CenteredMaster,
+            LayoutKind::CenteredMaster => LayoutKind::Manual,
+        }
+    }
+
+    /// Returns previous layout kind. Used for cycling layouts.
+    pub fn previous(self) -> Self {
+        match self {
+            LayoutKind::Manual => LayoutKind::CenteredMaster,
+            LayoutKind::MasterStack => LayoutKind::Manual,
+            LayoutKind::Grid => LayoutKind::MasterStack,
+            LayoutKind::Spiral => LayoutKind::Grid,
+            LayoutKind::CenteredMaster => LayoutKind::Spiral,
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Defines automatic layout of anchored subframes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Layout {
+    /// Algorithm used to place subframes.
+    pub kind: LayoutKind,
+
+    /// Number of master frames.
+    pub master_count: usize,
+
+    /// Part of available width taken by master frames.
+    pub master_ratio: f32,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Layout {
+    /// Constructs manual `Layout`.
+    pub fn manual() -> Self {
+        Layout {
+            kind: LayoutKind::Manual,
+            master_count: 1,
+            master_ratio: 0.5,
+        }
+    }
+
+    /// Returns `true` if subframes are placed automatically, `false` if geometry is used.
+    pub fn is_automatic(&self) -> bool {
+        self.kind != LayoutKind::Manual
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Default for Layout {
+    fn default() -> Self {
+        Layout::manual()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
 /// Parameters of the frame defining its properties.
 pub struct Parameters {
     /// ID of assigned surface.
@@ -198,6 +394,30 @@ pub struct Parameters {
 
     /// Title.
     pub title: String,
+
+    /// Size ratio relative to siblings. Used to distribute space between anchored frames.
+    pub ratio: f32,
+
+    /// Gaps and borders. Used only by workspaces.
+    pub spacing: Spacing,
+
+    /// Number of anchored leaves inside the frame. Used only by workspaces to decide if smart gaps
+    /// apply. Updated when spacing is refreshed.
+    pub anchored_leaves: usize,
+
+    /// Automatic layout of subframes.
+    pub layout: Layout,
+
+    /// Presentation of the surface. Used only by leaves.
+    pub presentation: Presentation,
+
+    /// Whether the frame is visible on every workspace of its display. Used only by floating
+    /// frames.
+    pub sticky: bool,
+
+    /// ID of placeholder the frame serves as. Placeholders are empty containers waiting for
+    /// surfaces. `None` for other frames.
+    pub placeholder: Option<u64>,
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -213,6 +433,13 @@ impl Parameters {
             pos: Position::default(),
             size: Size::default(),
             title: "root".to_owned(),
+            ratio: 1.0,
+            spacing: Spacing::default(),
+            anchored_leaves: 0,
+            layout: Layout::default(),
+            presentation: Presentation::Regular,
+            sticky: false,
+            placeholder: None,
         }
     }
 
@@ -226,6 +453,13 @@ impl Parameters {
             pos: area.pos,
             size: area.size,
             title: title,
+            ratio: 1.0,
+            spacing: Spacing::default(),
+            anchored_leaves: 0,
+            layout: Layout::default(),
+            presentation: Presentation::Regular,
+            sticky: false,
+            placeholder: None,
         }
     }
 
@@ -239,6 +473,13 @@ impl Parameters {
             pos: Position::default(),
             size: Size::default(),
             title: title,
+            ratio: 1.0,
+            spacing: Spacing::default(),
+            anchored_leaves: 0,
+            layout: Layout::default(),
+            presentation: Presentation::Regular,
+            sticky: false,
+            placeholder: None,
         }
     }
 
@@ -252,6 +493,13 @@ impl Parameters {
             pos: Position::default(),
             size: Size::default(),
             title: "".to_owned(),
+            ratio: 1.0,
+            spacing: Spacing::default(),
+            anchored_leaves: 0,
+            layout: Layout::default(),
+            presentation: Presentation::Regular,
+            sticky: false,
+            placeholder: None,
         }
     }
 
@@ -268,6 +516,13 @@ impl Parameters {
             pos: Position::default(),
             size: Size::default(),
             title: "".to_owned(),
+            ratio: 1.0,
+            spacing: Spacing::default(),
+            anchored_leaves: 0,
+            layout: Layout::default(),
+            presentation: Presentation::Regular,
+            sticky: false,
+            placeholder: None,
         }
     }
 }
@@ -314,6 +569,13 @@ impl Frame {
                                pos: pos,
                                size: size,
                                title: title,
+                               ratio: 1.0,
+                               spacing: Spacing::default(),
+                               anchored_leaves: 0,
+                               layout: Layout::default(),
+                               presentation: Presentation::Regular,
+                               sticky: false,
+                               placeholder: None,
                            },
                            node: Node::default(),
                        })
@@ -439,6 +701,48 @@ impl Frame {
         Area::new(self.get_position(), self.get_size())
     }
 
+    /// Gets size ratio relative to siblings.
+    #[inline]
+    pub fn get_ratio(&self) -> f32 {
+        unsafe { (*self.inner).params.ratio }
+    }
+
+    /// Gets gaps and borders configuration.
+    #[inline]
+    pub fn get_spacing(&self) -> Spacing {
+        unsafe { (*self.inner).params.spacing }
+    }
+
+    /// Gets number of anchored leaves as last counted when refreshing spacing.
+    #[inline]
+    pub fn get_anchored_leaves(&self) -> usize {
+        unsafe { (*self.inner).params.anchored_leaves }
+    }
+
+    /// Gets automatic layout configuration.
+    #[inline]
+    pub fn get_layout(&self) -> Layout {
+        unsafe { (*self.inner).params.layout }
+    }
+
+    /// Gets presentation.
+    #[inline]
+    pub fn get_presentation(&self) -> Presentation {
+        unsafe { (*self.inner).params.presentation }
+    }
+
+    /// Checks if the frame is visible on every workspace of its display.
+    #[inline]
+    pub fn is_sticky(&self) -> bool {
+        unsafe { (*self.inner).params.sticky }
+    }
+
+    /// Gets ID of placeholder the frame serves as.
+    #[inline]
+    pub fn get_placeholder(&self) -> Option<u64> {
+        unsafe { (*self.inner).params.placeholder }
+    }
+
     /// Gets title.
     pub fn get_title(&self) -> String {
         unsafe { (*self.inner).params.title.clone() }
@@ -521,6 +825,70 @@ impl Frame {
         }
     }
 
+    /// Sets size ratio without resizing the frame or its siblings.
+    #[inline]
+    pub fn set_plumbing_ratio(&mut self, ratio: f32) {
+        unsafe {
+            (*self.inner).params.ratio = ratio;
+        }
+    }
+
+    /// Sets gaps and borders configuration without relaxing the frame.
+    #[inline]
+    pub fn set_plumbing_spacing(&mut self, spacing: Spacing) {
+        unsafe {
+            (*self.inner).params.spacing = spacing;
+        }
+    }
+
+    /// Sets number of anchored leaves without relaxing the frame.
+    #[inline]
+    pub fn set_plumbing_anchored_leaves(&mut self, anchored_leaves: usize) {
+        unsafe {
+            (*self.inner).params.anchored_leaves = anchored_leaves;
+        }
+    }
+
+    /// Sets automatic layout configuration without relaxing the frame.
+    #[inline]
+    pub fn set_plumbing_layout(&mut self, layout: Layout) {
+        unsafe {
+            (*self.inner).params.layout = layout;
+        }
+    }
+
+    /// Sets presentation without reconfiguring the surface.
+    #[inline]
+    pub fn set_plumbing_presentation(&mut self, presentation: Presentation) {
+        unsafe {
+            (*self.inner).params.presentation = presentation;
+        }
+    }
+
+    /// Sets stickiness without moving the frame.
+    #[inline]
+    pub fn set_plumbing_sticky(&mut self, sticky: bool) {
+        unsafe {
+            (*self.inner).params.sticky = sticky;
+        }
+    }
+
+    /// Sets ID of placeholder the frame serves as.
+    #[inline]
+    pub fn set_plumbing_placeholder(&mut self, placeholder: Option<u64>) {
+        unsafe {
+            (*self.inner).params.placeholder = placeholder;
+        }
+    }
+
+    /// Sets title without any checks.
+    #[inline]
+    pub fn set_plumbing_title(&mut self, title: String) {
+        unsafe {
+            (*self.inner).params.title = title;
+        }
+    }
+
     /// Sets mode without any checks.
     #[inline]
     pub fn set_plumbing_mode(&mut self, mode: Mode) {
@@ -676,6 +1044,44 @@ impl Frame {
             self.reset_matter();
         }
     }
+
+    /// Puts given frame in place of `self` in both spatial and time order and removes `self` from
+    /// its parent children. Given frame must not have parent.
+    pub fn replace(&mut self, frame: &mut Frame) {
+        if let Some(ref mut parent) = self.get_parent() {
+            unsafe {
+                let node = &mut (*self.inner).node;
+                let other = &mut (*frame.inner).node;
+                other.time.prev = node.time.prev.take();
+                other.time.next = node.time.next.take();
+                other.space.prev = node.space.prev.take();
+                other.space.next = node.space.next.take();
+
+                if let Some(ref mut prev) = other.time.prev {
+                    (*prev.inner).node.time.next = Some(frame.clone());
+                } else {
+                    (*parent.inner).node.time.first = Some(frame.clone());
+                }
+                if let Some(ref mut next) = other.time.next {
+                    (*next.inner).node.time.prev = Some(frame.clone());
+                } else {
+                    (*parent.inner).node.time.last = Some(frame.clone());
+                }
+                if let Some(ref mut prev) = other.space.prev {
+                    (*prev.inner).node.space.next = Some(frame.clone());
+                } else {
+                    (*parent.inner).node.space.first = Some(frame.clone());
+                }
+                if let Some(ref mut next) = other.space.next {
+                    (*next.inner).node.space.prev = Some(frame.clone());
+                } else {
+                    (*parent.inner).node.space.last = Some(frame.clone());
+                }
+            }
+            frame.set_matter(parent);
+            self.reset_matter();
+        }
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -870,6 +1276,18 @@ impl Frame {
         result
     }
 
+    /// Checks if given frame is (direct or indirect) child of `self`.
+    pub fn contains(&self, other: &Frame) -> bool {
+        let mut frame = other.get_parent();
+        while let Some(parent) = frame {
+            if self.equals_exact(&parent) {
+                return true;
+            }
+            frame = parent.get_parent();
+        }
+        false
+    }
+
     /// Calculates global position of the frame.
     pub fn calculate_global_position(&self) -> Position {
         if let Some(parent) = self.get_parent() {
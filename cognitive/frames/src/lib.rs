@@ -48,7 +48,9 @@ extern crate cognitive_qualia as qualia;
 
 mod frame;
 pub use frame::{Frame, FrameSpaceIterator, FrameTimeIterator, Side, Parameters};
-pub use frame::{Geometry, Mobility, Mode};
+pub use frame::{Geometry, Layout, LayoutKind, Mobility, Mode, Presentation, Spacing};
+
+mod arranging;
 
 mod converting;
 pub use converting::Converting;
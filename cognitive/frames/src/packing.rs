@@ -1,88 +1,142 @@
 // This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
 // the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
 
-//! This module contains extra settling functionality for `frames::Frame`.
+//! This module contains extra packing functionality for `frames::Frame`.
 
 // -------------------------------------------------------------------------------------------------
 
-use qualia::{Position, Size, Vector};
+use qualia::{Area, Position, Size, Vector};
 use qualia::{SurfaceAccess, surface_state};
 
-use frame::{Frame, Geometry, Mobility};
+use arranging;
+use frame::{Frame, Geometry, Mobility, Presentation, Spacing};
+use searching::Searching;
 
 // -------------------------------------------------------------------------------------------------
 
 /// Extension trait for `Frame` adding more packing functionality.
 pub trait Packing {
-    /// TODO: Implement relaxing. Currently relaxing is equivalent to homogenizing.
+    /// Distribute space between anchored subframes proportionally to their size ratios and
+    /// place them in proper layout. If the frame has automatic layout subframes are placed
+    /// according to it instead.
     fn relax(&mut self, sa: &mut SurfaceAccess);
 
-    /// Make all subsurfaces have the same size and proper layout.
-    /// Homogenizing works only on directed frames.
+    /// Reset size ratios of all anchored subframes so they have the same size and then relax.
     fn homogenize(&mut self, sa: &mut SurfaceAccess);
 
+    /// Recalculate size ratios of anchored subframes basing on their current sizes. This should
+    /// be called after manual resizing so that the sizes are preserved during next relaxation.
+    fn update_ratios(&mut self);
+
     /// Set size of the frame and resize its subframe accordingly.
     fn change_size(&mut self, vactor: Vector, sa: &mut SurfaceAccess);
 
     /// Set size of the frame and resize its subframe accordingly.
     fn set_size(&mut self, size: Size, sa: &mut SurfaceAccess);
 
+    /// Change presentation of the frame and inform its surface about new size.
+    fn present(&mut self, presentation: Presentation, sa: &mut SurfaceAccess);
+
     /// Remove given frame and relax old parent.
     fn remove_self(&mut self, sa: &mut SurfaceAccess);
+
+    /// Relax the frame and all its subframes recursively. This is needed when gaps or borders of
+    /// workspace change. Anchored leaves of workspace are recounted.
+    fn repack(&mut self, sa: &mut SurfaceAccess);
+
+    /// Recount anchored leaves of workspace containing the frame and repack it if gaps effective
+    /// in it changed. Adding, removing or (de)anchorizing a frame may have changed them when smart
+    /// gaps are used.
+    fn refresh_spacing(&mut self, sa: &mut SurfaceAccess);
+
+    /// Returns gaps and borders effective in workspace containing the frame. Takes into account
+    /// if spacing is enabled and if smart gaps should be applied (basing on number of anchored
+    /// leaves counted when spacing was last refreshed).
+    fn get_effective_spacing(&self) -> Spacing;
 }
 
 // -------------------------------------------------------------------------------------------------
 
 impl Packing for Frame {
     fn relax(&mut self, sa: &mut SurfaceAccess) {
-        self.homogenize(sa);
-    }
-
-    fn homogenize(&mut self, sa: &mut SurfaceAccess) {
         let len = self.count_anchored_children();
         if len < 1 {
             return;
         }
 
-        // Decide how to resize and move twigs
-        let mut size = Size::new(0, 0);
-        let mut increment = Vector::new(0, 0);
-        match self.get_geometry() {
-            Geometry::Stacked => {
-                size = self.get_size();
-            }
-            Geometry::Vertical => {
-                let mut docked_height = 0;
-                for frame in self.space_iter() {
-                    if frame.get_mobility().is_docked() {
-                        docked_height += frame.get_size().height;
-                    }
+        // Decide how much space is available for anchored twigs
+        let spacing = self.get_effective_spacing();
+        let outer_gap = if self.get_mode().is_workspace() {
+            spacing.outer_gap
+        } else {
+            0
+        };
+        let inner_gap = match self.get_geometry() {
+            Geometry::Stacked => 0,
+            Geometry::Vertical | Geometry::Horizontal => spacing.inner_gap * (len - 1),
+        };
+
+        let mut ratio_sum = 0.0;
+        let mut docked = Size::new(0, 0);
+        for frame in self.space_iter() {
+            match frame.get_mobility() {
+                Mobility::Anchored => ratio_sum += frame.get_ratio(),
+                Mobility::Docked => {
+                    docked.width += frame.get_size().width;
+                    docked.height += frame.get_size().height;
                 }
-                size.width = self.get_size().width;
-                size.height = (self.get_size().height - docked_height) / len;
-                increment.y = size.height as isize;
+                Mobility::Floating => {}
             }
-            Geometry::Horizontal => {
-                let mut docked_width = 0;
-                for frame in self.space_iter() {
-                    if frame.get_mobility().is_docked() {
-                        docked_width += frame.get_size().width;
-                    }
-                }
-                size.height = self.get_size().height;
-                size.width = (self.get_size().width - docked_width) / len;
-                increment.x = size.width as isize;
+        }
+
+        let size = shrink(self.get_size(), outer_gap);
+
+        // Let automatic layout place subframes if requested
+        let layout = self.get_layout();
+        if layout.is_automatic() {
+            let pos = Position::new(outer_gap as isize, outer_gap as isize);
+            let areas = arranging::arrange(layout, Area::new(pos, size), len, spacing.inner_gap);
+            let anchored = self.space_iter().filter(|frame| frame.get_mobility().is_anchored());
+            for (mut frame, area) in anchored.zip(areas.into_iter()) {
+                frame.set_size(area.size, sa);
+                frame.set_plumbing_position(area.pos);
             }
+            return;
         }
 
+        let available = match self.get_geometry() {
+            Geometry::Stacked => 0,
+            Geometry::Vertical => size.height.saturating_sub(docked.height + inner_gap),
+            Geometry::Horizontal => size.width.saturating_sub(docked.width + inner_gap),
+        } as f32;
+
         // Resize and reposition all subframes recursively
-        let mut pos = Position::default();
+        let mut pos = Position::new(outer_gap as isize, outer_gap as isize);
         for mut frame in self.space_iter() {
             match frame.get_mobility() {
                 Mobility::Anchored => {
-                    frame.set_size(size, sa);
-                    frame.set_plumbing_position(pos);
-                    pos = pos + increment;
+                    let length = if ratio_sum > 0.0 {
+                        available * frame.get_ratio() / ratio_sum
+                    } else {
+                        available / len as f32
+                    } as usize;
+
+                    match self.get_geometry() {
+                        Geometry::Stacked => {
+                            frame.set_size(size, sa);
+                            frame.set_plumbing_position(pos);
+                        }
+                        Geometry::Vertical => {
+                            frame.set_size(Size::new(size.width, length), sa);
+                            frame.set_plumbing_position(pos);
+                            pos.y += (length + spacing.inner_gap) as isize;
+                        }
+                        Geometry::Horizontal => {
+                            frame.set_size(Size::new(length, size.height), sa);
+                            frame.set_plumbing_position(pos);
+                            pos.x += (length + spacing.inner_gap) as isize;
+                        }
+                    }
                 }
                 Mobility::Docked => {
                     match self.get_geometry() {
@@ -96,6 +150,40 @@ impl Packing for Frame {
         }
     }
 
+    fn homogenize(&mut self, sa: &mut SurfaceAccess) {
+        for mut frame in self.space_iter() {
+            if frame.get_mobility().is_anchored() {
+                frame.set_plumbing_ratio(1.0);
+            }
+        }
+        self.relax(sa);
+    }
+
+    fn update_ratios(&mut self) {
+        let geometry = self.get_geometry();
+        let length_of = |frame: &Frame| match geometry {
+            Geometry::Vertical => frame.get_size().height,
+            Geometry::Horizontal => frame.get_size().width,
+            Geometry::Stacked => 0,
+        };
+
+        let len = self.count_anchored_children();
+        let total = self.space_iter()
+            .filter(|frame| frame.get_mobility().is_anchored())
+            .fold(0, |sum, frame| sum + length_of(&frame));
+        if total == 0 {
+            return;
+        }
+
+        let average = total as f32 / len as f32;
+        for mut frame in self.space_iter() {
+            if frame.get_mobility().is_anchored() {
+                let ratio = length_of(&frame) as f32 / average;
+                frame.set_plumbing_ratio(ratio);
+            }
+        }
+    }
+
     fn change_size(&mut self, vector: Vector, sa: &mut SurfaceAccess) {
         let size = self.get_size().sized(vector);
         self.set_size(size, sa);
@@ -104,16 +192,48 @@ impl Packing for Frame {
     fn set_size(&mut self, size: Size, sa: &mut SurfaceAccess) {
         // Set size for given frame.
         let old_size = self.get_size();
+        let spacing = self.get_effective_spacing();
         self.set_plumbing_size(size.clone());
-        sa.reconfigure(self.get_sid(), size.clone(), surface_state::MAXIMIZED);
+
+        // Inform surface about its size reduced by border. Maximized and fullscreen surfaces are
+        // informed about size of their workspace or display instead.
+        match self.get_presentation() {
+            Presentation::Regular => {
+                let border = if self.get_mode().is_leaf() && self.get_mobility().is_anchored() {
+                    spacing.border
+                } else {
+                    0
+                };
+                sa.reconfigure(self.get_sid(), shrink(size, border), surface_state::MAXIMIZED);
+            }
+            Presentation::Maximized => {
+                let size = self.find_top().map_or(size, |top| top.get_size());
+                sa.reconfigure(self.get_sid(), size, surface_state::MAXIMIZED);
+            }
+            Presentation::Fullscreen => {
+                let size = self.find_display().map_or(size, |display| display.get_size());
+                sa.reconfigure(self.get_sid(), size, surface_state::FULLSCREEN);
+            }
+        }
 
         // Set size to frames children.
+        let inner_size = if self.get_mode().is_workspace() {
+            shrink(size, spacing.outer_gap)
+        } else {
+            size
+        };
+
+        if self.get_layout().is_automatic() {
+            self.relax(sa);
+            return;
+        }
+
         match self.get_geometry() {
             Geometry::Horizontal => {
                 if old_size.width == size.width {
                     for mut frame in self.space_iter() {
                         let mut frame_size = frame.get_size();
-                        frame_size.height = size.height;
+                        frame_size.height = inner_size.height;
                         frame.set_size(frame_size, sa);
                     }
                 } else {
@@ -124,7 +244,7 @@ impl Packing for Frame {
                 if old_size.height == size.height {
                     for mut frame in self.space_iter() {
                         let mut frame_size = frame.get_size();
-                        frame_size.width = size.width;
+                        frame_size.width = inner_size.width;
                         frame.set_size(frame_size, sa);
                     }
                 } else {
@@ -134,13 +254,19 @@ impl Packing for Frame {
             Geometry::Stacked => {
                 for mut frame in self.space_iter() {
                     if !frame.get_mobility().is_floating() {
-                        frame.set_size(size.clone(), sa);
+                        frame.set_size(inner_size, sa);
                     }
                 }
             }
         }
     }
 
+    fn present(&mut self, presentation: Presentation, sa: &mut SurfaceAccess) {
+        let size = self.get_size();
+        self.set_plumbing_presentation(presentation);
+        self.set_size(size, sa);
+    }
+
     fn remove_self(&mut self, sa: &mut SurfaceAccess) {
         if let Some(ref mut parent) = self.get_parent() {
             self.remove();
@@ -149,9 +275,78 @@ impl Packing for Frame {
                 parent.remove_self(sa);
             } else {
                 parent.relax(sa);
+                parent.refresh_spacing(sa);
+            }
+        }
+    }
+
+    fn repack(&mut self, sa: &mut SurfaceAccess) {
+        if self.get_mode().is_workspace() {
+            let anchored_leaves = count_anchored_leaves(self);
+            self.set_plumbing_anchored_leaves(anchored_leaves);
+        }
+        self.relax(sa);
+        for mut frame in self.space_iter() {
+            if frame.has_children() {
+                frame.repack(sa);
+            }
+        }
+    }
+
+    fn refresh_spacing(&mut self, sa: &mut SurfaceAccess) {
+        if let Some(mut workspace) = self.find_top() {
+            if workspace.get_mode().is_workspace() {
+                let old_spacing = workspace.get_effective_spacing();
+                let anchored_leaves = count_anchored_leaves(&workspace);
+                workspace.set_plumbing_anchored_leaves(anchored_leaves);
+                if workspace.get_effective_spacing() != old_spacing {
+                    workspace.repack(sa);
+                }
+            }
+        }
+    }
+
+    fn get_effective_spacing(&self) -> Spacing {
+        if let Some(workspace) = self.find_top() {
+            if workspace.get_mode().is_workspace() {
+                let mut spacing = workspace.get_spacing();
+                if !spacing.enabled {
+                    return Spacing::none();
+                }
+                if spacing.smart_gaps && workspace.get_anchored_leaves() < 2 {
+                    spacing.inner_gap = 0;
+                    spacing.outer_gap = 0;
+                }
+                return spacing;
+            }
+        }
+        Spacing::none()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Returns given size reduced by given margin on each side.
+fn shrink(size: Size, margin: usize) -> Size {
+    Size::new(size.width.saturating_sub(2 * margin),
+              size.height.saturating_sub(2 * margin))
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Counts anchored leaves inside given frame.
+fn count_anchored_leaves(frame: &Frame) -> usize {
+    let mut result = 0;
+    for subframe in frame.time_iter() {
+        if subframe.get_mode().is_leaf() {
+            if subframe.get_mobility().is_anchored() {
+                result += 1;
             }
+        } else {
+            result += count_anchored_leaves(&subframe);
         }
     }
+    result
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -22,6 +22,13 @@ pub trait Searching {
     /// Finds first trunk which is `Workspace`.
     fn find_top(&self) -> Option<Frame>;
 
+    /// Finds first trunk which is `Display`.
+    fn find_display(&self) -> Option<Frame>;
+
+    /// Finds the most recently used frame with not regular presentation (maximized or
+    /// fullscreen). Inactive workspaces contained in `self` are not searched.
+    fn find_presented(&self) -> Option<Frame>;
+
     /// Finds frame with given surface ID.
     fn find_with_sid(&self, sid: SurfaceId) -> Option<Frame>;
 
@@ -79,6 +86,37 @@ impl Searching for Frame {
         }
     }
 
+    fn find_display(&self) -> Option<Frame> {
+        let mut current = Some(self.clone());
+        loop {
+            current = if let Some(ref frame) = current {
+                if frame.get_mode().is_display() {
+                    return current.clone();
+                }
+                frame.get_parent()
+            } else {
+                return None;
+            }
+        }
+    }
+
+    fn find_presented(&self) -> Option<Frame> {
+        if !self.get_presentation().is_regular() && self.get_sid().is_valid() {
+            Some(self.clone())
+        } else {
+            for subsurface in self.time_iter() {
+                if let Mode::Workspace { is_active: false } = subsurface.get_mode() {
+                    continue;
+                }
+                let result = subsurface.find_presented();
+                if result.is_some() {
+                    return result;
+                }
+            }
+            None
+        }
+    }
+
     fn find_with_sid(&self, sid: SurfaceId) -> Option<Frame> {
         if self.get_sid() == sid {
             Some(self.clone())
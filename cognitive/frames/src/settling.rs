@@ -58,6 +58,11 @@ pub trait Settling {
     /// Removes frame `self` from frame layout and then places it using `jumpin` method.
     fn jump(&mut self, side: Side, target: &mut Frame, sa: &mut SurfaceAccess);
 
+    /// Exchanges places of frame `self` and `other` in frame layout. Frames take over each
+    /// other's mobility, position and size. Nothing is done if frames are the same or one of them
+    /// contains the other.
+    fn swap(&mut self, other: &mut Frame, sa: &mut SurfaceAccess);
+
     /// Places frame `self` in `target` frame as dock.
     fn dock(&mut self, target: &mut Frame, size: Size, sa: &mut SurfaceAccess);
 
@@ -98,6 +103,7 @@ impl Settling for Frame {
                 self.set_plumbing_mobility(Mobility::Anchored);
             }
             buildable.relax(sa);
+            buildable.refresh_spacing(sa);
         }
     }
 
@@ -129,8 +135,9 @@ impl Settling for Frame {
 
         // If there's nothing above we can finish
         if let Some(ref mut parent) = pop.get_parent() {
-            // If it is `stacked` frame we have to pop it also spatially
-            if parent.get_geometry() == Geometry::Stacked {
+            // If it is `stacked` frame we have to pop it also spatially. Workspaces keep their
+            // order as only one of them is visible at a time.
+            if parent.get_geometry() == Geometry::Stacked && !pop.get_mode().is_workspace() {
                 pop.remove();
                 parent.prepend(pop);
             }
@@ -145,7 +152,7 @@ impl Settling for Frame {
 
     fn change_geometry(&mut self, geometry: Geometry, sa: &mut SurfaceAccess) {
         self.set_plumbing_geometry(geometry);
-        self.homogenize(sa);
+        self.relax(sa);
     }
 
     fn ramify(&mut self, geometry: Geometry) -> Frame {
@@ -178,11 +185,13 @@ impl Settling for Frame {
                                        self.get_position(),
                                        self.get_size(),
                                        self.get_title());
+        distancer.set_plumbing_ratio(self.get_ratio());
         self.prejoin(&mut distancer);
         self.remove();
         self.set_plumbing_mobility(Mobility::Anchored);
         self.set_plumbing_mode(frame_mode);
         self.set_plumbing_position(Position::default());
+        self.set_plumbing_ratio(1.0);
         distancer.prepend(self);
         distancer
     }
@@ -213,10 +222,12 @@ impl Settling for Frame {
                 Side::Before => {
                     target.prejoin(self);
                     target_parent.relax(sa);
+                    target_parent.refresh_spacing(sa);
                 }
                 Side::After => {
                     target.adjoin(self);
                     target_parent.relax(sa);
+                    target_parent.refresh_spacing(sa);
                 }
                 Side::On => {
                     let mut new_target = {
@@ -240,6 +251,42 @@ impl Settling for Frame {
         self.jumpin(side, target, sa);
     }
 
+    fn swap(&mut self, other: &mut Frame, sa: &mut SurfaceAccess) {
+        if self.equals_exact(other) || self.contains(other) || other.contains(self) {
+            return;
+        }
+
+        let (mut self_parent, mut other_parent) = match (self.get_parent(), other.get_parent()) {
+            (Some(self_parent), Some(other_parent)) => (self_parent, other_parent),
+            _ => return,
+        };
+
+        // Exchange places using placeholder
+        let mut placeholder = Frame::new_container(Geometry::Stacked);
+        self.replace(&mut placeholder);
+        other.replace(self);
+        placeholder.replace(other);
+        placeholder.destroy();
+
+        // Exchange mobility, ratio and area
+        let self_mobility = self.get_mobility();
+        let self_ratio = self.get_ratio();
+        let self_area = self.get_area();
+        self.set_plumbing_mobility(other.get_mobility());
+        self.set_plumbing_ratio(other.get_ratio());
+        self.set_plumbing_position(other.get_position());
+        self.set_size(other.get_size(), sa);
+        other.set_plumbing_mobility(self_mobility);
+        other.set_plumbing_ratio(self_ratio);
+        other.set_plumbing_position(self_area.pos);
+        other.set_size(self_area.size, sa);
+
+        self_parent.relax(sa);
+        if !self_parent.equals_exact(&other_parent) {
+            other_parent.relax(sa);
+        }
+    }
+
     fn dock(&mut self, target: &mut Frame, size: Size, sa: &mut SurfaceAccess) {
         target.set_plumbing_geometry(Geometry::Vertical);
         self.set_plumbing_mobility(Mobility::Docked);
@@ -256,6 +303,7 @@ impl Settling for Frame {
             self.set_size(parent.get_size(), sa);
             self.set_plumbing_position(Position::default());
             self.set_plumbing_mobility(Mobility::Anchored);
+            self.refresh_spacing(sa);
         }
     }
 
@@ -270,6 +318,7 @@ impl Settling for Frame {
             self.set_size(area.size, sa);
             self.set_plumbing_position(area.pos);
             self.set_plumbing_mobility(Mobility::Floating);
+            self.refresh_spacing(sa);
         }
     }
 
@@ -302,6 +351,11 @@ impl Settling for Frame {
                     self.move_with_contents(move_vector);
                 }
                 Mobility::Anchored => {
+                    // Frames in automatic layouts are placed only by the layout
+                    if self.get_parent().map_or(false, |p| p.get_layout().is_automatic()) {
+                        return;
+                    }
+
                     if let Some(neighbour) = self.find_neighbouring(direction) {
                         if neighbour.get_mobility().is_anchored() {
                             let (mut first, mut second) = {
@@ -329,6 +383,11 @@ impl Settling for Frame {
                             first.change_size(resize_vector.opposite(), sa);
                             second.change_size(resize_vector, sa);
                             second.move_with_contents(move_vector);
+
+                            // Remember new proportions
+                            if let Some(mut parent) = self.get_parent() {
+                                parent.update_ratios();
+                            }
                         }
                     } else if let Some(mut parent) = self.get_parent() {
                         parent.resize(direction, magnitude, sa);
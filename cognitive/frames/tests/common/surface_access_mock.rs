@@ -31,6 +31,8 @@ impl SurfaceAccess for SurfaceAccessMock {
                    _size: Size,
                    _state_flags: surface_state::SurfaceState) {
     }
+
+    fn close(&mut self, _sid: SurfaceId) {}
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -12,8 +12,8 @@ extern crate cognitive_frames as frames;
 
 mod common;
 
-use qualia::Position;
-use frames::Parameters;
+use qualia::{Position, SurfaceId};
+use frames::{Frame, Parameters};
 use frames::Geometry::{Horizontal, Stacked, Vertical};
 use frames::representation::FrameRepresentation;
 use common::{assertions, layouts};
@@ -168,6 +168,65 @@ fn test_prejoin_adjoin() {
 
 // -------------------------------------------------------------------------------------------------
 
+/// Checks if replacing frames at the begin, center and end keeps both spaced and timed order.
+#[test]
+fn test_replace() {
+    let (r, _, _, _, mut v1, _, _, _, mut h2, _, _, _, mut s3) =
+        layouts::make_simple_frames_appending();
+
+    // Replace chosen frames with new ones.
+    let mut n1 = Frame::new_leaf(SurfaceId::new(41), Stacked);
+    let mut n2 = Frame::new_leaf(SurfaceId::new(42), Stacked);
+    let mut n3 = Frame::new_leaf(SurfaceId::new(43), Stacked);
+    v1.replace(&mut n1);
+    h2.replace(&mut n2);
+    s3.replace(&mut n3);
+    assert!(!v1.has_parent());
+    assert!(!h2.has_parent());
+    assert!(!s3.has_parent());
+    v1.destroy();
+    h2.destroy();
+    s3.destroy();
+
+    // Prepare representation.
+    let repr = FrameRepresentation::new(
+        Parameters::new_workspace(String::new(), Vertical, true),
+        vec![
+            FrameRepresentation::new(
+                Parameters::new_container(Vertical),
+                vec![
+                    FrameRepresentation::new_leaf(41, Stacked),
+                    FrameRepresentation::new_leaf(12, Stacked),
+                    FrameRepresentation::new_leaf(13, Stacked),
+                ]
+            ),
+            FrameRepresentation::new(
+                Parameters::new_container(Horizontal),
+                vec![
+                    FrameRepresentation::new_leaf(21, Stacked),
+                    FrameRepresentation::new_leaf(42, Stacked),
+                    FrameRepresentation::new_leaf(23, Stacked),
+                ]
+            ),
+            FrameRepresentation::new(
+                Parameters::new_container(Stacked),
+                vec![
+                    FrameRepresentation::new_leaf(31, Stacked),
+                    FrameRepresentation::new_leaf(32, Stacked),
+                    FrameRepresentation::new_leaf(43, Stacked),
+                ]
+            ),
+        ]
+    );
+
+    repr.assert_frames_timed(&r);
+    repr.assert_frames_spaced(&r);
+
+    r.destroy();
+}
+
+// -------------------------------------------------------------------------------------------------
+
 /// Tests forward iteration in time.
 #[test]
 fn test_iteration_forward_in_time() {
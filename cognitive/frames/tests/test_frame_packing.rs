@@ -12,8 +12,10 @@ extern crate cognitive_frames as frames;
 
 mod common;
 
-use qualia::{Position, Size};
-use frames::Packing;
+use qualia::{Position, Size, SurfaceId};
+use qualia::Direction::{East, South};
+use frames::{Frame, Layout, LayoutKind, Packing, Settling, Spacing};
+use frames::Geometry::{Stacked, Vertical};
 use common::{assertions, layouts, surface_access_mock};
 
 // -------------------------------------------------------------------------------------------------
@@ -89,6 +91,279 @@ fn test_homogenizing_horizontal_with_docked() {
 
 // -------------------------------------------------------------------------------------------------
 
+/// Test if relaxing preserves proportions set by resizing and if homogenizing resets them.
+#[test]
+fn test_relaxing_with_ratios() {
+    let mut sa = surface_access_mock::SurfaceAccessMock::new();
+    let (r, mut v, _, _, mut v1, v2, v3, _, _, _, _, _, _) =
+        layouts::make_simple_frames_appending();
+
+    // Resize and check if proportions were remembered
+    v1.resize(South, 5, &mut sa);
+    assertions::assert_area(&v1, Position::new(0,  0), Size::new(30, 15));
+    assertions::assert_area(&v2, Position::new(0, 15), Size::new(30,  5));
+    assertions::assert_area(&v3, Position::new(0, 20), Size::new(30, 10));
+    assert_eq!(v1.get_ratio(), 1.5);
+    assert_eq!(v2.get_ratio(), 0.5);
+    assert_eq!(v3.get_ratio(), 1.0);
+
+    // Enlarge parent
+    v.set_size(Size::new(30, 60), &mut sa);
+    assertions::assert_area(&v1, Position::new(0,  0), Size::new(30, 30));
+    assertions::assert_area(&v2, Position::new(0, 30), Size::new(30, 10));
+    assertions::assert_area(&v3, Position::new(0, 40), Size::new(30, 20));
+
+    // Add new frame
+    let mut v4 = Frame::new_leaf(SurfaceId::new(14), Stacked);
+    v.set_plumbing_size(Size::new(30, 40));
+    v.append(&mut v4);
+    v.relax(&mut sa);
+    assertions::assert_area(&v1, Position::new(0,  0), Size::new(30, 15));
+    assertions::assert_area(&v2, Position::new(0, 15), Size::new(30,  5));
+    assertions::assert_area(&v3, Position::new(0, 20), Size::new(30, 10));
+    assertions::assert_area(&v4, Position::new(0, 30), Size::new(30, 10));
+
+    // Equalize
+    v.homogenize(&mut sa);
+    assertions::assert_area(&v1, Position::new(0,  0), Size::new(30, 10));
+    assertions::assert_area(&v2, Position::new(0, 10), Size::new(30, 10));
+    assertions::assert_area(&v3, Position::new(0, 20), Size::new(30, 10));
+    assertions::assert_area(&v4, Position::new(0, 30), Size::new(30, 10));
+
+    r.destroy();
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Test if gaps are taken into account while repacking workspace.
+#[test]
+fn test_repacking_with_gaps() {
+    let mut sa = surface_access_mock::SurfaceAccessMock::new();
+    let (mut r, v, h, s, v1, v2, v3, h1, h2, h3, s1, s2, s3) =
+        layouts::make_simple_frames_appending();
+
+    r.set_plumbing_size(Size::new(40, 76));
+    r.set_plumbing_spacing(Spacing {
+        inner_gap: 2,
+        outer_gap: 3,
+        border: 1,
+        smart_gaps: false,
+        enabled: true,
+    });
+    r.repack(&mut sa);
+
+    assertions::assert_area(&v,  Position::new( 3,  3), Size::new(34, 22));
+    assertions::assert_area(&h,  Position::new( 3, 27), Size::new(34, 22));
+    assertions::assert_area(&s,  Position::new( 3, 51), Size::new(34, 22));
+    assertions::assert_area(&v1, Position::new( 0,  0), Size::new(34,  6));
+    assertions::assert_area(&v2, Position::new( 0,  8), Size::new(34,  6));
+    assertions::assert_area(&v3, Position::new( 0, 16), Size::new(34,  6));
+    assertions::assert_area(&h1, Position::new( 0,  0), Size::new(10, 22));
+    assertions::assert_area(&h2, Position::new(12,  0), Size::new(10, 22));
+    assertions::assert_area(&h3, Position::new(24,  0), Size::new(10, 22));
+    assertions::assert_area(&s1, Position::new( 0,  0), Size::new(34, 22));
+    assertions::assert_area(&s2, Position::new( 0,  0), Size::new(34, 22));
+    assertions::assert_area(&s3, Position::new( 0,  0), Size::new(34, 22));
+
+    r.destroy();
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Test if smart gaps are applied only when workspace contains more than one anchored frame and
+/// if disabling spacing removes gaps.
+#[test]
+fn test_repacking_with_smart_gaps() {
+    let mut sa = surface_access_mock::SurfaceAccessMock::new();
+    let mut r = Frame::new_workspace(String::new(), Vertical, true);
+    let mut l1 = Frame::new_leaf(SurfaceId::new(1), Stacked);
+    let mut l2 = Frame::new_leaf(SurfaceId::new(2), Stacked);
+    let mut spacing = Spacing {
+        inner_gap: 2,
+        outer_gap: 3,
+        border: 0,
+        smart_gaps: true,
+        enabled: true,
+    };
+
+    r.set_plumbing_position_and_size(Position::new(0, 0), Size::new(40, 40));
+    r.set_plumbing_spacing(spacing);
+    r.append(&mut l1);
+    r.repack(&mut sa);
+    assertions::assert_area(&l1, Position::new(0, 0), Size::new(40, 40));
+
+    r.append(&mut l2);
+    r.repack(&mut sa);
+    assertions::assert_area(&l1, Position::new(3,  3), Size::new(34, 16));
+    assertions::assert_area(&l2, Position::new(3, 21), Size::new(34, 16));
+
+    spacing.enabled = false;
+    r.set_plumbing_spacing(spacing);
+    r.repack(&mut sa);
+    assertions::assert_area(&l1, Position::new(0,  0), Size::new(40, 20));
+    assertions::assert_area(&l2, Position::new(0, 20), Size::new(40, 20));
+
+    r.destroy();
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Test if settling and removing frames refreshes smart gaps of workspace.
+#[test]
+fn test_refreshing_smart_gaps() {
+    let mut sa = surface_access_mock::SurfaceAccessMock::new();
+    let mut r = Frame::new_workspace(String::new(), Vertical, true);
+    let mut l1 = Frame::new_leaf(SurfaceId::new(1), Stacked);
+    let mut l2 = Frame::new_leaf(SurfaceId::new(2), Stacked);
+
+    r.set_plumbing_position_and_size(Position::new(0, 0), Size::new(40, 40));
+    r.set_plumbing_spacing(Spacing {
+        inner_gap: 2,
+        outer_gap: 3,
+        border: 0,
+        smart_gaps: true,
+        enabled: true,
+    });
+
+    l1.settle(&mut r, None, &mut sa);
+    assert_eq!(r.get_anchored_leaves(), 1);
+    assertions::assert_area(&l1, Position::new(0, 0), Size::new(40, 40));
+
+    l2.settle(&mut r, None, &mut sa);
+    assert_eq!(r.get_anchored_leaves(), 2);
+    assertions::assert_area(&l1, Position::new(3,  3), Size::new(34, 16));
+    assertions::assert_area(&l2, Position::new(3, 21), Size::new(34, 16));
+
+    l2.remove_self(&mut sa);
+    assert_eq!(r.get_anchored_leaves(), 1);
+    assertions::assert_area(&l1, Position::new(0, 0), Size::new(40, 40));
+
+    l2.destroy();
+    r.destroy();
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Test if automatic layouts place frames correctly.
+#[test]
+fn test_relaxing_with_automatic_layouts() {
+    let mut sa = surface_access_mock::SurfaceAccessMock::new();
+    let mut r = Frame::new_workspace(String::new(), Vertical, true);
+    let mut l1 = Frame::new_leaf(SurfaceId::new(1), Stacked);
+    let mut l2 = Frame::new_leaf(SurfaceId::new(2), Stacked);
+    let mut l3 = Frame::new_leaf(SurfaceId::new(3), Stacked);
+    let mut l4 = Frame::new_leaf(SurfaceId::new(4), Stacked);
+    r.set_plumbing_position_and_size(Position::new(0, 0), Size::new(100, 60));
+    r.append(&mut l1);
+    r.append(&mut l2);
+    r.append(&mut l3);
+    r.append(&mut l4);
+
+    let mut layout = Layout::manual();
+    layout.kind = LayoutKind::MasterStack;
+    r.set_plumbing_layout(layout);
+    r.relax(&mut sa);
+    assertions::assert_area(&l1, Position::new( 0,  0), Size::new(50, 60));
+    assertions::assert_area(&l2, Position::new(50,  0), Size::new(50, 20));
+    assertions::assert_area(&l3, Position::new(50, 20), Size::new(50, 20));
+    assertions::assert_area(&l4, Position::new(50, 40), Size::new(50, 20));
+
+    layout.master_count = 2;
+    r.set_plumbing_layout(layout);
+    r.relax(&mut sa);
+    assertions::assert_area(&l1, Position::new( 0,  0), Size::new(50, 30));
+    assertions::assert_area(&l2, Position::new( 0, 30), Size::new(50, 30));
+    assertions::assert_area(&l3, Position::new(50,  0), Size::new(50, 30));
+    assertions::assert_area(&l4, Position::new(50, 30), Size::new(50, 30));
+
+    layout.kind = LayoutKind::Grid;
+    r.set_plumbing_layout(layout);
+    r.relax(&mut sa);
+    assertions::assert_area(&l1, Position::new( 0,  0), Size::new(50, 30));
+    assertions::assert_area(&l2, Position::new(50,  0), Size::new(50, 30));
+    assertions::assert_area(&l3, Position::new( 0, 30), Size::new(50, 30));
+    assertions::assert_area(&l4, Position::new(50, 30), Size::new(50, 30));
+
+    layout.kind = LayoutKind::Spiral;
+    r.set_plumbing_layout(layout);
+    r.relax(&mut sa);
+    assertions::assert_area(&l1, Position::new( 0,  0), Size::new(50, 60));
+    assertions::assert_area(&l2, Position::new(50,  0), Size::new(50, 30));
+    assertions::assert_area(&l3, Position::new(75, 30), Size::new(25, 30));
+    assertions::assert_area(&l4, Position::new(50, 30), Size::new(25, 30));
+
+    layout.kind = LayoutKind::CenteredMaster;
+    layout.master_count = 1;
+    r.set_plumbing_layout(layout);
+    r.relax(&mut sa);
+    assertions::assert_area(&l1, Position::new(25,  0), Size::new(50, 60));
+    assertions::assert_area(&l2, Position::new(75,  0), Size::new(25, 30));
+    assertions::assert_area(&l3, Position::new( 0,  0), Size::new(25, 60));
+    assertions::assert_area(&l4, Position::new(75, 30), Size::new(25, 30));
+
+    r.destroy();
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Test if frames placed by automatic layouts are not resized.
+#[test]
+fn test_resizing_with_automatic_layout() {
+    let mut sa = surface_access_mock::SurfaceAccessMock::new();
+    let mut r = Frame::new_workspace(String::new(), Vertical, true);
+    let mut l1 = Frame::new_leaf(SurfaceId::new(1), Stacked);
+    let mut l2 = Frame::new_leaf(SurfaceId::new(2), Stacked);
+    r.set_plumbing_position_and_size(Position::new(0, 0), Size::new(100, 60));
+    r.append(&mut l1);
+    r.append(&mut l2);
+
+    let mut layout = Layout::manual();
+    layout.kind = LayoutKind::MasterStack;
+    r.set_plumbing_layout(layout);
+    r.relax(&mut sa);
+
+    l1.resize(East, 10, &mut sa);
+    assertions::assert_area(&l1, Position::new( 0,  0), Size::new(50, 60));
+    assertions::assert_area(&l2, Position::new(50,  0), Size::new(50, 60));
+
+    r.destroy();
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Test if gaps are used by automatic layouts.
+#[test]
+fn test_relaxing_with_automatic_layout_and_gaps() {
+    let mut sa = surface_access_mock::SurfaceAccessMock::new();
+    let mut r = Frame::new_workspace(String::new(), Vertical, true);
+    let mut l1 = Frame::new_leaf(SurfaceId::new(1), Stacked);
+    let mut l2 = Frame::new_leaf(SurfaceId::new(2), Stacked);
+    let mut l3 = Frame::new_leaf(SurfaceId::new(3), Stacked);
+    r.set_plumbing_position_and_size(Position::new(0, 0), Size::new(108, 68));
+    r.set_plumbing_spacing(Spacing {
+        inner_gap: 4,
+        outer_gap: 2,
+        border: 0,
+        smart_gaps: false,
+        enabled: true,
+    });
+    r.append(&mut l1);
+    r.append(&mut l2);
+    r.append(&mut l3);
+
+    let mut layout = Layout::manual();
+    layout.kind = LayoutKind::MasterStack;
+    r.set_plumbing_layout(layout);
+    r.relax(&mut sa);
+    assertions::assert_area(&l1, Position::new( 2,  2), Size::new(50, 64));
+    assertions::assert_area(&l2, Position::new(56,  2), Size::new(50, 30));
+    assertions::assert_area(&l3, Position::new(56, 36), Size::new(50, 30));
+
+    r.destroy();
+}
+
+// -------------------------------------------------------------------------------------------------
+
 /// Test if removing frame with siblings works correctly.
 #[test]
 fn test_removing_self_with_siblings() {
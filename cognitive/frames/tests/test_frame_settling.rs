@@ -527,6 +527,77 @@ fn should_jump_before_on_the_same_level() {
 
 // -------------------------------------------------------------------------------------------------
 
+/// Check if frames in different containers are correctly swapped. Frames should exchange places
+/// and sizes.
+#[test]
+fn test_swapping_across_containers() {
+    let mut sa = surface_access_mock::SurfaceAccessMock::new();
+    let (r, v, h, _, mut v1, _, _, _, _, mut h3, _, _, _) =
+        layouts::make_simple_frames_appending();
+
+    v1.swap(&mut h3, &mut sa);
+
+    assertions::assert_frame_equal_exact(&v1.get_parent().unwrap(), &h);
+    assertions::assert_frame_equal_exact(&h3.get_parent().unwrap(), &v);
+    assertions::assert_area(&v1, Position::new(20, 0), Size::new(10, 30));
+    assertions::assert_area(&h3, Position::new(0, 0), Size::new(30, 10));
+
+    let spaced_repr = FrameRepresentation::new(
+        Parameters::new_workspace(String::new(), Vertical, true),
+        vec![
+            FrameRepresentation::new(
+                Parameters::new_container(Vertical),
+                vec![
+                    FrameRepresentation::new_leaf(23, Stacked),
+                    FrameRepresentation::new_leaf(12, Stacked),
+                    FrameRepresentation::new_leaf(13, Stacked),
+                ]
+            ),
+            FrameRepresentation::new(
+                Parameters::new_container(Horizontal),
+                vec![
+                    FrameRepresentation::new_leaf(21, Stacked),
+                    FrameRepresentation::new_leaf(22, Stacked),
+                    FrameRepresentation::new_leaf(11, Stacked),
+                ]
+            ),
+            FrameRepresentation::new(
+                Parameters::new_container(Stacked),
+                vec![
+                    FrameRepresentation::new_leaf(31, Stacked),
+                    FrameRepresentation::new_leaf(32, Stacked),
+                    FrameRepresentation::new_leaf(33, Stacked),
+                ]
+            ),
+        ]
+    );
+
+    spaced_repr.assert_frames_spaced(&r);
+
+    r.destroy();
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if swapping frame with its own parent is ignored.
+#[test]
+fn test_swapping_with_parent() {
+    let mut sa = surface_access_mock::SurfaceAccessMock::new();
+    let (r, mut v, _, _, mut v1, _, _, _, _, _, _, _, _) =
+        layouts::make_simple_frames_appending();
+
+    v1.swap(&mut v, &mut sa);
+    v.swap(&mut v1, &mut sa);
+
+    assertions::assert_frame_equal_exact(&v1.get_parent().unwrap(), &v);
+    assertions::assert_frame_equal_exact(&v.get_parent().unwrap(), &r);
+    assertions::assert_simple_frames_spaced(&r);
+
+    r.destroy();
+}
+
+// -------------------------------------------------------------------------------------------------
+
 /// Tests resizing floating frame.
 #[test]
 fn test_resizing_floating() {
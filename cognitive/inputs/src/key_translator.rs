@@ -0,0 +1,111 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! This module contains functionality allowing to translate key codes between key maps.
+
+// -------------------------------------------------------------------------------------------------
+
+use xkbcommon::xkb;
+
+use qualia::InputCode;
+
+// -------------------------------------------------------------------------------------------------
+
+/// Number of shift levels searched for symbols in target key map. Levels above the fourth one are
+/// not reachable with `Shift` and `Mod5`.
+const NUM_LEVELS: u32 = 4;
+
+/// Mask of `Shift` modifier selecting second shift level.
+const SHIFT_MASK: u32 = 0x01;
+
+/// Mask of `Mod5` modifier (`ISO_Level3_Shift`) selecting third shift level.
+const LEVEL3_MASK: u32 = 0x80;
+
+// -------------------------------------------------------------------------------------------------
+
+/// Result of key translation: key code in target key map and modifiers which must be active for
+/// the key to produce the same symbol as in source key map.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TranslatedKey {
+    pub code: InputCode,
+    pub mods: u32,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Translates key codes from one key map to the other by matching symbols they produce.
+pub struct KeyTranslator {
+    source_state: xkb::State,
+    target_keymap: xkb::Keymap,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl KeyTranslator {
+    /// Constructs new `KeyTranslator` from textual representations of source and target key map.
+    pub fn new(source_keymap: String, target_keymap: String) -> Option<Self> {
+        let context = xkb::Context::new(xkb::CONTEXT_NO_FLAGS);
+        let source = xkb::Keymap::new_from_string(&context,
+                                                  source_keymap,
+                                                  xkb::KEYMAP_FORMAT_TEXT_V1,
+                                                  xkb::KEYMAP_COMPILE_NO_FLAGS);
+        let target = xkb::Keymap::new_from_string(&context,
+                                                  target_keymap,
+                                                  xkb::KEYMAP_FORMAT_TEXT_V1,
+                                                  xkb::KEYMAP_COMPILE_NO_FLAGS);
+        if let (Some(source), Some(target)) = (source, target) {
+            Some(KeyTranslator {
+                     source_state: xkb::State::new(&source),
+                     target_keymap: target,
+                 })
+        } else {
+            None
+        }
+    }
+
+    /// Sets state of modifiers and group used to interpret keys from source key map.
+    pub fn set_modifiers(&mut self, depressed: u32, latched: u32, locked: u32, group: u32) {
+        self.source_state.update_mask(depressed, latched, locked, 0, 0, group);
+    }
+
+    /// Translates key code from source key map to key code from target key map producing the same
+    /// symbol. Keys with lower shift levels are preferred. Returns `None` if target key map does
+    /// not contain the symbol.
+    pub fn translate(&self, code: InputCode) -> Option<TranslatedKey> {
+        // Offset the key code by 8, as the evdev XKB rules reflect X's
+        // broken key code system, which starts at 8.
+        let sym = self.source_state.key_get_one_sym(code as u32 + 8);
+        if sym == xkb::keysyms::KEY_NoSymbol {
+            return None;
+        }
+
+        let min_keycode = self.target_keymap.min_keycode();
+        let max_keycode = self.target_keymap.max_keycode();
+        for level in 0..NUM_LEVELS {
+            for keycode in min_keycode..(max_keycode + 1) {
+                let syms = self.target_keymap.key_get_syms_by_level(keycode, 0, level);
+                if syms.len() == 1 && syms[0] == sym && keycode >= 8 {
+                    return Some(TranslatedKey {
+                                    code: (keycode - 8) as InputCode,
+                                    mods: Self::get_level_mods(level),
+                                });
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns modifiers selecting given shift level assuming standard key types.
+    fn get_level_mods(level: u32) -> u32 {
+        let mut mods = 0;
+        if (level & 0x1) != 0 {
+            mods |= SHIFT_MASK;
+        }
+        if (level & 0x2) != 0 {
+            mods |= LEVEL3_MASK;
+        }
+        mods
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
@@ -13,3 +13,6 @@ pub use keyboard_state::{KeyboardState, KeyMods};
 
 pub mod keymap;
 pub use keymap::Keymap;
+
+pub mod key_translator;
+pub use key_translator::{KeyTranslator, TranslatedKey};
@@ -128,10 +128,11 @@ impl Output for DrmOutput {
     fn draw(&mut self,
             layunder: &Vec<SurfaceContext>,
             surfaces: &Vec<SurfaceContext>,
+            highlights: &Vec<Area>,
             layover: &Vec<SurfaceContext>,
             viewer: &SurfaceViewer)
             -> Result<(), Illusion> {
-        self.renderer.draw(layunder, surfaces, layover, viewer)
+        self.renderer.draw(layunder, surfaces, highlights, layover, viewer)
     }
 
     /// Takes screenshot. Returns `Buffer` containing image data.
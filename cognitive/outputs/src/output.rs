@@ -3,16 +3,18 @@
 
 //! This module contains interface for all output devices or mocks.
 
-use qualia::{Buffer, Illusion, OutputInfo, Position, SurfaceContext, SurfaceViewer};
+use qualia::{Area, Buffer, Illusion, OutputInfo, Position, SurfaceContext, SurfaceViewer};
 
 // -------------------------------------------------------------------------------------------------
 
 /// `Output` is representation of physical output device.
 pub trait Output {
-    /// Draws passed scene using renderer.
+    /// Draws passed scene using renderer. Outlines of `highlights` areas are drawn above
+    /// surfaces.
     fn draw(&mut self,
             layunder: &Vec<SurfaceContext>,
             surfaces: &Vec<SurfaceContext>,
+            highlights: &Vec<Area>,
             layover: &Vec<SurfaceContext>,
             viewer: &SurfaceViewer)
             -> Result<(), Illusion>;
@@ -6,7 +6,7 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 
-use qualia::{Buffer, Illusion, OutputInfo, Position, SurfaceContext, SurfaceViewer};
+use qualia::{Area, Buffer, Illusion, OutputInfo, Position, SurfaceContext, SurfaceViewer};
 
 use output::Output;
 
@@ -15,13 +15,17 @@ use output::Output;
 /// Mock of `Output`.
 pub struct InnerOutputMock {
     info: OutputInfo,
+    highlights: Vec<Area>,
 }
 
 // -------------------------------------------------------------------------------------------------
 
 impl InnerOutputMock {
     pub fn new(info: OutputInfo) -> Self {
-        InnerOutputMock { info: info }
+        InnerOutputMock {
+            info: info,
+            highlights: Vec::new(),
+        }
     }
 }
 
@@ -39,6 +43,12 @@ impl OutputMock {
     pub fn new(info: OutputInfo) -> Self {
         OutputMock { mock: Rc::new(RefCell::new(InnerOutputMock::new(info))) }
     }
+
+    /// Returns highlighted areas passed in last call to `draw`.
+    pub fn get_highlights(&self) -> Vec<Area> {
+        let mine = self.mock.borrow();
+        mine.highlights.clone()
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -48,9 +58,12 @@ impl Output for OutputMock {
     fn draw(&mut self,
             layunder: &Vec<SurfaceContext>,
             surfaces: &Vec<SurfaceContext>,
+            highlights: &Vec<Area>,
             layover: &Vec<SurfaceContext>,
             viewer: &SurfaceViewer)
             -> Result<(), Illusion> {
+        let mut mine = self.mock.borrow_mut();
+        mine.highlights = highlights.clone();
         Ok(())
     }
 
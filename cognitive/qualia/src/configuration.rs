@@ -3,6 +3,7 @@
 
 //! Default configuration for `cognitive` entities.
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 // -------------------------------------------------------------------------------------------------
@@ -16,6 +17,63 @@ pub struct AestheticsConfig {
 
 // -------------------------------------------------------------------------------------------------
 
+/// Configuration of gaps and borders.
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct SpacingConfig {
+    /// Gap in pixels between sibling frames.
+    pub inner_gap: u32,
+
+    /// Gap in pixels between frames and edges of workspace.
+    pub outer_gap: u32,
+
+    /// Width in pixels of border reserved around each anchored leaf frame.
+    pub border: u32,
+
+    /// If `true` gaps disappear when workspace contains only one anchored frame.
+    pub smart_gaps: bool,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Configuration of workspace.
+#[derive(Clone, Debug, Serialize)]
+pub struct WorkspaceConfig {
+    /// Geometry of newly created workspace and of containers created by ramifying frames.
+    pub geometry: String,
+
+    /// Name of strategy deciding how to handle new surfaces. Empty string stands for strategy
+    /// configured for strategist.
+    pub choose_target: String,
+
+    /// Name of strategy deciding where to place floating surfaces. Empty string stands for
+    /// strategy configured for strategist.
+    pub choose_floating: String,
+
+    /// Gaps and borders.
+    pub spacing: SpacingConfig,
+
+    /// Name of automatic layout.
+    pub layout: String,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Configuration of keyboard focus and its relation to the pointer.
+#[derive(Clone, Debug, Serialize)]
+pub struct FocusConfig {
+    /// Name of focus policy: `click`, `follow` or `sloppy`.
+    pub policy: String,
+
+    /// Time in milliseconds the pointer has to stay over surface before it gets focused when
+    /// focus follows the pointer.
+    pub delay: u32,
+
+    /// If `true` the pointer is placed at the center of frame selected by command.
+    pub warp_pointer: bool,
+}
+
+// -------------------------------------------------------------------------------------------------
+
 /// Configuration of compositor.
 #[derive(Clone, Debug, Serialize)]
 pub struct CompositorConfig {
@@ -24,6 +82,24 @@ pub struct CompositorConfig {
 
     /// Distance in pixels by which frames are resized by `resize` command.
     pub resize_step: u32,
+
+    /// Keyboard focus policy.
+    pub focus: FocusConfig,
+
+    /// Default configuration of workspaces.
+    pub workspace: WorkspaceConfig,
+
+    /// Configuration of workspaces with given titles.
+    pub workspaces: HashMap<String, WorkspaceConfig>,
+
+    /// Names of outputs on which workspaces with given titles should be placed.
+    pub workspace_outputs: HashMap<String, String>,
+
+    /// Directory where saved frame layouts of workspaces are kept.
+    pub layouts_path: Option<PathBuf>,
+
+    /// File where marks of frames are listed for other applications.
+    pub marks_path: Option<PathBuf>,
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -74,6 +150,66 @@ pub struct StrategistConfig {
 
     /// Strategy used to decide position and size of floating surface (new or deanchorized).
     pub choose_floating: String,
+
+    /// Rules overriding strategies for surfaces matching given criteria.
+    pub rules: Vec<RuleConfig>,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Configuration of window rule. Rule applies to new surface if all given criteria are met. Fields
+/// set to `None` are not taken into account.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct RuleConfig {
+    /// Application ID (or class) the surface must have.
+    pub app_id: Option<String>,
+
+    /// Regular expression the title of the surface must match.
+    pub title: Option<String>,
+
+    /// If `true` rule applies only to popups, if `false` only to toplevel surfaces.
+    pub popup: Option<bool>,
+
+    /// Title of workspace the surface should be placed in.
+    pub workspace: Option<String>,
+
+    /// Name of output on which active workspace the surface should be placed.
+    pub output: Option<String>,
+
+    /// If `true` the surface will be floating, if `false` anchored.
+    pub floating: Option<bool>,
+
+    /// Initial width of floating surface.
+    pub width: Option<u32>,
+
+    /// Initial height of floating surface.
+    pub height: Option<u32>,
+
+    /// Initial horizontal position of floating surface relative to workspace.
+    pub x: Option<i32>,
+
+    /// Initial vertical position of floating surface relative to workspace.
+    pub y: Option<i32>,
+
+    /// Geometry (`vertical`, `horizontal` or `stacked`) of container enclosing anchored surface.
+    pub geometry: Option<String>,
+
+    /// If `true` the surface will not be focused when mapped.
+    pub no_focus: bool,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Configuration of Wayland frontend.
+#[derive(Clone, Debug, Serialize)]
+pub struct WaylandConfig {
+    /// Absolute paths of executables (as resolved from `/proc/<pid>/exe`) of processes allowed to
+    /// use virtual keyboard and virtual pointer protocols. Entry `*` allows all clients.
+    pub virtual_input_clients: Vec<String>,
+
+    /// If `true` X11 socket is created on start up and `Xwayland` is spawned when the first X11
+    /// client connects.
+    pub xwayland: bool,
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -10,6 +10,7 @@ use std::rc::Rc;
 use defs::{DrmBundle, Position, SignalId, Size, Vector, WorkspaceState};
 use defs::{DmabufId, EglImageId, MemoryPoolId, MemoryViewId};
 use surface::{DataSource, SurfaceContext, SurfaceId, SurfaceInfo, surface_state, show_reason};
+use surface::Viewport;
 use surface::{SurfaceManagement, SurfaceControl, SurfaceViewer};
 use surface::{SurfaceAccess, SurfaceListing, SurfaceFocusing};
 use memory::{Buffer, Memory};
@@ -23,13 +24,17 @@ use traits::ExhibitorCoordinationTrait;
 /// Mock of `Coordinator`.
 pub struct InnerCoordinatorMock {
     surfaces: HashMap<SurfaceId, SurfaceInfo>,
+    closed_sids: Vec<SurfaceId>,
 }
 
 // -------------------------------------------------------------------------------------------------
 
 impl InnerCoordinatorMock {
     pub fn new() -> Self {
-        InnerCoordinatorMock { surfaces: HashMap::new() }
+        InnerCoordinatorMock {
+            surfaces: HashMap::new(),
+            closed_sids: Vec::new(),
+        }
     }
 
     pub fn add_surface(&mut self, sid: SurfaceId) {
@@ -41,10 +46,31 @@ impl InnerCoordinatorMock {
             requested_size: Size::default(),
             state_flags: surface_state::REGULAR,
             data_source: DataSource::None,
+            viewport: Viewport::new(),
+            title: String::new(),
+            app_id: String::new(),
         };
 
         self.surfaces.insert(sid, info);
     }
+
+    pub fn set_surface_title(&mut self, sid: SurfaceId, title: String) {
+        if !self.surfaces.contains_key(&sid) {
+            self.add_surface(sid);
+        }
+        if let Some(info) = self.surfaces.get_mut(&sid) {
+            info.title = title;
+        }
+    }
+
+    pub fn set_surface_app_id(&mut self, sid: SurfaceId, app_id: String) {
+        if !self.surfaces.contains_key(&sid) {
+            self.add_surface(sid);
+        }
+        if let Some(info) = self.surfaces.get_mut(&sid) {
+            info.app_id = app_id;
+        }
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -66,6 +92,12 @@ impl CoordinatorMock {
         let mut mock = self.mock.borrow_mut();
         mock.add_surface(sid);
     }
+
+    /// Returns IDs of surfaces which were requested to be closed.
+    pub fn get_closed_sids(&self) -> Vec<SurfaceId> {
+        let mock = self.mock.borrow();
+        mock.closed_sids.clone()
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -90,6 +122,18 @@ impl SurfaceControl for CoordinatorMock {
     fn hide_surface(&self, _sid: SurfaceId, _reason: show_reason::ShowReason) {}
     fn set_surface_offset(&self, _sid: SurfaceId, _offset: Vector) {}
     fn set_surface_requested_size(&self, _sid: SurfaceId, _size: Size) {}
+    fn set_surface_title(&self, sid: SurfaceId, title: String) {
+        self.mock.borrow_mut().set_surface_title(sid, title);
+    }
+    fn set_surface_app_id(&self, sid: SurfaceId, app_id: String) {
+        self.mock.borrow_mut().set_surface_app_id(sid, app_id);
+    }
+    fn request_surface_state(&self,
+                             _sid: SurfaceId,
+                             _state: surface_state::SurfaceState,
+                             _enable: bool) {
+    }
+    fn set_surface_viewport(&self, _sid: SurfaceId, _viewport: Viewport) {}
     fn set_surface_relative_position(&self, _sid: SurfaceId, _offset: Vector) {}
     fn relate_surfaces(&self, _sid: SurfaceId, _parent_sid: SurfaceId) {}
     fn unrelate_surface(&self, _sid: SurfaceId) {}
@@ -113,6 +157,9 @@ impl SurfaceViewer for CoordinatorMock {
                      requested_size: Size::default(),
                      state_flags: surface_state::REGULAR,
                      data_source: DataSource::None,
+                     viewport: Viewport::new(),
+                     title: String::new(),
+                     app_id: String::new(),
                  })
         }
     }
@@ -132,6 +179,11 @@ impl SurfaceAccess for CoordinatorMock {
             info.state_flags = state_flags;
         }
     }
+
+    fn close(&mut self, sid: SurfaceId) {
+        let mut mock = self.mock.borrow_mut();
+        mock.closed_sids.push(sid);
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -460,6 +460,9 @@ pub struct WorkspaceInfo {
 
     /// State of the workspace.
     pub is_active: bool,
+
+    /// Tells if any surface in the workspace demands attention.
+    pub is_urgent: bool,
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -470,6 +473,7 @@ impl WorkspaceInfo {
         WorkspaceInfo {
             name: name,
             is_active: is_active,
+            is_urgent: false,
         }
     }
 }
@@ -6,6 +6,7 @@
 // -------------------------------------------------------------------------------------------------
 
 use std::fmt;
+use std::str::FromStr;
 
 // -------------------------------------------------------------------------------------------------
 
@@ -71,6 +72,127 @@ pub enum Action {
 
     /// Change size.
     Resize,
+
+    /// Enables or disables gaps and borders.
+    Spacing,
+
+    /// Change automatic layout; number of master frames; master ratio.
+    Layout,
+
+    /// Save frame layout of workspace.
+    Save,
+
+    /// Restore frame layout of workspace.
+    Restore,
+
+    /// Enter; leave fullscreen.
+    Fullscreen,
+
+    /// Maximize; unmaximize.
+    Maximize,
+
+    /// Hide in scratchpad.
+    Hide,
+
+    /// Show; hide back scratchpad frames.
+    Scratchpad,
+
+    /// Attach named mark to frame.
+    Mark,
+
+    /// Undo last command modifying frame layout.
+    Undo,
+
+    /// Redo last undone command.
+    Redo,
+
+    /// Ask clients to close surfaces.
+    Close,
+
+    /// Rename current workspace.
+    Rename,
+
+    /// Move current workspace to other display or to other place among workspaces.
+    Relocate,
+
+    /// Change strategies of current workspace.
+    Strategy,
+
+    /// Make floating frame visible on every workspace; make it visible only on its workspace.
+    Stick,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Action {
+    /// Returns name of the action. It is the same as variant name and can be parsed back with
+    /// `from_str`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Action::None => "None",
+            Action::Anchor => "Anchor",
+            Action::Configure => "Configure",
+            Action::Focus => "Focus",
+            Action::Swap => "Swap",
+            Action::Move => "Move",
+            Action::Jump => "Jump",
+            Action::Dive => "Dive",
+            Action::Resize => "Resize",
+            Action::Spacing => "Spacing",
+            Action::Layout => "Layout",
+            Action::Save => "Save",
+            Action::Restore => "Restore",
+            Action::Fullscreen => "Fullscreen",
+            Action::Maximize => "Maximize",
+            Action::Hide => "Hide",
+            Action::Scratchpad => "Scratchpad",
+            Action::Mark => "Mark",
+            Action::Undo => "Undo",
+            Action::Redo => "Redo",
+            Action::Close => "Close",
+            Action::Rename => "Rename",
+            Action::Relocate => "Relocate",
+            Action::Strategy => "Strategy",
+            Action::Stick => "Stick",
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl FromStr for Action {
+    type Err = ();
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "None" => Ok(Action::None),
+            "Anchor" => Ok(Action::Anchor),
+            "Configure" => Ok(Action::Configure),
+            "Focus" => Ok(Action::Focus),
+            "Swap" => Ok(Action::Swap),
+            "Move" => Ok(Action::Move),
+            "Jump" => Ok(Action::Jump),
+            "Dive" => Ok(Action::Dive),
+            "Resize" => Ok(Action::Resize),
+            "Spacing" => Ok(Action::Spacing),
+            "Layout" => Ok(Action::Layout),
+            "Save" => Ok(Action::Save),
+            "Restore" => Ok(Action::Restore),
+            "Fullscreen" => Ok(Action::Fullscreen),
+            "Maximize" => Ok(Action::Maximize),
+            "Hide" => Ok(Action::Hide),
+            "Scratchpad" => Ok(Action::Scratchpad),
+            "Mark" => Ok(Action::Mark),
+            "Undo" => Ok(Action::Undo),
+            "Redo" => Ok(Action::Redo),
+            "Close" => Ok(Action::Close),
+            "Rename" => Ok(Action::Rename),
+            "Relocate" => Ok(Action::Relocate),
+            "Strategy" => Ok(Action::Strategy),
+            "Stick" => Ok(Action::Stick),
+            _ => Err(()),
+        }
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -110,6 +232,12 @@ pub enum Direction {
 
     /// Workspace.
     Workspace,
+
+    /// Frame with named mark.
+    Mark,
+
+    /// Frame demanding attention.
+    Urgent,
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -122,13 +250,15 @@ impl Direction {
             Direction::North => Direction::South,
             Direction::East => Direction::West,
             Direction::South => Direction::North,
-            Direction::West => Direction::South,
+            Direction::West => Direction::East,
             Direction::Backward => Direction::Forward,
             Direction::Forward => Direction::Backward,
             Direction::Begin => Direction::End,
             Direction::End => Direction::Begin,
             Direction::Up => Direction::Up,
             Direction::Workspace => Direction::Workspace,
+            Direction::Mark => Direction::Mark,
+            Direction::Urgent => Direction::Urgent,
         }
     }
 
@@ -143,12 +273,64 @@ impl Direction {
 
 // -------------------------------------------------------------------------------------------------
 
+impl Direction {
+    /// Returns name of the direction. It is the same as variant name and can be parsed back with
+    /// `from_str`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Direction::None => "None",
+            Direction::North => "North",
+            Direction::East => "East",
+            Direction::South => "South",
+            Direction::West => "West",
+            Direction::Backward => "Backward",
+            Direction::Forward => "Forward",
+            Direction::Begin => "Begin",
+            Direction::End => "End",
+            Direction::Up => "Up",
+            Direction::Workspace => "Workspace",
+            Direction::Mark => "Mark",
+            Direction::Urgent => "Urgent",
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl FromStr for Direction {
+    type Err = ();
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "None" => Ok(Direction::None),
+            "North" => Ok(Direction::North),
+            "East" => Ok(Direction::East),
+            "South" => Ok(Direction::South),
+            "West" => Ok(Direction::West),
+            "Backward" => Ok(Direction::Backward),
+            "Forward" => Ok(Direction::Forward),
+            "Begin" => Ok(Direction::Begin),
+            "End" => Ok(Direction::End),
+            "Up" => Ok(Direction::Up),
+            "Workspace" => Ok(Direction::Workspace),
+            "Mark" => Ok(Direction::Mark),
+            "Urgent" => Ok(Direction::Urgent),
+            _ => Err(()),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
 /// Defines different modes user may interact with application.
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum InteractionMode {
     Normal,
     Insert,
     Visual,
+    Drag,
+    Resize,
+    Overview,
 }
 
 // -------------------------------------------------------------------------------------------------
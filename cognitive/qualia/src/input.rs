@@ -6,7 +6,7 @@
 // -------------------------------------------------------------------------------------------------
 
 use timing;
-use defs::{Slide, Vector};
+use defs::{Position, Slide, Vector};
 
 // -------------------------------------------------------------------------------------------------
 
@@ -164,6 +164,9 @@ pub trait InputForwarding: Send {
     /// Emits exist event.
     fn emit_axis(&mut self, horizontal: isize, vertical: isize);
 
+    /// Emits pointer warp event (move to absolute position in global coordinates).
+    fn emit_warp(&mut self, x: isize, y: isize);
+
     /// Emits position reset event.
     fn emit_position_reset(&mut self);
 
@@ -172,3 +175,24 @@ pub trait InputForwarding: Send {
 }
 
 // -------------------------------------------------------------------------------------------------
+
+/// Input event generated by virtual input device (e.g. requested by automation client).
+#[derive(Clone, Copy, Debug)]
+pub enum VirtualInput {
+    /// Key event with evdev code.
+    Key { code: InputCode, value: InputValue },
+
+    /// Relative pointer motion.
+    Motion(Vector),
+
+    /// Absolute pointer position in global coordinates.
+    Warp(Position),
+
+    /// Pointer button event with evdev code.
+    Button { code: InputCode, value: InputValue },
+
+    /// Pointer axis event.
+    Axis(Vector),
+}
+
+// -------------------------------------------------------------------------------------------------
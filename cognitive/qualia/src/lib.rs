@@ -39,7 +39,7 @@ pub use defs::{DmabufId, EglImageId, MemoryPoolId, MemoryViewId, SignalId};
 
 pub mod input;
 pub use input::{Axis, Button, Binding, Key, CatchResult, InputCode, InputValue, modifier};
-pub use input::{InputForwarding, InputHandling};
+pub use input::{InputForwarding, InputHandling, VirtualInput};
 
 pub mod image;
 pub use image::{Image, Pixmap, PixelFormat};
@@ -48,11 +48,14 @@ pub mod memory;
 pub use memory::{Buffer, Memory, MemoryPool, MemoryView};
 
 pub mod configuration;
-pub use configuration::{AestheticsConfig, CompositorConfig, ExhibitorConfig};
-pub use configuration::{KeyboardConfig, InputConfig, StrategistConfig};
+pub use configuration::{AestheticsConfig, CompositorConfig, ExhibitorConfig, SpacingConfig};
+pub use configuration::{KeyboardConfig, InputConfig, RuleConfig, StrategistConfig};
+pub use configuration::{FocusConfig, WorkspaceConfig};
+pub use configuration::WaylandConfig;
 
 pub mod surface;
 pub use surface::{SurfaceContext, SurfaceId, SurfaceIdType, SurfaceInfo, DataSource};
+pub use surface::{SourceRect, Viewport};
 pub use surface::{SurfaceManagement, SurfaceControl, SurfaceViewer};
 pub use surface::{SurfaceAccess, SurfaceListing, SurfaceFocusing};
 pub use surface::{show_reason, surface_state};
@@ -66,7 +69,8 @@ pub use perceptron::Perceptron;
 pub mod traits;
 pub use traits::{AppearanceManagement, DataTransferring, EventHandling, StatePublishing};
 pub use traits::{Screenshooting, MemoryManagement, HwGraphics, WindowManagement};
-pub use traits::GraphicsManagement;
+pub use traits::{GraphicsManagement, InputInjecting, SessionLocking, ShortcutsInhibiting};
+pub use traits::SurfaceActivating;
 pub use traits::{AestheticsCoordinationTrait, ExhibitorCoordinationTrait};
 pub use traits::FrontendsCoordinationTrait;
 
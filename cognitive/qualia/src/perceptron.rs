@@ -16,7 +16,8 @@ use timing::Milliseconds;
 use enums::InteractionMode;
 use defs::{Command, OutputInfo, SurfaceId};
 use defs::{Position, OptionalPosition, Vector, Size, DrmBundle};
-use input::{Axis, Button, Key};
+use input::{Axis, Button, Key, VirtualInput};
+use surface::surface_state::SurfaceState;
 
 // -------------------------------------------------------------------------------------------------
 
@@ -35,12 +36,18 @@ pub const INPUT_POINTER_BUTTON: SignalId = 12;
 pub const INPUT_POINTER_AXIS: SignalId = 13;
 pub const INPUT_POINTER_POSITION_RESET: SignalId = 14;
 pub const INPUT_KEYBOARD: SignalId = 15;
+pub const INPUT_POINTER_WARP: SignalId = 16;
+pub const VIRTUAL_INPUT: SignalId = 17;
 pub const SURFACE_READY: SignalId = 20;
 pub const SURFACE_DESTROYED: SignalId = 21;
 pub const SURFACE_RECONFIGURED: SignalId = 22;
 pub const DOCK_SURFACE: SignalId = 23;
+pub const SURFACE_STATE_REQUESTED: SignalId = 24;
 pub const CURSOR_SURFACE_CHANGE: SignalId = 25;
 pub const BACKGROUND_SURFACE_CHANGE: SignalId = 26;
+pub const SURFACE_CLOSE_REQUESTED: SignalId = 27;
+pub const SURFACE_ACTIVATION_REQUESTED: SignalId = 28;
+pub const SURFACE_MOVED: SignalId = 29;
 pub const SURFACE_FRAME: SignalId = 30;
 pub const POINTER_FOCUS_CHANGED: SignalId = 31;
 pub const POINTER_RELATIVE_MOTION: SignalId = 32;
@@ -49,6 +56,12 @@ pub const TRANSFER_OFFERED: SignalId = 41;
 pub const TRANSFER_REQUESTED: SignalId = 42;
 pub const COMMAND: SignalId = 51;
 pub const MODE: SignalId = 52;
+pub const LOCK_SESSION: SignalId = 61;
+pub const UNLOCK_SESSION: SignalId = 62;
+pub const LOCK_SURFACE: SignalId = 63;
+pub const SESSION_LOCKED: SignalId = 64;
+pub const INHIBIT_SHORTCUTS: SignalId = 65;
+pub const SHORTCUTS_INHIBITION_ESCAPED: SignalId = 66;
 pub const TAKE_SCREENSHOT: SignalId = 101;
 pub const SCREENSHOT_DONE: SignalId = 102;
 pub const WORKSPACE_STATE_CHANGED: SignalId = 111;
@@ -80,12 +93,18 @@ pub enum Perceptron {
     InputPointerAxis(Axis),
     InputPointerPositionReset,
     InputKeyboard(Key),
+    InputPointerWarp(Position),
+    VirtualInput(VirtualInput),
     SurfaceReady(SurfaceId),
     SurfaceDestroyed(SurfaceId),
     SurfaceReconfigured(SurfaceId),
     DockSurface(SurfaceId, Size, i32),
+    SurfaceStateRequested(SurfaceId, SurfaceState, bool),
     CursorSurfaceChange(SurfaceId),
     BackgroundSurfaceChange(SurfaceId),
+    SurfaceCloseRequested(SurfaceId),
+    SurfaceActivationRequested(SurfaceId, bool),
+    SurfaceMoved(SurfaceId, Position),
     SurfaceFrame(SurfaceId, Milliseconds),
     PointerFocusChanged(SurfaceId, SurfaceId, Position),
     PointerRelativeMotion(SurfaceId, Position, Milliseconds),
@@ -94,6 +113,12 @@ pub enum Perceptron {
     TransferRequested(String, RawFd),
     Command(Command),
     Mode { active: bool, mode: InteractionMode },
+    LockSession,
+    UnlockSession,
+    LockSurface(SurfaceId, i32),
+    SessionLocked,
+    InhibitShortcuts(bool),
+    ShortcutsInhibitionEscaped,
     TakeScreenshot(i32),
     ScreenshotDone,
     WorkspaceStateChanged,
@@ -126,6 +151,8 @@ impl std::fmt::Debug for Perceptron {
             Perceptron::InputPointerAxis(ref axis) => write!(f, "InputPointerAxis({:?})", axis),
             Perceptron::InputPointerPositionReset => write!(f, "InputPointerPositionReset"),
             Perceptron::InputKeyboard(ref key) => write!(f, "InputKeyboard({:?})", key),
+            Perceptron::InputPointerWarp(ref pos) => write!(f, "InputPointerWarp({:?})", pos),
+            Perceptron::VirtualInput(ref event) => write!(f, "VirtualInput({:?})", event),
 
             Perceptron::SurfaceReady(ref sid) => write!(f, "SurfaceReady({})", sid),
             Perceptron::SurfaceDestroyed(ref sid) => write!(f, "SurfaceDestroyed({})", sid),
@@ -133,10 +160,22 @@ impl std::fmt::Debug for Perceptron {
             Perceptron::DockSurface(ref sid, ref size, display_id) => {
                 write!(f, "DockSurface({}, {:?}, {:?})", sid, size, display_id)
             }
+            Perceptron::SurfaceStateRequested(ref sid, ref state, enable) => {
+                write!(f, "SurfaceStateRequested({}, {:?}, {:?})", sid, state, enable)
+            }
             Perceptron::CursorSurfaceChange(ref sid) => write!(f, "CursorSurfaceChange({})", sid),
             Perceptron::BackgroundSurfaceChange(ref sid) => {
                 write!(f, "BackgroundSurfaceChange({})", sid)
             }
+            Perceptron::SurfaceCloseRequested(ref sid) => {
+                write!(f, "SurfaceCloseRequested({})", sid)
+            }
+            Perceptron::SurfaceActivationRequested(ref sid, granted) => {
+                write!(f, "SurfaceActivationRequested({}, {:?})", sid, granted)
+            }
+            Perceptron::SurfaceMoved(ref sid, ref pos) => {
+                write!(f, "SurfaceMoved({}, {:?})", sid, pos)
+            }
             Perceptron::SurfaceFrame(sid, time) => {
                 write!(f, "SurfaceFrame(sid: {}, milliseconds: {})", sid, time.get_value())
             }
@@ -157,6 +196,14 @@ impl std::fmt::Debug for Perceptron {
             Perceptron::Mode { active, mode } => {
                 write!(f, "Mode(active: {:?}, mode: {:?})", active, mode)
             }
+            Perceptron::LockSession => write!(f, "LockSession"),
+            Perceptron::UnlockSession => write!(f, "UnlockSession"),
+            Perceptron::LockSurface(ref sid, display_id) => {
+                write!(f, "LockSurface({}, {:?})", sid, display_id)
+            }
+            Perceptron::SessionLocked => write!(f, "SessionLocked"),
+            Perceptron::InhibitShortcuts(inhibit) => write!(f, "InhibitShortcuts({:?})", inhibit),
+            Perceptron::ShortcutsInhibitionEscaped => write!(f, "ShortcutsInhibitionEscaped"),
             Perceptron::TakeScreenshot(ref id) => write!(f, "TakeScreenshot({:?})", id),
             Perceptron::ScreenshotDone => write!(f, "ScreenshotDone"),
             Perceptron::WorkspaceStateChanged => write!(f, "WorkspaceStateChanged"),
@@ -5,6 +5,7 @@
 
 // -------------------------------------------------------------------------------------------------
 
+use std;
 use std::time::Instant;
 
 use graphics::attributes::{EglAttributes, DmabufAttributes};
@@ -18,10 +19,13 @@ pub use defs::{SurfaceId, SurfaceIdType};
 // -------------------------------------------------------------------------------------------------
 
 /// This structure defines how the surface should be drawn.
-#[derive(Clone, Debug, Eq, PartialEq)]
+///
+/// `scale` is applied to size of the surface. It is `1.0` unless surface is drawn as thumbnail.
+#[derive(Clone, Debug, PartialEq)]
 pub struct SurfaceContext {
     pub id: SurfaceId,
     pub pos: Position,
+    pub scale: f32,
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -29,12 +33,31 @@ pub struct SurfaceContext {
 impl SurfaceContext {
     /// `SurfaceContext` constructor.
     pub fn new(id: SurfaceId, pos: Position) -> Self {
-        SurfaceContext { id: id, pos: pos }
+        SurfaceContext {
+            id: id,
+            pos: pos,
+            scale: 1.0,
+        }
     }
 
     /// Creates new context with position moved by given vector.
     pub fn moved(&self, vector: Vector) -> Self {
-        SurfaceContext::new(self.id, self.pos + vector)
+        SurfaceContext {
+            id: self.id,
+            pos: self.pos + vector,
+            scale: self.scale,
+        }
+    }
+
+    /// Creates new context scaled by given factor. Position is scaled relatively to `origin`.
+    pub fn scaled(&self, origin: Position, scale: f32) -> Self {
+        let x = origin.x + ((self.pos.x - origin.x) as f32 * scale) as isize;
+        let y = origin.y + ((self.pos.y - origin.y) as f32 * scale) as isize;
+        SurfaceContext {
+            id: self.id,
+            pos: Position::new(x, y),
+            scale: self.scale * scale,
+        }
     }
 }
 
@@ -144,6 +167,96 @@ impl DataSource {
 
 // -------------------------------------------------------------------------------------------------
 
+/// Rectangle with continuous coordinates defining part of buffer to be presented.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SourceRect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl SourceRect {
+    /// `SourceRect` constructor.
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        SourceRect {
+            x: x,
+            y: y,
+            width: width,
+            height: height,
+        }
+    }
+
+    /// Checks if the rectangle lies within buffer of given size.
+    pub fn fits_in(&self, buffer_size: Size) -> bool {
+        ((self.x + self.width) <= buffer_size.width as f32) &&
+        ((self.y + self.height) <= buffer_size.height as f32)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Describes cropping and scaling of surface buffer.
+///
+/// Buffer is first cropped to `source` rectangle and then scaled to `destination` size. If
+/// `source` is not set whole buffer is used. If `destination` is not set size of `source` is used.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Viewport {
+    pub source: Option<SourceRect>,
+    pub destination: Option<Size>,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Viewport {
+    /// Constructs `Viewport` presenting whole buffer without scaling.
+    pub fn new() -> Self {
+        Viewport {
+            source: None,
+            destination: None,
+        }
+    }
+
+    /// Returns size of surface for buffer of given size.
+    pub fn get_surface_size(&self, buffer_size: Size) -> Size {
+        if let Some(destination) = self.destination {
+            destination
+        } else if let Some(ref source) = self.source {
+            Size::new(source.width as usize, source.height as usize)
+        } else {
+            buffer_size
+        }
+    }
+
+    /// Returns texture coordinates (left, top, right, bottom) of the part of buffer of given size
+    /// which should be presented.
+    pub fn get_texture_coordinates(&self, buffer_size: Size) -> (f32, f32, f32, f32) {
+        if let Some(ref source) = self.source {
+            if !buffer_size.is_zero() {
+                let width = buffer_size.width as f32;
+                let height = buffer_size.height as f32;
+                return (source.x / width,
+                        source.y / height,
+                        (source.x + source.width) / width,
+                        (source.y + source.height) / height);
+            }
+        }
+        (0.0, 0.0, 1.0, 1.0)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl std::default::Default for Viewport {
+    fn default() -> Self {
+        Viewport::new()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
 /// Structure containing public information about surface.
 #[derive(Clone)]
 pub struct SurfaceInfo {
@@ -154,6 +267,9 @@ pub struct SurfaceInfo {
     pub requested_size: Size,
     pub state_flags: surface_state::SurfaceState,
     pub data_source: DataSource,
+    pub viewport: Viewport,
+    pub title: String,
+    pub app_id: String,
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -201,6 +317,22 @@ pub trait SurfaceControl {
     /// Sets requested size for given surface.
     fn set_surface_requested_size(&self, sid: SurfaceId, size: Size);
 
+    /// Sets title of given surface.
+    fn set_surface_title(&self, sid: SurfaceId, title: String);
+
+    /// Sets application ID (or class) of given surface.
+    fn set_surface_app_id(&self, sid: SurfaceId, app_id: String);
+
+    /// Requests adding (`enable` is `true`) or removing given state (maximized or fullscreen) of
+    /// given surface.
+    fn request_surface_state(&self,
+                             sid: SurfaceId,
+                             state: surface_state::SurfaceState,
+                             enable: bool);
+
+    /// Sets pending cropping and scaling of surface buffer. It will be applied on next commit.
+    fn set_surface_viewport(&self, sid: SurfaceId, viewport: Viewport);
+
     /// Sets satellite surface position relative to its parent.
     fn set_surface_relative_position(&self, sid: SurfaceId, offset: Vector);
 
@@ -228,6 +360,9 @@ pub trait SurfaceAccess {
                    sid: SurfaceId,
                    size: Size,
                    state_flags: surface_state::SurfaceState);
+
+    /// Ask client to close the surface.
+    fn close(&mut self, sid: SurfaceId);
 }
 
 // -------------------------------------------------------------------------------------------------
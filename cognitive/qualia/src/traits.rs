@@ -13,6 +13,7 @@ use graphics::attributes::{EglAttributes, DmabufAttributes};
 use defs::{DrmBundle, WorkspaceState};
 use defs::{DmabufId, EglImageId, MemoryPoolId, MemoryViewId, SignalId, SurfaceId};
 use image::PixelFormat;
+use input::VirtualInput;
 use memory::{Buffer, Memory};
 use perceptron::Perceptron;
 use surface::{SurfaceManagement, SurfaceControl, SurfaceViewer};
@@ -95,6 +96,46 @@ pub trait StatePublishing {
 
 // -------------------------------------------------------------------------------------------------
 
+/// Injecting input events generated by virtual input devices.
+pub trait InputInjecting {
+    /// Injects given event to be handled as if it came from physical input device.
+    fn inject_input(&mut self, event: VirtualInput);
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Locking and unlocking the session.
+pub trait SessionLocking {
+    /// Requests locking the session. While locked only lock surfaces are shown and receive input.
+    fn lock_session(&mut self);
+
+    /// Requests unlocking the session.
+    fn unlock_session(&mut self);
+
+    /// Sets given surface as lock surface for display with given ID.
+    fn set_lock_surface(&mut self, sid: SurfaceId, display_id: i32);
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Inhibiting compositor key bindings on behalf of focused client.
+pub trait ShortcutsInhibiting {
+    /// Enables or disables passing all keys (except built-in ones and escape binding) to focused
+    /// client.
+    fn inhibit_shortcuts(&mut self, inhibit: bool);
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Requesting activation of surfaces on behalf of clients.
+pub trait SurfaceActivating {
+    /// Requests activation of given surface. If `granted` is `false` the surface is only marked as
+    /// demanding attention.
+    fn activate_surface(&mut self, sid: SurfaceId, granted: bool);
+}
+
+// -------------------------------------------------------------------------------------------------
+
 /// Managing memory pools and views.
 pub trait MemoryManagement {
     /// Creates new memory pool from mapped memory. Returns ID of newly created pool.
@@ -222,6 +263,10 @@ pub trait FrontendsCoordinationTrait: SurfaceManagement +
                                       DataTransferring +
                                       HwGraphics +
                                       Screenshooting +
-                                      MemoryManagement {}
+                                      MemoryManagement +
+                                      InputInjecting +
+                                      SessionLocking +
+                                      ShortcutsInhibiting +
+                                      SurfaceActivating {}
 
 // -------------------------------------------------------------------------------------------------
@@ -0,0 +1,65 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! Tests for surface related structures.
+
+// -------------------------------------------------------------------------------------------------
+
+extern crate cognitive_qualia;
+
+use cognitive_qualia::defs::Size;
+use cognitive_qualia::surface::{SourceRect, Viewport};
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if size of surface is correctly calculated from viewport and buffer size.
+#[test]
+fn should_calculate_surface_size_from_viewport() {
+    let buffer_size = Size::new(100, 50);
+    let source = SourceRect::new(10.0, 10.0, 40.0, 20.0);
+    let destination = Size::new(200, 100);
+
+    let mut viewport = Viewport::new();
+    assert_eq!(viewport.get_surface_size(buffer_size), buffer_size);
+
+    viewport.source = Some(source);
+    assert_eq!(viewport.get_surface_size(buffer_size), Size::new(40, 20));
+
+    viewport.destination = Some(destination);
+    assert_eq!(viewport.get_surface_size(buffer_size), destination);
+
+    viewport.source = None;
+    assert_eq!(viewport.get_surface_size(buffer_size), destination);
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if texture coordinates are correctly calculated from source rectangle.
+#[test]
+fn should_calculate_texture_coordinates_from_viewport() {
+    let buffer_size = Size::new(100, 50);
+
+    let mut viewport = Viewport::new();
+    assert_eq!(viewport.get_texture_coordinates(buffer_size), (0.0, 0.0, 1.0, 1.0));
+
+    viewport.destination = Some(Size::new(200, 100));
+    assert_eq!(viewport.get_texture_coordinates(buffer_size), (0.0, 0.0, 1.0, 1.0));
+
+    viewport.source = Some(SourceRect::new(25.0, 10.0, 50.0, 25.0));
+    assert_eq!(viewport.get_texture_coordinates(buffer_size), (0.25, 0.2, 0.75, 0.7));
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Check if source rectangle is correctly checked against buffer size.
+#[test]
+fn should_check_if_source_rectangle_fits_in_buffer() {
+    let buffer_size = Size::new(100, 50);
+
+    assert!(SourceRect::new(0.0, 0.0, 100.0, 50.0).fits_in(buffer_size));
+    assert!(SourceRect::new(25.5, 10.0, 50.0, 25.0).fits_in(buffer_size));
+    assert!(!SourceRect::new(50.0, 0.0, 50.5, 50.0).fits_in(buffer_size));
+    assert!(!SourceRect::new(0.0, 10.0, 100.0, 41.0).fits_in(buffer_size));
+}
+
+// -------------------------------------------------------------------------------------------------
@@ -13,7 +13,7 @@ use egl;
 
 use cognitive_graphics::{egl_tools, gl_tools};
 use cognitive_graphics::attributes::{DmabufAttributes, EglAttributes};
-use qualia::{SurfaceViewer, SurfaceContext, Illusion, Size, PixelFormat, SurfaceId};
+use qualia::{SurfaceViewer, SurfaceContext, Illusion, Area, Size, PixelFormat, SurfaceId};
 use qualia::{Buffer, DataSource, Image, MemoryView, Pixmap};
 
 use cache_gl::CacheGl;
@@ -32,6 +32,12 @@ const VERTEX_SHADER_300: &'static str = include_str!("vertex.300.glsl");
 /// Fragment shader source code for OpenGL ES 3.0 (GLSL ES 300)
 const FRAGMENT_SHADER_300: &'static str = include_str!("fragment.300.glsl");
 
+/// Width in pixels of outline drawn around highlighted areas.
+const HIGHLIGHT_WIDTH: usize = 3;
+
+/// Colour of outline drawn around highlighted areas.
+const HIGHLIGHT_COLOR: (f32, f32, f32, f32) = (0.9, 0.6, 0.1, 1.0);
+
 // -------------------------------------------------------------------------------------------------
 
 /// GL renderer.
@@ -120,6 +126,7 @@ impl RendererGl {
     pub fn draw(&mut self,
                 layunder: &Vec<SurfaceContext>,
                 surfaces: &Vec<SurfaceContext>,
+                highlights: &Vec<Area>,
                 layover: &Vec<SurfaceContext>,
                 viewer: &SurfaceViewer)
                 -> Result<(), Illusion> {
@@ -127,6 +134,7 @@ impl RendererGl {
         self.prepare_view();
         self.draw_surfaces(layunder, viewer);
         self.draw_surfaces(surfaces, viewer);
+        self.draw_highlights(highlights);
         self.draw_surfaces(layover, viewer);
         self.release_view();
         Ok(())
@@ -322,11 +330,20 @@ impl RendererGl {
                 }
             };
 
-            if let Some(size) = size {
-                let left = (context.pos.x - surface.offset.x) as gl::types::GLfloat;
-                let top = (context.pos.y - surface.offset.y) as gl::types::GLfloat;
-                let right = left + size.width as gl::types::GLfloat;
-                let bottom = top + size.height as gl::types::GLfloat;
+            if let Some(buffer_size) = size {
+                // Buffer may be cropped and scaled by client.
+                let size = surface.viewport.get_surface_size(buffer_size);
+                let (tex_left, tex_top, tex_right, tex_bottom) =
+                    surface.viewport.get_texture_coordinates(buffer_size);
+
+                // Surfaces shown as thumbnails are scaled down together with their offsets.
+                let scale = context.scale as gl::types::GLfloat;
+                let left = context.pos.x as gl::types::GLfloat -
+                           scale * surface.offset.x as gl::types::GLfloat;
+                let top = context.pos.y as gl::types::GLfloat -
+                          scale * surface.offset.y as gl::types::GLfloat;
+                let right = left + scale * size.width as gl::types::GLfloat;
+                let bottom = top + scale * size.height as gl::types::GLfloat;
 
                 vertices[0] = left;
                 vertices[1] = top;
@@ -342,18 +359,18 @@ impl RendererGl {
                 vertices[11] = bottom;
 
                 // TODO: Use element buffer.
-                texcoords[0] = 0.0;
-                texcoords[1] = 0.0;
-                texcoords[2] = 1.0;
-                texcoords[3] = 0.0;
-                texcoords[4] = 0.0;
-                texcoords[5] = 1.0;
-                texcoords[6] = 1.0;
-                texcoords[7] = 0.0;
-                texcoords[8] = 1.0;
-                texcoords[9] = 1.0;
-                texcoords[10] = 0.0;
-                texcoords[11] = 1.0;
+                texcoords[0] = tex_left;
+                texcoords[1] = tex_top;
+                texcoords[2] = tex_right;
+                texcoords[3] = tex_top;
+                texcoords[4] = tex_left;
+                texcoords[5] = tex_bottom;
+                texcoords[6] = tex_right;
+                texcoords[7] = tex_top;
+                texcoords[8] = tex_right;
+                texcoords[9] = tex_bottom;
+                texcoords[10] = tex_left;
+                texcoords[11] = tex_bottom;
             } else {
                 log_warn3!("Renderer: No buffer for surface {}", context.id);
             }
@@ -428,6 +445,40 @@ impl RendererGl {
         }
     }
 
+    /// Draws outlines of given areas. Outlines are drawn by clearing thin scissor boxes along
+    /// edges of areas so no additional shaders are needed.
+    fn draw_highlights(&self, highlights: &Vec<Area>) {
+        if highlights.is_empty() {
+            return;
+        }
+
+        let screen_height = self.size.height as i32;
+        let width = HIGHLIGHT_WIDTH as i32;
+        let (r, g, b, a) = HIGHLIGHT_COLOR;
+        unsafe {
+            gl::Enable(gl::SCISSOR_TEST);
+            gl::ClearColor(r, g, b, a);
+            for area in highlights.iter() {
+                let x = area.pos.x as i32;
+                let w = area.size.width as i32;
+                let h = area.size.height as i32;
+
+                // GL counts rows from the bottom of the screen
+                let y = screen_height - area.pos.y as i32 - h;
+
+                let edges = [(x, y + h - width, w, width),
+                             (x, y, w, width),
+                             (x, y, width, h),
+                             (x + w - width, y, width, h)];
+                for &(ex, ey, ew, eh) in edges.iter() {
+                    gl::Scissor(ex, ey, ew.max(0), eh.max(0));
+                    gl::Clear(gl::COLOR_BUFFER_BIT);
+                }
+            }
+            gl::Disable(gl::SCISSOR_TEST);
+        }
+    }
+
     /// Unbind framebuffer and program.
     fn release_view(&self) {
         unsafe {
@@ -0,0 +1,105 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! Bindings for protocols not (yet) provided by `skylane_protocols`.
+//!
+//! Code is generated by `build.rs` from XML files in `protocols` directory. It has the same form
+//! as in `skylane_protocols` but `Dispatcher` trait is private there so this module defines its
+//! own `Dispatcher` and `Handler`.
+
+// -------------------------------------------------------------------------------------------------
+
+mod private {
+    use std::io::Cursor;
+    use skylane::server::{Bundle, Header, Object, SkylaneError, Task};
+
+    /// Translates socket data to calls to methods of object implementing protocol interface.
+    pub trait Dispatcher<I> {
+        /// Constructs new `Dispatcher`.
+        fn new() -> Self;
+
+        /// Demarshals message and call appropriate callback in passed `object`.
+        fn dispatch(&mut self,
+                    object: &mut I,
+                    bundle: &mut Bundle,
+                    header: &Header,
+                    bytes_buf: &mut Cursor<&[u8]>,
+                    fds_buf: &mut Cursor<&[u8]>)
+                    -> Result<Task, SkylaneError>;
+    }
+
+    /// Binds `Dispatcher` with object implementing `Interface` trait from protocol definition.
+    pub struct Handler<I, D>
+        where D: Dispatcher<I>
+    {
+        object: I,
+        dispatcher: D,
+    }
+
+    impl<I, D> Handler<I, D>
+        where D: Dispatcher<I>
+    {
+        /// Constructs new `Handler`.
+        pub fn new(object: I) -> Self {
+            Handler {
+                object: object,
+                dispatcher: D::new(),
+            }
+        }
+    }
+
+    impl<I, D> Object for Handler<I, D>
+        where D: Dispatcher<I>
+    {
+        fn dispatch(&mut self,
+                    bundle: &mut Bundle,
+                    header: &Header,
+                    bytes_buf: &mut Cursor<&[u8]>,
+                    fds_buf: &mut Cursor<&[u8]>)
+                    -> Result<Task, SkylaneError> {
+            self.dispatcher.dispatch(&mut self.object, bundle, header, bytes_buf, fds_buf)
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Server-side protocols.
+#[allow(dead_code)]
+pub mod server {
+    use skylane::server::{Bundle, Header, ObjectId, SkylaneError, Socket, Task};
+    use bindings::private::Dispatcher;
+    pub use bindings::private::Handler;
+
+    /// Protocol generated from `virtual-keyboard-unstable-v1.xml`
+    pub mod virtual_keyboard_unstable_v1 {
+        include!(concat!(env!("OUT_DIR"), "/virtual_keyboard_unstable_v1_server.rs"));
+    }
+
+    /// Protocol generated from `wlr-virtual-pointer-unstable-v1.xml`
+    pub mod wlr_virtual_pointer_unstable_v1 {
+        include!(concat!(env!("OUT_DIR"), "/wlr_virtual_pointer_unstable_v1_server.rs"));
+    }
+
+    /// Protocol generated from `viewporter.xml`
+    pub mod viewporter {
+        include!(concat!(env!("OUT_DIR"), "/viewporter_server.rs"));
+    }
+
+    /// Protocol generated from `ext-session-lock-v1.xml`
+    pub mod ext_session_lock_v1 {
+        include!(concat!(env!("OUT_DIR"), "/ext_session_lock_v1_server.rs"));
+    }
+
+    /// Protocol generated from `keyboard-shortcuts-inhibit-unstable-v1.xml`
+    pub mod keyboard_shortcuts_inhibit_unstable_v1 {
+        include!(concat!(env!("OUT_DIR"), "/keyboard_shortcuts_inhibit_unstable_v1_server.rs"));
+    }
+
+    /// Protocol generated from `xdg-activation-v1.xml`
+    pub mod xdg_activation_v1 {
+        include!(concat!(env!("OUT_DIR"), "/xdg_activation_v1_server.rs"));
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
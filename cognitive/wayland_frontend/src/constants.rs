@@ -8,5 +8,9 @@
 pub const PROCESS_EVENTS: &'static str = "process_events";
 pub const HANDLE_NEW_CLIENT: &'static str = "handle_new_client";
 pub const TERMINATE_CLIENT: &'static str = "terminate_client";
+pub const START_XWAYLAND: &'static str = "start_xwayland";
+pub const XWAYLAND_READY: &'static str = "xwayland_ready";
+pub const PROCESS_XWM_EVENTS: &'static str = "process_xwm_events";
+pub const PROCESS_XWM_TRANSFER: &'static str = "process_xwm_transfer";
 
 // -------------------------------------------------------------------------------------------------
@@ -10,19 +10,23 @@ use std::collections::HashMap;
 use std::os::unix::io::RawFd;
 
 use dharma;
+use nix;
+use nix::libc;
 use skylane::server as wl;
 
 use qualia::{Axis, Button, DrmBundle, Milliseconds, OutputInfo, Position, Size};
-use qualia::{Key, KeyboardConfig, Perceptron, Settings};
+use qualia::{Key, KeyboardConfig, Perceptron, Settings, WaylandConfig};
 use qualia::{surface_state, SurfaceId};
 use qualia::FrontendsCoordinationTrait;
 use inputs::{KeyboardState, KeyMods};
 
+use constants;
 use protocol;
 use gateway::Gateway;
 use proxy::{Proxy, ProxyRef};
 use mediator::{Mediator, MediatorRef};
-use event_handlers::{ClientEventHandler, DisplayEventHandler};
+use event_handlers::{ClientEventHandler, DisplayEventHandler, XwaylandEventHandler};
+use xwayland::{WindowManager, XServer};
 use std::path::PathBuf;
 
 // -------------------------------------------------------------------------------------------------
@@ -35,6 +39,41 @@ struct Client {
 
 // -------------------------------------------------------------------------------------------------
 
+/// Helper structure for keeping state of `Xwayland`.
+struct Xwayland {
+    server: XServer,
+    sender: dharma::DirectSender<Perceptron>,
+    listen_handler_ids: Vec<dharma::EventHandlerId>,
+    ready_handler_id: Option<dharma::EventHandlerId>,
+    ready_fd: Option<RawFd>,
+    wm_fd: Option<RawFd>,
+    client_id: Option<dharma::EventHandlerId>,
+    wm: Option<WindowManager>,
+    wm_handler_id: Option<dharma::EventHandlerId>,
+    transfer_handler_id: Option<dharma::EventHandlerId>,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Xwayland {
+    pub fn new(server: XServer, sender: dharma::DirectSender<Perceptron>) -> Self {
+        Xwayland {
+            server: server,
+            sender: sender,
+            listen_handler_ids: Vec::new(),
+            ready_handler_id: None,
+            ready_fd: None,
+            wm_fd: None,
+            client_id: None,
+            wm: None,
+            wm_handler_id: None,
+            transfer_handler_id: None,
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
 /// This is main structure of `wayland_frontend` crate.
 ///
 /// For information about its role and place among other structures see crate-level documentation.
@@ -47,6 +86,8 @@ pub struct Engine {
     settings: Settings,
     dispatcher: dharma::LocalDispatcher,
     keyboard_state: KeyboardState,
+    config: WaylandConfig,
+    xwayland: Option<Xwayland>,
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -60,7 +101,8 @@ impl Engine {
     /// Panics if failed to set display socket up or to initialize keyboard state.
     pub fn new(coordinator: Box<FrontendsCoordinationTrait>,
                settings: Settings,
-               keyboard_config: KeyboardConfig)
+               keyboard_config: KeyboardConfig,
+               config: WaylandConfig)
                -> Self {
         Engine {
             display: Self::create_display_socket().expect("creating display socket"),
@@ -71,11 +113,24 @@ impl Engine {
             settings: settings,
             dispatcher: dharma::LocalDispatcher::new(),
             keyboard_state: KeyboardState::new(&keyboard_config).expect("creating keyboard state"),
+            config: config,
+            xwayland: None,
         }
     }
 
     /// Starts `Engine`: adds display socket to `LocalDispatcher`.
+    ///
+    /// If enabled in configuration, sets up X11 display. `Xwayland` will be spawned when first X11
+    /// client connects.
     pub fn start(&mut self, sender: dharma::DirectSender<Perceptron>) {
+        if self.config.xwayland {
+            if let Some(server) = XServer::new() {
+                std::env::set_var("DISPLAY", server.get_display_name());
+                self.xwayland = Some(Xwayland::new(server, sender.clone()));
+                self.watch_x11_sockets();
+            }
+        }
+
         let handler = Box::new(DisplayEventHandler::new(self.display.clone(), sender));
         self.dispatcher.add_source(handler, dharma::event_kind::READ);
     }
@@ -98,7 +153,210 @@ impl Engine {
                              sender: dharma::DirectSender<Perceptron>,
                              coordinator: Box<FrontendsCoordinationTrait>) {
         // Accept the client.
-        let mut client_socket = self.display.accept().expect("Accepting client");
+        let client_socket = self.display.accept().expect("Accepting client");
+        self.add_client(client_socket, sender, coordinator);
+    }
+
+    /// Handles termination (socket hung up) of client.
+    pub fn terminate_client(&mut self, id: dharma::EventHandlerId) {
+        let result1 = if let Some(_handler) = self.dispatcher.delete_source(id) {
+            true
+        } else {
+            log_warn2!("Dispatching handler not found for client {} on termination", id);
+            false
+        };
+
+        let result2 = if let Some(_client) = self.clients.remove(&id) {
+            true
+        } else {
+            log_warn2!("Proxy not found for client {} on termination", id);
+            false
+        };
+
+        if result1 && result2 {
+            log_wayl3!("Client {} terminated successfully", id);
+        }
+
+        if self.xwayland.as_ref().map_or(false, |xwayland| xwayland.client_id == Some(id)) {
+            self.terminate_xwayland();
+        }
+    }
+
+    /// Handles request from client associated with given `id`.
+    pub fn process_events(&mut self, id: dharma::EventHandlerId) {
+        if let Some(ref mut client) = self.clients.get_mut(&id) {
+            if let Err(err) = client.connection.process_events() {
+                log_warn3!("Wayland Engine: ERROR: {:?}", err);
+            }
+        } else {
+            log_warn1!("Wayland Engine: No client: {}", id);
+        }
+
+        // Surfaces of X11 windows may be created after window manager learned about them.
+        if let Some(ref mut xwayland) = self.xwayland {
+            if xwayland.client_id == Some(id) {
+                if let (Some(wm), Some(client)) = (xwayland.wm.as_mut(), self.clients.get(&id)) {
+                    wm.show_pending_windows(&client.proxy);
+                }
+            }
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Public handlers for `Xwayland` related events.
+impl Engine {
+    /// Spawns `Xwayland` after X11 client tried to connect and creates Wayland client for it.
+    pub fn start_xwayland(&mut self,
+                          sender: dharma::DirectSender<Perceptron>,
+                          coordinator: Box<FrontendsCoordinationTrait>) {
+        let fds = if let Some(ref mut xwayland) = self.xwayland {
+            if xwayland.server.is_running() {
+                return;
+            }
+
+            // `Xwayland` will accept connections on its own.
+            for id in xwayland.listen_handler_ids.drain(..) {
+                self.dispatcher.delete_source(id);
+            }
+
+            if let Some(fds) = xwayland.server.spawn() {
+                fds
+            } else {
+                log_warn1!("X11 clients will not be supported");
+                return;
+            }
+        } else {
+            return;
+        };
+
+        let client_id = self.add_client(fds.wayland_socket, sender, coordinator);
+        if let Some(ref mut xwayland) = self.xwayland {
+            let handler = XwaylandEventHandler::new(fds.ready_fd,
+                                                    constants::XWAYLAND_READY,
+                                                    xwayland.sender.clone());
+            let id = self.dispatcher.add_source(Box::new(handler), dharma::event_kind::READ);
+            xwayland.ready_handler_id = Some(id);
+            xwayland.ready_fd = Some(fds.ready_fd);
+            xwayland.wm_fd = Some(fds.wm_fd);
+            xwayland.client_id = Some(client_id);
+        }
+    }
+
+    /// Sets up X11 window manager after `Xwayland` notified it is ready.
+    pub fn on_xwayland_ready(&mut self, coordinator: Box<FrontendsCoordinationTrait>) {
+        if let Some(ref mut xwayland) = self.xwayland {
+            if let Some(id) = xwayland.ready_handler_id.take() {
+                self.dispatcher.delete_source(id);
+            }
+
+            // `Xwayland` writes display number when ready or closes the pipe on failure.
+            let is_ready = if let Some(fd) = xwayland.ready_fd.take() {
+                let mut buffer = [0u8; 16];
+                let result = nix::unistd::read(fd, &mut buffer);
+                Self::close(fd);
+                result.map(|size| size > 0).unwrap_or(false)
+            } else {
+                false
+            };
+
+            let wm_fd = xwayland.wm_fd.take();
+            match (is_ready, wm_fd, xwayland.client_id) {
+                (true, Some(wm_fd), Some(client_id)) => {
+                    log_info1!("Xwayland is ready");
+                    let mediator = self.mediator.clone();
+                    if let Some(wm) = WindowManager::new(wm_fd, client_id, coordinator, mediator) {
+                        let handler = XwaylandEventHandler::new(wm.get_fd(),
+                                                                constants::PROCESS_XWM_EVENTS,
+                                                                xwayland.sender.clone());
+                        let id = self.dispatcher.add_source(Box::new(handler),
+                                                            dharma::event_kind::READ);
+                        xwayland.wm_handler_id = Some(id);
+                        xwayland.wm = Some(wm);
+                    }
+                }
+                (_, wm_fd, _) => {
+                    log_warn1!("Xwayland failed to start");
+                    if let Some(wm_fd) = wm_fd {
+                        Self::close(wm_fd);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Handles events from `Xwayland` on window manager connection.
+    pub fn process_xwm_events(&mut self) {
+        if let Some(ref mut xwayland) = self.xwayland {
+            let proxy = match xwayland.client_id {
+                Some(id) => {
+                    match self.clients.get(&id) {
+                        Some(client) => client.proxy.clone(),
+                        None => return,
+                    }
+                }
+                None => return,
+            };
+
+            let mut is_broken = false;
+            if let Some(ref mut wm) = xwayland.wm {
+                wm.process_events(&proxy);
+                is_broken = wm.is_connection_broken();
+                if let Some(fd) = wm.take_new_incoming_transfer_fd() {
+                    let handler = XwaylandEventHandler::new(fd,
+                                                            constants::PROCESS_XWM_TRANSFER,
+                                                            xwayland.sender.clone());
+                    let id = self.dispatcher.add_source(Box::new(handler),
+                                                        dharma::event_kind::READ);
+                    xwayland.transfer_handler_id = Some(id);
+                }
+            }
+
+            if is_broken {
+                log_warn1!("Lost connection to Xwayland window manager");
+                if let Some(id) = xwayland.wm_handler_id.take() {
+                    self.dispatcher.delete_source(id);
+                }
+                xwayland.wm = None;
+            }
+        }
+    }
+
+    /// Handles data of selection transfer from Wayland client to X11 client.
+    pub fn process_xwm_transfer(&mut self, id: dharma::EventHandlerId) {
+        if let Some(ref mut xwayland) = self.xwayland {
+            let is_finished = if let Some(ref mut wm) = xwayland.wm {
+                wm.process_incoming_transfer()
+            } else {
+                true
+            };
+
+            if is_finished {
+                self.dispatcher.delete_source(id);
+                if xwayland.transfer_handler_id == Some(id) {
+                    xwayland.transfer_handler_id = None;
+                }
+            }
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Private helper methods.
+impl Engine {
+    /// Adds new client connected on given socket:
+    /// - adds socket to `Dispatcher`
+    /// - creates proxy for new client and registers global Wayland objects.
+    /// - creates global display Wayland objects and bind it to client
+    ///
+    /// Returns ID of the client.
+    fn add_client(&mut self,
+                  mut client_socket: wl::Socket,
+                  sender: dharma::DirectSender<Perceptron>,
+                  coordinator: Box<FrontendsCoordinationTrait>)
+                  -> dharma::EventHandlerId {
         client_socket.set_logger(Some(Self::logger));
 
         // Prepare event handler.
@@ -119,9 +377,17 @@ impl Engine {
         proxy.register_global(protocol::data_device_manager::get_global());
         proxy.register_global(protocol::seat::get_global());
         proxy.register_global(protocol::subcompositor::get_global());
+        proxy.register_global(protocol::viewporter::get_global());
         proxy.register_global(protocol::weston_screenshooter::get_global());
         proxy.register_global(protocol::linux_dmabuf_v1::get_global());
         proxy.register_global(protocol::mesa_drm::get_global());
+        proxy.register_global(protocol::session_lock_v1::get_global());
+        proxy.register_global(protocol::keyboard_shortcuts_inhibit_v1::get_global());
+        proxy.register_global(protocol::xdg_activation_v1::get_global());
+        if self.is_virtual_input_allowed(&client_socket) {
+            proxy.register_global(protocol::virtual_keyboard_v1::get_global());
+            proxy.register_global(protocol::virtual_pointer_v1::get_global());
+        }
         for info in self.output_infos.iter() {
             proxy.register_global(protocol::output::get_global(info.clone()));
         }
@@ -136,50 +402,101 @@ impl Engine {
             proxy: proxy_ref,
         };
         self.clients.insert(id, client);
+        id
     }
 
-    /// Handles termination (socket hung up) of client.
-    pub fn terminate_client(&mut self, id: dharma::EventHandlerId) {
-        let result1 = if let Some(_handler) = self.dispatcher.delete_source(id) {
-            true
-        } else {
-            log_warn2!("Dispatching handler not found for client {} on termination", id);
-            false
-        };
-
-        let result2 = if let Some(_client) = self.clients.remove(&id) {
-            true
-        } else {
-            log_warn2!("Proxy not found for client {} on termination", id);
-            false
-        };
-
-        if result1 && result2 {
-            log_wayl3!("Client {} terminated successfully", id);
+    /// Starts watching X11 listening sockets for connecting clients.
+    fn watch_x11_sockets(&mut self) {
+        if let Some(ref mut xwayland) = self.xwayland {
+            for fd in xwayland.server.get_listen_fds().iter() {
+                let handler = XwaylandEventHandler::new(*fd,
+                                                        constants::START_XWAYLAND,
+                                                        xwayland.sender.clone());
+                let id = self.dispatcher.add_source(Box::new(handler), dharma::event_kind::READ);
+                xwayland.listen_handler_ids.push(id);
+            }
         }
     }
 
-    /// Handles request from client associated with given `id`.
-    pub fn process_events(&mut self, id: dharma::EventHandlerId) {
-        if let Some(ref mut client) = self.clients.get_mut(&id) {
-            if let Err(err) = client.connection.process_events() {
-                log_warn3!("Wayland Engine: ERROR: {:?}", err);
+    /// Cleans up after `Xwayland` terminated. `Xwayland` will be spawned again when next X11
+    /// client connects.
+    fn terminate_xwayland(&mut self) {
+        if let Some(ref mut xwayland) = self.xwayland {
+            for id in vec![xwayland.ready_handler_id.take(),
+                           xwayland.wm_handler_id.take(),
+                           xwayland.transfer_handler_id.take()] {
+                if let Some(id) = id {
+                    self.dispatcher.delete_source(id);
+                }
             }
-        } else {
-            log_warn1!("Wayland Engine: No client: {}", id);
+            for fd in vec![xwayland.ready_fd.take(), xwayland.wm_fd.take()] {
+                if let Some(fd) = fd {
+                    Self::close(fd);
+                }
+            }
+            xwayland.wm = None;
+            xwayland.client_id = None;
+            xwayland.server.on_terminated();
         }
+        self.watch_x11_sockets();
     }
-}
 
-// -------------------------------------------------------------------------------------------------
+    /// Closes given file descriptor.
+    fn close(fd: RawFd) {
+        if let Err(err) = nix::unistd::close(fd) {
+            log_warn3!("Failed to close file descriptor {}: {:?}", fd, err);
+        }
+    }
 
-/// Private helper methods.
-impl Engine {
     /// Logs `skylane` debugs.
     fn logger(s: String) {
         log_wayl4!("Skylane: {}", s);
     }
 
+    /// Checks if client connected on given socket is allowed to use virtual input devices. Clients
+    /// are recognized by absolute path of their executable.
+    fn is_virtual_input_allowed(&self, socket: &wl::Socket) -> bool {
+        if self.config.virtual_input_clients.is_empty() {
+            return false;
+        }
+
+        // Fields of `ucred` are private in `nix` so `libc` is used directly.
+        let mut credentials: libc::ucred = unsafe { std::mem::zeroed() };
+        let mut len = std::mem::size_of::<libc::ucred>() as libc::socklen_t;
+        let result = unsafe {
+            libc::getsockopt(socket.get_fd(),
+                             libc::SOL_SOCKET,
+                             libc::SO_PEERCRED,
+                             &mut credentials as *mut libc::ucred as *mut libc::c_void,
+                             &mut len)
+        };
+        if result < 0 {
+            let err = std::io::Error::last_os_error();
+            log_warn2!("Failed to get credentials of client: {:?}", err);
+            return false;
+        }
+        let pid = credentials.pid;
+
+        // Name of process can be freely changed by the process itself while link to its executable
+        // can not.
+        let exe = match std::fs::read_link(format!("/proc/{}/exe", pid)) {
+            Ok(exe) => exe,
+            Err(err) => {
+                log_warn2!("Failed to resolve executable of client process {}: {:?}", pid, err);
+                return false;
+            }
+        };
+
+        let allowed = self.config
+            .virtual_input_clients
+            .iter()
+            .any(|client| (client == "*") || (std::path::Path::new(client) == exe));
+        if allowed {
+            log_info2!("Allowing {:?} ({}) to use virtual input devices", exe, pid);
+        }
+        allowed
+    }
+
     /// Creates new display socket.
     fn create_display_socket() -> Option<wl::DisplaySocket> {
         match wl::DisplaySocket::new_default() {
@@ -212,6 +529,7 @@ impl Gateway for Engine {
     }
 
     fn on_display_created(&mut self, output_info: OutputInfo) {
+        self.mediator.borrow_mut().set_display_area(output_info.id, output_info.area);
         self.output_infos.push(output_info.clone());
         for (_, client) in self.clients.iter() {
             client.proxy.borrow_mut().on_display_created(output_info.clone());
@@ -330,6 +648,12 @@ impl Gateway for Engine {
                 }
             }
         }
+
+        if let Some(ref mut xwayland) = self.xwayland {
+            if let Some(ref mut wm) = xwayland.wm {
+                wm.on_keyboard_focus_changed(new_sid);
+            }
+        }
     }
 
     fn on_transfer_offered(&mut self) {
@@ -340,13 +664,38 @@ impl Gateway for Engine {
                                                           client.proxy.clone());
             }
         }
+
+        // Make the transfer available for X11 clients unless it was offered by one of them.
+        if let Some(ref mut xwayland) = self.xwayland {
+            if let Some(ref mut wm) = xwayland.wm {
+                if self.mediator.borrow().get_transfer_offerer() != xwayland.client_id {
+                    wm.on_transfer_offered();
+                }
+            }
+        }
     }
 
     fn on_transfer_requested(&mut self, mime_type: String, fd: RawFd) {
         if let Some(id) = self.mediator.borrow().get_transfer_offerer() {
+            if let Some(ref mut xwayland) = self.xwayland {
+                if let Some(ref mut wm) = xwayland.wm {
+                    if xwayland.client_id == Some(id) {
+                        wm.on_transfer_requested(mime_type, fd);
+                        return;
+                    }
+                }
+            }
+
             if let Some(client) = self.clients.get_mut(&id) {
                 client.proxy.borrow_mut().on_transfer_requested(mime_type, fd);
             }
+
+            // Transfer may have been requested by window manager on behalf of X11 client.
+            if let Some(ref mut xwayland) = self.xwayland {
+                if let Some(ref mut wm) = xwayland.wm {
+                    wm.on_transfer_forwarded(fd);
+                }
+            }
         }
     }
 
@@ -354,6 +703,14 @@ impl Gateway for Engine {
                                sid: SurfaceId,
                                size: Size,
                                state_flags: surface_state::SurfaceState) {
+        if let Some(ref xwayland) = self.xwayland {
+            if let Some(ref wm) = xwayland.wm {
+                if wm.on_surface_reconfigured(sid, size) {
+                    return;
+                }
+            }
+        }
+
         if let Some(id) = self.mediator.borrow().get_client_for_sid(sid) {
             if let Some(client) = self.clients.get(&id) {
                 client.proxy.borrow().on_surface_reconfigured(sid, size, state_flags);
@@ -361,6 +718,30 @@ impl Gateway for Engine {
         }
     }
 
+    fn on_surface_moved(&mut self, sid: SurfaceId, position: Position) {
+        if let Some(ref mut xwayland) = self.xwayland {
+            if let Some(ref mut wm) = xwayland.wm {
+                wm.on_surface_moved(sid, position);
+            }
+        }
+    }
+
+    fn on_surface_close_requested(&self, sid: SurfaceId) {
+        if let Some(ref xwayland) = self.xwayland {
+            if let Some(ref wm) = xwayland.wm {
+                if wm.on_surface_close_requested(sid) {
+                    return;
+                }
+            }
+        }
+
+        if let Some(id) = self.mediator.borrow().get_client_for_sid(sid) {
+            if let Some(client) = self.clients.get(&id) {
+                client.proxy.borrow().on_surface_close_requested(sid);
+            }
+        }
+    }
+
     fn on_screenshot_done(&mut self) {
         let id = {
             let mediator = self.mediator.borrow();
@@ -373,6 +754,24 @@ impl Gateway for Engine {
             }
         }
     }
+
+    fn on_session_locked(&mut self) {
+        let id = self.mediator.borrow().get_session_locker();
+        if let Some(id) = id {
+            if let Some(client) = self.clients.get_mut(&id) {
+                client.proxy.borrow_mut().on_session_locked();
+            }
+        }
+    }
+
+    fn on_shortcuts_inhibition_escaped(&mut self) {
+        let sid = self.coordinator.get_keyboard_focused_sid();
+        if let Some(id) = self.mediator.borrow().get_client_for_sid(sid) {
+            if let Some(client) = self.clients.get_mut(&id) {
+                client.proxy.borrow_mut().on_shortcuts_inhibition_escaped();
+            }
+        }
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -1,7 +1,8 @@
 // This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
 // the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
 
-//! Definition of `dharma::EventHandler`s for global (display) socket and client sockets.
+//! Definition of `dharma::EventHandler`s for global (display) socket, client sockets and
+//! `Xwayland` related file descriptors.
 
 // -------------------------------------------------------------------------------------------------
 
@@ -123,3 +124,50 @@ impl dharma::EventHandler for ClientEventHandler {
 }
 
 // -------------------------------------------------------------------------------------------------
+
+/// Implementation of `dharma::EventHandler` for file descriptors related to `Xwayland` (listening
+/// X11 sockets, readiness notification pipe, window manager connection and selection transfers).
+///
+/// Every event is just forwarded as custom message with given ID.
+pub struct XwaylandEventHandler {
+    id: dharma::EventHandlerId,
+    fd: RawFd,
+    message: &'static str,
+    sender: dharma::DirectSender<Perceptron>,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl XwaylandEventHandler {
+    pub fn new(fd: RawFd,
+               message: &'static str,
+               sender: dharma::DirectSender<Perceptron>)
+               -> Self {
+        XwaylandEventHandler {
+            id: 0,
+            fd: fd,
+            message: message,
+            sender: sender,
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl dharma::EventHandler for XwaylandEventHandler {
+    fn get_fd(&self) -> RawFd {
+        self.fd
+    }
+
+    fn process_event(&mut self, event_kind: dharma::EventKind) {
+        if event_kind.intersects(dharma::event_kind::READ | dharma::event_kind::HANGUP) {
+            self.sender.send_custom(self.message, Perceptron::CustomId(self.id));
+        }
+    }
+
+    fn set_id(&mut self, id: dharma::EventHandlerId) {
+        self.id = id;
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
@@ -12,6 +12,8 @@ use skylane::server as wl;
 
 use cognitive_graphics::attributes::{EglAttributes, DmabufAttributes};
 use qualia::{Area, Memory, PixelFormat, Size, SurfaceId, Transfer, Vector, show_reason};
+use qualia::surface_state;
+use qualia::{Viewport, VirtualInput};
 use qualia::{DmabufId, EglImageId, MemoryPoolId, MemoryViewId};
 
 // -------------------------------------------------------------------------------------------------
@@ -21,6 +23,9 @@ use qualia::{DmabufId, EglImageId, MemoryPoolId, MemoryViewId};
 pub enum ShellSurfaceOid {
     Shell(wl::ObjectId),
     ZxdgToplevelV6(wl::ObjectId, wl::ObjectId),
+
+    /// Surface of X11 window. Contains X11 window ID.
+    Xwayland(u32),
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -149,13 +154,14 @@ pub trait Facade {
     fn create_surface(&mut self, surface_oid: wl::ObjectId) -> SurfaceId;
 
     /// Requests destruction of surface.
-    fn destroy_surface(&self, sid: SurfaceId);
+    fn destroy_surface(&mut self, sid: SurfaceId);
 
     /// Attaches memory view to surface. This will take effect after `commit`.
     fn attach(&mut self, buffer_oid: wl::ObjectId, sid: SurfaceId, x: i32, y: i32);
 
-    /// Commits all requests to surface.
-    fn commit(&self, sid: SurfaceId);
+    /// Commits all requests to surface. If source rectangle of viewport of the surface does not
+    /// fit in attached buffer the surface is not committed and ID of the viewport is returned.
+    fn commit(&self, sid: SurfaceId) -> Result<(), wl::ObjectId>;
 
     /// Requests (one-shot) notification about redrawing of given surface.
     fn set_frame(&mut self, sid: SurfaceId, frame_oid: wl::ObjectId);
@@ -177,6 +183,34 @@ pub trait Facade {
     /// Request setting size of surface.
     fn set_requested_size(&self, sid: SurfaceId, size: Size);
 
+    /// Marks given surface as having viewport. Returns ID of the surface or `None` if surface
+    /// already had viewport.
+    fn add_viewport(&mut self,
+                    viewport_oid: wl::ObjectId,
+                    surface_oid: wl::ObjectId)
+                    -> Option<SurfaceId>;
+
+    /// Sets pending source rectangle and destination size of given surface. They will take effect
+    /// after `commit`. Returns `false` if the surface was already destroyed.
+    fn set_viewport(&mut self, sid: SurfaceId, viewport: Viewport) -> bool;
+
+    /// Removes viewport from given surface. Default scaling and cropping will be restored after
+    /// `commit`.
+    fn remove_viewport(&mut self, sid: SurfaceId);
+
+    /// Sets title of given surface.
+    fn set_title(&self, surface_oid: wl::ObjectId, title: String);
+
+    /// Sets application ID (or class) of given surface.
+    fn set_app_id(&self, surface_oid: wl::ObjectId, app_id: String);
+
+    /// Requests setting (`enable` is `true`) or unsetting given state (maximized or fullscreen) of
+    /// given surface.
+    fn request_state(&self,
+                     surface_oid: wl::ObjectId,
+                     state: surface_state::SurfaceState,
+                     enable: bool);
+
     /// Requests setting relation (child-parent) between two surfaces.
     fn relate(&self, surface_oid: wl::ObjectId, parent_surface_oid: wl::ObjectId);
 
@@ -203,6 +237,50 @@ pub trait Facade {
 
     /// Returns path of current DRM device.
     fn get_drm_device_path(&self) -> Option<PathBuf>;
+
+    /// Injects input event from virtual input device.
+    fn inject_input(&mut self, event: VirtualInput);
+
+    /// Returns area covered by all displays.
+    fn get_screen_area(&self) -> Area;
+
+    /// Requests locking the session. Returns `false` if the session is already locked by other
+    /// lock object.
+    fn lock_session(&mut self, lock_oid: wl::ObjectId) -> bool;
+
+    /// Requests unlocking the session if it was locked by given lock object.
+    fn unlock_session(&mut self, lock_oid: wl::ObjectId);
+
+    /// Checks if given lock object locked the session and locking was already confirmed.
+    fn is_session_lock_confirmed(&self, lock_oid: wl::ObjectId) -> bool;
+
+    /// Sets surface as lock surface for given output and sends it initial configuration.
+    fn set_lock_surface(&mut self,
+                        lock_surface_oid: wl::ObjectId,
+                        surface_oid: wl::ObjectId,
+                        output_oid: wl::ObjectId);
+
+    /// Adds key bindings inhibitor for given surface. Returns `false` if the surface already has
+    /// one.
+    fn add_shortcuts_inhibitor(&mut self,
+                               inhibitor_oid: wl::ObjectId,
+                               surface_oid: wl::ObjectId)
+                               -> bool;
+
+    /// Removes key bindings inhibitor.
+    fn remove_shortcuts_inhibitor(&mut self, inhibitor_oid: wl::ObjectId);
+
+    /// Issues activation token. The token is valid only if given surface has keyboard focus and
+    /// given serial is the serial of one of the most recent key or button press events sent to
+    /// the client.
+    fn create_activation_token(&mut self,
+                               surface_oid: Option<wl::ObjectId>,
+                               serial: Option<u32>)
+                               -> String;
+
+    /// Requests activation of given surface. If the token is not valid or expired the surface is
+    /// only marked as demanding attention.
+    fn activate_surface(&mut self, token: &str, surface_oid: wl::ObjectId);
 }
 
 // -------------------------------------------------------------------------------------------------
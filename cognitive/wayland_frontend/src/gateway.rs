@@ -59,8 +59,20 @@ pub trait Gateway {
                                size: Size,
                                state_flags: surface_state::SurfaceState);
 
+    /// Notifies about change of global position of surface.
+    fn on_surface_moved(&mut self, sid: SurfaceId, position: Position);
+
+    /// Notifies that surface was requested to be closed.
+    fn on_surface_close_requested(&self, sid: SurfaceId);
+
     /// Notifies that screenshot data are ready.
     fn on_screenshot_done(&mut self);
+
+    /// Notifies that session was locked and nothing but lock surfaces is shown.
+    fn on_session_locked(&mut self);
+
+    /// Notifies that user escaped inhibition of key bindings requested by focused client.
+    fn on_shortcuts_inhibition_escaped(&mut self);
 }
 
 // -------------------------------------------------------------------------------------------------
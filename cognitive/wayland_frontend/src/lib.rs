@@ -35,6 +35,8 @@
 // TODO: Move common DRM functionality to module.
 extern crate drm as libdrm;
 extern crate nix;
+extern crate byteorder;
+extern crate xcb;
 
 extern crate skylane;
 extern crate skylane_protocols;
@@ -59,7 +61,9 @@ mod gateway;
 mod proxy;
 mod event_handlers;
 
+mod bindings;
 mod protocol;
+mod xwayland;
 
 pub mod engine;
 
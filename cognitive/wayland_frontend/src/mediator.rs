@@ -9,12 +9,30 @@ use std;
 use std::collections::HashMap;
 use std::os::unix::io::RawFd;
 use std::path::PathBuf;
+use std::io::Read;
+use std::time::{Duration, Instant};
 
 use libdrm;
 
 use dharma;
 
-use qualia::SurfaceId;
+use qualia::{Area, SurfaceId};
+
+// -------------------------------------------------------------------------------------------------
+
+/// Time in milliseconds after which activation token expires.
+const ACTIVATION_TOKEN_TIMEOUT_MS: u64 = 10000;
+
+/// Number of random bytes activation token is made of.
+const ACTIVATION_TOKEN_SIZE: usize = 16;
+
+// -------------------------------------------------------------------------------------------------
+
+/// Activation token issued for a client.
+struct ActivationToken {
+    creation_time: Instant,
+    is_valid: bool,
+}
 
 // -------------------------------------------------------------------------------------------------
 
@@ -27,6 +45,10 @@ pub struct Mediator {
     screenshoter_cid: Option<dharma::EventHandlerId>,
     drm_device_path: Option<PathBuf>,
     drm_device_fd: Option<RawFd>,
+    screen_area: Area,
+    display_areas: HashMap<i32, Area>,
+    session_locker_cid: Option<dharma::EventHandlerId>,
+    activation_tokens: HashMap<String, ActivationToken>,
 }
 
 define_ref!(struct Mediator as MediatorRef);
@@ -41,6 +63,10 @@ impl Mediator {
             screenshoter_cid: None,
             drm_device_fd: None,
             drm_device_path: None,
+            screen_area: Area::default(),
+            display_areas: HashMap::new(),
+            session_locker_cid: None,
+            activation_tokens: HashMap::new(),
         }
     }
 }
@@ -94,6 +120,93 @@ impl Mediator {
             }
         }
     }
+
+    /// Returns area covered by all displays.
+    pub fn get_screen_area(&self) -> Area {
+        self.screen_area
+    }
+
+    /// Stores area of display with given ID and recomputes area covered by all displays, so it
+    /// also shrinks when display gets smaller.
+    pub fn set_display_area(&mut self, id: i32, area: Area) {
+        self.display_areas.insert(id, area);
+
+        let mut areas = self.display_areas.values();
+        self.screen_area = areas.next().cloned().unwrap_or_default();
+        for area in areas {
+            self.screen_area.inflate(area);
+        }
+    }
+
+    /// Returns area of display with given ID.
+    pub fn get_display_area(&self, id: i32) -> Option<Area> {
+        self.display_areas.get(&id).cloned()
+    }
+
+    /// Registers client as the one locking the session. Returns `false` if other client already
+    /// holds the lock.
+    pub fn register_session_locker(&mut self, cid: dharma::EventHandlerId) -> bool {
+        match self.session_locker_cid {
+            Some(locker_cid) if locker_cid != cid => false,
+            _ => {
+                self.session_locker_cid = Some(cid);
+                true
+            }
+        }
+    }
+
+    /// Forgets client locking the session if it is the given one.
+    pub fn unregister_session_locker(&mut self, cid: dharma::EventHandlerId) {
+        if self.session_locker_cid == Some(cid) {
+            self.session_locker_cid = None;
+        }
+    }
+
+    /// Returns ID of client locking the session.
+    pub fn get_session_locker(&self) -> Option<dharma::EventHandlerId> {
+        self.session_locker_cid
+    }
+
+    /// Creates new activation token. Tokens are shared between clients so the token may be passed
+    /// to and used by other client than the one which requested it. Tokens are random so clients
+    /// can not guess tokens issued for others.
+    pub fn issue_activation_token(&mut self, is_valid: bool) -> String {
+        let token = match generate_token() {
+            Ok(token) => token,
+            Err(err) => {
+                // Token not stored by mediator is never granted activation.
+                log_warn2!("Failed to generate activation token: {}", err);
+                return String::new();
+            }
+        };
+
+        let timeout = Duration::from_millis(ACTIVATION_TOKEN_TIMEOUT_MS);
+        self.activation_tokens.retain(|_, entry| entry.creation_time.elapsed() < timeout);
+        self.activation_tokens.insert(token.clone(),
+                                      ActivationToken {
+                                          creation_time: Instant::now(),
+                                          is_valid: is_valid,
+                                      });
+        token
+    }
+
+    /// Forgets given activation token. Returns `true` if the token was valid and did not expire.
+    pub fn consume_activation_token(&mut self, token: &str) -> bool {
+        let timeout = Duration::from_millis(ACTIVATION_TOKEN_TIMEOUT_MS);
+        match self.activation_tokens.remove(token) {
+            Some(entry) => entry.is_valid && entry.creation_time.elapsed() < timeout,
+            None => false,
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Generates activation token from random bytes read from `/dev/urandom`.
+fn generate_token() -> std::io::Result<String> {
+    let mut bytes = [0u8; ACTIVATION_TOKEN_SIZE];
+    std::fs::File::open("/dev/urandom")?.read_exact(&mut bytes)?;
+    Ok(bytes.iter().map(|byte| format!("{:02x}", byte)).collect())
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -5,11 +5,13 @@
 
 use std::rc::Rc;
 
+use skylane::server as wl;
 use skylane::server::{Bundle, Object, ObjectId, Task};
 use skylane_protocols::server::Handler;
-use skylane_protocols::server::wayland::wl_compositor;
+use skylane_protocols::server::wayland::{wl_compositor, wl_display};
 use skylane_protocols::server::wayland::wl_surface;
 use skylane_protocols::server::wayland::wl_region;
+use bindings::server::viewporter::wp_viewport;
 
 use qualia::{Area, SurfaceId};
 
@@ -103,7 +105,7 @@ impl Surface {
 #[allow(unused_variables)]
 impl wl_surface::Interface for Surface {
     fn destroy(&mut self, this_object_id: ObjectId, bundle: &mut Bundle) -> Task {
-        let proxy = self.proxy.borrow();
+        let mut proxy = self.proxy.borrow_mut();
         proxy.destroy_surface(self.sid);
         Task::Destroy { id: this_object_id }
     }
@@ -159,7 +161,13 @@ impl wl_surface::Interface for Surface {
 
     fn commit(&mut self, this_object_id: ObjectId, bundle: &mut Bundle) -> Task {
         let proxy = self.proxy.borrow_mut();
-        proxy.commit(self.sid);
+        if let Err(viewport_oid) = proxy.commit(self.sid) {
+            send!(wl_display::error(&bundle.get_socket(),
+                                    wl::DISPLAY_ID,
+                                    viewport_oid,
+                                    wp_viewport::error::OUT_OF_BUFFER,
+                                    "Source rectangle extends outside of buffer"));
+        }
         Task::None
     }
 
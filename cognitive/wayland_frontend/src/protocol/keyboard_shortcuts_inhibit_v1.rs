@@ -0,0 +1,108 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! Implementations of `zwp_keyboard_shortcuts_inhibit_manager_v1` and
+//! `zwp_keyboard_shortcuts_inhibitor_v1` objects.
+//!
+//! Inhibitor is active only while its surface has keyboard focus. User can always escape the
+//! inhibition with configured escape binding; the inhibitor becomes active again when its surface
+//! regains focus.
+
+use std::rc::Rc;
+
+use skylane::server as wl;
+use skylane::server::{Bundle, Object, ObjectId, Task};
+use skylane_protocols::server::wayland::wl_display;
+use bindings::server::Handler;
+use bindings::server::keyboard_shortcuts_inhibit_unstable_v1::{
+    zwp_keyboard_shortcuts_inhibit_manager_v1 as manager,
+    zwp_keyboard_shortcuts_inhibitor_v1 as inhibitor};
+
+use global::Global;
+use facade::Facade;
+use proxy::ProxyRef;
+
+// -------------------------------------------------------------------------------------------------
+
+/// Wayland `zwp_keyboard_shortcuts_inhibit_manager_v1` object.
+struct ShortcutsInhibitManager {
+    proxy: ProxyRef,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+pub fn get_global() -> Global {
+    Global::new(manager::NAME,
+                manager::VERSION,
+                Rc::new(ShortcutsInhibitManager::new_object))
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl ShortcutsInhibitManager {
+    fn new(proxy_ref: ProxyRef) -> Self {
+        ShortcutsInhibitManager { proxy: proxy_ref }
+    }
+
+    fn new_object(_oid: ObjectId, _version: u32, proxy_ref: ProxyRef) -> Box<Object> {
+        let object = Self::new(proxy_ref);
+        Box::new(Handler::<_, manager::Dispatcher>::new(object))
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl manager::Interface for ShortcutsInhibitManager {
+    fn destroy(&mut self, this_object_id: ObjectId, _bundle: &mut Bundle) -> Task {
+        Task::Destroy { id: this_object_id }
+    }
+
+    fn inhibit_shortcuts(&mut self,
+                         this_object_id: ObjectId,
+                         bundle: &mut Bundle,
+                         id: ObjectId,
+                         surface: ObjectId,
+                         _seat: ObjectId)
+                         -> Task {
+        if !self.proxy.borrow_mut().add_shortcuts_inhibitor(id, surface) {
+            send!(wl_display::error(&bundle.get_socket(),
+                                    wl::DISPLAY_ID,
+                                    this_object_id,
+                                    manager::error::ALREADY_INHIBITED,
+                                    "Shortcuts are already inhibited for this surface"));
+            return Task::None;
+        }
+
+        Task::Create {
+            id: id,
+            object: ShortcutsInhibitor::new_object(self.proxy.clone()),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Wayland `zwp_keyboard_shortcuts_inhibitor_v1` object.
+struct ShortcutsInhibitor {
+    proxy: ProxyRef,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl ShortcutsInhibitor {
+    fn new_object(proxy_ref: ProxyRef) -> Box<Object> {
+        let object = ShortcutsInhibitor { proxy: proxy_ref };
+        Box::new(Handler::<_, inhibitor::Dispatcher>::new(object))
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl inhibitor::Interface for ShortcutsInhibitor {
+    fn destroy(&mut self, this_object_id: ObjectId, _bundle: &mut Bundle) -> Task {
+        self.proxy.borrow_mut().remove_shortcuts_inhibitor(this_object_id);
+        Task::Destroy { id: this_object_id }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
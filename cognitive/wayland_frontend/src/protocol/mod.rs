@@ -15,8 +15,15 @@ pub mod data_device_manager;
 pub mod seat;
 pub mod subcompositor;
 pub mod output;
+pub mod viewporter;
 
 pub mod weston_screenshooter;
+pub mod session_lock_v1;
+pub mod keyboard_shortcuts_inhibit_v1;
+pub mod xdg_activation_v1;
 
 pub mod linux_dmabuf_v1;
 pub mod mesa_drm;
+
+pub mod virtual_keyboard_v1;
+pub mod virtual_pointer_v1;
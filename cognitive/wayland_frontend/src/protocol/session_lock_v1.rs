@@ -0,0 +1,187 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! Implementations of `ext_session_lock_manager_v1`, `ext_session_lock_v1` and
+//! `ext_session_lock_surface_v1` objects.
+//!
+//! Only one client can lock the session at a time. If the locking client terminates without
+//! unlocking, the session stays locked and other client may lock it again.
+
+use std::rc::Rc;
+
+use skylane::server as wl;
+use skylane::server::{Bundle, Object, ObjectId, Task};
+use skylane_protocols::server::wayland::wl_display;
+use bindings::server::Handler;
+use bindings::server::ext_session_lock_v1::ext_session_lock_manager_v1;
+use bindings::server::ext_session_lock_v1::ext_session_lock_v1;
+use bindings::server::ext_session_lock_v1::ext_session_lock_surface_v1;
+
+use global::Global;
+use facade::Facade;
+use proxy::ProxyRef;
+
+// -------------------------------------------------------------------------------------------------
+
+/// Wayland `ext_session_lock_manager_v1` object.
+struct SessionLockManager {
+    proxy: ProxyRef,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+pub fn get_global() -> Global {
+    Global::new(ext_session_lock_manager_v1::NAME,
+                ext_session_lock_manager_v1::VERSION,
+                Rc::new(SessionLockManager::new_object))
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl SessionLockManager {
+    fn new(proxy_ref: ProxyRef) -> Self {
+        SessionLockManager { proxy: proxy_ref }
+    }
+
+    fn new_object(_oid: ObjectId, _version: u32, proxy_ref: ProxyRef) -> Box<Object> {
+        let manager = Self::new(proxy_ref);
+        Box::new(Handler::<_, ext_session_lock_manager_v1::Dispatcher>::new(manager))
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl ext_session_lock_manager_v1::Interface for SessionLockManager {
+    fn destroy(&mut self, this_object_id: ObjectId, _bundle: &mut Bundle) -> Task {
+        Task::Destroy { id: this_object_id }
+    }
+
+    fn lock(&mut self, _this_object_id: ObjectId, bundle: &mut Bundle, id: ObjectId) -> Task {
+        let is_active = self.proxy.borrow_mut().lock_session(id);
+        if !is_active {
+            log_warn2!("Session is already locked, rejecting lock request");
+            send!(ext_session_lock_v1::finished(&bundle.get_socket(), id));
+        }
+
+        Task::Create {
+            id: id,
+            object: SessionLock::new_object(is_active, self.proxy.clone()),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Wayland `ext_session_lock_v1` object.
+struct SessionLock {
+    proxy: ProxyRef,
+
+    /// `false` if locking was rejected. Requests of such lock are ignored.
+    is_active: bool,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl SessionLock {
+    fn new(is_active: bool, proxy_ref: ProxyRef) -> Self {
+        SessionLock {
+            proxy: proxy_ref,
+            is_active: is_active,
+        }
+    }
+
+    fn new_object(is_active: bool, proxy_ref: ProxyRef) -> Box<Object> {
+        let lock = Self::new(is_active, proxy_ref);
+        Box::new(Handler::<_, ext_session_lock_v1::Dispatcher>::new(lock))
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl ext_session_lock_v1::Interface for SessionLock {
+    /// Destroying the lock before locking was confirmed cancels locking. After confirmation the
+    /// client must use `unlock_and_destroy` request; otherwise the session stays locked.
+    fn destroy(&mut self, this_object_id: ObjectId, bundle: &mut Bundle) -> Task {
+        if self.is_active {
+            let mut proxy = self.proxy.borrow_mut();
+            if proxy.is_session_lock_confirmed(this_object_id) {
+                send!(wl_display::error(&bundle.get_socket(),
+                                        wl::DISPLAY_ID,
+                                        this_object_id,
+                                        ext_session_lock_v1::error::INVALID_DESTROY,
+                                        "Session lock destroyed without unlocking"));
+                return Task::None;
+            } else {
+                proxy.unlock_session(this_object_id);
+            }
+        }
+        Task::Destroy { id: this_object_id }
+    }
+
+    fn get_lock_surface(&mut self,
+                        _this_object_id: ObjectId,
+                        _bundle: &mut Bundle,
+                        id: ObjectId,
+                        surface: ObjectId,
+                        output: ObjectId)
+                        -> Task {
+        if self.is_active {
+            self.proxy.borrow_mut().set_lock_surface(id, surface, output);
+        }
+
+        Task::Create {
+            id: id,
+            object: SessionLockSurface::new_object(self.proxy.clone()),
+        }
+    }
+
+    fn unlock_and_destroy(&mut self, this_object_id: ObjectId, bundle: &mut Bundle) -> Task {
+        if self.is_active {
+            let mut proxy = self.proxy.borrow_mut();
+            if proxy.is_session_lock_confirmed(this_object_id) {
+                proxy.unlock_session(this_object_id);
+            } else {
+                send!(wl_display::error(&bundle.get_socket(),
+                                        wl::DISPLAY_ID,
+                                        this_object_id,
+                                        ext_session_lock_v1::error::INVALID_UNLOCK,
+                                        "Session was not locked yet"));
+                return Task::None;
+            }
+        }
+        Task::Destroy { id: this_object_id }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Wayland `ext_session_lock_surface_v1` object.
+///
+/// Surface is shown on display it was created for as soon as it is committed with buffer.
+struct SessionLockSurface {}
+
+// -------------------------------------------------------------------------------------------------
+
+impl SessionLockSurface {
+    fn new_object(_proxy_ref: ProxyRef) -> Box<Object> {
+        Box::new(Handler::<_, ext_session_lock_surface_v1::Dispatcher>::new(SessionLockSurface {}))
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl ext_session_lock_surface_v1::Interface for SessionLockSurface {
+    fn destroy(&mut self, this_object_id: ObjectId, _bundle: &mut Bundle) -> Task {
+        Task::Destroy { id: this_object_id }
+    }
+
+    fn ack_configure(&mut self,
+                     _this_object_id: ObjectId,
+                     _bundle: &mut Bundle,
+                     _serial: u32)
+                     -> Task {
+        Task::None
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
@@ -10,7 +10,7 @@ use skylane_protocols::server::Handler;
 use skylane_protocols::server::wayland::wl_shell;
 use skylane_protocols::server::wayland::wl_shell_surface;
 
-use qualia::show_reason;
+use qualia::{show_reason, surface_state};
 
 use facade::{Facade, ShellSurfaceOid};
 use global::Global;
@@ -166,6 +166,9 @@ impl wl_shell_surface::Interface for Surface {
                       framerate: u32,
                       output: ObjectId)
                       -> Task {
+        // Fullscreen and maximized surfaces are toplevel surfaces in special state.
+        self.set_toplevel(this_object_id, bundle);
+        self.proxy.borrow().request_state(self.surface_oid, surface_state::FULLSCREEN, true);
         Task::None
     }
 
@@ -198,14 +201,18 @@ impl wl_shell_surface::Interface for Surface {
                      bundle: &mut Bundle,
                      output: ObjectId)
                      -> Task {
+        self.set_toplevel(this_object_id, bundle);
+        self.proxy.borrow().request_state(self.surface_oid, surface_state::MAXIMIZED, true);
         Task::None
     }
 
     fn set_title(&mut self, this_object_id: ObjectId, bundle: &mut Bundle, title: String) -> Task {
+        self.proxy.borrow().set_title(self.surface_oid, title);
         Task::None
     }
 
     fn set_class(&mut self, this_object_id: ObjectId, bundle: &mut Bundle, class: String) -> Task {
+        self.proxy.borrow().set_app_id(self.surface_oid, class);
         Task::None
     }
 }
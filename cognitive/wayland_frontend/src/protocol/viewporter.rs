@@ -0,0 +1,176 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! Implementations of `wp_viewporter` and `wp_viewport` objects.
+//!
+//! Viewport state is double-buffered. It is kept as pending and applied on surface commit, when
+//! source rectangle is also validated against attached buffer. Viewport of destroyed surface is
+//! defunct.
+
+use std::rc::Rc;
+
+use skylane::server as wl;
+use skylane::server::{Bundle, Object, ObjectId, Task};
+use skylane_protocols::server::wayland::wl_display;
+use bindings::server::Handler;
+use bindings::server::viewporter::{wp_viewporter, wp_viewport};
+
+use qualia::{Size, SourceRect, SurfaceId, Viewport as ViewportState};
+
+use global::Global;
+use facade::Facade;
+use proxy::ProxyRef;
+
+// -------------------------------------------------------------------------------------------------
+
+/// Wayland `wp_viewporter` object.
+struct Viewporter {
+    proxy: ProxyRef,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+pub fn get_global() -> Global {
+    Global::new(wp_viewporter::NAME,
+                wp_viewporter::VERSION,
+                Rc::new(Viewporter::new_object))
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Viewporter {
+    fn new(proxy_ref: ProxyRef) -> Self {
+        Viewporter { proxy: proxy_ref }
+    }
+
+    fn new_object(_oid: ObjectId, _version: u32, proxy_ref: ProxyRef) -> Box<Object> {
+        Box::new(Handler::<_, wp_viewporter::Dispatcher>::new(Self::new(proxy_ref)))
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl wp_viewporter::Interface for Viewporter {
+    fn destroy(&mut self, this_object_id: ObjectId, _bundle: &mut Bundle) -> Task {
+        Task::Destroy { id: this_object_id }
+    }
+
+    fn get_viewport(&mut self,
+                    this_object_id: ObjectId,
+                    bundle: &mut Bundle,
+                    id: ObjectId,
+                    surface: ObjectId)
+                    -> Task {
+        let sid = self.proxy.borrow_mut().add_viewport(id, surface);
+        if let Some(sid) = sid {
+            Task::Create {
+                id: id,
+                object: Viewport::new_object(sid, self.proxy.clone()),
+            }
+        } else {
+            send!(wl_display::error(&bundle.get_socket(),
+                                    wl::DISPLAY_ID,
+                                    this_object_id,
+                                    wp_viewporter::error::VIEWPORT_EXISTS,
+                                    "Surface already has viewport"));
+            Task::None
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Wayland `wp_viewport` object.
+struct Viewport {
+    proxy: ProxyRef,
+    sid: SurfaceId,
+    state: ViewportState,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Viewport {
+    fn new(sid: SurfaceId, proxy_ref: ProxyRef) -> Self {
+        Viewport {
+            proxy: proxy_ref,
+            sid: sid,
+            state: ViewportState::new(),
+        }
+    }
+
+    fn new_object(sid: SurfaceId, proxy_ref: ProxyRef) -> Box<Object> {
+        let viewport = Self::new(sid, proxy_ref);
+        Box::new(Handler::<_, wp_viewport::Dispatcher>::new(viewport))
+    }
+
+    /// Posts `bad_value` error to client.
+    fn post_bad_value(this_object_id: ObjectId, bundle: &mut Bundle, msg: &str) {
+        send!(wl_display::error(&bundle.get_socket(),
+                                wl::DISPLAY_ID,
+                                this_object_id,
+                                wp_viewport::error::BAD_VALUE,
+                                msg));
+    }
+
+    /// Sets pending viewport state. Posts `no_surface` error to client if surface was destroyed.
+    fn update(&self, this_object_id: ObjectId, bundle: &mut Bundle) {
+        if !self.proxy.borrow_mut().set_viewport(self.sid, self.state) {
+            send!(wl_display::error(&bundle.get_socket(),
+                                    wl::DISPLAY_ID,
+                                    this_object_id,
+                                    wp_viewport::error::NO_SURFACE,
+                                    "Surface of viewport was destroyed"));
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl wp_viewport::Interface for Viewport {
+    fn destroy(&mut self, this_object_id: ObjectId, _bundle: &mut Bundle) -> Task {
+        self.proxy.borrow_mut().remove_viewport(self.sid);
+        Task::Destroy { id: this_object_id }
+    }
+
+    fn set_source(&mut self,
+                  this_object_id: ObjectId,
+                  bundle: &mut Bundle,
+                  x: f32,
+                  y: f32,
+                  width: f32,
+                  height: f32)
+                  -> Task {
+        if (x == -1.0) && (y == -1.0) && (width == -1.0) && (height == -1.0) {
+            self.state.source = None;
+        } else if (x >= 0.0) && (y >= 0.0) && (width > 0.0) && (height > 0.0) {
+            self.state.source = Some(SourceRect::new(x, y, width, height));
+        } else {
+            Self::post_bad_value(this_object_id, bundle, "Invalid source rectangle");
+            return Task::None;
+        }
+
+        self.update(this_object_id, bundle);
+        Task::None
+    }
+
+    fn set_destination(&mut self,
+                       this_object_id: ObjectId,
+                       bundle: &mut Bundle,
+                       width: i32,
+                       height: i32)
+                       -> Task {
+        if (width == -1) && (height == -1) {
+            self.state.destination = None;
+        } else if (width > 0) && (height > 0) {
+            self.state.destination = Some(Size::new(width as usize, height as usize));
+        } else {
+            Self::post_bad_value(this_object_id, bundle, "Invalid destination size");
+            return Task::None;
+        }
+
+        self.update(this_object_id, bundle);
+        Task::None
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
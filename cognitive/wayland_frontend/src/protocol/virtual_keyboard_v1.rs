@@ -0,0 +1,337 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! Implementations of `zwp_virtual_keyboard_manager_v1` and `zwp_virtual_keyboard_v1` objects.
+//!
+//! Keys from virtual keyboard are injected into the same input path as keys from evdev devices so
+//! they are subject to built-in and user-defined key bindings.
+//!
+//! Injected keys reach clients together with keys from other keyboards, so they are interpreted
+//! with the compositor keymap. Keys from client keymap are translated to compositor keys producing
+//! the same symbols, pressing `Shift` or `AltGr` around them when needed. Modifier state requested
+//! by client is translated to presses and releases of modifier keys.
+
+use std::rc::Rc;
+use std::collections::HashMap;
+use std::os::unix::io::RawFd;
+
+use nix;
+
+use skylane::server as wl;
+use skylane::server::{Bundle, Object, ObjectId, Task};
+use skylane_protocols::server::wayland::{wl_display, wl_keyboard};
+use bindings::server::Handler;
+use bindings::server::virtual_keyboard_unstable_v1::zwp_virtual_keyboard_manager_v1;
+use bindings::server::virtual_keyboard_unstable_v1::zwp_virtual_keyboard_v1;
+
+use qualia::{InputCode, InputValue, VirtualInput};
+use inputs::{codes, KeyTranslator};
+
+use global::Global;
+use facade::Facade;
+use proxy::ProxyRef;
+
+// -------------------------------------------------------------------------------------------------
+
+/// Modifiers active while key is held together with keys setting them. Masks are the same as of
+/// core modifiers in `xkbcommon` (`Shift`, `Control`, `Mod1`, `Mod4` and `Mod5`).
+const HELD_MODIFIERS: [(u32, u16); 5] = [(0x01, codes::KEY_LEFTSHIFT),
+                                         (0x04, codes::KEY_LEFTCTRL),
+                                         (0x08, codes::KEY_LEFTALT),
+                                         (0x40, codes::KEY_LEFTMETA),
+                                         (0x80, codes::KEY_RIGHTALT)];
+
+/// Modifiers toggled by pressing key together with keys toggling them (`Lock` and `Mod2`).
+const LOCKED_MODIFIERS: [(u32, u16); 2] = [(0x02, codes::KEY_CAPSLOCK),
+                                           (0x10, codes::KEY_NUMLOCK)];
+
+/// Input value of pressed key.
+const KEY_PRESSED: InputValue = 1;
+
+/// Input value of released key.
+const KEY_RELEASED: InputValue = 0;
+
+// -------------------------------------------------------------------------------------------------
+
+/// Wayland `zwp_virtual_keyboard_manager_v1` object.
+struct VirtualKeyboardManager {
+    proxy: ProxyRef,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+pub fn get_global() -> Global {
+    Global::new(zwp_virtual_keyboard_manager_v1::NAME,
+                zwp_virtual_keyboard_manager_v1::VERSION,
+                Rc::new(VirtualKeyboardManager::new_object))
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl VirtualKeyboardManager {
+    fn new(proxy_ref: ProxyRef) -> Self {
+        VirtualKeyboardManager { proxy: proxy_ref }
+    }
+
+    fn new_object(_oid: ObjectId, _version: u32, proxy_ref: ProxyRef) -> Box<Object> {
+        let manager = Self::new(proxy_ref);
+        Box::new(Handler::<_, zwp_virtual_keyboard_manager_v1::Dispatcher>::new(manager))
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl zwp_virtual_keyboard_manager_v1::Interface for VirtualKeyboardManager {
+    fn create_virtual_keyboard(&mut self,
+                               _this_object_id: ObjectId,
+                               _bundle: &mut Bundle,
+                               _seat: ObjectId,
+                               id: ObjectId)
+                               -> Task {
+        Task::Create {
+            id: id,
+            object: VirtualKeyboard::new_object(self.proxy.clone()),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Wayland `zwp_virtual_keyboard_v1` object.
+struct VirtualKeyboard {
+    proxy: ProxyRef,
+    has_keymap: bool,
+    translator: Option<KeyTranslator>,
+    pressed_keys: HashMap<u32, u16>,
+    held_mods: u32,
+    locked_mods: u32,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl VirtualKeyboard {
+    fn new(proxy_ref: ProxyRef) -> Self {
+        VirtualKeyboard {
+            proxy: proxy_ref,
+            has_keymap: false,
+            translator: None,
+            pressed_keys: HashMap::new(),
+            held_mods: 0,
+            locked_mods: 0,
+        }
+    }
+
+    fn new_object(proxy_ref: ProxyRef) -> Box<Object> {
+        Box::new(Handler::<_, zwp_virtual_keyboard_v1::Dispatcher>::new(Self::new(proxy_ref)))
+    }
+
+    /// Checks if keymap was set and if not posts error to client.
+    fn check_keymap(&self, this_object_id: ObjectId, bundle: &mut Bundle) -> bool {
+        if !self.has_keymap {
+            send!(wl_display::error(&bundle.get_socket(),
+                                    wl::DISPLAY_ID,
+                                    this_object_id,
+                                    zwp_virtual_keyboard_v1::error::NO_KEYMAP,
+                                    "Keymap must be set before sending keys"));
+        }
+        self.has_keymap
+    }
+
+    /// Creates translator from client keymap stored in given file to compositor keymap.
+    fn create_translator(&self, fd: RawFd, size: u32) -> Option<KeyTranslator> {
+        let keymap = self.proxy.borrow().get_settings().get_keymap();
+        match (read_keymap(fd, size as usize), read_keymap(keymap.fd, keymap.size)) {
+            (Some(client_keymap), Some(compositor_keymap)) => {
+                match (String::from_utf8(client_keymap), String::from_utf8(compositor_keymap)) {
+                    (Ok(client_keymap), Ok(compositor_keymap)) => {
+                        KeyTranslator::new(client_keymap, compositor_keymap)
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Translates client key to compositor key and presses it. Modifiers needed to reach shift
+    /// level of the symbol are pressed only for the time of key press.
+    fn press_key(&mut self, key: u32) {
+        let translated = if let Some(ref translator) = self.translator {
+            translator.translate(key as InputCode)
+        } else {
+            return;
+        };
+
+        if let Some(translated) = translated {
+            let extra_mods = translated.mods & !self.held_mods;
+            let extra_codes: Vec<u16> = HELD_MODIFIERS.iter()
+                .filter(|&&(mask, _)| (extra_mods & mask) != 0)
+                .map(|&(_, code)| code)
+                .collect();
+
+            for code in extra_codes.iter() {
+                self.inject_key(*code, KEY_PRESSED);
+            }
+            self.track_modifier_key(translated.code, KEY_PRESSED);
+            self.inject_key(translated.code, KEY_PRESSED);
+            for code in extra_codes.iter().rev() {
+                self.inject_key(*code, KEY_RELEASED);
+            }
+            self.pressed_keys.insert(key, translated.code);
+        } else {
+            log_warn3!("Key {} of virtual keyboard has no counterpart in compositor keymap", key);
+        }
+    }
+
+    /// Releases compositor key pressed for given client key.
+    fn release_key(&mut self, key: u32) {
+        if let Some(code) = self.pressed_keys.remove(&key) {
+            self.track_modifier_key(code, KEY_RELEASED);
+            self.inject_key(code, KEY_RELEASED);
+        }
+    }
+
+    /// Injects key event.
+    fn inject_key(&self, code: u16, value: InputValue) {
+        let event = VirtualInput::Key {
+            code: code as InputCode,
+            value: value,
+        };
+        self.proxy.borrow_mut().inject_input(event);
+    }
+
+    /// Presses or releases modifier keys so that set of held modifiers is the same as requested.
+    /// Locked modifiers are toggled with key press and release.
+    fn update_modifiers(&mut self, held_mods: u32, locked_mods: u32) {
+        for &(mask, code) in HELD_MODIFIERS.iter() {
+            if (held_mods & mask) != (self.held_mods & mask) {
+                let value = if (held_mods & mask) != 0 { KEY_PRESSED } else { KEY_RELEASED };
+                self.inject_key(code, value);
+            }
+        }
+
+        for &(mask, code) in LOCKED_MODIFIERS.iter() {
+            if (locked_mods & mask) != (self.locked_mods & mask) {
+                self.inject_key(code, KEY_PRESSED);
+                self.inject_key(code, KEY_RELEASED);
+            }
+        }
+
+        self.held_mods = held_mods;
+        self.locked_mods = locked_mods;
+    }
+
+    /// Keeps track of modifier keys sent directly as keys.
+    fn track_modifier_key(&mut self, code: u16, value: InputValue) {
+        if let Some(&(mask, _)) = HELD_MODIFIERS.iter().find(|&&(_, c)| c == code) {
+            if value == KEY_RELEASED {
+                self.held_mods &= !mask;
+            } else {
+                self.held_mods |= mask;
+            }
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl zwp_virtual_keyboard_v1::Interface for VirtualKeyboard {
+    fn keymap(&mut self,
+              _this_object_id: ObjectId,
+              _bundle: &mut Bundle,
+              format: u32,
+              fd: RawFd,
+              size: u32)
+              -> Task {
+        self.translator = if format == wl_keyboard::keymap_format::XKB_V1 {
+            self.create_translator(fd, size)
+        } else {
+            None
+        };
+        if let Err(err) = nix::unistd::close(fd) {
+            log_warn3!("Failed to close keymap of virtual keyboard: {:?}", err);
+        }
+
+        if self.translator.is_none() {
+            log_warn2!("Failed to load keymap of virtual keyboard; its keys will be ignored");
+        }
+        self.has_keymap = true;
+        Task::None
+    }
+
+    fn key(&mut self,
+           this_object_id: ObjectId,
+           bundle: &mut Bundle,
+           _time: u32,
+           key: u32,
+           state: u32)
+           -> Task {
+        if self.check_keymap(this_object_id, bundle) {
+            if state == KEY_RELEASED as u32 {
+                self.release_key(key);
+            } else if !self.pressed_keys.contains_key(&key) {
+                self.press_key(key);
+            }
+        }
+        Task::None
+    }
+
+    /// Modifier masks are assumed to be the core ones. Latched modifiers are handled like held
+    /// ones. Group is used only to interpret keys from client keymap.
+    fn modifiers(&mut self,
+                 this_object_id: ObjectId,
+                 bundle: &mut Bundle,
+                 mods_depressed: u32,
+                 mods_latched: u32,
+                 mods_locked: u32,
+                 group: u32)
+                 -> Task {
+        if self.check_keymap(this_object_id, bundle) {
+            if let Some(ref mut translator) = self.translator {
+                translator.set_modifiers(mods_depressed, mods_latched, mods_locked, group);
+            }
+            self.update_modifiers(mods_depressed | mods_latched, mods_locked);
+        }
+        Task::None
+    }
+
+    fn destroy(&mut self, this_object_id: ObjectId, _bundle: &mut Bundle) -> Task {
+        Task::Destroy { id: this_object_id }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Drop for VirtualKeyboard {
+    /// Releases keys and modifiers still held so they do not stay pressed after client is gone.
+    fn drop(&mut self) {
+        let keys: Vec<u32> = self.pressed_keys.keys().cloned().collect();
+        for key in keys {
+            self.release_key(key);
+        }
+        let locked_mods = self.locked_mods;
+        self.update_modifiers(0, locked_mods);
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Reads contents of keymap file skipping trailing null characters.
+fn read_keymap(fd: RawFd, size: usize) -> Option<Vec<u8>> {
+    let mut buffer = vec![0; size];
+    match nix::sys::uio::pread(fd, &mut buffer, 0) {
+        Ok(len) => {
+            buffer.truncate(len);
+            while buffer.last() == Some(&0) {
+                buffer.pop();
+            }
+            Some(buffer)
+        }
+        Err(err) => {
+            log_warn3!("Failed to read keymap of virtual keyboard: {:?}", err);
+            None
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
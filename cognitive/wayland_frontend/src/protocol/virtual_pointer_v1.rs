@@ -0,0 +1,238 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! Implementations of `zwlr_virtual_pointer_manager_v1` and `zwlr_virtual_pointer_v1` objects.
+//!
+//! Events from virtual pointer are injected into the same input path as events from evdev devices
+//! so they are subject to button bindings.
+
+use std::rc::Rc;
+
+use skylane::server::{Bundle, Object, ObjectId, Task};
+use skylane_protocols::server::wayland::wl_pointer;
+use bindings::server::Handler;
+use bindings::server::wlr_virtual_pointer_unstable_v1::zwlr_virtual_pointer_manager_v1;
+use bindings::server::wlr_virtual_pointer_unstable_v1::zwlr_virtual_pointer_v1;
+
+use qualia::{InputCode, InputValue, Position, Slide, Vector, VirtualInput};
+
+use global::Global;
+use facade::Facade;
+use proxy::ProxyRef;
+
+// -------------------------------------------------------------------------------------------------
+
+/// Distance of continuous scroll corresponding to one discrete step.
+const AXIS_STEP: f32 = 10.0;
+
+// -------------------------------------------------------------------------------------------------
+
+/// Wayland `zwlr_virtual_pointer_manager_v1` object.
+struct VirtualPointerManager {
+    proxy: ProxyRef,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+pub fn get_global() -> Global {
+    Global::new(zwlr_virtual_pointer_manager_v1::NAME,
+                zwlr_virtual_pointer_manager_v1::VERSION,
+                Rc::new(VirtualPointerManager::new_object))
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl VirtualPointerManager {
+    fn new(proxy_ref: ProxyRef) -> Self {
+        VirtualPointerManager { proxy: proxy_ref }
+    }
+
+    fn new_object(_oid: ObjectId, _version: u32, proxy_ref: ProxyRef) -> Box<Object> {
+        let manager = Self::new(proxy_ref);
+        Box::new(Handler::<_, zwlr_virtual_pointer_manager_v1::Dispatcher>::new(manager))
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl zwlr_virtual_pointer_manager_v1::Interface for VirtualPointerManager {
+    fn create_virtual_pointer(&mut self,
+                              _this_object_id: ObjectId,
+                              _bundle: &mut Bundle,
+                              _seat: ObjectId,
+                              id: ObjectId)
+                              -> Task {
+        Task::Create {
+            id: id,
+            object: VirtualPointer::new_object(self.proxy.clone()),
+        }
+    }
+
+    fn destroy(&mut self, this_object_id: ObjectId, _bundle: &mut Bundle) -> Task {
+        Task::Destroy { id: this_object_id }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Wayland `zwlr_virtual_pointer_v1` object.
+///
+/// Axis events are accumulated and injected on `frame` request.
+struct VirtualPointer {
+    proxy: ProxyRef,
+    continuous: Slide,
+    discrete: Vector,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl VirtualPointer {
+    fn new(proxy_ref: ProxyRef) -> Self {
+        VirtualPointer {
+            proxy: proxy_ref,
+            continuous: Slide::default(),
+            discrete: Vector::default(),
+        }
+    }
+
+    fn new_object(proxy_ref: ProxyRef) -> Box<Object> {
+        Box::new(Handler::<_, zwlr_virtual_pointer_v1::Dispatcher>::new(Self::new(proxy_ref)))
+    }
+
+    /// Accumulates scroll value on given axis.
+    fn accumulate_axis(&mut self, axis: u32, value: f32, discrete: i32) {
+        if axis == wl_pointer::axis::HORIZONTAL_SCROLL {
+            self.continuous.x += value;
+            self.discrete.x += discrete as isize;
+        } else {
+            self.continuous.y += value;
+            self.discrete.y += discrete as isize;
+        }
+    }
+
+    /// Converts accumulated scroll to steps. Discrete values take precedence over continuous.
+    ///
+    /// Wayland and evdev have opposite directions of vertical axis.
+    fn take_axis_steps(&mut self) -> Vector {
+        let horizontal = if self.discrete.x != 0 {
+            self.discrete.x
+        } else {
+            (self.continuous.x / AXIS_STEP).round() as isize
+        };
+        let vertical = if self.discrete.y != 0 {
+            self.discrete.y
+        } else {
+            (self.continuous.y / AXIS_STEP).round() as isize
+        };
+
+        self.continuous = Slide::default();
+        self.discrete = Vector::default();
+        Vector::new(horizontal, -vertical)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl zwlr_virtual_pointer_v1::Interface for VirtualPointer {
+    fn motion(&mut self,
+              _this_object_id: ObjectId,
+              _bundle: &mut Bundle,
+              _time: u32,
+              dx: f32,
+              dy: f32)
+              -> Task {
+        let vector = Vector::new(dx.round() as isize, dy.round() as isize);
+        if !vector.is_zero() {
+            self.proxy.borrow_mut().inject_input(VirtualInput::Motion(vector));
+        }
+        Task::None
+    }
+
+    fn motion_absolute(&mut self,
+                       _this_object_id: ObjectId,
+                       _bundle: &mut Bundle,
+                       _time: u32,
+                       x: u32,
+                       y: u32,
+                       x_extent: u32,
+                       y_extent: u32)
+                       -> Task {
+        if (x_extent != 0) && (y_extent != 0) {
+            let mut proxy = self.proxy.borrow_mut();
+            let area = proxy.get_screen_area();
+            let x = (x as u64 * area.size.width as u64 / x_extent as u64) as isize;
+            let y = (y as u64 * area.size.height as u64 / y_extent as u64) as isize;
+            proxy.inject_input(VirtualInput::Warp(area.pos + Position::new(x, y)));
+        }
+        Task::None
+    }
+
+    fn button(&mut self,
+              _this_object_id: ObjectId,
+              _bundle: &mut Bundle,
+              _time: u32,
+              button: u32,
+              state: u32)
+              -> Task {
+        let event = VirtualInput::Button {
+            code: button as InputCode,
+            value: state as InputValue,
+        };
+        self.proxy.borrow_mut().inject_input(event);
+        Task::None
+    }
+
+    fn axis(&mut self,
+            _this_object_id: ObjectId,
+            _bundle: &mut Bundle,
+            _time: u32,
+            axis: u32,
+            value: f32)
+            -> Task {
+        self.accumulate_axis(axis, value, 0);
+        Task::None
+    }
+
+    fn frame(&mut self, _this_object_id: ObjectId, _bundle: &mut Bundle) -> Task {
+        let steps = self.take_axis_steps();
+        if !steps.is_zero() {
+            self.proxy.borrow_mut().inject_input(VirtualInput::Axis(steps));
+        }
+        Task::None
+    }
+
+    fn axis_source(&mut self,
+                   _this_object_id: ObjectId,
+                   _bundle: &mut Bundle,
+                   _axis_source: u32)
+                   -> Task {
+        Task::None
+    }
+
+    fn axis_stop(&mut self,
+                 _this_object_id: ObjectId,
+                 _bundle: &mut Bundle,
+                 _time: u32,
+                 _axis: u32)
+                 -> Task {
+        Task::None
+    }
+
+    fn axis_discrete(&mut self,
+                     _this_object_id: ObjectId,
+                     _bundle: &mut Bundle,
+                     _time: u32,
+                     axis: u32,
+                     value: f32,
+                     discrete: i32)
+                     -> Task {
+        self.accumulate_axis(axis, value, discrete);
+        Task::None
+    }
+
+    fn destroy(&mut self, this_object_id: ObjectId, _bundle: &mut Bundle) -> Task {
+        Task::Destroy { id: this_object_id }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
@@ -0,0 +1,166 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! Implementations of `xdg_activation_v1` and `xdg_activation_token_v1` objects.
+//!
+//! Tokens are valid only if requested for surface having keyboard focus at the time of commit.
+//! Surfaces activated with invalid, expired or unknown tokens are not focused but marked as
+//! demanding attention.
+
+use std::rc::Rc;
+
+use skylane::server as wl;
+use skylane::server::{Bundle, Object, ObjectId, Task};
+use skylane_protocols::server::wayland::wl_display;
+use bindings::server::Handler;
+use bindings::server::xdg_activation_v1::{xdg_activation_v1, xdg_activation_token_v1};
+
+use global::Global;
+use facade::Facade;
+use proxy::ProxyRef;
+
+// -------------------------------------------------------------------------------------------------
+
+/// Wayland `xdg_activation_v1` object.
+struct Activation {
+    proxy: ProxyRef,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+pub fn get_global() -> Global {
+    Global::new(xdg_activation_v1::NAME,
+                xdg_activation_v1::VERSION,
+                Rc::new(Activation::new_object))
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Activation {
+    fn new(proxy_ref: ProxyRef) -> Self {
+        Activation { proxy: proxy_ref }
+    }
+
+    fn new_object(_oid: ObjectId, _version: u32, proxy_ref: ProxyRef) -> Box<Object> {
+        let object = Self::new(proxy_ref);
+        Box::new(Handler::<_, xdg_activation_v1::Dispatcher>::new(object))
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl xdg_activation_v1::Interface for Activation {
+    fn destroy(&mut self, this_object_id: ObjectId, _bundle: &mut Bundle) -> Task {
+        Task::Destroy { id: this_object_id }
+    }
+
+    fn get_activation_token(&mut self,
+                            _this_object_id: ObjectId,
+                            _bundle: &mut Bundle,
+                            id: ObjectId)
+                            -> Task {
+        Task::Create {
+            id: id,
+            object: ActivationToken::new_object(self.proxy.clone()),
+        }
+    }
+
+    fn activate(&mut self,
+                _this_object_id: ObjectId,
+                _bundle: &mut Bundle,
+                token: String,
+                surface: ObjectId)
+                -> Task {
+        self.proxy.borrow_mut().activate_surface(&token, surface);
+        Task::None
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Wayland `xdg_activation_token_v1` object.
+struct ActivationToken {
+    proxy: ProxyRef,
+    surface_oid: Option<ObjectId>,
+    serial: Option<u32>,
+    is_committed: bool,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl ActivationToken {
+    fn new_object(proxy_ref: ProxyRef) -> Box<Object> {
+        let object = ActivationToken {
+            proxy: proxy_ref,
+            surface_oid: None,
+            serial: None,
+            is_committed: false,
+        };
+        Box::new(Handler::<_, xdg_activation_token_v1::Dispatcher>::new(object))
+    }
+
+    /// Checks if token was not committed yet. Otherwise posts protocol error and returns `false`.
+    fn check_not_committed(&self, this_object_id: ObjectId, bundle: &mut Bundle) -> bool {
+        if self.is_committed {
+            send!(wl_display::error(&bundle.get_socket(),
+                                    wl::DISPLAY_ID,
+                                    this_object_id,
+                                    xdg_activation_token_v1::error::ALREADY_USED,
+                                    "Activation token was already committed"));
+        }
+        !self.is_committed
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl xdg_activation_token_v1::Interface for ActivationToken {
+    fn set_serial(&mut self,
+                  this_object_id: ObjectId,
+                  bundle: &mut Bundle,
+                  serial: u32,
+                  _seat: ObjectId)
+                  -> Task {
+        if self.check_not_committed(this_object_id, bundle) {
+            self.serial = Some(serial);
+        }
+        Task::None
+    }
+
+    fn set_app_id(&mut self,
+                  this_object_id: ObjectId,
+                  bundle: &mut Bundle,
+                  _app_id: String)
+                  -> Task {
+        self.check_not_committed(this_object_id, bundle);
+        Task::None
+    }
+
+    fn set_surface(&mut self,
+                   this_object_id: ObjectId,
+                   bundle: &mut Bundle,
+                   surface: ObjectId)
+                   -> Task {
+        if self.check_not_committed(this_object_id, bundle) {
+            self.surface_oid = Some(surface);
+        }
+        Task::None
+    }
+
+    fn commit(&mut self, this_object_id: ObjectId, bundle: &mut Bundle) -> Task {
+        if self.check_not_committed(this_object_id, bundle) {
+            self.is_committed = true;
+            let token = self.proxy
+                .borrow_mut()
+                .create_activation_token(self.surface_oid, self.serial);
+            send!(xdg_activation_token_v1::done(&bundle.get_socket(), this_object_id, &token));
+        }
+        Task::None
+    }
+
+    fn destroy(&mut self, this_object_id: ObjectId, _bundle: &mut Bundle) -> Task {
+        Task::Destroy { id: this_object_id }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
@@ -16,7 +16,7 @@ use skylane_protocols::server::xdg_shell_unstable_v6::zxdg_surface_v6;
 use skylane_protocols::server::xdg_shell_unstable_v6::zxdg_toplevel_v6;
 use skylane_protocols::server::xdg_shell_unstable_v6::zxdg_popup_v6;
 
-use qualia::{show_reason, Area};
+use qualia::{show_reason, surface_state, Area};
 
 use facade::{Facade, PositionerInfo, ShellSurfaceOid};
 use global::Global;
@@ -299,7 +299,10 @@ impl zxdg_surface_v6::Interface for ZxdgSurfaceV6 {
 // -------------------------------------------------------------------------------------------------
 
 /// Wayland `zxdg_toplevel_v6` object.
-struct ZxdgToplevelV6 {}
+struct ZxdgToplevelV6 {
+    surface_oid: ObjectId,
+    proxy: ProxyRef,
+}
 
 // -------------------------------------------------------------------------------------------------
 
@@ -316,7 +319,10 @@ impl ZxdgToplevelV6 {
                        show_reason::IN_SHELL);
         }
 
-        ZxdgToplevelV6 {}
+        ZxdgToplevelV6 {
+            surface_oid: surface_oid,
+            proxy: proxy_ref,
+        }
     }
 
     fn new_object(oid: ObjectId,
@@ -346,6 +352,7 @@ impl zxdg_toplevel_v6::Interface for ZxdgToplevelV6 {
     }
 
     fn set_title(&mut self, this_object_id: ObjectId, bundle: &mut Bundle, title: String) -> Task {
+        self.proxy.borrow().set_title(self.surface_oid, title);
         Task::None
     }
 
@@ -354,6 +361,7 @@ impl zxdg_toplevel_v6::Interface for ZxdgToplevelV6 {
                   bundle: &mut Bundle,
                   app_id: String)
                   -> Task {
+        self.proxy.borrow().set_app_id(self.surface_oid, app_id);
         Task::None
     }
 
@@ -406,10 +414,12 @@ impl zxdg_toplevel_v6::Interface for ZxdgToplevelV6 {
     }
 
     fn set_maximized(&mut self, this_object_id: ObjectId, bundle: &mut Bundle) -> Task {
+        self.proxy.borrow().request_state(self.surface_oid, surface_state::MAXIMIZED, true);
         Task::None
     }
 
     fn unset_maximized(&mut self, this_object_id: ObjectId, bundle: &mut Bundle) -> Task {
+        self.proxy.borrow().request_state(self.surface_oid, surface_state::MAXIMIZED, false);
         Task::None
     }
 
@@ -418,10 +428,12 @@ impl zxdg_toplevel_v6::Interface for ZxdgToplevelV6 {
                       bundle: &mut Bundle,
                       output: ObjectId)
                       -> Task {
+        self.proxy.borrow().request_state(self.surface_oid, surface_state::FULLSCREEN, true);
         Task::None
     }
 
     fn unset_fullscreen(&mut self, this_object_id: ObjectId, bundle: &mut Bundle) -> Task {
+        self.proxy.borrow().request_state(self.surface_oid, surface_state::FULLSCREEN, false);
         Task::None
     }
 
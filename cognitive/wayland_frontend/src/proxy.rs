@@ -6,7 +6,8 @@
 // -------------------------------------------------------------------------------------------------
 
 use std;
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::path::PathBuf;
 use std::os::unix::io::RawFd;
 
@@ -18,13 +19,16 @@ use skylane_protocols::server::wayland::{wl_data_source, wl_data_device, wl_data
 use skylane_protocols::server::wayland::wl_shell_surface;
 use skylane_protocols::server::xdg_shell_unstable_v6::{zxdg_toplevel_v6, zxdg_surface_v6};
 use skylane_protocols::server::weston_screenshooter::weston_screenshooter;
+use bindings::server::ext_session_lock_v1::ext_session_lock_v1;
+use bindings::server::ext_session_lock_v1::ext_session_lock_surface_v1;
+use bindings::server::keyboard_shortcuts_inhibit_unstable_v1::zwp_keyboard_shortcuts_inhibitor_v1;
 
 use cognitive_graphics::attributes::{EglAttributes, DmabufAttributes};
 use qualia::{Settings, Transfer, DrmBundle, Memory};
 use qualia::{Area, Axis, Button, Key, Milliseconds};
 use qualia::{OutputInfo, PixelFormat, Position, Size, Vector};
 use qualia::{DmabufId, EglImageId, MemoryPoolId, MemoryViewId};
-use qualia::{show_reason, surface_state, SurfaceId};
+use qualia::{show_reason, surface_state, SurfaceId, Viewport, VirtualInput};
 use qualia::FrontendsCoordinationTrait;
 use inputs::KeyMods;
 
@@ -36,6 +40,12 @@ use mediator::MediatorRef;
 
 // -------------------------------------------------------------------------------------------------
 
+/// Number of serials of the most recent key and button presses remembered for validating
+/// activation token requests.
+const NUM_PRESS_SERIALS: usize = 8;
+
+// -------------------------------------------------------------------------------------------------
+
 /// Helper macro for relating surface information with surface ID
 macro_rules! relate_sid_with {
     ($member:ident, $dict:expr, $sid:ident, $obj:ident) => {
@@ -107,27 +117,43 @@ enum BufferInfo {
     Shm {
         mpid: MemoryPoolId,
         mvid: MemoryViewId,
+        size: Size,
     },
-    EglImage { eiid: EglImageId },
-    Dmabuf { dbid: DmabufId },
+    EglImage { eiid: EglImageId, size: Size },
+    Dmabuf { dbid: DmabufId, size: Size },
 }
 
 // -------------------------------------------------------------------------------------------------
 
 impl BufferInfo {
-    pub fn new_shm(mpid: MemoryPoolId, mvid: MemoryViewId) -> Self {
+    pub fn new_shm(mpid: MemoryPoolId, mvid: MemoryViewId, size: Size) -> Self {
         BufferInfo::Shm {
             mpid: mpid,
             mvid: mvid,
+            size: size,
         }
     }
 
-    pub fn new_egl_image(eiid: EglImageId) -> Self {
-        BufferInfo::EglImage { eiid: eiid }
+    pub fn new_egl_image(eiid: EglImageId, size: Size) -> Self {
+        BufferInfo::EglImage {
+            eiid: eiid,
+            size: size,
+        }
     }
 
-    pub fn new_dmabuf(dbid: DmabufId) -> Self {
-        BufferInfo::Dmabuf { dbid: dbid }
+    pub fn new_dmabuf(dbid: DmabufId, size: Size) -> Self {
+        BufferInfo::Dmabuf {
+            dbid: dbid,
+            size: size,
+        }
+    }
+
+    pub fn get_size(&self) -> Size {
+        match *self {
+            BufferInfo::Shm { size, .. } => size,
+            BufferInfo::EglImage { size, .. } => size,
+            BufferInfo::Dmabuf { size, .. } => size,
+        }
     }
 }
 
@@ -163,6 +189,7 @@ pub struct Proxy {
     memory_pools: HashSet<MemoryPoolId>,
     surface_oid_to_sid_dict: HashMap<wl::ObjectId, SurfaceId>,
     sid_to_surface_info_dict: HashMap<SurfaceId, SurfaceInfo>,
+    pending_viewports: HashMap<SurfaceId, (wl::ObjectId, Viewport)>,
 
     /// Surface object does not know if its buffer is memory shared or hardware image. It can only
     /// tell its buffer object ID, so we map this ID to structure defining how it can be attached.
@@ -170,6 +197,14 @@ pub struct Proxy {
     output_oid_to_id: HashMap<wl::ObjectId, i32>,
     screenshooter_oid: Option<wl::ObjectId>,
     screenshot_memory: Option<Memory>,
+    session_lock_oid: Option<wl::ObjectId>,
+    is_session_lock_confirmed: bool,
+    shortcuts_inhibitors: HashMap<SurfaceId, wl::ObjectId>,
+    active_shortcuts_inhibitor: Option<wl::ObjectId>,
+
+    /// Serials of the most recent key and button press events sent to client. Activation tokens
+    /// can be requested only in response to user input.
+    press_serials: RefCell<VecDeque<u32>>,
     last_global_id: u32,
 }
 
@@ -202,10 +237,16 @@ impl Proxy {
             memory_pools: HashSet::new(),
             surface_oid_to_sid_dict: HashMap::new(),
             sid_to_surface_info_dict: HashMap::new(),
+            pending_viewports: HashMap::new(),
             buffer_oid_to_info_dict: HashMap::new(),
             output_oid_to_id: HashMap::new(),
             screenshooter_oid: None,
             screenshot_memory: None,
+            session_lock_oid: None,
+            is_session_lock_confirmed: false,
+            shortcuts_inhibitors: HashMap::new(),
+            active_shortcuts_inhibitor: None,
+            press_serials: RefCell::new(VecDeque::with_capacity(NUM_PRESS_SERIALS)),
             last_global_id: 0,
         }
     }
@@ -232,8 +273,24 @@ impl Proxy {
         self.globals.insert(self.last_global_id, global);
     }
 
+    /// Returns surface ID of surface with given object ID.
+    pub fn get_sid_for_surface_oid(&self, surface_oid: wl::ObjectId) -> Option<SurfaceId> {
+        self.surface_oid_to_sid_dict.get(&surface_oid).cloned()
+    }
+
     /// Handles termination of client by destroying its resources.
+    ///
+    /// If the client locked the session, the session stays locked.
     pub fn terminate(&mut self) {
+        if self.session_lock_oid.take().is_some() {
+            log_warn1!("Client {} locking the session terminated", self.client_id);
+            self.mediator.borrow_mut().unregister_session_locker(self.client_id);
+        }
+
+        if self.active_shortcuts_inhibitor.take().is_some() {
+            self.coordinator.inhibit_shortcuts(false);
+        }
+
         for mpid in self.memory_pools.iter() {
             self.coordinator.destroy_memory_pool(*mpid);
         }
@@ -272,6 +329,45 @@ impl Proxy {
 
 // -------------------------------------------------------------------------------------------------
 
+impl Proxy {
+    /// Activates key bindings inhibitor of given surface if it has one.
+    fn activate_shortcuts_inhibitor(&mut self, sid: SurfaceId) {
+        if let Some(&inhibitor_oid) = self.shortcuts_inhibitors.get(&sid) {
+            if self.active_shortcuts_inhibitor != Some(inhibitor_oid) {
+                send!(zwp_keyboard_shortcuts_inhibitor_v1::active(&self.socket, inhibitor_oid));
+                self.active_shortcuts_inhibitor = Some(inhibitor_oid);
+                self.coordinator.inhibit_shortcuts(true);
+            }
+        }
+    }
+
+    /// Deactivates currently active key bindings inhibitor. If `inform` is `true` the rest of
+    /// application is informed bindings should be executed again.
+    fn deactivate_shortcuts_inhibitor(&mut self, inform: bool) {
+        if let Some(inhibitor_oid) = self.active_shortcuts_inhibitor.take() {
+            send!(zwp_keyboard_shortcuts_inhibitor_v1::inactive(&self.socket, inhibitor_oid));
+            if inform {
+                self.coordinator.inhibit_shortcuts(false);
+            }
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Proxy {
+    /// Remembers serial of key or button press event. The oldest serials are forgotten.
+    fn remember_press_serial(&self, serial: u32) {
+        let mut serials = self.press_serials.borrow_mut();
+        if serials.len() >= NUM_PRESS_SERIALS {
+            serials.pop_front();
+        }
+        serials.push_back(serial);
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
 impl Proxy {
     /// Helper method for unsetting shell information for surface.
     fn unrelate_sid_with_shell_surface(&mut self, sid: SurfaceId) {
@@ -353,7 +449,7 @@ impl Facade for Proxy {
         let result =
             self.coordinator.create_memory_view(mpid, format, offset, width, height, stride);
         if let Some(mvid) = result {
-            let info = BufferInfo::new_shm(mpid, mvid);
+            let info = BufferInfo::new_shm(mpid, mvid, Size::new(width, height));
             self.buffer_oid_to_info_dict.insert(buffer_oid, info);
         }
         result
@@ -367,9 +463,10 @@ impl Facade for Proxy {
                         buffer_oid: wl::ObjectId,
                         attrs: EglAttributes)
                         -> Option<EglImageId> {
+        let size = Size::new(attrs.width as usize, attrs.height as usize);
         let eiid = self.coordinator.create_egl_image(attrs);
         if let Some(eiid) = eiid {
-            let info = BufferInfo::new_egl_image(eiid);
+            let info = BufferInfo::new_egl_image(eiid, size);
             self.buffer_oid_to_info_dict.insert(buffer_oid, info);
         }
         eiid
@@ -383,9 +480,10 @@ impl Facade for Proxy {
                      buffer_oid: wl::ObjectId,
                      attrs: DmabufAttributes)
                      -> Option<DmabufId> {
+        let size = Size::new(attrs.width as usize, attrs.height as usize);
         let dbid = self.coordinator.import_dmabuf(attrs);
         if let Some(dbid) = dbid {
-            let info = BufferInfo::new_dmabuf(dbid);
+            let info = BufferInfo::new_dmabuf(dbid, size);
             self.buffer_oid_to_info_dict.insert(buffer_oid, info);
         }
         dbid
@@ -489,7 +587,9 @@ impl Facade for Proxy {
         sid
     }
 
-    fn destroy_surface(&self, sid: SurfaceId) {
+    fn destroy_surface(&mut self, sid: SurfaceId) {
+        // Viewport may outlive its surface. It becomes defunct then.
+        self.pending_viewports.remove(&sid);
         self.coordinator.destroy_surface(sid)
     }
 
@@ -502,13 +602,13 @@ impl Facade for Proxy {
         } else if let Some(info) = self.buffer_oid_to_info_dict.get(&buffer_oid).cloned() {
             self.relate_sid_with_buffer(sid, buffer_oid);
             match info {
-                BufferInfo::Shm { mpid, mvid } => {
+                BufferInfo::Shm { mvid, .. } => {
                     self.coordinator.attach_shm(mvid, sid);
                 }
-                BufferInfo::EglImage { eiid } => {
+                BufferInfo::EglImage { eiid, .. } => {
                     self.coordinator.attach_egl_image(eiid, sid);
                 }
-                BufferInfo::Dmabuf { dbid } => {
+                BufferInfo::Dmabuf { dbid, .. } => {
                     self.coordinator.attach_dmabuf(dbid, sid);
                 }
             }
@@ -517,8 +617,22 @@ impl Facade for Proxy {
         }
     }
 
-    fn commit(&self, sid: SurfaceId) {
+    fn commit(&self, sid: SurfaceId) -> Result<(), wl::ObjectId> {
+        if let Some(&(viewport_oid, viewport)) = self.pending_viewports.get(&sid) {
+            let buffer_size = self.sid_to_surface_info_dict
+                .get(&sid)
+                .and_then(|info| info.buffer_oid)
+                .and_then(|oid| self.buffer_oid_to_info_dict.get(&oid))
+                .map(|info| info.get_size());
+            if let (Some(source), Some(size)) = (viewport.source, buffer_size) {
+                if !source.fits_in(size) {
+                    return Err(viewport_oid);
+                }
+            }
+            self.coordinator.set_surface_viewport(sid, viewport);
+        }
         self.coordinator.commit_surface(sid);
+        Ok(())
     }
 
     fn set_frame(&mut self, sid: SurfaceId, frame_oid: wl::ObjectId) {
@@ -554,6 +668,55 @@ impl Facade for Proxy {
         self.coordinator.set_surface_requested_size(sid, size);
     }
 
+    fn add_viewport(&mut self,
+                    viewport_oid: wl::ObjectId,
+                    surface_oid: wl::ObjectId)
+                    -> Option<SurfaceId> {
+        if let Some(&sid) = self.surface_oid_to_sid_dict.get(&surface_oid) {
+            if !self.pending_viewports.contains_key(&sid) {
+                self.pending_viewports.insert(sid, (viewport_oid, Viewport::new()));
+                return Some(sid);
+            }
+        }
+        None
+    }
+
+    fn set_viewport(&mut self, sid: SurfaceId, viewport: Viewport) -> bool {
+        if let Some(&mut (_, ref mut pending_viewport)) = self.pending_viewports.get_mut(&sid) {
+            *pending_viewport = viewport;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn remove_viewport(&mut self, sid: SurfaceId) {
+        if self.pending_viewports.remove(&sid).is_some() {
+            self.coordinator.set_surface_viewport(sid, Viewport::new());
+        }
+    }
+
+    fn set_title(&self, surface_oid: wl::ObjectId, title: String) {
+        if let Some(&sid) = self.surface_oid_to_sid_dict.get(&surface_oid) {
+            self.coordinator.set_surface_title(sid, title);
+        }
+    }
+
+    fn set_app_id(&self, surface_oid: wl::ObjectId, app_id: String) {
+        if let Some(&sid) = self.surface_oid_to_sid_dict.get(&surface_oid) {
+            self.coordinator.set_surface_app_id(sid, app_id);
+        }
+    }
+
+    fn request_state(&self,
+                     surface_oid: wl::ObjectId,
+                     state: surface_state::SurfaceState,
+                     enable: bool) {
+        if let Some(&sid) = self.surface_oid_to_sid_dict.get(&surface_oid) {
+            self.coordinator.request_surface_state(sid, state, enable);
+        }
+    }
+
     fn set_relative_position(&self, surface_oid: wl::ObjectId, x: isize, y: isize) {
         if let Some(&sid) = self.surface_oid_to_sid_dict.get(&surface_oid) {
             let position = Position::new(x, y);
@@ -593,7 +756,7 @@ impl Facade for Proxy {
                        output_oid: wl::ObjectId,
                        buffer_oid: wl::ObjectId) {
         // Destroy memory pool to be used to transfer screenshot.
-        if let Some(&BufferInfo::Shm { mpid, mvid }) =
+        if let Some(&BufferInfo::Shm { mpid, .. }) =
             self.buffer_oid_to_info_dict.get(&buffer_oid) {
             self.screenshot_memory = self.coordinator.destroy_memory_pool(mpid);
         }
@@ -625,6 +788,126 @@ impl Facade for Proxy {
     fn get_drm_device_path(&self) -> Option<PathBuf> {
         self.mediator.borrow().get_drm_device_path()
     }
+
+    fn inject_input(&mut self, event: VirtualInput) {
+        self.coordinator.inject_input(event);
+    }
+
+    fn get_screen_area(&self) -> Area {
+        self.mediator.borrow().get_screen_area()
+    }
+
+    fn lock_session(&mut self, lock_oid: wl::ObjectId) -> bool {
+        if self.session_lock_oid.is_some() ||
+           !self.mediator.borrow_mut().register_session_locker(self.client_id) {
+            return false;
+        }
+
+        self.session_lock_oid = Some(lock_oid);
+        self.is_session_lock_confirmed = false;
+        self.coordinator.lock_session();
+        true
+    }
+
+    fn unlock_session(&mut self, lock_oid: wl::ObjectId) {
+        if self.session_lock_oid == Some(lock_oid) {
+            self.session_lock_oid = None;
+            self.is_session_lock_confirmed = false;
+            self.mediator.borrow_mut().unregister_session_locker(self.client_id);
+            self.coordinator.unlock_session();
+        }
+    }
+
+    fn is_session_lock_confirmed(&self, lock_oid: wl::ObjectId) -> bool {
+        (self.session_lock_oid == Some(lock_oid)) && self.is_session_lock_confirmed
+    }
+
+    fn set_lock_surface(&mut self,
+                        lock_surface_oid: wl::ObjectId,
+                        surface_oid: wl::ObjectId,
+                        output_oid: wl::ObjectId) {
+        let sid = if let Some(&sid) = self.surface_oid_to_sid_dict.get(&surface_oid) {
+            sid
+        } else {
+            log_warn2!("No surface {:?} for lock surface", surface_oid);
+            return;
+        };
+
+        let display_id = if let Some(&display_id) = self.output_oid_to_id.get(&output_oid) {
+            display_id
+        } else {
+            log_warn2!("No output {:?} for lock surface", output_oid);
+            return;
+        };
+
+        if let Some(area) = self.mediator.borrow().get_display_area(display_id) {
+            let serial = self.socket.get_next_serial();
+            send!(ext_session_lock_surface_v1::configure(&self.socket,
+                                                         lock_surface_oid,
+                                                         serial,
+                                                         area.size.width as u32,
+                                                         area.size.height as u32));
+        }
+
+        self.coordinator.set_lock_surface(sid, display_id);
+    }
+
+    fn add_shortcuts_inhibitor(&mut self,
+                               inhibitor_oid: wl::ObjectId,
+                               surface_oid: wl::ObjectId)
+                               -> bool {
+        let sid = if let Some(&sid) = self.surface_oid_to_sid_dict.get(&surface_oid) {
+            sid
+        } else {
+            log_warn2!("No surface {:?} for shortcuts inhibitor", surface_oid);
+            return true;
+        };
+
+        if self.shortcuts_inhibitors.contains_key(&sid) {
+            return false;
+        }
+
+        self.shortcuts_inhibitors.insert(sid, inhibitor_oid);
+        if sid == self.coordinator.get_keyboard_focused_sid() {
+            self.activate_shortcuts_inhibitor(sid);
+        }
+        true
+    }
+
+    fn remove_shortcuts_inhibitor(&mut self, inhibitor_oid: wl::ObjectId) {
+        self.shortcuts_inhibitors.retain(|_, oid| *oid != inhibitor_oid);
+        if self.active_shortcuts_inhibitor == Some(inhibitor_oid) {
+            self.active_shortcuts_inhibitor = None;
+            self.coordinator.inhibit_shortcuts(false);
+        }
+    }
+
+    fn create_activation_token(&mut self,
+                               surface_oid: Option<wl::ObjectId>,
+                               serial: Option<u32>)
+                               -> String {
+        let sid = surface_oid.and_then(|oid| self.surface_oid_to_sid_dict.get(&oid).cloned());
+        let is_valid = match (sid, serial) {
+            (Some(sid), Some(serial)) => {
+                sid == self.coordinator.get_keyboard_focused_sid() &&
+                self.press_serials.borrow().contains(&serial)
+            }
+            _ => false,
+        };
+        self.mediator.borrow_mut().issue_activation_token(is_valid)
+    }
+
+    fn activate_surface(&mut self, token: &str, surface_oid: wl::ObjectId) {
+        let sid = if let Some(&sid) = self.surface_oid_to_sid_dict.get(&surface_oid) {
+            sid
+        } else {
+            log_warn2!("No surface {:?} to activate", surface_oid);
+            return;
+        };
+
+        let granted = self.mediator.borrow_mut().consume_activation_token(token);
+        self.coordinator.activate_surface(sid, granted);
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -645,6 +928,9 @@ impl Gateway for Proxy {
                                    key.time.get_value() as u32,
                                    key.code as u32,
                                    key.value as u32));
+            if key.value != 0 {
+                self.remember_press_serial(serial);
+            }
 
             if let Some(mods) = mods {
                 serial = self.socket.get_next_serial();
@@ -724,6 +1010,7 @@ impl Gateway for Proxy {
         let state = if btn.value == 0 {
             wl_pointer::button_state::RELEASED
         } else {
+            self.remember_press_serial(serial);
             wl_pointer::button_state::PRESSED
         };
 
@@ -794,6 +1081,10 @@ impl Gateway for Proxy {
     }
 
     fn on_keyboard_focus_changed(&mut self, old_sid: SurfaceId, new_sid: SurfaceId) {
+        if old_sid != new_sid {
+            self.deactivate_shortcuts_inhibitor(true);
+        }
+
         if old_sid != SurfaceId::invalid() {
             if let Some(surface_info) = self.sid_to_surface_info_dict.get(&old_sid) {
                 if let Some(surface_oid) = surface_info.surface_oid {
@@ -834,6 +1125,7 @@ impl Gateway for Proxy {
                     }
                 }
             }
+            self.activate_shortcuts_inhibitor(new_sid);
         }
     }
 
@@ -862,11 +1154,15 @@ impl Gateway for Proxy {
                     }
                     ShellSurfaceOid::ZxdgToplevelV6(shell_surface_oid, shell_toplevel_oid) => {
                         let mut pos = 0;
-                        let mut states: [u32; 2] = [0; 2];
+                        let mut states: [u32; 3] = [0; 3];
                         if state_flags.intersects(surface_state::MAXIMIZED) {
                             states[pos] = zxdg_toplevel_v6::state::MAXIMIZED;
                             pos += 1;
                         }
+                        if state_flags.intersects(surface_state::FULLSCREEN) {
+                            states[pos] = zxdg_toplevel_v6::state::FULLSCREEN;
+                            pos += 1;
+                        }
                         if sid == self.coordinator.get_keyboard_focused_sid() {
                             states[pos] = zxdg_toplevel_v6::state::ACTIVATED;
                             pos += 1;
@@ -879,6 +1175,9 @@ impl Gateway for Proxy {
                         let serial = self.socket.get_next_serial();
                         send!(zxdg_surface_v6::configure(&self.socket, shell_surface_oid, serial));
                     }
+                    ShellSurfaceOid::Xwayland(_) => {
+                        // X11 windows are configured by window manager.
+                    }
                 }
             } else {
                 log_warn3!("Received reconfiguration request for surface {:?} \
@@ -888,6 +1187,30 @@ impl Gateway for Proxy {
         }
     }
 
+    fn on_surface_moved(&mut self, _sid: SurfaceId, _position: Position) {
+        // Wayland surfaces are not informed about their global position.
+    }
+
+    fn on_surface_close_requested(&self, sid: SurfaceId) {
+        if let Some(info) = self.sid_to_surface_info_dict.get(&sid) {
+            if let Some(shell_surface) = info.shell_surface_oid {
+                match shell_surface {
+                    ShellSurfaceOid::Shell(_) => {
+                        log_warn2!("Surface {:?} can not be closed: `wl_shell` does not support \
+                                   closing",
+                                   sid);
+                    }
+                    ShellSurfaceOid::ZxdgToplevelV6(_, shell_toplevel_oid) => {
+                        send!(zxdg_toplevel_v6::close(&self.socket, shell_toplevel_oid));
+                    }
+                    ShellSurfaceOid::Xwayland(_) => {
+                        // X11 windows are closed by window manager.
+                    }
+                }
+            }
+        }
+    }
+
     fn on_screenshot_done(&mut self) {
         if let Some(screenshooter_oid) = self.screenshooter_oid {
             if let Some(ref mut screenshot_memory) = self.screenshot_memory {
@@ -904,6 +1227,20 @@ impl Gateway for Proxy {
             self.mediator.borrow_mut().register_screenshoter(None);
         }
     }
+
+    fn on_session_locked(&mut self) {
+        if let Some(lock_oid) = self.session_lock_oid {
+            if !self.is_session_lock_confirmed {
+                send!(ext_session_lock_v1::locked(&self.socket, lock_oid));
+                self.is_session_lock_confirmed = true;
+            }
+        }
+    }
+
+    fn on_shortcuts_inhibition_escaped(&mut self) {
+        // Input manager already stopped the inhibition.
+        self.deactivate_shortcuts_inhibitor(false);
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
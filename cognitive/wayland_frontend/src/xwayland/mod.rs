@@ -0,0 +1,10 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! This module contains support for X11 clients: spawning `Xwayland` and managing its windows.
+
+mod server;
+mod window_manager;
+
+pub use self::server::XServer;
+pub use self::window_manager::WindowManager;
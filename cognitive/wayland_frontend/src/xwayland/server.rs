@@ -0,0 +1,373 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! This module contains code for setting up X11 display and spawning `Xwayland`.
+
+// -------------------------------------------------------------------------------------------------
+
+use std;
+use std::fs;
+use std::io::{Read, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::os::unix::io::RawFd;
+use std::os::unix::process::CommandExt;
+use std::path::PathBuf;
+use std::process::{Child, Command};
+
+use nix;
+use nix::fcntl;
+use nix::sys::socket;
+use nix::unistd;
+
+use skylane::server as wl;
+
+// -------------------------------------------------------------------------------------------------
+
+/// Directory containing X11 sockets.
+const SOCKET_DIR: &'static str = "/tmp/.X11-unix";
+
+/// Maximal number of X11 display tried when searching for free one.
+const MAX_DISPLAY: u32 = 32;
+
+// -------------------------------------------------------------------------------------------------
+
+/// File descriptors of connections to spawned `Xwayland`.
+pub struct XwaylandFds {
+    /// Compositor side of Wayland connection.
+    pub wayland_socket: wl::Socket,
+
+    /// Compositor side of X11 window manager connection.
+    pub wm_fd: RawFd,
+
+    /// Read end of pipe `Xwayland` writes display number to when it is ready.
+    pub ready_fd: RawFd,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// `XServer` holds X11 display (lock file and listening sockets) and spawns `Xwayland` on demand.
+///
+/// Listening sockets are passed to `Xwayland` which accepts connections on its own. `Xwayland`
+/// is started with `-terminate` flag so it exits after the last X11 client disconnects and sockets
+/// have to be watched again.
+pub struct XServer {
+    display: u32,
+    lock_path: PathBuf,
+    socket_path: PathBuf,
+    listen_fds: Vec<RawFd>,
+    process: Option<Child>,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Public methods.
+impl XServer {
+    /// Finds free X11 display, locks it and creates listening sockets for it.
+    pub fn new() -> Option<Self> {
+        if let Err(err) = fs::create_dir_all(SOCKET_DIR) {
+            log_warn1!("Failed to create X11 socket directory: {:?}", err);
+            return None;
+        }
+
+        for display in 0..MAX_DISPLAY {
+            let lock_path = PathBuf::from(format!("/tmp/.X{}-lock", display));
+            if !Self::acquire_lock(&lock_path) {
+                continue;
+            }
+
+            let socket_path = PathBuf::from(format!("{}/X{}", SOCKET_DIR, display));
+            match Self::create_sockets(&socket_path) {
+                Ok(fds) => {
+                    log_info1!("Using X11 display :{}", display);
+                    return Some(XServer {
+                                    display: display,
+                                    lock_path: lock_path,
+                                    socket_path: socket_path,
+                                    listen_fds: fds,
+                                    process: None,
+                                });
+                }
+                Err(err) => {
+                    log_warn2!("Failed to create sockets for X11 display {}: {:?}", display, err);
+                    Self::remove_file(&lock_path);
+                }
+            }
+        }
+
+        log_warn1!("Failed to find free X11 display");
+        None
+    }
+
+    /// Returns name of the display in format suitable for `DISPLAY` environment variable.
+    pub fn get_display_name(&self) -> String {
+        format!(":{}", self.display)
+    }
+
+    /// Returns file descriptors of listening sockets.
+    pub fn get_listen_fds(&self) -> &Vec<RawFd> {
+        &self.listen_fds
+    }
+
+    /// Checks if `Xwayland` was spawned and did not terminate yet.
+    pub fn is_running(&self) -> bool {
+        self.process.is_some()
+    }
+
+    /// Spawns `Xwayland` process.
+    ///
+    /// Returns compositor sides of connections to `Xwayland` or `None` if spawning failed.
+    pub fn spawn(&mut self) -> Option<XwaylandFds> {
+        let (wayland_socket, xwayland_wayland_fd) = match self.connect_wayland() {
+            Some(connection) => connection,
+            None => return None,
+        };
+        let wayland_fd = wayland_socket.get_fd();
+
+        let wm_fds = socket::socketpair(socket::AddressFamily::Unix,
+                                        socket::SockType::Stream,
+                                        0,
+                                        socket::SOCK_CLOEXEC);
+        let ready_fds = unistd::pipe2(fcntl::O_CLOEXEC);
+        let (wm_fd, xwayland_wm_fd, ready_fd, xwayland_ready_fd) =
+            match (wm_fds, ready_fds) {
+                (Ok(wm_fds), Ok(ready_fds)) => (wm_fds.0, wm_fds.1, ready_fds.0, ready_fds.1),
+                (wm_fds, ready_fds) => {
+                    log_warn1!("Failed to create connections for Xwayland");
+                    Self::close_all(&[wayland_fd, xwayland_wayland_fd]);
+                    for fds in vec![wm_fds, ready_fds] {
+                        if let Ok(fds) = fds {
+                            Self::close_all(&[fds.0, fds.1]);
+                        }
+                    }
+                    return None;
+                }
+            };
+
+        let mut inherited_fds = vec![xwayland_wayland_fd, xwayland_wm_fd, xwayland_ready_fd];
+        let mut command = Command::new("Xwayland");
+        command.arg(self.get_display_name())
+            .arg("-rootless")
+            .arg("-terminate")
+            .arg("-displayfd")
+            .arg(xwayland_ready_fd.to_string())
+            .arg("-wm")
+            .arg(xwayland_wm_fd.to_string())
+            .env("WAYLAND_SOCKET", xwayland_wayland_fd.to_string());
+        for fd in self.listen_fds.iter() {
+            command.arg("-listen").arg(fd.to_string());
+            inherited_fds.push(*fd);
+        }
+
+        // All descriptors are created with `CLOEXEC` flag so they do not leak to other spawned
+        // processes. Here the flag is cleared only for `Xwayland`.
+        let child_fds = inherited_fds.clone();
+        command.before_exec(move || {
+            for fd in child_fds.iter() {
+                if fcntl::fcntl(*fd, fcntl::FcntlArg::F_SETFD(fcntl::FdFlag::empty())).is_err() {
+                    return Err(std::io::Error::last_os_error());
+                }
+            }
+            Ok(())
+        });
+
+        let result = command.spawn();
+        Self::close_all(&[xwayland_wayland_fd, xwayland_wm_fd, xwayland_ready_fd]);
+        match result {
+            Ok(child) => {
+                log_info1!("Spawned Xwayland ({})", child.id());
+                self.process = Some(child);
+                Some(XwaylandFds {
+                         wayland_socket: wayland_socket,
+                         wm_fd: wm_fd,
+                         ready_fd: ready_fd,
+                     })
+            }
+            Err(err) => {
+                log_error!("Failed to spawn Xwayland: {}", err);
+                Self::close_all(&[wayland_fd, wm_fd, ready_fd]);
+                None
+            }
+        }
+    }
+
+    /// Reaps `Xwayland` process after it terminated.
+    pub fn on_terminated(&mut self) {
+        if let Some(mut child) = self.process.take() {
+            // The process may be already gone. Make sure it will not hang around.
+            let _ = child.kill();
+            match child.wait() {
+                Ok(status) => log_info1!("Xwayland terminated ({})", status),
+                Err(err) => log_warn1!("Failed to wait for Xwayland: {}", err),
+            }
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Private helper methods.
+impl XServer {
+    /// Creates lock file for display. If lock file exists but process which created it does not,
+    /// the lock file is replaced.
+    fn acquire_lock(path: &PathBuf) -> bool {
+        for _ in 0..2 {
+            let file = fs::OpenOptions::new().write(true).create_new(true).mode(0o444).open(path);
+            match file {
+                Ok(mut file) => {
+                    let pid = format!("{:>10}\n", unistd::getpid());
+                    if file.write_all(pid.as_bytes()).is_ok() {
+                        return true;
+                    } else {
+                        Self::remove_file(path);
+                        return false;
+                    }
+                }
+                Err(_) => {
+                    if Self::is_lock_stale(path) {
+                        log_info2!("Removing stale X11 lock file {:?}", path);
+                        Self::remove_file(path);
+                    } else {
+                        return false;
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    /// Checks if process which created given lock file still exists.
+    fn is_lock_stale(path: &PathBuf) -> bool {
+        let mut content = String::new();
+        if fs::File::open(path).and_then(|mut f| f.read_to_string(&mut content)).is_err() {
+            return false;
+        }
+
+        match content.trim().parse::<i32>() {
+            Ok(pid) => !PathBuf::from(format!("/proc/{}", pid)).exists(),
+            Err(_) => false,
+        }
+    }
+
+    /// Creates abstract and file system listening sockets for display.
+    fn create_sockets(socket_path: &PathBuf) -> nix::Result<Vec<RawFd>> {
+        // Display is locked so the socket file (if any) is stale.
+        let _ = fs::remove_file(socket_path);
+
+        let abstract_name = socket_path.to_string_lossy().into_owned();
+        let addresses = vec![socket::UnixAddr::new_abstract(abstract_name.as_bytes())?,
+                             socket::UnixAddr::new(socket_path)?];
+
+        let mut fds = Vec::new();
+        for address in addresses {
+            match Self::listen(address) {
+                Ok(fd) => fds.push(fd),
+                Err(err) => {
+                    Self::close_all(&fds);
+                    return Err(err);
+                }
+            }
+        }
+        Ok(fds)
+    }
+
+    /// Creates Wayland connection for `Xwayland`. Returns compositor and `Xwayland` sides of the
+    /// connection.
+    ///
+    /// `skylane` creates server side sockets only for accepted connections so the connection is
+    /// made through temporary display socket.
+    fn connect_wayland(&self) -> Option<(wl::Socket, RawFd)> {
+        let mut path = std::env::var_os("XDG_RUNTIME_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(std::env::temp_dir);
+        path.push(format!("perceptia-xwayland-{}", self.display));
+        let _ = fs::remove_file(&path);
+
+        let display_socket = match wl::DisplaySocket::new(&path) {
+            Ok(display_socket) => display_socket,
+            Err(err) => {
+                log_warn1!("Failed to create Wayland socket for Xwayland: {:?}", err);
+                return None;
+            }
+        };
+
+        let fd = match Self::connect(&path) {
+            Ok(fd) => fd,
+            Err(err) => {
+                log_warn1!("Failed to connect Xwayland to {:?}: {:?}", path, err);
+                return None;
+            }
+        };
+
+        match display_socket.accept() {
+            Ok(socket) => Some((socket, fd)),
+            Err(err) => {
+                log_warn1!("Failed to accept Xwayland connection: {:?}", err);
+                Self::close_all(&[fd]);
+                None
+            }
+        }
+    }
+
+    /// Creates socket connected to given path.
+    fn connect(path: &PathBuf) -> nix::Result<RawFd> {
+        let fd = socket::socket(socket::AddressFamily::Unix,
+                                socket::SockType::Stream,
+                                socket::SOCK_CLOEXEC,
+                                0)?;
+        let result = socket::UnixAddr::new(path.as_path())
+            .and_then(|address| socket::connect(fd, &socket::SockAddr::Unix(address)));
+        match result {
+            Ok(_) => Ok(fd),
+            Err(err) => {
+                Self::close_all(&[fd]);
+                Err(err)
+            }
+        }
+    }
+
+    /// Creates socket listening on given address.
+    fn listen(address: socket::UnixAddr) -> nix::Result<RawFd> {
+        let fd = socket::socket(socket::AddressFamily::Unix,
+                                socket::SockType::Stream,
+                                socket::SOCK_CLOEXEC,
+                                0)?;
+        let result = socket::bind(fd, &socket::SockAddr::Unix(address))
+            .and_then(|_| socket::listen(fd, 1));
+        match result {
+            Ok(_) => Ok(fd),
+            Err(err) => {
+                Self::close_all(&[fd]);
+                Err(err)
+            }
+        }
+    }
+
+    /// Closes all given file descriptors.
+    fn close_all(fds: &[RawFd]) {
+        for fd in fds.iter() {
+            if let Err(err) = unistd::close(*fd) {
+                log_warn3!("Failed to close file descriptor {}: {:?}", fd, err);
+            }
+        }
+    }
+
+    /// Removes given file logging failure.
+    fn remove_file(path: &PathBuf) {
+        if let Err(err) = fs::remove_file(path) {
+            log_warn2!("Failed to remove {:?}: {}", path, err);
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Drop for XServer {
+    fn drop(&mut self) {
+        self.on_terminated();
+        Self::close_all(&self.listen_fds);
+        Self::remove_file(&self.socket_path);
+        Self::remove_file(&self.lock_path);
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
@@ -0,0 +1,1239 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! This module contains X11 window manager mapping X11 windows to surfaces created by `Xwayland`.
+//!
+//! `Xwayland` creates Wayland surface for every mapped X11 window and informs window manager about
+//! relation between them with `WL_SURFACE_ID` client message. Window manager then gives the
+//! surface a role:
+//!
+//! - regular windows are shown in the frame tree like shell surfaces,
+//! - transient windows are related to the window they are transient for,
+//! - override-redirect windows (menus, tooltips) are related as pop-ups to their owner (window
+//!   they are transient for) or to the window they were placed over.
+//!
+//! Position of every window in X11 coordinates is kept in sync with position of its surface on the
+//! screen, so position of related window relative to its parent is the difference of their X11
+//! positions. Clients are informed about geometry decided by compositor with synthetic
+//! `ConfigureNotify` events.
+//!
+//! Window manager also bridges X11 `CLIPBOARD` selection with application data transfers.
+//! `INCR` transfers (bigger than maximal X11 request size) are not supported.
+
+// -------------------------------------------------------------------------------------------------
+
+use std;
+use std::collections::HashMap;
+use std::os::unix::io::{AsRawFd, RawFd};
+
+use nix;
+use nix::{fcntl, unistd};
+use dharma;
+use xcb;
+use xcb::xfixes;
+
+use skylane::server as wl;
+
+use qualia::{Area, Position, Size, SurfaceId, Transfer, show_reason};
+use qualia::FrontendsCoordinationTrait;
+
+use facade::{Facade, ShellSurfaceOid};
+use mediator::MediatorRef;
+use proxy::ProxyRef;
+
+// -------------------------------------------------------------------------------------------------
+
+/// Mime type X11 `UTF8_STRING` target is translated to.
+const TEXT_MIME_TYPE: &'static str = "text/plain;charset=utf-8";
+
+/// Maximal length of property read at once (in 32-bit units).
+const MAX_PROPERTY_LENGTH: u32 = 0x1fffffff;
+
+/// Value of `WM_STATE` property for withdrawn windows.
+const WM_STATE_WITHDRAWN: u32 = 0;
+
+/// Value of `WM_STATE` property for normal windows.
+const WM_STATE_NORMAL: u32 = 1;
+
+// -------------------------------------------------------------------------------------------------
+
+/// Set of atoms used by window manager.
+struct Atoms {
+    wl_surface_id: xcb::Atom,
+    wm_protocols: xcb::Atom,
+    wm_take_focus: xcb::Atom,
+    wm_delete_window: xcb::Atom,
+    wm_state: xcb::Atom,
+    wm_s0: xcb::Atom,
+    net_wm_cm_s0: xcb::Atom,
+    net_wm_name: xcb::Atom,
+    net_supported: xcb::Atom,
+    net_supporting_wm_check: xcb::Atom,
+    net_active_window: xcb::Atom,
+    utf8_string: xcb::Atom,
+    clipboard: xcb::Atom,
+    targets: xcb::Atom,
+    text: xcb::Atom,
+    incr: xcb::Atom,
+    wl_selection: xcb::Atom,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Atoms {
+    /// Interns all atoms. Requests are sent first and replies collected later to avoid round
+    /// trips.
+    fn new(connection: &xcb::Connection) -> Option<Self> {
+        let names = ["WL_SURFACE_ID",
+                     "WM_PROTOCOLS",
+                     "WM_TAKE_FOCUS",
+                     "WM_DELETE_WINDOW",
+                     "WM_STATE",
+                     "WM_S0",
+                     "_NET_WM_CM_S0",
+                     "_NET_WM_NAME",
+                     "_NET_SUPPORTED",
+                     "_NET_SUPPORTING_WM_CHECK",
+                     "_NET_ACTIVE_WINDOW",
+                     "UTF8_STRING",
+                     "CLIPBOARD",
+                     "TARGETS",
+                     "TEXT",
+                     "INCR",
+                     "_WL_SELECTION"];
+
+        let cookies: Vec<_> =
+            names.iter().map(|name| xcb::intern_atom(connection, false, name)).collect();
+        let mut atoms = Vec::with_capacity(cookies.len());
+        for (cookie, name) in cookies.iter().zip(names.iter()) {
+            match cookie.get_reply() {
+                Ok(reply) => atoms.push(reply.atom()),
+                Err(err) => {
+                    log_warn1!("XWM: failed to intern atom '{}': {}", name, err.error_code());
+                    return None;
+                }
+            }
+        }
+
+        Some(Atoms {
+                 wl_surface_id: atoms[0],
+                 wm_protocols: atoms[1],
+                 wm_take_focus: atoms[2],
+                 wm_delete_window: atoms[3],
+                 wm_state: atoms[4],
+                 wm_s0: atoms[5],
+                 net_wm_cm_s0: atoms[6],
+                 net_wm_name: atoms[7],
+                 net_supported: atoms[8],
+                 net_supporting_wm_check: atoms[9],
+                 net_active_window: atoms[10],
+                 utf8_string: atoms[11],
+                 clipboard: atoms[12],
+                 targets: atoms[13],
+                 text: atoms[14],
+                 incr: atoms[15],
+                 wl_selection: atoms[16],
+             })
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Information about X11 window.
+struct Window {
+    /// Object ID of Wayland surface created for this window by `Xwayland`.
+    surface_oid: Option<wl::ObjectId>,
+
+    /// Surface ID of surface created for this window.
+    sid: SurfaceId,
+
+    /// Window this override-redirect window was related to.
+    parent: Option<xcb::Window>,
+
+    /// Window this window is transient for.
+    transient_for: Option<xcb::Window>,
+
+    area: Area,
+    is_override_redirect: bool,
+    is_mapped: bool,
+    is_shown: bool,
+    takes_focus: bool,
+    supports_delete: bool,
+    title: String,
+    class: String,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Window {
+    fn new(area: Area, is_override_redirect: bool) -> Self {
+        Window {
+            surface_oid: None,
+            sid: SurfaceId::invalid(),
+            parent: None,
+            transient_for: None,
+            area: area,
+            is_override_redirect: is_override_redirect,
+            is_mapped: false,
+            is_shown: false,
+            takes_focus: false,
+            supports_delete: false,
+            title: String::new(),
+            class: String::new(),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Data transfer from application to X11 client requesting selection.
+struct IncomingTransfer {
+    read_fd: RawFd,
+    write_fd: Option<RawFd>,
+    requestor: xcb::Window,
+    target: xcb::Atom,
+    property: xcb::Atom,
+    time: xcb::Timestamp,
+    data: Vec<u8>,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// X11 window manager for windows created by `Xwayland`.
+pub struct WindowManager {
+    connection: xcb::Connection,
+    atoms: Atoms,
+    root: xcb::Window,
+    window: xcb::Window,
+    xfixes_event_base: u8,
+    windows: HashMap<xcb::Window, Window>,
+    focused_window: Option<xcb::Window>,
+    coordinator: Box<FrontendsCoordinationTrait>,
+    mediator: MediatorRef,
+    client_id: dharma::EventHandlerId,
+
+    /// Mime types offered by X11 selection owner.
+    offered_targets: HashMap<String, xcb::Atom>,
+
+    /// File descriptor X11 selection is being transferred to.
+    outgoing_transfer_fd: Option<RawFd>,
+
+    /// Transfer of selection to X11 client.
+    incoming_transfer: Option<IncomingTransfer>,
+
+    /// Tells if file descriptor of incoming transfer should be watched.
+    is_incoming_transfer_new: bool,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Public methods.
+impl WindowManager {
+    /// Connects to `Xwayland` on given socket and sets up window manager.
+    ///
+    /// `client_id` is ID of `Xwayland` Wayland client.
+    pub fn new(fd: RawFd,
+               client_id: dharma::EventHandlerId,
+               coordinator: Box<FrontendsCoordinationTrait>,
+               mediator: MediatorRef)
+               -> Option<Self> {
+        let connection = unsafe {
+            let raw = xcb::ffi::base::xcb_connect_to_fd(fd, std::ptr::null_mut());
+            xcb::Connection::from_raw_conn(raw)
+        };
+        if let Err(err) = connection.has_error() {
+            log_warn1!("XWM: failed to connect to Xwayland: {:?}", err);
+            return None;
+        }
+
+        let (root, visual) = if let Some(screen) = connection.get_setup().roots().next() {
+            (screen.root(), screen.root_visual())
+        } else {
+            log_warn1!("XWM: Xwayland has no screens");
+            return None;
+        };
+
+        let atoms = Atoms::new(&connection)?;
+
+        let xfixes_event_base = match connection.get_extension_data(xfixes::id()) {
+            Some(ref data) if data.present() => data.first_event(),
+            _ => {
+                log_warn1!("XWM: XFixes extension not available");
+                return None;
+            }
+        };
+        if let Err(err) = xfixes::query_version(&connection,
+                                                xfixes::MAJOR_VERSION,
+                                                xfixes::MINOR_VERSION)
+            .get_reply() {
+            log_warn1!("XWM: failed to query XFixes version: {}", err.error_code());
+            return None;
+        }
+
+        let window = connection.generate_id();
+        let mut mine = WindowManager {
+            connection: connection,
+            atoms: atoms,
+            root: root,
+            window: window,
+            xfixes_event_base: xfixes_event_base,
+            windows: HashMap::new(),
+            focused_window: None,
+            coordinator: coordinator,
+            mediator: mediator,
+            client_id: client_id,
+            offered_targets: HashMap::new(),
+            outgoing_transfer_fd: None,
+            incoming_transfer: None,
+            is_incoming_transfer_new: false,
+        };
+
+        mine.initialize(visual);
+        Some(mine)
+    }
+
+    /// Returns file descriptor of connection to `Xwayland`.
+    pub fn get_fd(&self) -> RawFd {
+        self.connection.as_raw_fd()
+    }
+
+    /// Handles all pending events from `Xwayland`.
+    ///
+    /// `proxy` is proxy of `Xwayland` Wayland client.
+    pub fn process_events(&mut self, proxy: &ProxyRef) {
+        while let Some(event) = self.connection.poll_for_event() {
+            let kind = event.response_type() & !0x80;
+            match kind {
+                xcb::CREATE_NOTIFY => {
+                    self.handle_create_notify(unsafe { xcb::cast_event(&event) });
+                }
+                xcb::DESTROY_NOTIFY => {
+                    self.handle_destroy_notify(unsafe { xcb::cast_event(&event) }, proxy);
+                }
+                xcb::MAP_REQUEST => {
+                    self.handle_map_request(unsafe { xcb::cast_event(&event) }, proxy);
+                }
+                xcb::MAP_NOTIFY => {
+                    self.handle_map_notify(unsafe { xcb::cast_event(&event) }, proxy);
+                }
+                xcb::UNMAP_NOTIFY => {
+                    self.handle_unmap_notify(unsafe { xcb::cast_event(&event) }, proxy);
+                }
+                xcb::CONFIGURE_REQUEST => {
+                    self.handle_configure_request(unsafe { xcb::cast_event(&event) });
+                }
+                xcb::CONFIGURE_NOTIFY => {
+                    self.handle_configure_notify(unsafe { xcb::cast_event(&event) }, proxy);
+                }
+                xcb::PROPERTY_NOTIFY => {
+                    self.handle_property_notify(unsafe { xcb::cast_event(&event) });
+                }
+                xcb::CLIENT_MESSAGE => {
+                    self.handle_client_message(unsafe { xcb::cast_event(&event) }, proxy);
+                }
+                xcb::SELECTION_REQUEST => {
+                    self.handle_selection_request(unsafe { xcb::cast_event(&event) });
+                }
+                xcb::SELECTION_NOTIFY => {
+                    self.handle_selection_notify(unsafe { xcb::cast_event(&event) });
+                }
+                _ => {
+                    if kind == self.xfixes_event_base + xfixes::SELECTION_NOTIFY {
+                        self.handle_xfixes_selection_notify(unsafe { xcb::cast_event(&event) });
+                    }
+                }
+            }
+        }
+        self.connection.flush();
+    }
+
+    /// Shows windows which surfaces were not known at the time they were mapped.
+    ///
+    /// Surfaces are created on Wayland connection which is independent from X11 connection so
+    /// `WL_SURFACE_ID` message may come before the surface is created.
+    pub fn show_pending_windows(&mut self, proxy: &ProxyRef) {
+        let pending: Vec<xcb::Window> = self.windows
+            .iter()
+            .filter(|&(_, w)| w.is_mapped && !w.is_shown && w.surface_oid.is_some())
+            .map(|(id, _)| *id)
+            .collect();
+        for id in pending {
+            self.show_window(id, proxy);
+        }
+    }
+
+    /// Handles reconfiguration of surface. Returns `true` if the surface belongs to X11 window.
+    pub fn on_surface_reconfigured(&self, sid: SurfaceId, size: Size) -> bool {
+        if let Some(id) = self.find_window(sid) {
+            xcb::configure_window(&self.connection,
+                                  id,
+                                  &[(xcb::CONFIG_WINDOW_WIDTH as u16, size.width as u32),
+                                    (xcb::CONFIG_WINDOW_HEIGHT as u16, size.height as u32)]);
+            if let Some(window) = self.windows.get(&id) {
+                self.send_configure_notify(id, Area::new(window.area.pos, size));
+            }
+            self.connection.flush();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Handles change of position of surface on the screen by moving its window to the same
+    /// position in X11 coordinates.
+    pub fn on_surface_moved(&mut self, sid: SurfaceId, position: Position) {
+        if let Some(id) = self.find_window(sid) {
+            let area = if let Some(window) = self.windows.get_mut(&id) {
+                if window.area.pos == position {
+                    return;
+                }
+                window.area.pos = position;
+                window.area
+            } else {
+                return;
+            };
+
+            xcb::configure_window(&self.connection,
+                                  id,
+                                  &[(xcb::CONFIG_WINDOW_X as u16, position.x as u32),
+                                    (xcb::CONFIG_WINDOW_Y as u16, position.y as u32)]);
+            self.send_configure_notify(id, area);
+            self.connection.flush();
+        }
+    }
+
+    /// Handles request to close surface. Windows supporting `WM_DELETE_WINDOW` protocol are asked
+    /// to close, other windows are killed. Returns `true` if the surface belongs to X11 window.
+    pub fn on_surface_close_requested(&self, sid: SurfaceId) -> bool {
+        if let Some(id) = self.find_window(sid) {
+            let supports_delete = self.windows.get(&id).map_or(false, |w| w.supports_delete);
+            if supports_delete {
+                let data = xcb::ClientMessageData::from_data32([self.atoms.wm_delete_window,
+                                                                xcb::CURRENT_TIME,
+                                                                0,
+                                                                0,
+                                                                0]);
+                let event = xcb::ClientMessageEvent::new(32, id, self.atoms.wm_protocols, data);
+                xcb::send_event(&self.connection, false, id, xcb::EVENT_MASK_NO_EVENT, &event);
+            } else {
+                xcb::kill_client(&self.connection, id);
+            }
+            self.connection.flush();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Checks if connection to `Xwayland` was broken.
+    pub fn is_connection_broken(&self) -> bool {
+        self.connection.has_error().is_err()
+    }
+
+    /// Passes keyboard focus to window related with given surface or takes it from X11 windows
+    /// if the surface does not belong to any of them.
+    pub fn on_keyboard_focus_changed(&mut self, sid: SurfaceId) {
+        let window = self.find_window(sid);
+        if window == self.focused_window {
+            return;
+        }
+
+        if let Some(id) = window {
+            let takes_focus = self.windows.get(&id).map_or(false, |w| w.takes_focus);
+            xcb::configure_window(&self.connection,
+                                  id,
+                                  &[(xcb::CONFIG_WINDOW_STACK_MODE as u16,
+                                     xcb::STACK_MODE_ABOVE)]);
+            xcb::set_input_focus(&self.connection,
+                                 xcb::INPUT_FOCUS_POINTER_ROOT as u8,
+                                 id,
+                                 xcb::CURRENT_TIME);
+            if takes_focus {
+                let data = xcb::ClientMessageData::from_data32([self.atoms.wm_take_focus,
+                                                                xcb::CURRENT_TIME,
+                                                                0,
+                                                                0,
+                                                                0]);
+                let event = xcb::ClientMessageEvent::new(32, id, self.atoms.wm_protocols, data);
+                xcb::send_event(&self.connection, false, id, xcb::EVENT_MASK_NO_EVENT, &event);
+            }
+        } else {
+            xcb::set_input_focus(&self.connection,
+                                 xcb::INPUT_FOCUS_POINTER_ROOT as u8,
+                                 xcb::NONE,
+                                 xcb::CURRENT_TIME);
+        }
+
+        let active = window.unwrap_or(xcb::NONE);
+        xcb::change_property(&self.connection,
+                             xcb::PROP_MODE_REPLACE as u8,
+                             self.root,
+                             self.atoms.net_active_window,
+                             xcb::ATOM_WINDOW,
+                             32,
+                             &[active]);
+        self.focused_window = window;
+        self.connection.flush();
+    }
+
+    /// Takes ownership of X11 selection after Wayland client offered data transfer.
+    pub fn on_transfer_offered(&mut self) {
+        self.offered_targets.clear();
+        xcb::set_selection_owner(&self.connection,
+                                 self.window,
+                                 self.atoms.clipboard,
+                                 xcb::CURRENT_TIME);
+        self.connection.flush();
+    }
+
+    /// Requests conversion of X11 selection to given mime type. Data will be written to given file
+    /// descriptor when X11 selection owner provides it.
+    pub fn on_transfer_requested(&mut self, mime_type: String, fd: RawFd) {
+        if let Some(&target) = self.offered_targets.get(&mime_type) {
+            if let Some(old_fd) = self.outgoing_transfer_fd.take() {
+                log_warn2!("XWM: new transfer requested before previous finished");
+                Self::close(old_fd);
+            }
+            self.outgoing_transfer_fd = Some(fd);
+            xcb::convert_selection(&self.connection,
+                                   self.window,
+                                   self.atoms.clipboard,
+                                   target,
+                                   self.atoms.wl_selection,
+                                   xcb::CURRENT_TIME);
+            self.connection.flush();
+        } else {
+            log_warn2!("XWM: requested not offered mime type '{}'", mime_type);
+            Self::close(fd);
+        }
+    }
+
+    /// Closes write end of incoming transfer pipe after it was passed to transfer offerer.
+    pub fn on_transfer_forwarded(&mut self, fd: RawFd) {
+        if let Some(ref mut transfer) = self.incoming_transfer {
+            if transfer.write_fd == Some(fd) {
+                transfer.write_fd = None;
+                Self::close(fd);
+            }
+        }
+    }
+
+    /// Returns file descriptor of newly started incoming transfer which should be watched.
+    pub fn take_new_incoming_transfer_fd(&mut self) -> Option<RawFd> {
+        if self.is_incoming_transfer_new {
+            self.is_incoming_transfer_new = false;
+            self.incoming_transfer.as_ref().map(|transfer| transfer.read_fd)
+        } else {
+            None
+        }
+    }
+
+    /// Reads data of incoming transfer. When all data was read it is passed to requesting X11
+    /// client. Returns `true` if transfer ended and its file descriptor should not be watched any
+    /// more.
+    pub fn process_incoming_transfer(&mut self) -> bool {
+        let mut buffer = [0u8; 4096];
+        let (finished, succeeded) = if let Some(ref mut transfer) = self.incoming_transfer {
+            match unistd::read(transfer.read_fd, &mut buffer) {
+                Ok(0) => (true, true),
+                Ok(size) => {
+                    transfer.data.extend_from_slice(&buffer[0..size]);
+                    (false, true)
+                }
+                Err(nix::Error::Sys(nix::Errno::EAGAIN)) |
+                Err(nix::Error::Sys(nix::Errno::EINTR)) => (false, true),
+                Err(err) => {
+                    log_warn2!("XWM: failed to read transfer: {:?}", err);
+                    (true, false)
+                }
+            }
+        } else {
+            (true, false)
+        };
+
+        if finished {
+            if let Some(transfer) = self.incoming_transfer.take() {
+                let property = if succeeded {
+                    xcb::change_property(&self.connection,
+                                         xcb::PROP_MODE_REPLACE as u8,
+                                         transfer.requestor,
+                                         transfer.property,
+                                         transfer.target,
+                                         8,
+                                         &transfer.data);
+                    transfer.property
+                } else {
+                    xcb::NONE
+                };
+                self.notify_selection(transfer.requestor,
+                                      transfer.target,
+                                      property,
+                                      transfer.time);
+                Self::close(transfer.read_fd);
+                if let Some(write_fd) = transfer.write_fd {
+                    Self::close(write_fd);
+                }
+                self.connection.flush();
+            }
+        }
+        finished
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Handlers of X11 events.
+impl WindowManager {
+    /// Starts tracking newly created window.
+    fn handle_create_notify(&mut self, event: &xcb::CreateNotifyEvent) {
+        if event.window() != self.window {
+            let area = Area::new(Position::new(event.x() as isize, event.y() as isize),
+                                 Size::new(event.width() as usize, event.height() as usize));
+            let window = Window::new(area, event.override_redirect());
+            self.windows.insert(event.window(), window);
+        }
+    }
+
+    /// Stops tracking destroyed window.
+    fn handle_destroy_notify(&mut self, event: &xcb::DestroyNotifyEvent, proxy: &ProxyRef) {
+        self.hide_window(event.window(), proxy);
+        self.windows.remove(&event.window());
+        if self.focused_window == Some(event.window()) {
+            self.focused_window = None;
+        }
+    }
+
+    /// Reads properties of window and maps it.
+    fn handle_map_request(&mut self, event: &xcb::MapRequestEvent, proxy: &ProxyRef) {
+        let id = event.window();
+        xcb::change_window_attributes(&self.connection,
+                                      id,
+                                      &[(xcb::CW_EVENT_MASK,
+                                         xcb::EVENT_MASK_PROPERTY_CHANGE |
+                                         xcb::EVENT_MASK_FOCUS_CHANGE)]);
+        self.read_properties(id);
+        self.set_wm_state(id, WM_STATE_NORMAL);
+        xcb::map_window(&self.connection, id);
+
+        if let Some(window) = self.windows.get_mut(&id) {
+            window.is_mapped = true;
+        }
+        self.show_window(id, proxy);
+    }
+
+    /// Marks override-redirect window as mapped. Regular windows are marked on map request.
+    fn handle_map_notify(&mut self, event: &xcb::MapNotifyEvent, proxy: &ProxyRef) {
+        let id = event.window();
+        let is_override_redirect = if let Some(window) = self.windows.get_mut(&id) {
+            if window.is_override_redirect {
+                window.is_mapped = true;
+            }
+            window.is_override_redirect
+        } else {
+            false
+        };
+
+        if is_override_redirect {
+            self.read_properties(id);
+            self.show_window(id, proxy);
+        }
+    }
+
+    /// Hides unmapped window.
+    fn handle_unmap_notify(&mut self, event: &xcb::UnmapNotifyEvent, proxy: &ProxyRef) {
+        let id = event.window();
+        self.hide_window(id, proxy);
+        let is_override_redirect = if let Some(window) = self.windows.get_mut(&id) {
+            window.is_mapped = false;
+            window.is_override_redirect
+        } else {
+            return;
+        };
+
+        if !is_override_redirect {
+            self.set_wm_state(id, WM_STATE_WITHDRAWN);
+        }
+    }
+
+    /// Handles configuration request. Geometry of managed windows is decided by compositor so only
+    /// requests for unmapped windows are fulfilled. Mapped windows are informed about their
+    /// current geometry as required by ICCCM.
+    fn handle_configure_request(&mut self, event: &xcb::ConfigureRequestEvent) {
+        let id = event.window();
+        if let Some(window) = self.windows.get(&id) {
+            if window.is_mapped {
+                self.send_configure_notify(id, window.area);
+                return;
+            }
+        }
+
+        let mask = event.value_mask();
+        let mut values = Vec::new();
+        if mask & (xcb::CONFIG_WINDOW_X as u16) != 0 {
+            values.push((xcb::CONFIG_WINDOW_X as u16, event.x() as u32));
+        }
+        if mask & (xcb::CONFIG_WINDOW_Y as u16) != 0 {
+            values.push((xcb::CONFIG_WINDOW_Y as u16, event.y() as u32));
+        }
+        if mask & (xcb::CONFIG_WINDOW_WIDTH as u16) != 0 {
+            values.push((xcb::CONFIG_WINDOW_WIDTH as u16, event.width() as u32));
+        }
+        if mask & (xcb::CONFIG_WINDOW_HEIGHT as u16) != 0 {
+            values.push((xcb::CONFIG_WINDOW_HEIGHT as u16, event.height() as u32));
+        }
+        if values.len() > 0 {
+            xcb::configure_window(&self.connection, id, &values);
+        }
+    }
+
+    /// Updates known geometry of window. Pop-ups moved by their clients are moved relatively to
+    /// their parents.
+    fn handle_configure_notify(&mut self, event: &xcb::ConfigureNotifyEvent, proxy: &ProxyRef) {
+        let id = event.window();
+        let related = if let Some(window) = self.windows.get_mut(&id) {
+            let position = Position::new(event.x() as isize, event.y() as isize);
+            let is_moved = window.area.pos != position;
+            window.area = Area::new(position,
+                                    Size::new(event.width() as usize, event.height() as usize));
+            match (window.is_shown && window.is_override_redirect && is_moved,
+                   window.surface_oid,
+                   window.parent) {
+                (true, Some(surface_oid), Some(parent)) => Some((surface_oid, position, parent)),
+                _ => None,
+            }
+        } else {
+            None
+        };
+
+        if let Some((surface_oid, position, parent)) = related {
+            if let Some(parent_window) = self.windows.get(&parent) {
+                let offset = position - parent_window.area.pos;
+                proxy.borrow().set_relative_position(surface_oid, offset.x, offset.y);
+            }
+        }
+    }
+
+    /// Rereads changed properties.
+    fn handle_property_notify(&mut self, event: &xcb::PropertyNotifyEvent) {
+        let atom = event.atom();
+        if (atom == xcb::ATOM_WM_NAME) || (atom == self.atoms.net_wm_name) ||
+           (atom == xcb::ATOM_WM_CLASS) {
+            self.read_properties(event.window());
+        }
+    }
+
+    /// Handles `WL_SURFACE_ID` message relating window with Wayland surface.
+    fn handle_client_message(&mut self, event: &xcb::ClientMessageEvent, proxy: &ProxyRef) {
+        if event.type_() == self.atoms.wl_surface_id {
+            let id = event.window();
+            if let Some(window) = self.windows.get_mut(&id) {
+                window.surface_oid = Some(wl::ObjectId::new(event.data().data32()[0]));
+            }
+            self.show_window(id, proxy);
+        }
+    }
+
+    /// Handles request for selection owned by window manager (on behalf of Wayland client).
+    fn handle_selection_request(&mut self, event: &xcb::SelectionRequestEvent) {
+        let requestor = event.requestor();
+        let target = event.target();
+        let property = event.property();
+        let time = event.time();
+
+        let transfer = self.coordinator.get_transfer();
+        let transfer = match transfer {
+            Some(ref transfer) if event.selection() == self.atoms.clipboard => transfer,
+            _ => {
+                self.notify_selection(requestor, target, xcb::NONE, time);
+                self.connection.flush();
+                return;
+            }
+        };
+
+        if target == self.atoms.targets {
+            let mut targets = vec![self.atoms.targets];
+            for mime_type in transfer.get_mime_types() {
+                if Self::is_text(mime_type) {
+                    targets.push(self.atoms.utf8_string);
+                    targets.push(self.atoms.text);
+                    targets.push(xcb::ATOM_STRING);
+                } else if let Some(atom) = self.intern_atom(mime_type) {
+                    targets.push(atom);
+                }
+            }
+            targets.dedup();
+            xcb::change_property(&self.connection,
+                                 xcb::PROP_MODE_REPLACE as u8,
+                                 requestor,
+                                 property,
+                                 xcb::ATOM_ATOM,
+                                 32,
+                                 &targets);
+            self.notify_selection(requestor, target, property, time);
+        } else if self.incoming_transfer.is_some() {
+            log_warn2!("XWM: selection requested before previous request finished");
+            self.notify_selection(requestor, target, xcb::NONE, time);
+        } else {
+            let mime_type = self.target_to_mime_type(target, transfer);
+            match (mime_type, unistd::pipe2(fcntl::O_CLOEXEC | fcntl::O_NONBLOCK)) {
+                (Some(mime_type), Ok((read_fd, write_fd))) => {
+                    self.incoming_transfer = Some(IncomingTransfer {
+                                                      read_fd: read_fd,
+                                                      write_fd: Some(write_fd),
+                                                      requestor: requestor,
+                                                      target: target,
+                                                      property: property,
+                                                      time: time,
+                                                      data: Vec::new(),
+                                                  });
+                    self.is_incoming_transfer_new = true;
+                    self.coordinator.request_transfer(mime_type, write_fd);
+                }
+                (None, _) => {
+                    self.notify_selection(requestor, target, xcb::NONE, time);
+                }
+                (Some(_), Err(err)) => {
+                    log_warn2!("XWM: failed to create pipe for transfer: {:?}", err);
+                    self.notify_selection(requestor, target, xcb::NONE, time);
+                }
+            }
+        }
+        self.connection.flush();
+    }
+
+    /// Handles conversion of X11 selection requested by window manager.
+    fn handle_selection_notify(&mut self, event: &xcb::SelectionNotifyEvent) {
+        if event.property() == xcb::NONE {
+            log_warn3!("XWM: conversion of selection failed");
+            if let Some(fd) = self.outgoing_transfer_fd.take() {
+                Self::close(fd);
+            }
+            return;
+        }
+
+        let cookie = xcb::get_property(&self.connection,
+                                       true,
+                                       self.window,
+                                       self.atoms.wl_selection,
+                                       xcb::GET_PROPERTY_TYPE_ANY,
+                                       0,
+                                       MAX_PROPERTY_LENGTH);
+        let reply = match cookie.get_reply() {
+            Ok(reply) => reply,
+            Err(err) => {
+                log_warn2!("XWM: failed to read selection: {}", err.error_code());
+                return;
+            }
+        };
+
+        if event.target() == self.atoms.targets {
+            self.offer_targets(reply.value::<xcb::Atom>());
+        } else if let Some(fd) = self.outgoing_transfer_fd.take() {
+            if reply.type_() == self.atoms.incr {
+                log_warn2!("XWM: incremental transfers are not supported");
+            } else {
+                Self::write_all(fd, reply.value::<u8>());
+            }
+            Self::close(fd);
+        }
+    }
+
+    /// Handles change of X11 selection owner. If the new owner is an X11 client, asks it for
+    /// offered targets.
+    fn handle_xfixes_selection_notify(&mut self, event: &xfixes::SelectionNotifyEvent) {
+        if (event.selection() != self.atoms.clipboard) || (event.owner() == self.window) {
+            return;
+        }
+
+        if event.owner() == xcb::NONE {
+            // X11 owner went away. Keep the last offer if it came from Wayland client.
+            if self.mediator.borrow().get_transfer_offerer() == Some(self.client_id) {
+                self.offered_targets.clear();
+                self.mediator.borrow_mut().register_transfer_offerer(None);
+                self.coordinator.set_transfer(None);
+            }
+        } else {
+            xcb::convert_selection(&self.connection,
+                                   self.window,
+                                   self.atoms.clipboard,
+                                   self.atoms.targets,
+                                   self.atoms.wl_selection,
+                                   event.timestamp());
+            self.connection.flush();
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Private helper methods.
+impl WindowManager {
+    /// Sets up root window and creates window manager window.
+    fn initialize(&mut self, visual: xcb::Visualid) {
+        xcb::change_window_attributes(&self.connection,
+                                      self.root,
+                                      &[(xcb::CW_EVENT_MASK,
+                                         xcb::EVENT_MASK_SUBSTRUCTURE_NOTIFY |
+                                         xcb::EVENT_MASK_SUBSTRUCTURE_REDIRECT |
+                                         xcb::EVENT_MASK_PROPERTY_CHANGE)]);
+
+        xcb::create_window(&self.connection,
+                           xcb::COPY_FROM_PARENT as u8,
+                           self.window,
+                           self.root,
+                           0,
+                           0,
+                           10,
+                           10,
+                           0,
+                           xcb::WINDOW_CLASS_INPUT_OUTPUT as u16,
+                           visual,
+                           &[(xcb::CW_EVENT_MASK, xcb::EVENT_MASK_PROPERTY_CHANGE)]);
+
+        for window in vec![self.root, self.window] {
+            xcb::change_property(&self.connection,
+                                 xcb::PROP_MODE_REPLACE as u8,
+                                 window,
+                                 self.atoms.net_supporting_wm_check,
+                                 xcb::ATOM_WINDOW,
+                                 32,
+                                 &[self.window]);
+        }
+        xcb::change_property(&self.connection,
+                             xcb::PROP_MODE_REPLACE as u8,
+                             self.window,
+                             self.atoms.net_wm_name,
+                             self.atoms.utf8_string,
+                             8,
+                             "perceptia".as_bytes());
+        xcb::change_property(&self.connection,
+                             xcb::PROP_MODE_REPLACE as u8,
+                             self.root,
+                             self.atoms.net_supported,
+                             xcb::ATOM_ATOM,
+                             32,
+                             &[self.atoms.net_active_window, self.atoms.net_wm_name]);
+
+        xcb::set_selection_owner(&self.connection,
+                                 self.window,
+                                 self.atoms.wm_s0,
+                                 xcb::CURRENT_TIME);
+        xcb::set_selection_owner(&self.connection,
+                                 self.window,
+                                 self.atoms.net_wm_cm_s0,
+                                 xcb::CURRENT_TIME);
+
+        xfixes::select_selection_input(&self.connection,
+                                       self.window,
+                                       self.atoms.clipboard,
+                                       xfixes::SELECTION_EVENT_MASK_SET_SELECTION_OWNER |
+                                       xfixes::SELECTION_EVENT_MASK_SELECTION_WINDOW_DESTROY |
+                                       xfixes::SELECTION_EVENT_MASK_SELECTION_CLIENT_CLOSE);
+
+        self.connection.flush();
+        log_info1!("XWM: initialized");
+    }
+
+    /// Gives the surface of window a role if the window is mapped and its surface is known.
+    /// Override-redirect windows are shown only if they can be related to other window.
+    fn show_window(&mut self, id: xcb::Window, proxy: &ProxyRef) {
+        let (surface_oid, is_override_redirect, transient_for, area, title, class) =
+            match self.windows.get(&id) {
+                Some(w) if w.is_mapped && !w.is_shown && w.surface_oid.is_some() => {
+                    (w.surface_oid.unwrap(),
+                     w.is_override_redirect,
+                     w.transient_for,
+                     w.area,
+                     w.title.clone(),
+                     w.class.clone())
+                }
+                _ => return,
+            };
+
+        let sid = if let Some(sid) = proxy.borrow().get_sid_for_surface_oid(surface_oid) {
+            sid
+        } else {
+            // Surface was not created yet.
+            return;
+        };
+
+        let parent = if is_override_redirect {
+            self.find_popup_parent(id, transient_for, area.pos)
+        } else {
+            transient_for.and_then(|parent_id| self.get_shown_window(parent_id))
+        };
+
+        {
+            let mut proxy = proxy.borrow_mut();
+            if let Some((_, parent_surface_oid, parent_position)) = parent {
+                let offset = area.pos - parent_position;
+                proxy.relate(surface_oid, parent_surface_oid);
+                proxy.set_relative_position(surface_oid, offset.x, offset.y);
+            } else if is_override_redirect {
+                log_warn3!("XWM: no parent for override-redirect window {}", id);
+                return;
+            } else {
+                proxy.set_title(surface_oid, title);
+                proxy.set_app_id(surface_oid, class);
+                proxy.set_requested_size(sid, area.size);
+                proxy.show(surface_oid, ShellSurfaceOid::Xwayland(id), show_reason::IN_SHELL);
+            }
+        }
+
+        if let Some(window) = self.windows.get_mut(&id) {
+            log_info2!("XWM: showing window {} ({:?}) '{}' of class '{}'{}",
+                       id,
+                       sid,
+                       window.title,
+                       window.class,
+                       window.transient_for
+                           .map_or(String::new(), |t| format!(", transient for {}", t)));
+            window.sid = sid;
+            window.parent = parent.map(|(parent_id, _, _)| parent_id);
+            window.is_shown = true;
+        }
+    }
+
+    /// Returns ID, surface object ID and position of given window if it is shown.
+    fn get_shown_window(&self, id: xcb::Window) -> Option<(xcb::Window, wl::ObjectId, Position)> {
+        self.windows.get(&id).and_then(|w| if w.is_shown {
+                                           w.surface_oid.map(|oid| (id, oid, w.area.pos))
+                                       } else {
+                                           None
+                                       })
+    }
+
+    /// Finds parent for override-redirect window. Preferred is the window it is transient for,
+    /// then focused window and then any other regular window if it was placed over them.
+    fn find_popup_parent(&self,
+                         id: xcb::Window,
+                         transient_for: Option<xcb::Window>,
+                         position: Position)
+                         -> Option<(xcb::Window, wl::ObjectId, Position)> {
+        if let Some(parent) = transient_for.and_then(|parent_id| self.get_shown_window(parent_id)) {
+            return Some(parent);
+        }
+
+        let is_placed_over = |parent_id: &xcb::Window| {
+            self.windows.get(parent_id).map_or(false, |w| {
+                (*parent_id != id) && !w.is_override_redirect && w.area.contains(&position)
+            })
+        };
+
+        if let Some(focused) = self.focused_window.filter(|f| is_placed_over(f)) {
+            if let Some(parent) = self.get_shown_window(focused) {
+                return Some(parent);
+            }
+        }
+
+        self.windows
+            .keys()
+            .filter(|parent_id| is_placed_over(parent_id))
+            .filter_map(|parent_id| self.get_shown_window(*parent_id))
+            .next()
+    }
+
+    /// Takes role from surface of the window.
+    fn hide_window(&mut self, id: xcb::Window, proxy: &ProxyRef) {
+        if let Some(window) = self.windows.get_mut(&id) {
+            if window.is_shown {
+                if let Some(surface_oid) = window.surface_oid {
+                    let mut proxy = proxy.borrow_mut();
+                    if window.parent.is_some() {
+                        proxy.unrelate(surface_oid);
+                    } else {
+                        proxy.hide(surface_oid, show_reason::IN_SHELL);
+                    }
+                }
+                window.is_shown = false;
+                window.parent = None;
+                window.sid = SurfaceId::invalid();
+            }
+        }
+    }
+
+    /// Finds window related with given surface.
+    fn find_window(&self, sid: SurfaceId) -> Option<xcb::Window> {
+        if !sid.is_valid() {
+            return None;
+        }
+        self.windows.iter().find(|&(_, w)| w.is_shown && (w.sid == sid)).map(|(id, _)| *id)
+    }
+
+    /// Reads title, class, transiency and supported protocols of window.
+    fn read_properties(&mut self, id: xcb::Window) {
+        let net_wm_name = self.read_property(id, self.atoms.net_wm_name);
+        let wm_name = self.read_property(id, xcb::ATOM_WM_NAME);
+        let wm_class = self.read_property(id, xcb::ATOM_WM_CLASS);
+        let transient_for = self.read_property(id, xcb::ATOM_WM_TRANSIENT_FOR);
+        let protocols = self.read_property(id, self.atoms.wm_protocols);
+
+        if let Some(window) = self.windows.get_mut(&id) {
+            if let Some(ref reply) = net_wm_name.or(wm_name) {
+                window.title = String::from_utf8_lossy(reply.value::<u8>()).into_owned();
+            }
+            if let Some(ref reply) = wm_class {
+                // `WM_CLASS` consists of instance name and class name separated by null byte.
+                let class = String::from_utf8_lossy(reply.value::<u8>()).into_owned();
+                window.class = class.split('\0').nth(1).unwrap_or("").to_owned();
+            }
+            if let Some(ref reply) = transient_for {
+                window.transient_for = reply.value::<xcb::Window>().first().cloned();
+            }
+            if let Some(ref reply) = protocols {
+                let take_focus = self.atoms.wm_take_focus;
+                let delete_window = self.atoms.wm_delete_window;
+                window.takes_focus = reply.value::<xcb::Atom>().contains(&take_focus);
+                window.supports_delete = reply.value::<xcb::Atom>().contains(&delete_window);
+            }
+        }
+    }
+
+    /// Reads property of window. Returns `None` if the property is not set.
+    fn read_property(&self, id: xcb::Window, property: xcb::Atom) -> Option<xcb::GetPropertyReply> {
+        let cookie = xcb::get_property(&self.connection,
+                                       false,
+                                       id,
+                                       property,
+                                       xcb::GET_PROPERTY_TYPE_ANY,
+                                       0,
+                                       2048);
+        match cookie.get_reply() {
+            Ok(reply) => if reply.type_() != xcb::NONE { Some(reply) } else { None },
+            Err(_) => None,
+        }
+    }
+
+    /// Sets `WM_STATE` property of window.
+    fn set_wm_state(&self, id: xcb::Window, state: u32) {
+        xcb::change_property(&self.connection,
+                             xcb::PROP_MODE_REPLACE as u8,
+                             id,
+                             self.atoms.wm_state,
+                             self.atoms.wm_state,
+                             32,
+                             &[state, xcb::NONE]);
+    }
+
+    /// Sends synthetic `ConfigureNotify` event informing client about geometry of its window.
+    fn send_configure_notify(&self, id: xcb::Window, area: Area) {
+        let event = xcb::ConfigureNotifyEvent::new(id,
+                                                   id,
+                                                   xcb::NONE,
+                                                   area.pos.x as i16,
+                                                   area.pos.y as i16,
+                                                   area.size.width as u16,
+                                                   area.size.height as u16,
+                                                   0,
+                                                   false);
+        xcb::send_event(&self.connection,
+                        false,
+                        id,
+                        xcb::EVENT_MASK_STRUCTURE_NOTIFY,
+                        &event);
+    }
+
+    /// Interns atom with given name.
+    fn intern_atom(&self, name: &str) -> Option<xcb::Atom> {
+        xcb::intern_atom(&self.connection, false, name).get_reply().ok().map(|r| r.atom())
+    }
+
+    /// Returns name of given atom.
+    fn get_atom_name(&self, atom: xcb::Atom) -> Option<String> {
+        xcb::get_atom_name(&self.connection, atom).get_reply().ok().map(|r| r.name().to_owned())
+    }
+
+    /// Checks if given mime type denotes plain text.
+    fn is_text(mime_type: &str) -> bool {
+        mime_type == TEXT_MIME_TYPE || mime_type == "text/plain" || mime_type == "UTF8_STRING"
+    }
+
+    /// Finds mime type offered by Wayland client matching given X11 target.
+    fn target_to_mime_type(&self, target: xcb::Atom, transfer: &Transfer) -> Option<String> {
+        let is_text_target = (target == self.atoms.utf8_string) || (target == self.atoms.text) ||
+                             (target == xcb::ATOM_STRING);
+        if is_text_target {
+            transfer.get_mime_types().iter().find(|m| Self::is_text(m)).cloned()
+        } else if let Some(name) = self.get_atom_name(target) {
+            transfer.get_mime_types().iter().find(|m| **m == name).cloned()
+        } else {
+            None
+        }
+    }
+
+    /// Makes targets offered by X11 selection owner available for Wayland clients.
+    fn offer_targets(&mut self, targets: &[xcb::Atom]) {
+        self.offered_targets.clear();
+        let mut transfer = Transfer::new();
+        for &target in targets.iter() {
+            if target == self.atoms.utf8_string {
+                self.offered_targets.insert(TEXT_MIME_TYPE.to_owned(), target);
+                self.offered_targets.insert("text/plain".to_owned(), target);
+                transfer.add_mime_type(TEXT_MIME_TYPE.to_owned());
+                transfer.add_mime_type("text/plain".to_owned());
+            } else if let Some(name) = self.get_atom_name(target) {
+                // Only real mime types are offered. Other targets are X11 specific.
+                if name.contains('/') && !self.offered_targets.contains_key(&name) {
+                    self.offered_targets.insert(name.clone(), target);
+                    transfer.add_mime_type(name);
+                }
+            }
+        }
+
+        self.mediator.borrow_mut().register_transfer_offerer(Some(self.client_id));
+        self.coordinator.set_transfer(Some(transfer));
+    }
+
+    /// Sends `SelectionNotify` event to requestor.
+    fn notify_selection(&self,
+                        requestor: xcb::Window,
+                        target: xcb::Atom,
+                        property: xcb::Atom,
+                        time: xcb::Timestamp) {
+        let event = xcb::SelectionNotifyEvent::new(time,
+                                                   requestor,
+                                                   self.atoms.clipboard,
+                                                   target,
+                                                   property);
+        xcb::send_event(&self.connection, false, requestor, xcb::EVENT_MASK_NO_EVENT, &event);
+    }
+
+    /// Writes all data to given file descriptor.
+    fn write_all(fd: RawFd, mut data: &[u8]) {
+        while data.len() > 0 {
+            match unistd::write(fd, data) {
+                Ok(size) => data = &data[size..],
+                Err(nix::Error::Sys(nix::Errno::EINTR)) => {}
+                Err(err) => {
+                    log_warn2!("XWM: failed to write transfer: {:?}", err);
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Closes given file descriptor.
+    fn close(fd: RawFd) {
+        if let Err(err) = unistd::close(fd) {
+            log_warn3!("XWM: failed to close file descriptor {}: {:?}", fd, err);
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Drop for WindowManager {
+    fn drop(&mut self) {
+        if let Some(fd) = self.outgoing_transfer_fd.take() {
+            Self::close(fd);
+        }
+        if let Some(transfer) = self.incoming_transfer.take() {
+            Self::close(transfer.read_fd);
+            if let Some(write_fd) = transfer.write_fd {
+                Self::close(write_fd);
+            }
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
@@ -16,14 +16,14 @@ use dharma;
 use cognitive_graphics::attributes::{EglAttributes, DmabufAttributes};
 use qualia::{Position, Size, Vector, DmabufId, EglImageId, MemoryPoolId, MemoryViewId};
 use qualia::{Buffer, Memory, PixelFormat, GraphicsManagement, WorkspaceState};
-use qualia::{perceptron, Perceptron, Transfer, DrmBundle};
-use qualia::{SurfaceContext, SurfaceId, SurfaceInfo, DataSource};
+use qualia::{perceptron, Perceptron, Transfer, DrmBundle, VirtualInput};
+use qualia::{SurfaceContext, SurfaceId, SurfaceInfo, DataSource, Viewport};
 use qualia::{SurfaceManagement, SurfaceControl, SurfaceViewer};
 use qualia::{SurfaceAccess, SurfaceListing, SurfaceFocusing};
 use qualia::{AppearanceManagement, DataTransferring, EventHandling, StatePublishing};
-use qualia::{MemoryManagement, HwGraphics, WindowManagement, Screenshooting};
+use qualia::{MemoryManagement, HwGraphics, WindowManagement, Screenshooting, InputInjecting};
 use qualia::{AestheticsCoordinationTrait, ExhibitorCoordinationTrait};
-use qualia::FrontendsCoordinationTrait;
+use qualia::{FrontendsCoordinationTrait, SessionLocking, ShortcutsInhibiting, SurfaceActivating};
 use qualia::{show_reason, surface_state};
 
 use resource_storage::ResourceStorage;
@@ -222,6 +222,37 @@ impl InnerCoordinator {
     pub fn take_screenshot_buffer(&mut self) -> Option<Buffer> {
         self.screenshot_buffer.take()
     }
+
+    /// Passes event from virtual input device to input handling.
+    pub fn inject_input(&mut self, event: VirtualInput) {
+        self.signaler.emit(perceptron::VIRTUAL_INPUT, Perceptron::VirtualInput(event));
+    }
+
+    /// Requests locking the session.
+    pub fn lock_session(&mut self) {
+        self.signaler.emit(perceptron::LOCK_SESSION, Perceptron::LockSession);
+    }
+
+    /// Requests unlocking the session.
+    pub fn unlock_session(&mut self) {
+        self.signaler.emit(perceptron::UNLOCK_SESSION, Perceptron::UnlockSession);
+    }
+
+    /// Informs other parts of application about new lock surface.
+    pub fn set_lock_surface(&mut self, sid: SurfaceId, display_id: i32) {
+        self.signaler.emit(perceptron::LOCK_SURFACE, Perceptron::LockSurface(sid, display_id));
+    }
+
+    /// Requests enabling or disabling inhibition of key bindings.
+    pub fn inhibit_shortcuts(&mut self, inhibit: bool) {
+        self.signaler.emit(perceptron::INHIBIT_SHORTCUTS, Perceptron::InhibitShortcuts(inhibit));
+    }
+
+    /// Requests activation of surface or marking it as demanding attention.
+    pub fn activate_surface(&mut self, sid: SurfaceId, granted: bool) {
+        self.signaler.emit(perceptron::SURFACE_ACTIVATION_REQUESTED,
+                           Perceptron::SurfaceActivationRequested(sid, granted));
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -361,6 +392,33 @@ impl SurfaceControl for Coordinator {
         mine.set_surface_requested_size(sid, size)
     }
 
+    /// Lock and call corresponding method from `InnerCoordinator`.
+    fn set_surface_title(&self, sid: SurfaceId, title: String) {
+        let mut mine = self.resources.lock().unwrap();
+        mine.set_surface_title(sid, title)
+    }
+
+    /// Lock and call corresponding method from `InnerCoordinator`.
+    fn set_surface_app_id(&self, sid: SurfaceId, app_id: String) {
+        let mut mine = self.resources.lock().unwrap();
+        mine.set_surface_app_id(sid, app_id)
+    }
+
+    /// Lock and call corresponding method from `InnerCoordinator`.
+    fn request_surface_state(&self,
+                             sid: SurfaceId,
+                             state: surface_state::SurfaceState,
+                             enable: bool) {
+        let mut mine = self.resources.lock().unwrap();
+        mine.request_surface_state(sid, state, enable)
+    }
+
+    /// Lock and call corresponding method from `InnerCoordinator`.
+    fn set_surface_viewport(&self, sid: SurfaceId, viewport: Viewport) {
+        let mut mine = self.resources.lock().unwrap();
+        mine.set_surface_viewport(sid, viewport)
+    }
+
     /// Lock and call corresponding method from `InnerCoordinator`.
     fn set_surface_relative_position(&self, sid: SurfaceId, offset: Vector) {
         let mut mine = self.resources.lock().unwrap();
@@ -401,6 +459,12 @@ impl SurfaceAccess for Coordinator {
         let mut mine = self.resources.lock().unwrap();
         mine.reconfigure(sid, size, state_flags);
     }
+
+    /// Lock and call corresponding method from `InnerCoordinator`.
+    fn close(&mut self, sid: SurfaceId) {
+        let mut mine = self.resources.lock().unwrap();
+        mine.close(sid);
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -681,6 +745,58 @@ impl Screenshooting for Coordinator {
 
 // -------------------------------------------------------------------------------------------------
 
+impl InputInjecting for Coordinator {
+    /// Lock and call corresponding method from `InnerCoordinator`.
+    fn inject_input(&mut self, event: VirtualInput) {
+        let mut mine = self.inner.lock().unwrap();
+        mine.inject_input(event);
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl SessionLocking for Coordinator {
+    /// Lock and call corresponding method from `InnerCoordinator`.
+    fn lock_session(&mut self) {
+        let mut mine = self.inner.lock().unwrap();
+        mine.lock_session()
+    }
+
+    /// Lock and call corresponding method from `InnerCoordinator`.
+    fn unlock_session(&mut self) {
+        let mut mine = self.inner.lock().unwrap();
+        mine.unlock_session()
+    }
+
+    /// Lock and call corresponding method from `InnerCoordinator`.
+    fn set_lock_surface(&mut self, sid: SurfaceId, display_id: i32) {
+        let mut mine = self.inner.lock().unwrap();
+        mine.set_lock_surface(sid, display_id)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl ShortcutsInhibiting for Coordinator {
+    /// Lock and call corresponding method from `InnerCoordinator`.
+    fn inhibit_shortcuts(&mut self, inhibit: bool) {
+        let mut mine = self.inner.lock().unwrap();
+        mine.inhibit_shortcuts(inhibit)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl SurfaceActivating for Coordinator {
+    /// Lock and call corresponding method from `InnerCoordinator`.
+    fn activate_surface(&mut self, sid: SurfaceId, granted: bool) {
+        let mut mine = self.inner.lock().unwrap();
+        mine.activate_surface(sid, granted)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
 impl AestheticsCoordinationTrait for Coordinator {}
 impl ExhibitorCoordinationTrait for Coordinator {}
 impl FrontendsCoordinationTrait for Coordinator {}
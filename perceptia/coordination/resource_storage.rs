@@ -14,7 +14,7 @@ use cognitive_graphics::attributes::{EglAttributes, DmabufAttributes};
 use qualia::{Position, Size, Vector, DmabufId, EglImageId, MemoryPoolId, MemoryViewId};
 use qualia::{Memory, MemoryPool, MemoryView, PixelFormat, GraphicsManagement};
 use qualia::{perceptron, Perceptron};
-use qualia::{SurfaceContext, SurfaceId, SurfaceInfo, DataSource};
+use qualia::{SurfaceContext, SurfaceId, SurfaceInfo, DataSource, Viewport};
 use qualia::{show_reason, surface_state};
 
 use surfaces::Surface;
@@ -279,6 +279,15 @@ impl ResourceStorage {
                            Perceptron::DockSurface(sid, size, display_id));
     }
 
+    /// Informs other parts of application about client requesting change of surface state.
+    pub fn request_surface_state(&mut self,
+                                 sid: SurfaceId,
+                                 state: surface_state::SurfaceState,
+                                 enable: bool) {
+        self.signaler.emit(perceptron::SURFACE_STATE_REQUESTED,
+                           Perceptron::SurfaceStateRequested(sid, state, enable));
+    }
+
     /// Subtracts given show reason flag from set of surfaces show reason. If not all reasons
     /// needed for surface to be drawn are meet, emit signal `surface destroyed`.
     pub fn hide_surface(&mut self, sid: SurfaceId, reason: show_reason::ShowReason) {
@@ -301,6 +310,24 @@ impl ResourceStorage {
         surface.set_requested_size(size)
     }
 
+    /// Sets title of given surface.
+    pub fn set_surface_title(&mut self, sid: SurfaceId, title: String) {
+        let surface = try_get_surface!(self, sid);
+        surface.set_title(title)
+    }
+
+    /// Sets application ID (or class) of given surface.
+    pub fn set_surface_app_id(&mut self, sid: SurfaceId, app_id: String) {
+        let surface = try_get_surface!(self, sid);
+        surface.set_app_id(app_id)
+    }
+
+    /// Sets pending cropping and scaling for given surface.
+    pub fn set_surface_viewport(&mut self, sid: SurfaceId, viewport: Viewport) {
+        let surface = try_get_surface!(self, sid);
+        surface.set_viewport(viewport)
+    }
+
     /// Sets satellite surface position relative to its parent.
     pub fn set_surface_relative_position(&mut self, sid: SurfaceId, position: Position) {
         let surface = try_get_surface!(self, sid);
@@ -353,6 +380,12 @@ impl ResourceStorage {
         }
     }
 
+    /// Send notification about request to close surface.
+    pub fn close(&mut self, sid: SurfaceId) {
+        self.signaler.emit(perceptron::SURFACE_CLOSE_REQUESTED,
+                           Perceptron::SurfaceCloseRequested(sid));
+    }
+
     /// Returns surface context.
     pub fn get_renderer_context(&self, sid: SurfaceId) -> Option<Vec<SurfaceContext>> {
         let surface = try_get_surface_or_none!(self, sid);
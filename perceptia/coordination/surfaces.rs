@@ -7,7 +7,7 @@
 
 use cognitive_graphics::attributes::{DmabufAttributes, EglAttributes};
 use qualia::{DataSource, MemoryView, Position, Size, Vector};
-use qualia::{SurfaceContext, SurfaceId, SurfaceInfo, show_reason, surface_state};
+use qualia::{SurfaceContext, SurfaceId, SurfaceInfo, Viewport, show_reason, surface_state};
 
 // -------------------------------------------------------------------------------------------------
 
@@ -41,11 +41,23 @@ pub struct Surface {
     /// Data to be used after commit.
     pending_buffer: DataSource,
 
+    /// Cropping and scaling of buffer.
+    viewport: Viewport,
+
+    /// Cropping and scaling to be used after commit.
+    pending_viewport: Viewport,
+
     /// Flags describing logical state of surface
     state_flags: surface_state::SurfaceState,
 
     /// Flags indicating if surface is ready to be shown.
     show_reasons: show_reason::ShowReason,
+
+    /// Title set by client.
+    title: String,
+
+    /// Application ID (or class) set by client.
+    app_id: String,
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -63,8 +75,12 @@ impl Surface {
             relative_position: Position::default(),
             buffer: DataSource::None,
             pending_buffer: DataSource::None,
+            viewport: Viewport::new(),
+            pending_viewport: Viewport::new(),
             show_reasons: show_reason::NONE,
             state_flags: surface_state::REGULAR,
+            title: String::new(),
+            app_id: String::new(),
         }
     }
 
@@ -86,6 +102,18 @@ impl Surface {
         self.desired_size = size
     }
 
+    /// Sets title.
+    #[inline]
+    pub fn set_title(&mut self, title: String) {
+        self.title = title
+    }
+
+    /// Sets application ID.
+    #[inline]
+    pub fn set_app_id(&mut self, app_id: String) {
+        self.app_id = app_id
+    }
+
     /// Sets parent SID.
     #[inline]
     pub fn set_parent_sid(&mut self, sid: SurfaceId) {
@@ -177,25 +205,40 @@ impl Surface {
         self.pending_buffer = DataSource::new_dmabuf(attrs);
     }
 
-    /// Sets pending buffer as current. If surface was committed for the first time and sizes are
-    /// not set, assign size of buffer as requested size. Return `true` if surface was committed for
-    /// the first time, `false` otherwise.
+    /// Sets pending cropping and scaling.
+    #[inline]
+    pub fn set_viewport(&mut self, viewport: Viewport) {
+        self.pending_viewport = viewport;
+    }
+
+    /// Sets pending buffer and viewport as current. If surface was committed for the first time
+    /// and sizes are not set, assign size of buffer (after cropping and scaling) as requested size.
+    /// Return `true` if surface was committed for the first time, `false` otherwise.
     pub fn commit(&mut self) -> bool {
         let is_first_time_committed = self.buffer.is_none();
+        let is_viewport_changed = self.viewport != self.pending_viewport;
         self.buffer = self.pending_buffer.clone();
+        self.viewport = self.pending_viewport;
 
         if let Some(ref image) = self.buffer.as_image() {
+            let size = self.viewport.get_surface_size(image.get_size());
             // If surface was just created...
             if is_first_time_committed {
                 // ... size was not yet requested by surface ...
                 if (self.requested_size.width == 0) || (self.requested_size.height == 0) {
                     // ... use its image size as requested size ...
-                    self.requested_size = image.get_size();
+                    self.requested_size = size;
                 }
                 // ... and if it is subsurface ...
                 if self.parent_sid.is_valid() {
                     // ... set its desired size.
-                    self.desired_size = image.get_size();
+                    self.desired_size = size;
+                }
+            } else if is_viewport_changed {
+                // If client rescaled the surface, it requests new size.
+                self.requested_size = size;
+                if self.parent_sid.is_valid() {
+                    self.desired_size = size;
                 }
             }
         }
@@ -213,6 +256,9 @@ impl Surface {
             requested_size: self.requested_size,
             state_flags: self.state_flags,
             data_source: self.buffer.clone(),
+            viewport: self.viewport,
+            title: self.title.clone(),
+            app_id: self.app_id.clone(),
         }
     }
 
@@ -223,10 +269,7 @@ impl Surface {
 
     /// Returns surfaces rendering context.
     pub fn get_renderer_context(&self) -> SurfaceContext {
-        SurfaceContext {
-            id: self.id,
-            pos: self.relative_position,
-        }
+        SurfaceContext::new(self.id, self.relative_position)
     }
 
     /// Returns size desired by compositor.
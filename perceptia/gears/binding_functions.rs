@@ -5,6 +5,8 @@
 
 // -------------------------------------------------------------------------------------------------
 
+use uinput_sys;
+
 use qualia::{Action, Direction, InputCode};
 
 use input_manager::mode_name;
@@ -77,6 +79,18 @@ pub trait InputContext {
     /// Clears command.
     fn clean_command(&mut self);
 
+    /// Tells compositor to execute the last executed command again given number of times.
+    fn repeat_command(&mut self, count: u32);
+
+    /// Starts recording executed commands into register with given name.
+    fn start_recording(&mut self, register: char);
+
+    /// Stops recording and saves recorded commands. Returns `false` if nothing was being recorded.
+    fn stop_recording(&mut self) -> bool;
+
+    /// Tells compositor to execute commands recorded in given register given number of times.
+    fn replay_register(&mut self, register: char, count: u32);
+
     /// Activates/deactivates input mode.
     fn activate_mode(&mut self, mode_name: &'static str, active: bool);
 
@@ -85,6 +99,10 @@ pub trait InputContext {
 
     /// Just like `get_code` but returns number if number key was pressed, `None` otherwise.
     fn get_code_as_number(&self) -> Option<i32>;
+
+    /// Just like `get_code` but returns lowercase letter if letter key was pressed, `None`
+    /// otherwise.
+    fn get_code_as_letter(&self) -> Option<char>;
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -115,6 +133,17 @@ fn put_direction(context: &mut InputContext, direction: Direction) {
 
 // -------------------------------------------------------------------------------------------------
 
+/// Helper function returning number of repetitions given as magnitude prefix. Defaults to one.
+fn get_count(context: &mut InputContext) -> u32 {
+    if context.previous_modification() == PreviousModification::Magnitude {
+        context.get_magnitude().max(1) as u32
+    } else {
+        1
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
 /// Helper macro defining implementation of `Executor` without `release` method.
 macro_rules! define_simple_executor {
     ($name:ident($context:ident) $callback:block) => {
@@ -217,6 +246,51 @@ define_simple_executor!(PutResize(context) {
 
 // -------------------------------------------------------------------------------------------------
 
+/// Sets layout action in command but do not execute.
+define_simple_executor!(PutLayout(context) {
+    put_action(context, Action::Layout);
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Sets mark action in command and waits for name of the mark.
+define_simple_executor!(PutMark(context) {
+    context.set_action(Action::Mark);
+    context.set_direction(Direction::None);
+    context.activate_mode(mode_name::MARK, true);
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Sets mark direction in command and waits for name of the mark. If no action was set focus
+/// action will be used.
+define_simple_executor!(PutMarkReference(context) {
+    context.set_direction(Direction::Mark);
+    context.activate_mode(mode_name::MARK, true);
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Completes command started with `PutMark` or `PutMarkReference` with name of the mark taken from
+/// pressed letter key and executes it. Escape cancels the command. Other keys are ignored.
+define_simple_executor!(PutMarkName(context) {
+    if let Some(letter) = context.get_code_as_letter() {
+        context.activate_mode(mode_name::MARK, false);
+        if context.get_action() == Action::None {
+            context.set_action(Action::Focus);
+        }
+        context.set_magnitude(1);
+        context.set_string(letter.to_string());
+        context.execute_command();
+        context.clean_command();
+    } else if context.get_code() == uinput_sys::KEY_ESC as InputCode {
+        context.activate_mode(mode_name::MARK, false);
+        context.clean_command();
+    }
+});
+
+// -------------------------------------------------------------------------------------------------
+
 /// Sets north direction in command but do not execute.
 define_simple_executor!(PutNorth(context) {
     put_direction(context, Direction::North);
@@ -322,6 +396,15 @@ define_simple_executor!(Stackize(context) {
 
 // -------------------------------------------------------------------------------------------------
 
+/// Executes command resetting sizes of frames in selected container so they are equal.
+define_simple_executor!(Equalize(context) {
+    context.set_action(Action::Configure);
+    context.set_direction(Direction::None);
+    context.execute_command();
+});
+
+// -------------------------------------------------------------------------------------------------
+
 /// Executes command toggling anchorization.
 define_simple_executor!(ToggleAnchorization(context) {
     context.set_action(Action::Anchor);
@@ -331,6 +414,253 @@ define_simple_executor!(ToggleAnchorization(context) {
 
 // -------------------------------------------------------------------------------------------------
 
+/// Executes command toggling fullscreen state of focused surface.
+define_simple_executor!(ToggleFullscreen(context) {
+    context.set_action(Action::Fullscreen);
+    context.set_direction(Direction::None);
+    context.execute_command();
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Executes command toggling maximization of focused surface.
+define_simple_executor!(ToggleMaximization(context) {
+    context.set_action(Action::Maximize);
+    context.set_direction(Direction::None);
+    context.execute_command();
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Executes command focusing the most recent surface demanding attention.
+define_simple_executor!(FocusUrgent(context) {
+    context.set_action(Action::Focus);
+    context.set_direction(Direction::Urgent);
+    context.execute_command();
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Executes command toggling stickiness of focused floating surface.
+define_simple_executor!(ToggleSticky(context) {
+    context.set_action(Action::Stick);
+    context.set_direction(Direction::None);
+    context.execute_command();
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Executes command hiding focused surface in scratchpad.
+define_simple_executor!(HideInScratchpad(context) {
+    context.set_action(Action::Hide);
+    context.set_direction(Direction::None);
+    context.execute_command();
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Executes command showing most recent surface from scratchpad or hiding it back if focused.
+define_simple_executor!(ToggleScratchpad(context) {
+    context.set_action(Action::Scratchpad);
+    context.set_direction(Direction::None);
+    context.execute_command();
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Executes command enabling or disabling gaps and borders in current workspace.
+define_simple_executor!(ToggleSpacing(context) {
+    context.set_action(Action::Spacing);
+    context.set_direction(Direction::None);
+    context.execute_command();
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Sets spacing action in command but do not execute. Directions change sizes of gaps.
+define_simple_executor!(PutSpacing(context) {
+    put_action(context, Action::Spacing);
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Executes command switching current workspace to next strategy of handling new surfaces.
+define_simple_executor!(CicleStrategyForward(context) {
+    context.set_action(Action::Strategy);
+    context.set_direction(Direction::Forward);
+    context.set_magnitude(1);
+    context.execute_command();
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Executes command switching current workspace to next automatic layout.
+define_simple_executor!(CicleLayoutForward(context) {
+    context.set_action(Action::Layout);
+    context.set_direction(Direction::Forward);
+    context.set_magnitude(1);
+    context.execute_command();
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Executes command switching current workspace to previous automatic layout.
+define_simple_executor!(CicleLayoutBackward(context) {
+    context.set_action(Action::Layout);
+    context.set_direction(Direction::Backward);
+    context.set_magnitude(1);
+    context.execute_command();
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Helper function executing command setting layout with given name in current workspace.
+fn set_layout(context: &mut InputContext, name: &str) {
+    context.set_action(Action::Layout);
+    context.set_direction(Direction::None);
+    context.set_string(name.to_owned());
+    context.execute_command();
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Executes command disabling automatic layout in current workspace.
+define_simple_executor!(LayoutManual(context) {
+    set_layout(context, "manual");
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Executes command setting master-stack layout in current workspace.
+define_simple_executor!(LayoutMasterStack(context) {
+    set_layout(context, "master_stack");
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Executes command setting grid layout in current workspace.
+define_simple_executor!(LayoutGrid(context) {
+    set_layout(context, "grid");
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Executes command setting spiral layout in current workspace.
+define_simple_executor!(LayoutSpiral(context) {
+    set_layout(context, "spiral");
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Executes command setting centered-master layout in current workspace.
+define_simple_executor!(LayoutCenteredMaster(context) {
+    set_layout(context, "centered_master");
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Executes command saving frame layout of current workspace to file named after the workspace.
+define_simple_executor!(SaveLayout(context) {
+    context.set_action(Action::Save);
+    context.set_direction(Direction::None);
+    context.set_string(String::new());
+    context.execute_command();
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Executes command restoring frame layout of current workspace from file named after the
+/// workspace.
+define_simple_executor!(RestoreLayout(context) {
+    context.set_action(Action::Restore);
+    context.set_direction(Direction::None);
+    context.set_string(String::new());
+    context.execute_command();
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Executes command undoing last change of frame layout.
+define_simple_executor!(Undo(context) {
+    context.set_action(Action::Undo);
+    context.set_direction(Direction::None);
+    context.execute_command();
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Executes command redoing last undone change of frame layout.
+define_simple_executor!(Redo(context) {
+    context.set_action(Action::Redo);
+    context.set_direction(Direction::None);
+    context.execute_command();
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Executes command asking client to close selected surface or all surfaces selected in visual
+/// mode.
+define_simple_executor!(Close(context) {
+    context.set_action(Action::Close);
+    context.set_direction(Direction::None);
+    context.execute_command();
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Sets rename action in command and waits for new title of current workspace.
+define_simple_executor!(PutRename(context) {
+    context.set_action(Action::Rename);
+    context.set_direction(Direction::None);
+    context.set_string(String::new());
+    context.activate_mode(mode_name::NAME, true);
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Appends letter or digit of pressed key to the string of the command started with `PutRename`.
+/// Backspace removes last character, enter executes the command and escape cancels it. Other keys
+/// are ignored.
+define_simple_executor!(PutNameCharacter(context) {
+    let code = context.get_code();
+    let digit = match context.get_code_as_number() {
+        Some(number) if number >= 0 => Some(number),
+        _ => None,
+    };
+
+    if let Some(letter) = context.get_code_as_letter() {
+        let mut string = context.get_string();
+        string.push(letter);
+        context.set_string(string);
+    } else if let Some(number) = digit {
+        let mut string = context.get_string();
+        string.push_str(&number.to_string());
+        context.set_string(string);
+    } else if code == uinput_sys::KEY_BACKSPACE as InputCode {
+        let mut string = context.get_string();
+        string.pop();
+        context.set_string(string);
+    } else if code == uinput_sys::KEY_ENTER as InputCode {
+        context.activate_mode(mode_name::NAME, false);
+        context.set_magnitude(1);
+        context.execute_command();
+        context.clean_command();
+    } else if code == uinput_sys::KEY_ESC as InputCode {
+        context.activate_mode(mode_name::NAME, false);
+        context.clean_command();
+    }
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Sets relocate action in command. Planar direction moves current workspace to other display,
+/// temporal direction moves it among workspaces of its display.
+define_simple_executor!(PutRelocate(context) {
+    put_action(context, Action::Relocate);
+});
+
+// -------------------------------------------------------------------------------------------------
+
 /// Executes command for circling surfaces forward.
 define_simple_executor!(CicleHistoryForward(context) {
     context.set_action(Action::Focus);
@@ -552,15 +882,155 @@ define_simple_executor!(SwapModeInsertToNormal(context) {
 
 // -------------------------------------------------------------------------------------------------
 
-/// Switches visual mode on and off.
+/// Switches normal mode off and visual mode on.
+define_simple_executor!(SwapModeNormalToVisual(context) {
+    log_info2!("Swap mode from normal to visual");
+    context.clean_command();
+    context.activate_mode(mode_name::NORMAL, false);
+    context.activate_mode(mode_name::VISUAL, true);
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Switches visual mode off and normal mode on.
+define_simple_executor!(SwapModeVisualToNormal(context) {
+    log_info2!("Swap mode from visual to normal");
+    context.clean_command();
+    context.activate_mode(mode_name::VISUAL, false);
+    context.activate_mode(mode_name::NORMAL, true);
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Switches overview mode on. Thumbnails of all surfaces are shown until overview mode is switched
+/// off.
+define_simple_executor!(StartOverview(context) {
+    log_info2!("Start overview");
+    context.clean_command();
+    context.activate_mode(mode_name::OVERVIEW, true);
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Switches overview mode off focusing surface of chosen thumbnail.
+define_simple_executor!(StopOverview(context) {
+    log_info2!("Stop overview");
+    context.clean_command();
+    context.activate_mode(mode_name::OVERVIEW, false);
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Switches overview mode off bringing back focus to surface focused before overview was started.
+define_simple_executor!(CancelOverview(context) {
+    log_info2!("Cancel overview");
+    context.set_action(Action::Focus);
+    context.set_direction(Direction::Backward);
+    context.set_magnitude(1);
+    context.execute_command();
+    context.clean_command();
+    context.activate_mode(mode_name::OVERVIEW, false);
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Chooses thumbnail in overview mode basing on key code and switches overview mode off.
+///
+/// E.g. if key [5] was pressed, surface shown on fifth thumbnail will be focused.
+define_simple_executor!(PickThumbnail(context) {
+    if let Some(number) = context.get_code_as_number() {
+        if number > 0 {
+            context.set_action(Action::Focus);
+            context.set_direction(Direction::Begin);
+            context.set_magnitude(number);
+            context.execute_command();
+            context.clean_command();
+            context.activate_mode(mode_name::OVERVIEW, false);
+        }
+    }
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Executes the last executed command again. Magnitude prefix tells how many times.
+define_simple_executor!(RepeatCommand(context) {
+    let count = get_count(context);
+    context.clean_command();
+    context.repeat_command(count);
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Stops recording commands if recording is ongoing. Otherwise waits for name of the register to
+/// start recording into.
+define_simple_executor!(ToggleRecording(context) {
+    context.clean_command();
+    if !context.stop_recording() {
+        context.activate_mode(mode_name::RECORD, true);
+    }
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Starts recording commands into register named after pressed letter key. Escape cancels. Other
+/// keys are ignored.
+define_simple_executor!(PutRecordRegister(context) {
+    if let Some(letter) = context.get_code_as_letter() {
+        context.activate_mode(mode_name::RECORD, false);
+        context.start_recording(letter);
+    } else if context.get_code() == uinput_sys::KEY_ESC as InputCode {
+        context.activate_mode(mode_name::RECORD, false);
+    }
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Waits for name of the register to replay. Magnitude prefix is kept as number of repetitions.
+define_simple_executor!(PutReplay(context) {
+    context.activate_mode(mode_name::REPLAY, true);
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Replays commands from register named after pressed letter key. Escape cancels. Other keys are
+/// ignored.
+define_simple_executor!(PutReplayRegister(context) {
+    if let Some(letter) = context.get_code_as_letter() {
+        let count = get_count(context);
+        context.activate_mode(mode_name::REPLAY, false);
+        context.clean_command();
+        context.replay_register(letter, count);
+    } else if context.get_code() == uinput_sys::KEY_ESC as InputCode {
+        context.activate_mode(mode_name::REPLAY, false);
+        context.clean_command();
+    }
+});
+
+// -------------------------------------------------------------------------------------------------
+
+/// Starts dragging surface under cursor on press and stops on release.
 define_full_executor!{SurfaceDrag(context)
     activate => {
         log_info2!("Start surface drag");
-        context.activate_mode(mode_name::VISUAL, true);
+        context.activate_mode(mode_name::DRAG, true);
     }
     release => {
         log_info2!("Stop surface drag");
-        context.activate_mode(mode_name::VISUAL, false);
+        context.activate_mode(mode_name::DRAG, false);
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Starts resizing surface under cursor on press and stops on release.
+define_full_executor!{SurfaceResize(context)
+    activate => {
+        log_info2!("Start surface resize");
+        context.activate_mode(mode_name::RESIZE, true);
+    }
+    release => {
+        log_info2!("Stop surface resize");
+        context.activate_mode(mode_name::RESIZE, false);
     }
 }
 
@@ -17,7 +17,8 @@ use serde_yaml;
 use serde::ser::{Serialize, Serializer, SerializeMap};
 
 use qualia::{Binding, Directories, Illusion, modifier};
-use qualia::{AestheticsConfig, KeyboardConfig, ExhibitorConfig, InputConfig};
+use qualia::{AestheticsConfig, KeyboardConfig, ExhibitorConfig, InputConfig, WaylandConfig};
+use qualia::{RuleConfig, SpacingConfig, WorkspaceConfig};
 
 use config_defaults::DefaultConfig;
 use binding_functions;
@@ -40,6 +41,11 @@ macro_rules! load_config {
             $config.$key = value as u32;
         }
     };
+    ( _entry_ $config:expr; $section:expr; $key:ident: bool ) => {
+        if let Some(value) = $section[stringify!($key)].as_bool() {
+            $config.$key = value;
+        }
+    };
     ( _entry_ $config:expr; $section:expr; $key:ident: f32 ) => {
         if let Some(value) = $section[stringify!($key)].as_f64() {
             $config.$key = value as f32;
@@ -110,6 +116,15 @@ pub struct KeybindingsConfig {
 
     /// Bindings for `visual` mode.
     pub visual: Vec<BindingEntry>,
+
+    /// Bindings for `overview` mode.
+    pub overview: Vec<BindingEntry>,
+
+    /// Binding which makes compositor bindings work again while focused client inhibits them.
+    pub inhibit_escape: Binding,
+
+    /// Path to file where commands recorded in registers are stored.
+    pub registers_path: Option<PathBuf>,
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -131,6 +146,9 @@ pub struct Config {
 
     /// Set of key bindings.
     keybindings: KeybindingsConfig,
+
+    /// Config for Wayland frontend.
+    wayland: WaylandConfig,
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -141,7 +159,8 @@ impl Config {
                exhibitor: ExhibitorConfig,
                input: InputConfig,
                keyboard: KeyboardConfig,
-               keybindings: KeybindingsConfig)
+               keybindings: KeybindingsConfig,
+               wayland: WaylandConfig)
                -> Self {
         Config {
             aesthetics: aesthetics,
@@ -149,6 +168,7 @@ impl Config {
             input: input,
             keyboard: keyboard,
             keybindings: keybindings,
+            wayland: wayland,
         }
     }
 
@@ -160,6 +180,12 @@ impl Config {
     /// TODO: Keep reading files even if parsing one fails.
     pub fn read(dirs: &Directories) -> Result<Self, Illusion> {
         let mut config = Config::default();
+        config.exhibitor.compositor.layouts_path =
+            dirs.user_config.as_ref().map(|dir| dir.join("layouts"));
+        // There is no separate state directory so state kept between sessions (like marks or
+        // registers) is stored in data directory.
+        config.exhibitor.compositor.marks_path = Some(dirs.data.join("marks"));
+        config.keybindings.registers_path = Some(dirs.data.join("registers"));
 
         for dir in vec![dirs.system_config.clone(), dirs.user_config.clone()] {
             if let Some(mut path) = dir {
@@ -202,7 +228,58 @@ impl Config {
 
             load_config!{self.exhibitor.compositor; yaml["exhibitor"]["compositor"];
                 move_step: u32,
-                resize_step: u32
+                resize_step: u32,
+                layouts_path: PathBuf,
+                marks_path: PathBuf
+            }
+
+            load_config!{self.exhibitor.compositor.focus; yaml["exhibitor"]["compositor"]["focus"];
+                policy: String,
+                delay: u32,
+                warp_pointer: bool
+            }
+
+            let compositor = &yaml["exhibitor"]["compositor"];
+
+            // Keys used before workspaces could be configured separately are still accepted
+            if !compositor["spacing"].is_badvalue() {
+                log_warn1!("Config: 'spacing' is deprecated, use 'workspaces.default.spacing'");
+                Self::load_spacing(&mut self.exhibitor.compositor.workspace.spacing,
+                                   &compositor["spacing"]);
+            }
+
+            if let Some(hash) = compositor["workspace_spacing"].as_hash() {
+                log_warn1!("Config: 'workspace_spacing' is deprecated, \
+                            use 'workspaces.<title>.spacing'");
+                for (title, entry) in hash.iter() {
+                    if let Some(title) = Self::workspace_title(title) {
+                        let mut workspace = self.get_workspace_config(&title);
+                        Self::load_spacing(&mut workspace.spacing, entry);
+                        self.exhibitor.compositor.workspaces.insert(title, workspace);
+                    }
+                }
+            }
+
+            let workspaces = &compositor["workspaces"];
+            Self::load_workspace(&mut self.exhibitor.compositor.workspace, &workspaces["default"]);
+
+            if let Some(hash) = workspaces.as_hash() {
+                for (title, entry) in hash.iter() {
+                    if let Some(title) = Self::workspace_title(title) {
+                        let mut workspace = self.get_workspace_config(&title);
+                        Self::load_workspace(&mut workspace, entry);
+                        self.exhibitor.compositor.workspaces.insert(title, workspace);
+                    }
+                }
+            }
+
+            if let Some(hash) = compositor["workspace_outputs"].as_hash() {
+                for (title, output) in hash.iter() {
+                    if let (Some(title), Some(output)) = (title.as_str(), output.as_str()) {
+                        let map = &mut self.exhibitor.compositor.workspace_outputs;
+                        map.insert(title.to_owned(), output.to_owned());
+                    }
+                }
             }
 
             load_config!{self.exhibitor.strategist; yaml["exhibitor"]["strategist"];
@@ -210,6 +287,12 @@ impl Config {
                 choose_floating: String
             }
 
+            if let Some(array) = yaml["exhibitor"]["strategist"]["rules"].as_vec() {
+                for entry in array.iter() {
+                    self.exhibitor.strategist.rules.push(Self::load_rule(entry));
+                }
+            }
+
             load_config!{self.input; yaml["input"];
                 touchpad_scale: f32,
                 touchpad_pressure_threshold: i32,
@@ -221,6 +304,31 @@ impl Config {
                 variant: String
             }
 
+            load_config!{self.wayland; yaml["wayland"];
+                xwayland: bool
+            }
+
+            if let yaml_rust::yaml::Yaml::Array(ref array) =
+                yaml["wayland"]["virtual_input_clients"] {
+                self.wayland.virtual_input_clients = Self::array_to_strings(array);
+            }
+
+            load_config!{self.keybindings; yaml["keybindings"];
+                registers_path: PathBuf
+            }
+
+            if let Some(value) = yaml["keybindings"]["inhibit_escape"]["key"].as_str() {
+                let mut mods = modifier::NONE;
+                let entry = &yaml["keybindings"]["inhibit_escape"];
+                if let yaml_rust::yaml::Yaml::Array(ref array) = entry["mods"] {
+                    for m in Self::array_to_strings(array) {
+                        mods |= Self::string_to_key_mod(&m);
+                    }
+                }
+                let code = Self::string_to_key_code(value);
+                self.keybindings.inhibit_escape = Binding::new(code, mods);
+            }
+
             if let yaml_rust::yaml::Yaml::Array(ref array) = yaml["keybindings"]["insert"] {
                 for e in array.iter() {
                     let code = {
@@ -281,8 +389,91 @@ impl Config {
 
 // -------------------------------------------------------------------------------------------------
 
-// Helper methods for parsing keybindings configuration
+// Helper methods for parsing configuration
 impl Config {
+    /// Reads window rule. Entries not given or having wrong type are left unset.
+    fn load_rule(entry: &yaml_rust::Yaml) -> RuleConfig {
+        RuleConfig {
+            app_id: entry["app_id"].as_str().map(|s| s.to_owned()),
+            title: entry["title"].as_str().map(|s| s.to_owned()),
+            popup: entry["popup"].as_bool(),
+            workspace: match entry["workspace"] {
+                yaml_rust::yaml::Yaml::String(ref value) => Some(value.clone()),
+                yaml_rust::yaml::Yaml::Integer(value) => Some(value.to_string()),
+                _ => None,
+            },
+            output: entry["output"].as_str().map(|s| s.to_owned()),
+            floating: entry["floating"].as_bool(),
+            width: Self::load_dimension(&entry["width"], "width"),
+            height: Self::load_dimension(&entry["height"], "height"),
+            x: entry["x"].as_i64().map(|v| v as i32),
+            y: entry["y"].as_i64().map(|v| v as i32),
+            geometry: entry["geometry"].as_str().map(|s| s.to_owned()),
+            no_focus: entry["no_focus"].as_bool().unwrap_or(false),
+        }
+    }
+
+    /// Reads workspace configuration. Entries not given or having wrong type are left untouched.
+    fn load_workspace(workspace: &mut WorkspaceConfig, entry: &yaml_rust::Yaml) {
+        load_config!{workspace; entry;
+            geometry: String,
+            choose_target: String,
+            choose_floating: String,
+            layout: String
+        }
+
+        Self::load_spacing(&mut workspace.spacing, &entry["spacing"]);
+    }
+
+    /// Reads gaps and borders configuration. Entries not given or having wrong type are left
+    /// untouched.
+    fn load_spacing(spacing: &mut SpacingConfig, entry: &yaml_rust::Yaml) {
+        load_config!{spacing; entry;
+            inner_gap: u32,
+            outer_gap: u32,
+            border: u32,
+            smart_gaps: bool
+        }
+    }
+
+    /// Returns configuration of workspace with given title. If it was not configured yet default
+    /// workspace configuration is returned.
+    fn get_workspace_config(&self, title: &str) -> WorkspaceConfig {
+        self.exhibitor
+            .compositor
+            .workspaces
+            .get(title)
+            .cloned()
+            .unwrap_or_else(|| self.exhibitor.compositor.workspace.clone())
+    }
+
+    /// Translates YAML key to workspace title. Numeric titles are accepted. `default` is reserved
+    /// for configuration of all workspaces.
+    fn workspace_title(key: &yaml_rust::Yaml) -> Option<String> {
+        match *key {
+            yaml_rust::yaml::Yaml::String(ref value) if value != "default" => Some(value.clone()),
+            yaml_rust::yaml::Yaml::Integer(value) => Some(value.to_string()),
+            _ => None,
+        }
+    }
+
+    /// Reads width or height of window rule. Values which are not positive are ignored.
+    fn load_dimension(entry: &yaml_rust::Yaml, name: &str) -> Option<u32> {
+        match entry.as_i64() {
+            Some(value) if value > 0 && value <= u32::max_value() as i64 => Some(value as u32),
+            Some(value) => {
+                log_warn1!("Config: rule {} must be positive, ignoring '{}'", name, value);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Translates YAML array to vector of strings skipping entries which are not strings.
+    fn array_to_strings(array: &Vec<yaml_rust::Yaml>) -> Vec<String> {
+        array.iter().filter_map(|e| e.as_str()).map(|e| e.to_owned()).collect()
+    }
+
     /// Translates string to key code.
     fn string_to_key_code(value: &str) -> i32 {
         match value.to_ascii_lowercase().as_ref() {
@@ -323,6 +514,7 @@ impl Config {
             "n" => uinput_sys::KEY_N,
             "m" => uinput_sys::KEY_M,
             "space" => uinput_sys::KEY_SPACE,
+            "esc" => uinput_sys::KEY_ESC,
             _ => uinput_sys::KEY_SPACE,
         }
     }
@@ -353,6 +545,7 @@ impl Config {
             "put_jump" => binding_functions::PutJump::new(),
             "put_dive" => binding_functions::PutDive::new(),
             "put_move" => binding_functions::PutMove::new(),
+            "put_layout" => binding_functions::PutLayout::new(),
             "put_north" => binding_functions::PutNorth::new(),
             "put_east" => binding_functions::PutEast::new(),
             "put_south" => binding_functions::PutSouth::new(),
@@ -365,7 +558,31 @@ impl Config {
             "horizontalize" => binding_functions::Horizontalize::new(),
             "verticalize" => binding_functions::Verticalize::new(),
             "stackize" => binding_functions::Stackize::new(),
+            "equalize" => binding_functions::Equalize::new(),
             "toggle_anchorization" => binding_functions::ToggleAnchorization::new(),
+            "toggle_fullscreen" => binding_functions::ToggleFullscreen::new(),
+            "toggle_maximization" => binding_functions::ToggleMaximization::new(),
+            "toggle_sticky" => binding_functions::ToggleSticky::new(),
+            "focus_urgent" => binding_functions::FocusUrgent::new(),
+            "hide_in_scratchpad" => binding_functions::HideInScratchpad::new(),
+            "toggle_scratchpad" => binding_functions::ToggleScratchpad::new(),
+            "toggle_spacing" => binding_functions::ToggleSpacing::new(),
+            "put_spacing" => binding_functions::PutSpacing::new(),
+            "cicle_strategy_forward" => binding_functions::CicleStrategyForward::new(),
+            "cicle_layout_forward" => binding_functions::CicleLayoutForward::new(),
+            "cicle_layout_backward" => binding_functions::CicleLayoutBackward::new(),
+            "layout_manual" => binding_functions::LayoutManual::new(),
+            "layout_master_stack" => binding_functions::LayoutMasterStack::new(),
+            "layout_grid" => binding_functions::LayoutGrid::new(),
+            "layout_spiral" => binding_functions::LayoutSpiral::new(),
+            "layout_centered_master" => binding_functions::LayoutCenteredMaster::new(),
+            "save_layout" => binding_functions::SaveLayout::new(),
+            "restore_layout" => binding_functions::RestoreLayout::new(),
+            "undo" => binding_functions::Undo::new(),
+            "redo" => binding_functions::Redo::new(),
+            "close" => binding_functions::Close::new(),
+            "put_rename" => binding_functions::PutRename::new(),
+            "put_relocate" => binding_functions::PutRelocate::new(),
             "cicle_history_forward" => binding_functions::CicleHistoryForward::new(),
             "cicle_history_backward" => binding_functions::CicleHistoryBackward::new(),
             "focus_right" => binding_functions::FocusRight::new(),
@@ -387,6 +604,12 @@ impl Config {
             "focus_workspace" => binding_functions::FocusWorkspace::new(),
             "swap_mode_normal_to_insert" => binding_functions::SwapModeNormalToInsert::new(),
             "swap_mode_insert_to_normal" => binding_functions::SwapModeInsertToNormal::new(),
+            "swap_mode_normal_to_visual" => binding_functions::SwapModeNormalToVisual::new(),
+            "swap_mode_visual_to_normal" => binding_functions::SwapModeVisualToNormal::new(),
+            "start_overview" => binding_functions::StartOverview::new(),
+            "repeat_command" => binding_functions::RepeatCommand::new(),
+            "toggle_recording" => binding_functions::ToggleRecording::new(),
+            "put_replay" => binding_functions::PutReplay::new(),
             _ => binding_functions::Nop::new(),
         }
     }
@@ -425,6 +648,11 @@ impl Config {
     pub fn get_keybindings_config(&self) -> &KeybindingsConfig {
         &self.keybindings
     }
+
+    /// Returns configuration for Wayland frontend.
+    pub fn get_wayland_config(&self) -> &WaylandConfig {
+        &self.wayland
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -433,11 +661,12 @@ impl Serialize for Config {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where S: Serializer
     {
-        let mut map = serializer.serialize_map(Some(5))?;
+        let mut map = serializer.serialize_map(Some(6))?;
         map.serialize_entry("aesthetics", &self.aesthetics)?;
         map.serialize_entry("exhibitor", &self.exhibitor)?;
         map.serialize_entry("input", &self.input)?;
         map.serialize_entry("keyboard", &self.keyboard)?;
+        map.serialize_entry("wayland", &self.wayland)?;
         // TODO: Serialize key bindings.
         // map.serialize_entry("keybindings", &self.keybindings)?;
         map.end()
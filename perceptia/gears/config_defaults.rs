@@ -5,11 +5,14 @@
 
 // -------------------------------------------------------------------------------------------------
 
+use std::collections::HashMap;
+
 use uinput_sys;
 
-use qualia::modifier;
+use qualia::{modifier, Binding};
 pub use qualia::{AestheticsConfig, CompositorConfig, KeyboardConfig};
-pub use qualia::{ExhibitorConfig, InputConfig, StrategistConfig};
+pub use qualia::{ExhibitorConfig, InputConfig, SpacingConfig, StrategistConfig, WaylandConfig};
+pub use qualia::{FocusConfig, WorkspaceConfig};
 
 use config::{BindingEntry, Config, KeybindingsConfig};
 use binding_functions;
@@ -29,7 +32,8 @@ impl DefaultConfig for Config {
                     ExhibitorConfig::default(),
                     InputConfig::default(),
                     KeyboardConfig::default(),
-                    KeybindingsConfig::default())
+                    KeybindingsConfig::default(),
+                    WaylandConfig::default())
     }
 }
 
@@ -48,6 +52,51 @@ impl DefaultConfig for CompositorConfig {
         CompositorConfig {
             move_step: 10,
             resize_step: 10,
+            focus: FocusConfig::default(),
+            workspace: WorkspaceConfig::default(),
+            workspaces: HashMap::new(),
+            workspace_outputs: HashMap::new(),
+            layouts_path: None,
+            marks_path: None,
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl DefaultConfig for FocusConfig {
+    fn default() -> Self {
+        FocusConfig {
+            policy: "click".to_owned(),
+            delay: 0,
+            warp_pointer: false,
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl DefaultConfig for WorkspaceConfig {
+    fn default() -> Self {
+        WorkspaceConfig {
+            geometry: "stacked".to_owned(),
+            choose_target: String::new(),
+            choose_floating: String::new(),
+            spacing: SpacingConfig::default(),
+            layout: "manual".to_owned(),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl DefaultConfig for SpacingConfig {
+    fn default() -> Self {
+        SpacingConfig {
+            inner_gap: 0,
+            outer_gap: 0,
+            border: 0,
+            smart_gaps: false,
         }
     }
 }
@@ -98,12 +147,43 @@ impl DefaultConfig for KeybindingsConfig {
                      BindingEntry::new(uinput_sys::KEY_S,
                                        modifier::NONE,
                                        binding_functions::Stackize::new()),
+                     BindingEntry::new(uinput_sys::KEY_E,
+                                       modifier::NONE,
+                                       binding_functions::Equalize::new()),
+                     BindingEntry::new(uinput_sys::KEY_G,
+                                       modifier::NONE,
+                                       binding_functions::ToggleSpacing::new()),
+                     BindingEntry::new(uinput_sys::KEY_A,
+                                       modifier::NONE,
+                                       binding_functions::CicleStrategyForward::new()),
+                     BindingEntry::new(uinput_sys::KEY_U,
+                                       modifier::NONE,
+                                       binding_functions::Undo::new()),
+                     BindingEntry::new(uinput_sys::KEY_R,
+                                       modifier::LCTL,
+                                       binding_functions::Redo::new()),
                      BindingEntry::new(uinput_sys::KEY_I,
                                        modifier::NONE,
                                        binding_functions::SwapModeNormalToInsert::new()),
                      BindingEntry::new(uinput_sys::KEY_SPACE,
                                        modifier::NONE,
                                        binding_functions::SwapModeNormalToInsert::new()),
+                     BindingEntry::new(uinput_sys::KEY_V,
+                                       modifier::LSHF,
+                                       binding_functions::SwapModeNormalToVisual::new()),
+                     BindingEntry::new(uinput_sys::KEY_X,
+                                       modifier::NONE,
+                                       binding_functions::Close::new()),
+                     // repeating
+                     BindingEntry::new(uinput_sys::KEY_DOT,
+                                       modifier::NONE,
+                                       binding_functions::RepeatCommand::new()),
+                     BindingEntry::new(uinput_sys::KEY_Q,
+                                       modifier::NONE,
+                                       binding_functions::ToggleRecording::new()),
+                     BindingEntry::new(uinput_sys::KEY_2,
+                                       modifier::LSHF,
+                                       binding_functions::PutReplay::new()),
                      // actions
                      BindingEntry::new(uinput_sys::KEY_F,
                                        modifier::NONE,
@@ -123,6 +203,26 @@ impl DefaultConfig for KeybindingsConfig {
                      BindingEntry::new(uinput_sys::KEY_R,
                                        modifier::NONE,
                                        binding_functions::PutResize::new()),
+                     BindingEntry::new(uinput_sys::KEY_L,
+                                       modifier::NONE,
+                                       binding_functions::PutLayout::new()),
+                     BindingEntry::new(uinput_sys::KEY_G,
+                                       modifier::LSHF,
+                                       binding_functions::PutSpacing::new()),
+                     // marks
+                     BindingEntry::new(uinput_sys::KEY_M,
+                                       modifier::LSHF,
+                                       binding_functions::PutMark::new()),
+                     BindingEntry::new(uinput_sys::KEY_APOSTROPHE,
+                                       modifier::NONE,
+                                       binding_functions::PutMarkReference::new()),
+                     // workspaces
+                     BindingEntry::new(uinput_sys::KEY_W,
+                                       modifier::NONE,
+                                       binding_functions::PutRename::new()),
+                     BindingEntry::new(uinput_sys::KEY_W,
+                                       modifier::LSHF,
+                                       binding_functions::PutRelocate::new()),
                      // directions
                      BindingEntry::new(uinput_sys::KEY_RIGHT,
                                        modifier::NONE,
@@ -336,9 +436,45 @@ impl DefaultConfig for KeybindingsConfig {
                      BindingEntry::new(uinput_sys::KEY_SPACE,
                                        modifier::LMTA,
                                        binding_functions::ToggleAnchorization::new()),
+                     BindingEntry::new(uinput_sys::KEY_F,
+                                       modifier::LMTA,
+                                       binding_functions::ToggleFullscreen::new()),
+                     BindingEntry::new(uinput_sys::KEY_M,
+                                       modifier::LMTA,
+                                       binding_functions::ToggleMaximization::new()),
+                     BindingEntry::new(uinput_sys::KEY_P,
+                                       modifier::LMTA,
+                                       binding_functions::ToggleSticky::new()),
+                     BindingEntry::new(uinput_sys::KEY_U,
+                                       modifier::LMTA,
+                                       binding_functions::FocusUrgent::new()),
+                     BindingEntry::new(uinput_sys::KEY_O,
+                                       modifier::LMTA,
+                                       binding_functions::StartOverview::new()),
+                     BindingEntry::new(uinput_sys::KEY_MINUS,
+                                       modifier::LMTA | modifier::LSHF,
+                                       binding_functions::HideInScratchpad::new()),
+                     BindingEntry::new(uinput_sys::KEY_MINUS,
+                                       modifier::LMTA,
+                                       binding_functions::ToggleScratchpad::new()),
+                     BindingEntry::new(uinput_sys::KEY_L,
+                                       modifier::LMTA,
+                                       binding_functions::CicleLayoutForward::new()),
+                     BindingEntry::new(uinput_sys::KEY_L,
+                                       modifier::LMTA | modifier::LSHF,
+                                       binding_functions::CicleLayoutBackward::new()),
+                     BindingEntry::new(uinput_sys::KEY_S,
+                                       modifier::LCTL | modifier::LMTA,
+                                       binding_functions::SaveLayout::new()),
+                     BindingEntry::new(uinput_sys::KEY_R,
+                                       modifier::LCTL | modifier::LMTA,
+                                       binding_functions::RestoreLayout::new()),
                      BindingEntry::new(uinput_sys::BTN_LEFT,
                                        modifier::LMTA,
                                        binding_functions::SurfaceDrag::new()),
+                     BindingEntry::new(uinput_sys::BTN_RIGHT,
+                                       modifier::LMTA,
+                                       binding_functions::SurfaceResize::new()),
                      // spawning processes
                      BindingEntry::new(uinput_sys::KEY_T,
                                        modifier::LCTL | modifier::LMTA,
@@ -346,10 +482,177 @@ impl DefaultConfig for KeybindingsConfig {
             },
             visual: {
                 vec![// visual
+                     BindingEntry::new(uinput_sys::KEY_ESC,
+                                       modifier::NONE,
+                                       binding_functions::SwapModeVisualToNormal::new()),
+                     BindingEntry::new(uinput_sys::KEY_H,
+                                       modifier::NONE,
+                                       binding_functions::Horizontalize::new()),
+                     BindingEntry::new(uinput_sys::KEY_V,
+                                       modifier::NONE,
+                                       binding_functions::Verticalize::new()),
+                     BindingEntry::new(uinput_sys::KEY_S,
+                                       modifier::NONE,
+                                       binding_functions::Stackize::new()),
+                     BindingEntry::new(uinput_sys::KEY_X,
+                                       modifier::NONE,
+                                       binding_functions::Close::new()),
+                     // extending selection
+                     BindingEntry::new(uinput_sys::KEY_RIGHT,
+                                       modifier::NONE,
+                                       binding_functions::FocusRight::new()),
+                     BindingEntry::new(uinput_sys::KEY_DOWN,
+                                       modifier::NONE,
+                                       binding_functions::FocusDown::new()),
+                     BindingEntry::new(uinput_sys::KEY_LEFT,
+                                       modifier::NONE,
+                                       binding_functions::FocusLeft::new()),
+                     BindingEntry::new(uinput_sys::KEY_UP,
+                                       modifier::NONE,
+                                       binding_functions::FocusUp::new()),
+                     // jumping
+                     BindingEntry::new(uinput_sys::KEY_RIGHT,
+                                       modifier::LSHF,
+                                       binding_functions::JumpRight::new()),
+                     BindingEntry::new(uinput_sys::KEY_DOWN,
+                                       modifier::LSHF,
+                                       binding_functions::JumpDown::new()),
+                     BindingEntry::new(uinput_sys::KEY_LEFT,
+                                       modifier::LSHF,
+                                       binding_functions::JumpLeft::new()),
+                     BindingEntry::new(uinput_sys::KEY_UP,
+                                       modifier::LSHF,
+                                       binding_functions::JumpUp::new()),
+                     // diving
+                     BindingEntry::new(uinput_sys::KEY_RIGHT,
+                                       modifier::LALT,
+                                       binding_functions::DiveRight::new()),
+                     BindingEntry::new(uinput_sys::KEY_DOWN,
+                                       modifier::LALT,
+                                       binding_functions::DiveDown::new()),
+                     BindingEntry::new(uinput_sys::KEY_LEFT,
+                                       modifier::LALT,
+                                       binding_functions::DiveLeft::new()),
+                     BindingEntry::new(uinput_sys::KEY_UP,
+                                       modifier::LALT,
+                                       binding_functions::DiveUp::new()),
+                     // jumping to workspace
+                     BindingEntry::new(uinput_sys::KEY_1,
+                                       modifier::LSHF,
+                                       binding_functions::JumpToWorkspace::new()),
+                     BindingEntry::new(uinput_sys::KEY_2,
+                                       modifier::LSHF,
+                                       binding_functions::JumpToWorkspace::new()),
+                     BindingEntry::new(uinput_sys::KEY_3,
+                                       modifier::LSHF,
+                                       binding_functions::JumpToWorkspace::new()),
+                     BindingEntry::new(uinput_sys::KEY_4,
+                                       modifier::LSHF,
+                                       binding_functions::JumpToWorkspace::new()),
+                     BindingEntry::new(uinput_sys::KEY_5,
+                                       modifier::LSHF,
+                                       binding_functions::JumpToWorkspace::new()),
+                     BindingEntry::new(uinput_sys::KEY_6,
+                                       modifier::LSHF,
+                                       binding_functions::JumpToWorkspace::new()),
+                     BindingEntry::new(uinput_sys::KEY_7,
+                                       modifier::LSHF,
+                                       binding_functions::JumpToWorkspace::new()),
+                     BindingEntry::new(uinput_sys::KEY_8,
+                                       modifier::LSHF,
+                                       binding_functions::JumpToWorkspace::new()),
+                     BindingEntry::new(uinput_sys::KEY_9,
+                                       modifier::LSHF,
+                                       binding_functions::JumpToWorkspace::new()),
+                     // diving to workspace
+                     BindingEntry::new(uinput_sys::KEY_1,
+                                       modifier::LCTL,
+                                       binding_functions::DiveToWorkspace::new()),
+                     BindingEntry::new(uinput_sys::KEY_2,
+                                       modifier::LCTL,
+                                       binding_functions::DiveToWorkspace::new()),
+                     BindingEntry::new(uinput_sys::KEY_3,
+                                       modifier::LCTL,
+                                       binding_functions::DiveToWorkspace::new()),
+                     BindingEntry::new(uinput_sys::KEY_4,
+                                       modifier::LCTL,
+                                       binding_functions::DiveToWorkspace::new()),
+                     BindingEntry::new(uinput_sys::KEY_5,
+                                       modifier::LCTL,
+                                       binding_functions::DiveToWorkspace::new()),
+                     BindingEntry::new(uinput_sys::KEY_6,
+                                       modifier::LCTL,
+                                       binding_functions::DiveToWorkspace::new()),
+                     BindingEntry::new(uinput_sys::KEY_7,
+                                       modifier::LCTL,
+                                       binding_functions::DiveToWorkspace::new()),
+                     BindingEntry::new(uinput_sys::KEY_8,
+                                       modifier::LCTL,
+                                       binding_functions::DiveToWorkspace::new()),
+                     BindingEntry::new(uinput_sys::KEY_9,
+                                       modifier::LCTL,
+                                       binding_functions::DiveToWorkspace::new()),
                      BindingEntry::new(uinput_sys::BTN_LEFT,
                                        modifier::LMTA,
-                                       binding_functions::SurfaceDrag::new())]
+                                       binding_functions::SurfaceDrag::new()),
+                     BindingEntry::new(uinput_sys::BTN_RIGHT,
+                                       modifier::LMTA,
+                                       binding_functions::SurfaceResize::new())]
             },
+            overview: {
+                vec![// overview
+                     BindingEntry::new(uinput_sys::KEY_ESC,
+                                       modifier::NONE,
+                                       binding_functions::CancelOverview::new()),
+                     BindingEntry::new(uinput_sys::KEY_ENTER,
+                                       modifier::NONE,
+                                       binding_functions::StopOverview::new()),
+                     BindingEntry::new(uinput_sys::BTN_LEFT,
+                                       modifier::NONE,
+                                       binding_functions::StopOverview::new()),
+                     // choosing thumbnail
+                     BindingEntry::new(uinput_sys::KEY_RIGHT,
+                                       modifier::NONE,
+                                       binding_functions::FocusRight::new()),
+                     BindingEntry::new(uinput_sys::KEY_DOWN,
+                                       modifier::NONE,
+                                       binding_functions::FocusDown::new()),
+                     BindingEntry::new(uinput_sys::KEY_LEFT,
+                                       modifier::NONE,
+                                       binding_functions::FocusLeft::new()),
+                     BindingEntry::new(uinput_sys::KEY_UP,
+                                       modifier::NONE,
+                                       binding_functions::FocusUp::new()),
+                     BindingEntry::new(uinput_sys::KEY_1,
+                                       modifier::NONE,
+                                       binding_functions::PickThumbnail::new()),
+                     BindingEntry::new(uinput_sys::KEY_2,
+                                       modifier::NONE,
+                                       binding_functions::PickThumbnail::new()),
+                     BindingEntry::new(uinput_sys::KEY_3,
+                                       modifier::NONE,
+                                       binding_functions::PickThumbnail::new()),
+                     BindingEntry::new(uinput_sys::KEY_4,
+                                       modifier::NONE,
+                                       binding_functions::PickThumbnail::new()),
+                     BindingEntry::new(uinput_sys::KEY_5,
+                                       modifier::NONE,
+                                       binding_functions::PickThumbnail::new()),
+                     BindingEntry::new(uinput_sys::KEY_6,
+                                       modifier::NONE,
+                                       binding_functions::PickThumbnail::new()),
+                     BindingEntry::new(uinput_sys::KEY_7,
+                                       modifier::NONE,
+                                       binding_functions::PickThumbnail::new()),
+                     BindingEntry::new(uinput_sys::KEY_8,
+                                       modifier::NONE,
+                                       binding_functions::PickThumbnail::new()),
+                     BindingEntry::new(uinput_sys::KEY_9,
+                                       modifier::NONE,
+                                       binding_functions::PickThumbnail::new())]
+            },
+            inhibit_escape: Binding::new(uinput_sys::KEY_ESC, modifier::LMTA | modifier::LSHF),
+            registers_path: None,
         }
     }
 }
@@ -372,6 +675,18 @@ impl DefaultConfig for StrategistConfig {
         StrategistConfig {
             choose_target: "always_floating".to_owned(),
             choose_floating: "random".to_owned(),
+            rules: Vec::new(),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl DefaultConfig for WaylandConfig {
+    fn default() -> Self {
+        WaylandConfig {
+            virtual_input_clients: Vec::new(),
+            xwayland: false,
         }
     }
 }
@@ -15,13 +15,15 @@ use std::time::Instant;
 use uinput_sys;
 
 use dharma::Signaler;
-use qualia::{Action, Command, Direction, OptionalPosition, InteractionMode, Slide, Vector};
+use qualia::{Action, Command, Direction, OptionalPosition, InteractionMode, Position, Slide};
+use qualia::Vector;
 use qualia::{modifier, Axis, Binding, Button, CatchResult, Key, InputCode, InputValue, KeyState};
 use qualia::{InputForwarding, InputHandling};
 use qualia::{perceptron, Perceptron, Milliseconds};
 
 use config::KeybindingsConfig;
 use binding_functions::{self, Executor};
+use registers::Registers;
 
 // -------------------------------------------------------------------------------------------------
 
@@ -30,6 +32,13 @@ pub mod mode_name {
     pub const INSERT: &'static str = "insert";
     pub const NORMAL: &'static str = "normal";
     pub const VISUAL: &'static str = "visual";
+    pub const OVERVIEW: &'static str = "overview";
+    pub const MARK: &'static str = "mark";
+    pub const NAME: &'static str = "name";
+    pub const RECORD: &'static str = "record";
+    pub const REPLAY: &'static str = "replay";
+    pub const DRAG: &'static str = "drag";
+    pub const RESIZE: &'static str = "resize";
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -96,7 +105,13 @@ struct InnerInputManager {
     code: InputCode,
     command: Command,
     previous_modification: binding_functions::PreviousModification,
+    last_command: Option<Command>,
+    recording: Option<(char, Vec<Command>)>,
+    registers: Registers,
     signaler: Signaler<Perceptron>,
+    is_session_locked: bool,
+    is_shortcuts_inhibited: bool,
+    inhibit_escape: Binding,
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -110,13 +125,54 @@ impl InnerInputManager {
         let normal_mode =
             Mode::new(false, mode_name::NORMAL.to_owned(), Some(binding_functions::Nop::new()));
 
+        // Mark mode is active only while waiting for name of the mark so it has to precede all
+        // other modes except common
+        let mark_mode = Mode::new(false,
+                                  mode_name::MARK.to_owned(),
+                                  Some(binding_functions::PutMarkName::new()));
+
+        // Name mode similarly takes all keys while new title of workspace is being typed
+        let name_mode = Mode::new(false,
+                                  mode_name::NAME.to_owned(),
+                                  Some(binding_functions::PutNameCharacter::new()));
+
+        // Record and replay modes take all keys while waiting for name of the register
+        let record_mode = Mode::new(false,
+                                    mode_name::RECORD.to_owned(),
+                                    Some(binding_functions::PutRecordRegister::new()));
+        let replay_mode = Mode::new(false,
+                                    mode_name::REPLAY.to_owned(),
+                                    Some(binding_functions::PutReplayRegister::new()));
+
+        // Visual mode replaces normal mode while frames are being selected
+        let visual_mode =
+            Mode::new(false, mode_name::VISUAL.to_owned(), Some(binding_functions::Nop::new()));
+
+        // Overview mode takes all keys while thumbnail is being chosen
+        let overview_mode =
+            Mode::new(false, mode_name::OVERVIEW.to_owned(), Some(binding_functions::Nop::new()));
+
         // Create manager
         let mut inner = InnerInputManager {
-            modes: vec![common_mode, insert_mode, normal_mode],
+            modes: vec![common_mode,
+                        mark_mode,
+                        name_mode,
+                        record_mode,
+                        replay_mode,
+                        overview_mode,
+                        visual_mode,
+                        insert_mode,
+                        normal_mode],
             code: 0,
             command: Command::default(),
             previous_modification: binding_functions::PreviousModification::None,
+            last_command: None,
+            recording: None,
+            registers: Registers::new(config.registers_path.clone()),
             signaler: signaler,
+            is_session_locked: false,
+            is_shortcuts_inhibited: false,
+            inhibit_escape: config.inhibit_escape.clone(),
         };
 
         inner.apply_configuration(config);
@@ -152,6 +208,13 @@ impl InnerInputManager {
                              b.binding.clone(),
                              b.executor.duplicate());
         }
+
+        // Apply overview mode bindings
+        for b in config.overview.iter() {
+            self.add_binding(mode_name::OVERVIEW.to_owned(),
+                             b.binding.clone(),
+                             b.executor.duplicate());
+        }
     }
 
     /// Helper method for finding executor for given binding in active modes.
@@ -187,13 +250,36 @@ impl InnerInputManager {
     }
 
     /// Helper method for searching and executing an executor.
+    ///
+    /// While session is locked no bindings are executed and all input is passed to lock surfaces.
+    ///
+    /// While focused client inhibits shortcuts only escape binding is caught. It stops the
+    /// inhibition.
     fn catch(&mut self,
              code: InputCode,
              value: InputValue,
              modifiers: modifier::ModifierType)
              -> CatchResult {
+        if self.is_session_locked {
+            return CatchResult::Passed;
+        }
+
+        let binding = Binding::create(code, modifiers);
+        if self.is_shortcuts_inhibited {
+            if binding == self.inhibit_escape {
+                if value == KeyState::Pressed as InputValue {
+                    log_info2!("Escaping shortcuts inhibition");
+                    self.is_shortcuts_inhibited = false;
+                    self.signaler.emit(perceptron::SHORTCUTS_INHIBITION_ESCAPED,
+                                       Perceptron::ShortcutsInhibitionEscaped);
+                }
+                return CatchResult::Caught;
+            }
+            return CatchResult::Passed;
+        }
+
         self.code = code;
-        if let Some(executor) = self.find_executor(&Binding::create(code, modifiers)) {
+        if let Some(executor) = self.find_executor(&binding) {
             if value == KeyState::Pressed as InputValue {
                 executor.activate(self);
             } else if value == KeyState::Released as InputValue {
@@ -215,6 +301,25 @@ impl InnerInputManager {
         }
     }
 
+    /// Enables or disables executing bindings depending on session lock state.
+    pub fn set_session_locked(&mut self, locked: bool) {
+        self.is_session_locked = locked;
+        if locked {
+            self.command = Command::default();
+            self.previous_modification = binding_functions::PreviousModification::None;
+            self.make_mode_active(mode_name::MARK.to_owned(), false);
+            self.make_mode_active(mode_name::RECORD.to_owned(), false);
+            self.make_mode_active(mode_name::REPLAY.to_owned(), false);
+            self.make_mode_active(mode_name::OVERVIEW.to_owned(), false);
+        }
+    }
+
+    /// Enables or disables executing bindings depending on shortcuts inhibition state of focused
+    /// client.
+    pub fn set_shortcuts_inhibited(&mut self, inhibited: bool) {
+        self.is_shortcuts_inhibited = inhibited;
+    }
+
     /// Adds given binding to mode identified by name.
     pub fn add_binding(&mut self, mode_name: String, binding: Binding, executor: Box<Executor>) {
         // Try to find mode and add binding to it
@@ -234,6 +339,16 @@ impl InnerInputManager {
             self.modes.push(mode);
         }
     }
+
+    /// Emits given command remembering it as the last executed one and appending it to commands
+    /// being recorded.
+    fn emit_command(&mut self, command: Command) {
+        if let Some((_, ref mut commands)) = self.recording {
+            commands.push(command.clone());
+        }
+        self.last_command = Some(command.clone());
+        self.signaler.emit(perceptron::COMMAND, Perceptron::Command(command));
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -282,7 +397,8 @@ impl binding_functions::InputContext for InnerInputManager {
     }
 
     fn execute_command(&mut self) {
-        self.signaler.emit(perceptron::COMMAND, Perceptron::Command(self.command.clone()));
+        let command = self.command.clone();
+        self.emit_command(command);
     }
 
     fn clean_command(&mut self) {
@@ -290,13 +406,57 @@ impl binding_functions::InputContext for InnerInputManager {
         self.previous_modification = binding_functions::PreviousModification::None;
     }
 
+    fn repeat_command(&mut self, count: u32) {
+        if let Some(command) = self.last_command.clone() {
+            for _ in 0..count {
+                self.emit_command(command.clone());
+            }
+        } else {
+            log_info2!("No command to repeat");
+        }
+    }
+
+    fn start_recording(&mut self, register: char) {
+        log_info2!("Start recording into register '{}'", register);
+        self.recording = Some((register, Vec::new()));
+    }
+
+    fn stop_recording(&mut self) -> bool {
+        if let Some((register, commands)) = self.recording.take() {
+            log_info2!("Stop recording into register '{}'", register);
+            self.registers.set(register, commands);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn replay_register(&mut self, register: char, count: u32) {
+        if let Some(commands) = self.registers.get(register) {
+            for _ in 0..count {
+                for command in commands.iter() {
+                    self.emit_command(command.clone());
+                }
+            }
+        } else {
+            log_warn2!("Register '{}' is empty", register);
+        }
+    }
+
     fn activate_mode(&mut self, mode_name: &'static str, active: bool) {
         self.make_mode_active(mode_name.to_string(), active);
-        if mode_name == mode_name::VISUAL {
+        let mode = match mode_name {
+            mode_name::VISUAL => Some(InteractionMode::Visual),
+            mode_name::OVERVIEW => Some(InteractionMode::Overview),
+            mode_name::DRAG => Some(InteractionMode::Drag),
+            mode_name::RESIZE => Some(InteractionMode::Resize),
+            _ => None,
+        };
+        if let Some(mode) = mode {
             self.signaler.emit(perceptron::MODE,
                                Perceptron::Mode {
                                    active: active,
-                                   mode: InteractionMode::Visual,
+                                   mode: mode,
                                });
         }
     }
@@ -332,6 +492,38 @@ impl binding_functions::InputContext for InnerInputManager {
             _ => None,
         }
     }
+
+    fn get_code_as_letter(&self) -> Option<char> {
+        match self.code as i32 {
+            uinput_sys::KEY_A => Some('a'),
+            uinput_sys::KEY_B => Some('b'),
+            uinput_sys::KEY_C => Some('c'),
+            uinput_sys::KEY_D => Some('d'),
+            uinput_sys::KEY_E => Some('e'),
+            uinput_sys::KEY_F => Some('f'),
+            uinput_sys::KEY_G => Some('g'),
+            uinput_sys::KEY_H => Some('h'),
+            uinput_sys::KEY_I => Some('i'),
+            uinput_sys::KEY_J => Some('j'),
+            uinput_sys::KEY_K => Some('k'),
+            uinput_sys::KEY_L => Some('l'),
+            uinput_sys::KEY_M => Some('m'),
+            uinput_sys::KEY_N => Some('n'),
+            uinput_sys::KEY_O => Some('o'),
+            uinput_sys::KEY_P => Some('p'),
+            uinput_sys::KEY_Q => Some('q'),
+            uinput_sys::KEY_R => Some('r'),
+            uinput_sys::KEY_S => Some('s'),
+            uinput_sys::KEY_T => Some('t'),
+            uinput_sys::KEY_U => Some('u'),
+            uinput_sys::KEY_V => Some('v'),
+            uinput_sys::KEY_W => Some('w'),
+            uinput_sys::KEY_X => Some('x'),
+            uinput_sys::KEY_Y => Some('y'),
+            uinput_sys::KEY_Z => Some('z'),
+            _ => None,
+        }
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -363,6 +555,18 @@ impl InputManager {
         let mut mine = self.inner.lock().unwrap();
         mine.add_binding(mode_name, binding, executor)
     }
+
+    /// Lock and call corresponding method from `InnerInputManager`.
+    pub fn set_session_locked(&mut self, locked: bool) {
+        let mut mine = self.inner.lock().unwrap();
+        mine.set_session_locked(locked)
+    }
+
+    /// Lock and call corresponding method from `InnerInputManager`.
+    pub fn set_shortcuts_inhibited(&mut self, inhibited: bool) {
+        let mut mine = self.inner.lock().unwrap();
+        mine.set_shortcuts_inhibited(inhibited)
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -466,6 +670,13 @@ impl InputForwarding for InputForwarder {
         self.signaler.emit(perceptron::INPUT_POINTER_AXIS, Perceptron::InputPointerAxis(axis));
     }
 
+    /// Emits pointer warp event.
+    fn emit_warp(&mut self, x: isize, y: isize) {
+        // Signal event
+        self.signaler.emit(perceptron::INPUT_POINTER_WARP,
+                           Perceptron::InputPointerWarp(Position::new(x, y)));
+    }
+
     /// Emits position reset event.
     fn emit_position_reset(&mut self) {
         // Signal event
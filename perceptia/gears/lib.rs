@@ -18,6 +18,7 @@ extern crate cognitive_qualia as qualia;
 
 mod binding_functions;
 mod config_defaults;
+mod registers;
 
 pub mod functions;
 
@@ -0,0 +1,217 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! This module contains functionality for keeping sequences of commands recorded in registers.
+//!
+//! Registers are stored in file so they persist across restarts. Each line of the file contains
+//! name of the register, action, direction, magnitude and string of one command separated by
+//! tabulators.
+
+// -------------------------------------------------------------------------------------------------
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+
+use qualia::{Action, Command, Direction};
+
+// -------------------------------------------------------------------------------------------------
+
+/// Set of named sequences of commands.
+pub struct Registers {
+    registers: HashMap<char, Vec<Command>>,
+    path: Option<PathBuf>,
+}
+
+// -------------------------------------------------------------------------------------------------
+
+impl Registers {
+    /// Constructs new `Registers` loading their contents from given file if provided.
+    pub fn new(path: Option<PathBuf>) -> Self {
+        let mut registers = Registers {
+            registers: HashMap::new(),
+            path: path,
+        };
+        registers.load();
+        registers
+    }
+
+    /// Returns commands recorded in given register.
+    pub fn get(&self, name: char) -> Option<Vec<Command>> {
+        self.registers.get(&name).cloned()
+    }
+
+    /// Replaces contents of given register and stores all registers in file.
+    pub fn set(&mut self, name: char, commands: Vec<Command>) {
+        self.registers.insert(name, commands);
+        self.store();
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Helper methods.
+impl Registers {
+    /// Reads registers from file. Lines which can not be parsed are skipped.
+    fn load(&mut self) {
+        let contents = {
+            let path = if let Some(ref path) = self.path {
+                path
+            } else {
+                return;
+            };
+
+            let mut contents = String::new();
+            if let Ok(mut file) = File::open(path) {
+                if let Err(err) = file.read_to_string(&mut contents) {
+                    log_warn1!("Failed to read registers from {:?}: {}", path, err);
+                    return;
+                }
+            }
+            contents
+        };
+
+        for line in contents.lines() {
+            if let Some((name, command)) = parse_line(line) {
+                self.registers.entry(name).or_insert_with(Vec::new).push(command);
+            } else {
+                log_warn2!("Failed to parse register entry: '{}'", line);
+            }
+        }
+    }
+
+    /// Writes all registers to file.
+    fn store(&self) {
+        let path = if let Some(ref path) = self.path {
+            path
+        } else {
+            return;
+        };
+
+        let mut names = self.registers.keys().collect::<Vec<_>>();
+        names.sort();
+
+        let mut contents = String::new();
+        for name in names {
+            for command in self.registers[name].iter() {
+                let line = format!("{}\t{}\t{}\t{}\t{}",
+                                   name,
+                                   command.action.as_str(),
+                                   command.direction.as_str(),
+                                   command.magnitude,
+                                   command.string.replace('\t', " "));
+                contents.push_str(&line.replace('\n', " "));
+                contents.push('\n');
+            }
+        }
+
+        let result = File::create(path).and_then(|mut file| file.write_all(contents.as_bytes()));
+        if let Err(err) = result {
+            log_warn1!("Failed to store registers in {:?}: {}", path, err);
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Parses one line of registers file. Returns name of the register and the command.
+fn parse_line(line: &str) -> Option<(char, Command)> {
+    let fields = line.splitn(5, '\t').collect::<Vec<_>>();
+    if fields.len() != 5 {
+        return None;
+    }
+
+    let mut chars = fields[0].chars();
+    let name = chars.next();
+    let action = fields[1].parse::<Action>().ok();
+    let direction = fields[2].parse::<Direction>().ok();
+    let magnitude = fields[3].parse::<i32>().ok();
+    match (name, chars.next(), action, direction, magnitude) {
+        (Some(name), None, Some(action), Some(direction), Some(magnitude)) => {
+            let command = Command {
+                action: action,
+                direction: direction,
+                magnitude: magnitude,
+                string: fields[4].to_owned(),
+            };
+            Some((name, command))
+        }
+        _ => None,
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use qualia::{Action, Command, Direction};
+    use super::{Registers, parse_line};
+
+    fn command(action: Action, direction: Direction, magnitude: i32, string: &str) -> Command {
+        Command {
+            action: action,
+            direction: direction,
+            magnitude: magnitude,
+            string: string.to_owned(),
+        }
+    }
+
+    fn assert_commands_equal(commands: &Vec<Command>, expected: &Vec<Command>) {
+        assert_eq!(commands.len(), expected.len());
+        for (command, expected) in commands.iter().zip(expected.iter()) {
+            assert_eq!(command.action, expected.action);
+            assert_eq!(command.direction, expected.direction);
+            assert_eq!(command.magnitude, expected.magnitude);
+            assert_eq!(command.string, expected.string);
+        }
+    }
+
+    /// Check if lines with wrong number of fields or unknown names are rejected.
+    #[test]
+    fn test_parsing_line() {
+        let (name, command) = parse_line("a\tFocus\tNorth\t-2\tsome text").unwrap();
+        assert_eq!(name, 'a');
+        assert_eq!(command.action, Action::Focus);
+        assert_eq!(command.direction, Direction::North);
+        assert_eq!(command.magnitude, -2);
+        assert_eq!(command.string, "some text");
+
+        assert!(parse_line("a\tFocus\tNorth\t1").is_none());
+        assert!(parse_line("ab\tFocus\tNorth\t1\t").is_none());
+        assert!(parse_line("a\tfocus\tNorth\t1\t").is_none());
+        assert!(parse_line("a\tFocus\tnowhere\t1\t").is_none());
+        assert!(parse_line("a\tFocus\tNorth\tx\t").is_none());
+    }
+
+    /// Check if commands stored in file are read back unchanged.
+    #[test]
+    fn test_storing_and_loading_registers() {
+        let path = ::std::env::temp_dir().join("perceptia-test-registers");
+        let commands = vec![
+            command(Action::Dive, Direction::West, 2, ""),
+            command(Action::Rename, Direction::None, 0, "tab\there"),
+            command(Action::Stick, Direction::Urgent, -1, ""),
+        ];
+        let other = vec![command(Action::None, Direction::Forward, 1, "")];
+
+        {
+            let mut registers = Registers::new(Some(path.clone()));
+            registers.set('q', commands.clone());
+            registers.set('1', other.clone());
+        }
+
+        let registers = Registers::new(Some(path.clone()));
+        fs::remove_file(&path).unwrap();
+
+        let mut expected = commands.clone();
+        expected[1].string = "tab here".to_owned();
+        assert_commands_equal(&registers.get('q').unwrap(), &expected);
+        assert_commands_equal(&registers.get('1').unwrap(), &other);
+        assert!(registers.get('w').is_none());
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
@@ -15,6 +15,7 @@ use device_manager::DeviceManager;
 
 pub struct DeviceManagerModule {
     manager: DeviceManager<Coordinator>,
+    input_manager: InputManager,
 }
 
 // -------------------------------------------------------------------------------------------------
@@ -34,10 +35,11 @@ impl DeviceManagerModule {
 
         // Construct the module.
         DeviceManagerModule {
-            manager: DeviceManager::new(Box::new(input_manager),
+            manager: DeviceManager::new(Box::new(input_manager.clone()),
                                         Box::new(input_forwarder),
                                         config.get_input_config().clone(),
                                         coordinator),
+            input_manager: input_manager,
         }
     }
 }
@@ -52,7 +54,11 @@ impl Module for DeviceManagerModule {
         vec![perceptron::SUSPEND,
              perceptron::WAKEUP,
              perceptron::INPUTS_CHANGED,
-             perceptron::OUTPUTS_CHANGED]
+             perceptron::OUTPUTS_CHANGED,
+             perceptron::VIRTUAL_INPUT,
+             perceptron::LOCK_SESSION,
+             perceptron::UNLOCK_SESSION,
+             perceptron::INHIBIT_SHORTCUTS]
     }
 
     fn initialize(&mut self) {
@@ -66,6 +72,12 @@ impl Module for DeviceManagerModule {
             Perceptron::WakeUp => self.manager.on_wakeup(),
             Perceptron::InputsChanged => self.manager.on_inputs_changed(),
             Perceptron::OutputsChanged => self.manager.on_outputs_changed(),
+            Perceptron::VirtualInput(event) => self.manager.on_virtual_input(event),
+            Perceptron::LockSession => self.input_manager.set_session_locked(true),
+            Perceptron::UnlockSession => self.input_manager.set_session_locked(false),
+            Perceptron::InhibitShortcuts(inhibit) => {
+                self.input_manager.set_shortcuts_inhibited(inhibit)
+            }
             _ => {}
         }
     }
@@ -54,13 +54,20 @@ impl Module for ExhibitorModule {
              perceptron::INPUT_POINTER_POSITION,
              perceptron::INPUT_POINTER_BUTTON,
              perceptron::INPUT_POINTER_POSITION_RESET,
+             perceptron::INPUT_POINTER_WARP,
              perceptron::CURSOR_SURFACE_CHANGE,
              perceptron::BACKGROUND_SURFACE_CHANGE,
              perceptron::SURFACE_READY,
              perceptron::DOCK_SURFACE,
              perceptron::SURFACE_DESTROYED,
+             perceptron::SURFACE_STATE_REQUESTED,
+             perceptron::SURFACE_ACTIVATION_REQUESTED,
              perceptron::KEYBOARD_FOCUS_CHANGED,
-             perceptron::TAKE_SCREENSHOT]
+             perceptron::TAKE_SCREENSHOT,
+             perceptron::LOCK_SESSION,
+             perceptron::UNLOCK_SESSION,
+             perceptron::LOCK_SURFACE,
+             perceptron::TIMER_500]
     }
 
     fn initialize(&mut self) {
@@ -78,6 +85,7 @@ impl Module for ExhibitorModule {
             Perceptron::InputPointerPosition(ref pos) => self.exhibitor.on_position(pos.clone()),
             Perceptron::InputPointerButton(ref btn) => self.exhibitor.on_button(btn.clone()),
             Perceptron::InputPointerPositionReset => self.exhibitor.on_position_reset(),
+            Perceptron::InputPointerWarp(ref pos) => self.exhibitor.on_warp(pos.clone()),
 
             Perceptron::CursorSurfaceChange(sid) => self.exhibitor.on_cursor_surface_change(sid),
 
@@ -86,6 +94,12 @@ impl Module for ExhibitorModule {
                 self.exhibitor.on_dock_surface(sid, size, display_id);
             }
             Perceptron::SurfaceDestroyed(sid) => self.exhibitor.on_surface_destroyed(sid),
+            Perceptron::SurfaceStateRequested(sid, state, enable) => {
+                self.exhibitor.on_surface_state_requested(sid, state, enable);
+            }
+            Perceptron::SurfaceActivationRequested(sid, granted) => {
+                self.exhibitor.on_surface_activation_requested(sid, granted);
+            }
 
             Perceptron::KeyboardFocusChanged(_, sid) => {
                 self.exhibitor.on_keyboard_focus_changed(sid);
@@ -95,12 +109,19 @@ impl Module for ExhibitorModule {
                 self.exhibitor.on_mode_switched(active, mode);
             }
 
+            Perceptron::Timer500 => self.exhibitor.on_timer(),
             Perceptron::Suspend => self.exhibitor.on_suspend(),
             Perceptron::WakeUp => self.exhibitor.on_wakeup(),
             Perceptron::TakeScreenshot(id) => self.exhibitor.take_screenshot(id),
             Perceptron::BackgroundSurfaceChange(sid) => {
                 self.exhibitor.on_background_surface_change(sid);
             }
+
+            Perceptron::LockSession => self.exhibitor.on_lock_session(),
+            Perceptron::UnlockSession => self.exhibitor.on_unlock_session(),
+            Perceptron::LockSurface(sid, display_id) => {
+                self.exhibitor.on_lock_surface(sid, display_id);
+            }
             _ => {}
         }
     }
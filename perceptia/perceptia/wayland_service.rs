@@ -42,7 +42,8 @@ impl WaylandService {
         dharma::system::block_signals();
         let engine = Engine::new(Box::new(context.get_coordinator().clone()),
                                  context.get_settings().clone(),
-                                 context.get_config().get_keyboard_config().clone());
+                                 context.get_config().get_keyboard_config().clone(),
+                                 context.get_config().get_wayland_config().clone());
 
         WaylandService {
             engine: engine,
@@ -80,9 +81,13 @@ impl WaylandService {
                       perceptron::POINTER_RELATIVE_MOTION,
                       perceptron::KEYBOARD_FOCUS_CHANGED,
                       perceptron::SURFACE_RECONFIGURED,
+                      perceptron::SURFACE_MOVED,
+                      perceptron::SURFACE_CLOSE_REQUESTED,
                       perceptron::TRANSFER_OFFERED,
                       perceptron::TRANSFER_REQUESTED,
-                      perceptron::SCREENSHOT_DONE] {
+                      perceptron::SCREENSHOT_DONE,
+                      perceptron::SESSION_LOCKED,
+                      perceptron::SHORTCUTS_INHIBITION_ESCAPED] {
             self.context.get_signaler().subscribe(s, &self.receiver);
         }
 
@@ -181,9 +186,21 @@ impl WaylandService {
                     self.engine.on_surface_reconfigured(sid, info.desired_size, info.state_flags);
                 }
             }
+            Perceptron::SurfaceMoved(sid, position) => {
+                self.engine.on_surface_moved(sid, position);
+            }
+            Perceptron::SurfaceCloseRequested(sid) => {
+                self.engine.on_surface_close_requested(sid);
+            }
             Perceptron::ScreenshotDone => {
                 self.engine.on_screenshot_done();
             }
+            Perceptron::SessionLocked => {
+                self.engine.on_session_locked();
+            }
+            Perceptron::ShortcutsInhibitionEscaped => {
+                self.engine.on_shortcuts_inhibition_escaped();
+            }
             Perceptron::OutputFound(bundle) => self.engine.on_output_found(bundle),
             _ => {}
         }
@@ -206,6 +223,19 @@ impl WaylandService {
                 Perceptron::CustomId(handler_id) => self.engine.terminate_client(handler_id),
                 _ => warn_wrong!(constants::TERMINATE_CLIENT, package),
             }
+        } else if id == constants::START_XWAYLAND {
+            let mut sender = dharma::DirectSender::new();
+            dharma::direct_connect(&mut sender, &self.receiver);
+            self.engine.start_xwayland(sender, Box::new(self.context.get_coordinator().clone()));
+        } else if id == constants::XWAYLAND_READY {
+            self.engine.on_xwayland_ready(Box::new(self.context.get_coordinator().clone()));
+        } else if id == constants::PROCESS_XWM_EVENTS {
+            self.engine.process_xwm_events();
+        } else if id == constants::PROCESS_XWM_TRANSFER {
+            match package {
+                Perceptron::CustomId(handler_id) => self.engine.process_xwm_transfer(handler_id),
+                _ => warn_wrong!(constants::PROCESS_XWM_TRANSFER, package),
+            }
         }
     }
 
@@ -0,0 +1,52 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License, v. 2.0. If a copy of
+// the MPL was not distributed with this file, You can obtain one at http://mozilla.org/MPL/2.0/
+
+//! Listing marks of frames.
+
+use std::fs::File;
+use std::io::Read;
+
+use qualia;
+use gears::Config;
+
+// -------------------------------------------------------------------------------------------------
+
+pub fn process() {
+    list_marks();
+}
+
+// -------------------------------------------------------------------------------------------------
+
+/// Prints marks stored by `perceptia` in file configured as `marks_path` together with application
+/// ID and title of marked surfaces.
+fn list_marks() {
+    let env = qualia::env::Env::create(qualia::LogDestination::Disabled, "perceptia");
+    let config = Config::read_or_default(env.get_directories());
+    let path = if let Some(ref path) = config.get_exhibitor_config().compositor.marks_path {
+        path.clone()
+    } else {
+        println!("Path to marks file is not configured");
+        return;
+    };
+
+    let mut contents = String::new();
+    if let Err(err) = File::open(&path).and_then(|mut file| file.read_to_string(&mut contents)) {
+        println!("Failed to read marks from {:?}: {}", path, err);
+        return;
+    }
+
+    if contents.is_empty() {
+        println!("No marks");
+        return;
+    }
+
+    println!("{:<6}{:<24}{}", "mark", "application", "title");
+    for line in contents.lines() {
+        let fields = line.splitn(3, '\t').collect::<Vec<_>>();
+        if fields.len() == 3 {
+            println!("{:<6}{:<24}{}", fields[0], fields[1], fields[2]);
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
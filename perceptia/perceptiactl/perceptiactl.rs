@@ -21,6 +21,7 @@ extern crate skylane_simple_framework;
 
 mod info;
 mod about;
+mod marks;
 mod screenshot;
 mod verify_config;
 
@@ -36,6 +37,8 @@ fn main() {
             .about("Prints basic information about system."))
         .subcommand(clap::SubCommand::with_name("about")
             .about("Prints information about this program."))
+        .subcommand(clap::SubCommand::with_name("marks")
+            .about("Lists marks of frames"))
         .subcommand(clap::SubCommand::with_name("screenshot")
             .about("Takes screenshot"))
         .subcommand(clap::SubCommand::with_name("verify-config")
@@ -49,6 +52,9 @@ fn main() {
         ("about", Some(_)) => {
             about::process();
         }
+        ("marks", Some(_)) => {
+            marks::process();
+        }
         ("screenshot", Some(_)) => {
             screenshot::process();
         }